@@ -1,9 +1,9 @@
 use sp_core::{Pair, Public, sr25519, crypto::UncheckedInto};
 use bunbi_runtime::{
-	AccountId, AuraConfig, BalancesConfig,
-	GenesisConfig, GrandpaConfig, UtilsConfig,
-	SudoConfig, SpacesConfig, SystemConfig,
-	WASM_BINARY, Signature, constants::currency::SMNS,
+	AccountId, AuraConfig, BalancesConfig, CouncilMembershipConfig, FaucetsConfig,
+	GenesisConfig, GrandpaConfig, PostsConfig, ProfilesConfig, ReactionsConfig, UtilsConfig,
+	SudoConfig, ScoresConfig, SpaceFollowsConfig, SpacesConfig, SystemConfig,
+	WASM_BINARY, Signature, constants::{currency::{SMNS, TOKEN_DECIMALS, TOKEN_SYMBOL}, time::DAYS},
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -16,6 +16,12 @@ use hex_literal::hex;
 const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 const DEFAULT_PROTOCOL_ID: &str = "sub";
 
+/// Bootnodes of the public testnet.
+const BUNBI_TESTNET_BOOTNODES: [&str; 2] = [
+    "/dns/bootnode-1.testnet.bunbi.com.mx/tcp/30333/p2p/12D3KooWD8Pv4L3VHU9QAnkFXhecZ7GjbhYq77Y5Ln4q3KC5e1gr",
+    "/dns/bootnode-2.testnet.bunbi.com.mx/tcp/30333/p2p/12D3KooWMbGNjs1rMQDhK8ftBpsTwDRfs1MANkD3D3aaMyVueyZf",
+];
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
@@ -43,6 +49,11 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
     )
 }
 
+/// The faucet account of the development and local chains.
+fn dev_faucet_account() -> AccountId {
+    get_account_id_from_seed::<sr25519::Public>("Faucet")
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
     let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
@@ -67,6 +78,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
                 get_account_id_from_seed::<sr25519::Public>("Alice"),
                 endowed_accounts.iter().cloned().map(|k| (k, 10_000)).collect(),
                 get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+                vec![(dev_faucet_account(), 10_000)],
                 true,
             )
         },
@@ -103,6 +115,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                 get_account_id_from_seed::<sr25519::Public>("Alice"),
                 endowed_accounts.iter().cloned().map(|k| (k, 10_000)).collect(),
                 get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+                vec![(dev_faucet_account(), 10_000)],
                 true,
             )
         },
@@ -177,6 +190,8 @@ pub fn subsocial_staging_config() -> Result<ChainSpec, String> {
             ],
             // Treasury
             hex!["24d6d683750c4c10e90dd81430efec95133e1ec1f5be781d3267390d03174706"].into(),
+            // Faucets
+            vec![],
             true,
         ),
         vec![],
@@ -189,12 +204,73 @@ pub fn subsocial_staging_config() -> Result<ChainSpec, String> {
     ))
 }
 
+pub fn bunbi_testnet_config() -> Result<ChainSpec, String> {
+    let wasm_binary = WASM_BINARY.ok_or("Testnet wasm binary not available".to_string())?;
+
+    let boot_nodes = BUNBI_TESTNET_BOOTNODES.iter()
+        .map(|addr| addr.parse().map_err(|e| format!("Invalid bootnode address {}: {:?}", addr, e)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(ChainSpec::from_genesis(
+        "Bunbi Testnet",
+        "bunbi_testnet",
+        ChainType::Live,
+        move || testnet_genesis(
+            wasm_binary,
+            vec![
+                (
+                    /* AuraId SR25519 */
+                    hex!["ac940b8ee399d42faeb7169f322e6623f8219d12ad4c42dfe0995fa9f9713a0d"].unchecked_into(),
+                    /* GrandpaId ED25519 */
+                    hex!["e97b51af33429b5c4ab8ddd9b3fc542d24154bbeef807d559eff3906afca8413"].unchecked_into()
+                ),
+                (
+                    /* AuraId SR25519 */
+                    hex!["0c053087dd7782de467228b5f826c5031be2faf315baa766a89b48bb6e2dfb71"].unchecked_into(),
+                    /* GrandpaId ED25519 */
+                    hex!["b48a83ed87ef39bc90c205fb551af3c076e1a952881d7fefec08cbb76e17ab8b"].unchecked_into()
+                ),
+            ],
+            /* Sudo Account, it also owns the reserved spaces */
+            hex!["24d6d7cd9a0500be768efc7b5508e7861cbde7cfc06819e4dfd9120b97d46d3e"].into(),
+            vec![
+                (
+                    /* Sudo Account */
+                    hex!["24d6d7cd9a0500be768efc7b5508e7861cbde7cfc06819e4dfd9120b97d46d3e"].into(),
+                    /* Balance */
+                    100_000
+                ),
+            ],
+            // Treasury
+            hex!["24d6d683750c4c10e90dd81430efec95133e1ec1f5be781d3267390d03174706"].into(),
+            // Faucets
+            vec![
+                (
+                    /* Account X1 */
+                    hex!["24d6d996a8bb42a63904afc36d610986e8d502f65898da62cb281cfe7f23b02f"].into(),
+                    /* Balance */
+                    1_000_000
+                ),
+            ],
+            true,
+        ),
+        boot_nodes,
+        Some(TelemetryEndpoints::new(
+            vec![(STAGING_TELEMETRY_URL.to_string(), 0)]
+        ).expect("Staging telemetry url is valid; qed")),
+        Some(DEFAULT_PROTOCOL_ID),
+        Some(subsocial_properties()),
+        None,
+    ))
+}
+
 fn testnet_genesis(
     wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<(AccountId, u128)>,
 	treasury_account_id: AccountId,
+	faucets: Vec<(AccountId, u128)>,
 	_enable_println: bool
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -217,8 +293,25 @@ fn testnet_genesis(
 		pallet_utils: Some(UtilsConfig {
 			treasury_account: treasury_account_id,
 		}),
+		pallet_faucets: Some(FaucetsConfig {
+			// Every faucet can drip up to 10 tokens at once and 100 tokens per day.
+			faucets: faucets.iter().cloned().map(|(k, b)| (k, DAYS, 100 * SMNS, 10 * SMNS, b * SMNS)).collect(),
+		}),
 		pallet_spaces: Some(SpacesConfig {
-			endowed_account: root_key,
+			endowed_account: root_key.clone(),
+			spaces: vec![],
+		}),
+		pallet_space_follows: Some(SpaceFollowsConfig {}),
+		pallet_posts: Some(PostsConfig {}),
+		pallet_reactions: Some(ReactionsConfig {}),
+		pallet_profiles: Some(ProfilesConfig {}),
+		pallet_scores: Some(ScoresConfig {
+			// Reactions are scored with the default weights of their actions.
+			reaction_kind_weights: vec![],
+		}),
+		pallet_membership_Instance1: Some(CouncilMembershipConfig {
+			members: vec![root_key],
+			phantom: Default::default(),
 		}),
 	}
 }
@@ -227,8 +320,28 @@ pub fn subsocial_properties() -> Properties {
 	let mut properties = Properties::new();
 
 	properties.insert("ss58Format".into(), 28.into());
-	properties.insert("tokenDecimals".into(), 12.into());
-	properties.insert("tokenSymbol".into(), "BBU".into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
 
 	properties
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_runtime::BuildStorage;
+
+    #[test]
+    fn bunbi_testnet_config_should_be_built() {
+        let spec = bunbi_testnet_config().unwrap();
+
+        assert_eq!(spec.chain_type(), ChainType::Live);
+        assert_eq!(spec.boot_nodes().len(), BUNBI_TESTNET_BOOTNODES.len());
+        assert!(spec.telemetry_endpoints().is_some());
+        assert_eq!(spec.protocol_id(), Some(DEFAULT_PROTOCOL_ID));
+        assert_eq!(spec.properties().get("tokenSymbol"), Some(&TOKEN_SYMBOL.into()));
+        assert_eq!(spec.properties().get("tokenDecimals"), Some(&TOKEN_DECIMALS.into()));
+
+        assert!(spec.build_storage().is_ok());
+    }
+}
@@ -36,6 +36,7 @@ impl SubstrateCli for Cli {
             "dev" => Box::new(chain_spec::development_config()?),
             "local" => Box::new(chain_spec::local_testnet_config()?),
             "staging" => Box::new(chain_spec::subsocial_staging_config()?),
+            "bunbi-testnet" => Box::new(chain_spec::bunbi_testnet_config()?),
             "" | "df" => Box::new(chain_spec::subsocial_config()?),
             path => Box::new(chain_spec::ChainSpec::from_json_file(
                 std::path::PathBuf::from(path),
@@ -7,13 +7,15 @@
 
 use std::sync::Arc;
 
-use bunbi_runtime::{opaque::Block, AccountId, Balance, Index};
+use bunbi_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index, Moment};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_transaction_pool::TransactionPool;
 
+/// RPC methods over spaces, posts, profiles and reactions.
+pub mod social;
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -35,10 +37,17 @@ pub fn create_full<C, P>(
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
+    C::Api: spaces_runtime_api::SpacesApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: posts_runtime_api::PostsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: profiles_runtime_api::ProfilesApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: reactions_runtime_api::ReactionsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: space_follows_runtime_api::SpaceFollowsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: stats_runtime_api::StatsApi<Block>,
     P: TransactionPool + 'static,
 {
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+    use social::{Social, SocialApi};
 
     let mut io = jsonrpc_core::IoHandler::default();
     let FullDeps {
@@ -55,6 +64,10 @@ pub fn create_full<C, P>(
         TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
     );
 
+    io.extend_with(
+        SocialApi::to_delegate(Social::new(client.clone()))
+    );
+
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
     // to call into the runtime.
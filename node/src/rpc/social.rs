@@ -0,0 +1,430 @@
+//! `social_*` RPC methods backed by the runtime APIs of the social pallets.
+//!
+//! The methods return the flat structs of the pallets (e.g. `FlatSpace`) serialized to JSON,
+//! except for byte fields that are made human-readable:
+//! - `handle` and `username` are strings;
+//! - `content` is `null` for `Content::None`, `{ "IPFS": "<cid>" }` for IPFS content,
+//!   and `{ "Raw": "0x.." }` or `{ "Hyper": "0x.." }` with hex-encoded bytes otherwise.
+//!
+//! Every method takes an optional block hash as the last parameter
+//! and queries the best block if it is omitted.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use bunbi_runtime::{AccountId, BlockNumber, Moment};
+use pallet_posts::{PostId, rpc::FlatPost};
+use pallet_profiles::rpc::FlatSocialAccount;
+use pallet_reactions::rpc::FlatReaction;
+use pallet_spaces::rpc::FlatSpace;
+use pallet_utils::{Content, SpaceId};
+use posts_runtime_api::PostsApi;
+use profiles_runtime_api::ProfilesApi;
+use reactions_runtime_api::ReactionsApi;
+use space_follows_runtime_api::SpaceFollowsApi;
+use spaces_runtime_api::SpacesApi;
+use stats_runtime_api::StatsApi;
+
+/// The number of items in a page if `limit` is omitted.
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+
+/// The max number of items that can be requested in one page.
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// An error code of a failed runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+/// `social_*` RPC methods.
+#[rpc(server)]
+pub trait SocialApi<BlockHash, AccountId> {
+    /// Find a space by its handle in any case.
+    ///
+    /// Params: `[handle: string, at?: BlockHash]`.
+    /// Returns a space or `null` if there is no space with this handle.
+    #[rpc(name = "social_spaceByHandle")]
+    fn space_by_handle(&self, handle: String, at: Option<BlockHash>) -> Result<Option<Value>>;
+
+    /// Check whether a handle in any case is already taken by a space,
+    /// so a new space cannot be created with it.
+    ///
+    /// Params: `[handle: string, at?: BlockHash]`.
+    #[rpc(name = "social_wouldHandleCollide")]
+    fn would_handle_collide(&self, handle: String, at: Option<BlockHash>) -> Result<bool>;
+
+    /// Get a page of root posts of a space in the order they were created.
+    ///
+    /// Params: `[space_id: number, offset?: number, limit?: number, at?: BlockHash]`,
+    /// where `offset` is `0` and `limit` is `DEFAULT_PAGE_LIMIT` by default,
+    /// and `limit` cannot be greater than `MAX_PAGE_LIMIT`.
+    #[rpc(name = "social_postsBySpace")]
+    fn posts_by_space(
+        &self,
+        space_id: SpaceId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Value>>;
+
+    /// Get a page of direct replies to a post, from the oldest to the newest one by default.
+    ///
+    /// Params: `[post_id: number, newest_first?: boolean, offset?: number, limit?: number, at?: BlockHash]`,
+    /// with the same defaults as in `social_postsBySpace`.
+    #[rpc(name = "social_postReplies")]
+    fn post_replies(
+        &self,
+        post_id: PostId,
+        newest_first: Option<bool>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Value>>;
+
+    /// Get a social account together with its profile.
+    ///
+    /// Params: `[account: AccountId, at?: BlockHash]`.
+    /// Returns `null` if an account has never been active on a social network.
+    #[rpc(name = "social_profile")]
+    fn profile(&self, account: AccountId, at: Option<BlockHash>) -> Result<Option<Value>>;
+
+    /// Get a page of reactions on a post in the order they were created.
+    ///
+    /// Params: `[post_id: number, offset?: number, limit?: number, at?: BlockHash]`,
+    /// with the same defaults as in `social_postsBySpace`.
+    #[rpc(name = "social_reactionsByPost")]
+    fn reactions_by_post(
+        &self,
+        post_id: PostId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Value>>;
+
+    /// Get the number of reactions of every kind on a post.
+    ///
+    /// Params: `[post_id: number, at?: BlockHash]`.
+    /// Returns `[kind, count]` pairs, e.g. `[["Upvote", 5], ["Downvote", 2]]`,
+    /// or an empty array if there is no such post.
+    #[rpc(name = "social_reactionsSummary")]
+    fn reactions_summary(&self, post_id: PostId, at: Option<BlockHash>) -> Result<Vec<Value>>;
+
+    /// Get a page of followers of a space with the block and time they followed it.
+    /// Followers are ordered by their storage keys rather than by follow time.
+    ///
+    /// Params: `[space_id: number, offset?: number, limit?: number, at?: BlockHash]`,
+    /// with the same defaults as in `social_postsBySpace`.
+    #[rpc(name = "social_spaceFollowers")]
+    fn space_followers(
+        &self,
+        space_id: SpaceId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Value>>;
+
+    /// Get the ids that will be given to the next spaces, posts, reactions and roles.
+    ///
+    /// Params: `[at?: BlockHash]`.
+    #[rpc(name = "social_nextIds")]
+    fn next_ids(&self, at: Option<BlockHash>) -> Result<Value>;
+}
+
+/// An implementation of `SocialApi` that calls the runtime of a client.
+pub struct Social<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Social<C, B> {
+    /// Create a new instance that queries the runtime of `client`.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+impl<C, Block> SocialApi<<Block as BlockT>::Hash, AccountId> for Social<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: SpacesApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: PostsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: ProfilesApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: ReactionsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: SpaceFollowsApi<Block, AccountId, BlockNumber, Moment>,
+    C::Api: StatsApi<Block>,
+{
+    fn space_by_handle(&self, handle: String, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Value>> {
+        let at = self.block_id(at);
+        let space = self.client.runtime_api().space_by_handle(&at, handle.into_bytes())
+            .map_err(runtime_error)?;
+
+        space.map(space_to_json).transpose()
+    }
+
+    fn would_handle_collide(&self, handle: String, at: Option<<Block as BlockT>::Hash>) -> Result<bool> {
+        let at = self.block_id(at);
+        self.client.runtime_api().would_handle_collide(&at, handle.into_bytes())
+            .map_err(runtime_error)
+    }
+
+    fn posts_by_space(
+        &self,
+        space_id: SpaceId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<Value>> {
+        let at = self.block_id(at);
+        let (offset, limit) = page(offset, limit)?;
+        let posts = self.client.runtime_api().posts_by_space(&at, space_id, offset, limit)
+            .map_err(runtime_error)?;
+
+        posts.into_iter().map(post_to_json).collect()
+    }
+
+    fn post_replies(
+        &self,
+        post_id: PostId,
+        newest_first: Option<bool>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<Value>> {
+        let at = self.block_id(at);
+        let (offset, limit) = page(offset, limit)?;
+        let replies = self.client.runtime_api()
+            .replies_paginated(&at, post_id, newest_first.unwrap_or_default(), offset, limit)
+            .map_err(runtime_error)?;
+
+        replies.into_iter().map(post_to_json).collect()
+    }
+
+    fn profile(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Value>> {
+        let at = self.block_id(at);
+        let social_account = self.client.runtime_api().social_account_by_id(&at, account)
+            .map_err(runtime_error)?;
+
+        social_account.map(social_account_to_json).transpose()
+    }
+
+    fn reactions_by_post(
+        &self,
+        post_id: PostId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<Value>> {
+        let at = self.block_id(at);
+        let (offset, limit) = page(offset, limit)?;
+        let reactions = self.client.runtime_api().reactions_by_post(&at, post_id, offset, limit)
+            .map_err(runtime_error)?;
+
+        reactions.into_iter().map(to_json).collect()
+    }
+
+    fn reactions_summary(&self, post_id: PostId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Value>> {
+        let at = self.block_id(at);
+        let summary = self.client.runtime_api().reactions_summary(&at, post_id)
+            .map_err(runtime_error)?;
+
+        summary.into_iter().map(to_json).collect()
+    }
+
+    fn space_followers(
+        &self,
+        space_id: SpaceId,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<Value>> {
+        let at = self.block_id(at);
+        let (offset, limit) = page(offset, limit)?;
+        let followers = self.client.runtime_api().space_followers(&at, space_id, offset, limit)
+            .map_err(runtime_error)?;
+
+        followers.into_iter().map(to_json).collect()
+    }
+
+    fn next_ids(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Value> {
+        let at = self.block_id(at);
+        let next_ids = self.client.runtime_api().next_ids(&at)
+            .map_err(runtime_error)?;
+
+        to_json(next_ids)
+    }
+}
+
+impl<C: HeaderBackend<Block>, Block: BlockT> Social<C, Block> {
+    /// Use the given block or the best one if none is given.
+    fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+        BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+    }
+}
+
+fn runtime_error<E: std::fmt::Debug>(error: E) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Unable to query the runtime.".into(),
+        data: Some(format!("{:?}", error).into()),
+    }
+}
+
+/// Get `(offset, limit)` of a page with the defaults applied.
+pub fn page(offset: Option<u32>, limit: Option<u32>) -> Result<(u32, u32)> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+
+    if limit > MAX_PAGE_LIMIT {
+        return Err(RpcError::invalid_params(format!("limit should not be greater than {}", MAX_PAGE_LIMIT)));
+    }
+
+    Ok((offset.unwrap_or_default(), limit))
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|error| RpcError {
+        code: ErrorCode::InternalError,
+        message: "Unable to serialize a response.".into(),
+        data: Some(error.to_string().into()),
+    })
+}
+
+/// Convert bytes to a `0x`-prefixed hex string.
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", HexDisplay::from(&bytes))
+}
+
+/// Convert bytes to a string if they are valid UTF-8, otherwise to a hex string.
+pub fn bytes_to_text(bytes: &[u8]) -> String {
+    std::str::from_utf8(bytes)
+        .map(ToString::to_string)
+        .unwrap_or_else(|_| bytes_to_hex(bytes))
+}
+
+/// Convert content to JSON as described in the docs of this module.
+pub fn content_to_json(content: &Content) -> Value {
+    match content {
+        Content::None => Value::Null,
+        Content::Raw(bytes) => json!({ "Raw": bytes_to_hex(bytes) }),
+        Content::IPFS(cid) => json!({ "IPFS": bytes_to_text(cid) }),
+        Content::Hyper(id) => json!({ "Hyper": bytes_to_hex(id) }),
+    }
+}
+
+fn optional_text_to_json(bytes: &Option<Vec<u8>>) -> Value {
+    bytes.as_ref().map_or(Value::Null, |bytes| bytes_to_text(bytes).into())
+}
+
+/// Convert a space to JSON with a readable handle and content.
+pub fn space_to_json<AccountId: Serialize, BlockNumber: Serialize, Moment: Serialize>(
+    space: FlatSpace<AccountId, BlockNumber, Moment>,
+) -> Result<Value> {
+    let handle = optional_text_to_json(&space.handle);
+    let content = content_to_json(&space.content);
+
+    let mut json = to_json(space)?;
+    json["handle"] = handle;
+    json["content"] = content;
+    Ok(json)
+}
+
+/// Convert a post to JSON with readable content.
+pub fn post_to_json<AccountId: Serialize, BlockNumber: Serialize, Moment: Serialize>(
+    post: FlatPost<AccountId, BlockNumber, Moment>,
+) -> Result<Value> {
+    let content = content_to_json(&post.content);
+
+    let mut json = to_json(post)?;
+    json["content"] = content;
+    Ok(json)
+}
+
+/// Convert a social account to JSON with a readable username and content of its profile.
+pub fn social_account_to_json<AccountId: Serialize, BlockNumber: Serialize, Moment: Serialize>(
+    social_account: FlatSocialAccount<AccountId, BlockNumber, Moment>,
+) -> Result<Value> {
+    let profile = social_account.profile.as_ref()
+        .map(|profile| (optional_text_to_json(&profile.username), content_to_json(&profile.content)));
+
+    let mut json = to_json(social_account)?;
+    if let Some((username, content)) = profile {
+        json["profile"]["username"] = username;
+        json["profile"]["content"] = content;
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn space(handle: Option<Vec<u8>>, content: Content) -> FlatSpace<u64, u32, u64> {
+        FlatSpace {
+            id: 1001,
+            created_by: 1,
+            created_at_block: 1,
+            created_at_time: 0,
+            updated_by: None,
+            updated_at_block: None,
+            updated_at_time: None,
+            owner: 1,
+            is_original_owner: true,
+            parent_id: None,
+            handle,
+            content,
+            hidden: false,
+            posts_count: 0,
+            hidden_posts_count: 0,
+            followers_count: 1,
+            score: 0,
+            permissions: None,
+            comments_count: 0,
+        }
+    }
+
+    #[test]
+    fn content_should_be_converted_to_readable_json() {
+        assert_eq!(content_to_json(&Content::None), Value::Null);
+        assert_eq!(content_to_json(&Content::IPFS(b"bafyrei".to_vec())), json!({ "IPFS": "bafyrei" }));
+        assert_eq!(content_to_json(&Content::Raw(vec![0xab, 0x01])), json!({ "Raw": "0xab01" }));
+        assert_eq!(content_to_json(&Content::Hyper(vec![0x0f])), json!({ "Hyper": "0x0f" }));
+    }
+
+    #[test]
+    fn invalid_utf8_should_be_converted_to_hex() {
+        assert_eq!(bytes_to_text(b"handle"), "handle");
+        assert_eq!(bytes_to_text(&[0xff, 0xfe]), "0xfffe");
+    }
+
+    #[test]
+    fn space_should_have_readable_handle_and_content() {
+        let json = space_to_json(space(Some(b"bunbi".to_vec()), Content::IPFS(b"bafyrei".to_vec()))).unwrap();
+
+        assert_eq!(json["id"], 1001);
+        assert_eq!(json["handle"], "bunbi");
+        assert_eq!(json["content"], json!({ "IPFS": "bafyrei" }));
+        assert_eq!(json["followers_count"], 1);
+    }
+
+    #[test]
+    fn space_without_handle_should_have_null_handle() {
+        let json = space_to_json(space(None, Content::None)).unwrap();
+
+        assert_eq!(json["handle"], Value::Null);
+        assert_eq!(json["content"], Value::Null);
+    }
+
+    #[test]
+    fn page_should_apply_defaults_and_reject_too_big_limit() {
+        assert_eq!(page(None, None).unwrap(), (0, DEFAULT_PAGE_LIMIT));
+        assert_eq!(page(Some(40), Some(MAX_PAGE_LIMIT)).unwrap(), (40, MAX_PAGE_LIMIT));
+        assert!(page(None, Some(MAX_PAGE_LIMIT + 1)).is_err());
+    }
+}
@@ -1,49 +1,102 @@
 #[cfg(test)]
 mod tests {
+    use codec::Encode;
     use frame_support::{
         assert_ok, assert_noop,
-        impl_outer_origin, parameter_types,
-        weights::Weight,
-        dispatch::DispatchResult,
-        storage::StorageMap,
+        impl_outer_origin, impl_outer_event, impl_outer_dispatch, parameter_types,
+        traits::{Currency, EnsureOrigin, OnFinalize, OnInitialize, Filter, Get},
+        weights::{Weight, IdentityFee},
+        dispatch::{DispatchError, DispatchResult},
+        storage::{self, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue},
+        StorageHasher, Twox64Concat,
     };
     use sp_core::H256;
     use sp_io::TestExternalities;
     use sp_runtime::{
-        traits::{BlakeTwo256, IdentityLookup, Zero},
-        testing::Header,
+        traits::{BlakeTwo256, IdentityLookup, ValidateUnsigned, Zero},
+        testing::{Header, TestSignature, UintAuthorityId},
+        transaction_validity::{
+            InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionSource,
+            TransactionValidity,
+        },
         Perbill,
-        Storage,
     };
     use frame_system::{self as system};
+    use std::{cell::RefCell, collections::BTreeSet};
+
+    use df_traits::IdentityProvider;
 
     use pallet_permissions::{
         SpacePermission,
         SpacePermission as SP,
         SpacePermissions,
     };
-    use pallet_posts::{PostId, Post, PostUpdate, PostExtension, Comment, Error as PostsError};
-    use pallet_profiles::{ProfileUpdate, Error as ProfilesError};
-    use pallet_profile_follows::Error as ProfileFollowsError;
-    use pallet_reactions::{ReactionId, ReactionKind, PostReactionScores, Error as ReactionsError};
-    use pallet_scores::ScoringAction;
-    use pallet_spaces::{SpaceById, SpaceUpdate, Error as SpacesError};
-    use pallet_space_follows::Error as SpaceFollowsError;
+    use pallet_posts::{PostId, Post, PostById, ContentToPostIds, PostUpdate, PostExtension, Comment, Error as PostsError, rpc::Visibility};
+    use pallet_profiles::{
+        ProfileUpdate, SocialAccountById, Error as ProfilesError,
+        migrations::{ProfileV0, SocialAccountV0, SocialAccountV1},
+    };
+    use pallet_profile_follows::{FollowAccountPayload, Error as ProfileFollowsError};
+    use pallet_reactions::{
+        ReactionId, ReactionKind, PostReactionScores, PostIdByReactionId, ReactionIdsByAccount,
+        Error as ReactionsError,
+    };
+    use pallet_scores::{ScoringAction, Error as ScoresError};
+    use pallet_spaces::{
+        BeforeSpaceCreated, Space, SpaceById, SpaceUpdate, SpaceSettingsById, SpaceSettingsUpdate, SPACE_SETTINGS_VERSION,
+        SpaceStatsRecount, Error as SpacesError,
+        migrations::{SpaceSettingsV1, SpaceSettingsV2, SpaceV0},
+    };
+    use pallet_space_follows::{FollowSpacePayload, SpaceFollowersCount, UnsignedFollowNonce, Error as SpaceFollowsError};
     use pallet_space_ownership::Error as SpaceOwnershipError;
     use pallet_moderation::{EntityId, EntityStatus, ReportId};
+    use pallet_donations::{DonationRecipient, Error as DonationsError};
+    use pallet_subscriptions::{SubscriptionPeriod, SubscriptionPlanId, SubscriptionId, Error as SubscriptionsError};
     use pallet_utils::{
         mock_functions::*,
         Error as UtilsError, Module as Utils,
-        SpaceId, User, Content,
+        SpaceId, User, Content, HistoryOverflowPolicy, WhoAndWhen,
     };
 
     impl_outer_origin! {
         pub enum Origin for TestRuntime {}
     }
 
-    #[derive(Clone, Eq, PartialEq)]
+    impl_outer_dispatch! {
+        pub enum Call for TestRuntime where origin: Origin {
+            system::System,
+            pallet_balances::Balances,
+            pallet_reactions::Reactions,
+            pallet_session_keys::SessionKeys,
+            pallet_subscriptions::Subscriptions,
+        }
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
     pub struct TestRuntime;
 
+    impl_outer_event! {
+        pub enum TestEvent for TestRuntime {
+            system<T>,
+            pallet_balances<T>,
+            pallet_donations<T>,
+            pallet_moderation<T>,
+            pallet_posts<T>,
+            pallet_profile_follows<T>,
+            pallet_profiles<T>,
+            pallet_reactions<T>,
+            pallet_roles<T>,
+            pallet_scheduler<T>,
+            pallet_scores<T>,
+            pallet_session_keys<T>,
+            pallet_space_follows<T>,
+            pallet_space_ownership<T>,
+            pallet_spaces<T>,
+            pallet_subscriptions<T>,
+            pallet_utils<T>,
+        }
+    }
+
     parameter_types! {
         pub const BlockHashCount: u64 = 250;
         pub const MaximumBlockWeight: Weight = 1024;
@@ -54,7 +107,7 @@ mod tests {
     impl system::Trait for TestRuntime {
         type BaseCallFilter = ();
         type Origin = Origin;
-        type Call = ();
+        type Call = Call;
         type Index = u64;
         type BlockNumber = u64;
         type Hash = H256;
@@ -62,7 +115,7 @@ mod tests {
         type AccountId = u64;
         type Lookup = IdentityLookup<Self::AccountId>;
         type Header = Header;
-        type Event = ();
+        type Event = TestEvent;
         type BlockHashCount = BlockHashCount;
         type MaximumBlockWeight = MaximumBlockWeight;
         type DbWeight = ();
@@ -97,7 +150,7 @@ mod tests {
     impl pallet_balances::Trait for TestRuntime {
         type Balance = u64;
         type DustRemoval = ();
-        type Event = ();
+        type Event = TestEvent;
         type ExistentialDeposit = ExistentialDeposit;
         type AccountStore = System;
         type WeightInfo = ();
@@ -110,10 +163,12 @@ mod tests {
     }
 
     impl pallet_utils::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type Currency = Balances;
         type MinHandleLen = MinHandleLen;
         type MaxHandleLen = MaxHandleLen;
+        type ForceOrigin = system::EnsureRoot<AccountId>;
+        type UnpausablePallets = ();
     }
 
     use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -122,59 +177,248 @@ mod tests {
         type DefaultSpacePermissions = DefaultSpacePermissions;
     }
 
+    const MAX_POSTS_PER_CONTENT: u32 = 20;
     parameter_types! {
         pub const MaxCommentDepth: u32 = 10;
+        pub const MaxShareDepth: u32 = 2;
+        pub const MaxPostsPerContent: u32 = MAX_POSTS_PER_CONTENT;
     }
 
     impl pallet_posts::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type MaxCommentDepth = MaxCommentDepth;
+        type MaxShareDepth = MaxShareDepth;
         type PostScores = Scores;
+        type BeforePostUpdated = PostHistory;
         type AfterPostUpdated = PostHistory;
+        type AfterPostCreated = Profiles;
         type IsPostBlocked = Moderation;
+        type SpaceSettingsProvider = Spaces;
+        type MaxPostsPerContent = MaxPostsPerContent;
+        type ReputationProvider = Profiles;
+        type WeightInfo = ();
     }
 
-    parameter_types! {}
+    const MAX_POST_HISTORY_RECORDS: u32 = 3;
+    parameter_types! {
+        pub const MaxPostHistoryRecords: u32 = MAX_POST_HISTORY_RECORDS;
+        pub const PostHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+    }
 
-    impl pallet_post_history::Trait for TestRuntime {}
+    impl pallet_post_history::Trait for TestRuntime {
+        type MaxHistoryRecordsPerEntity = MaxPostHistoryRecords;
+        type HistoryOverflowPolicy = PostHistoryOverflowPolicy;
+    }
 
-    parameter_types! {}
+    parameter_types! {
+        pub const UnsignedFollowsAllowance: u32 = 2;
+        pub const MaxUnsignedFollowsPerBlock: u32 = 3;
+        pub const UnsignedFollowerMinBalance: u64 = 10;
+        pub const UnsignedFollowPriority: TransactionPriority = TransactionPriority::max_value();
+        pub const UnsignedFollowLongevity: TransactionLongevity = 5;
+    }
+
+    impl system::offchain::SigningTypes for TestRuntime {
+        type Public = UintAuthorityId;
+        type Signature = TestSignature;
+    }
 
     impl pallet_profile_follows::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type BeforeAccountFollowed = Scores;
         type BeforeAccountUnfollowed = Scores;
+        type UnsignedFollowSignature = TestSignature;
+        type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+        type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+        type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+        type UnsignedFollowPriority = UnsignedFollowPriority;
+        type UnsignedFollowLongevity = UnsignedFollowLongevity;
+        type WeightInfo = ();
     }
 
     parameter_types! {}
 
+    thread_local! {
+        static VERIFIED_ACCOUNTS: RefCell<BTreeSet<AccountId>> = RefCell::new(BTreeSet::new());
+        static AUTO_FOLLOW_OWN_SPACE: RefCell<bool> = RefCell::new(true);
+        static HANDLE_CHANGE_COOLDOWN: RefCell<u64> = RefCell::new(0);
+        static MAX_SCORED_ACTIONS_PER_PERIOD: RefCell<u32> = RefCell::new(0);
+        static FAIL_BEFORE_SPACE_CREATED: RefCell<bool> = RefCell::new(false);
+        static ROOT_SPACE_CREATORS: RefCell<Option<BTreeSet<AccountId>>> = RefCell::new(None);
+    }
+
+    /// Auto-follow of own spaces that is on by default and can be turned off in a test.
+    pub struct AutoFollowOwnSpace;
+    impl AutoFollowOwnSpace {
+        fn set(auto_follow: bool) {
+            AUTO_FOLLOW_OWN_SPACE.with(|value| *value.borrow_mut() = auto_follow);
+        }
+    }
+
+    impl Get<bool> for AutoFollowOwnSpace {
+        fn get() -> bool {
+            AUTO_FOLLOW_OWN_SPACE.with(|value| *value.borrow())
+        }
+    }
+
+    /// A cooldown of space handle changes that is disabled by default and can be set in a test.
+    pub struct HandleChangeCooldown;
+    impl HandleChangeCooldown {
+        fn set(blocks: u64) {
+            HANDLE_CHANGE_COOLDOWN.with(|value| *value.borrow_mut() = blocks);
+        }
+    }
+
+    impl Get<u64> for HandleChangeCooldown {
+        fn get() -> u64 {
+            HANDLE_CHANGE_COOLDOWN.with(|value| *value.borrow())
+        }
+    }
+
+    /// A limit of scored actions per period that is disabled by default and can be set in a test.
+    pub struct MaxScoredActionsPerPeriod;
+    impl MaxScoredActionsPerPeriod {
+        fn set(max_actions: u32) {
+            MAX_SCORED_ACTIONS_PER_PERIOD.with(|value| *value.borrow_mut() = max_actions);
+        }
+    }
+
+    impl Get<u32> for MaxScoredActionsPerPeriod {
+        fn get() -> u32 {
+            MAX_SCORED_ACTIONS_PER_PERIOD.with(|value| *value.borrow())
+        }
+    }
+
+    /// Lets any signed account create root spaces, unless an allowlist of creators is set in a test.
+    pub struct CreateRootSpaceOrigin;
+    impl CreateRootSpaceOrigin {
+        fn allow_only(accounts: Vec<AccountId>) {
+            ROOT_SPACE_CREATORS.with(|creators| *creators.borrow_mut() = Some(accounts.into_iter().collect()));
+        }
+    }
+
+    impl EnsureOrigin<Origin> for CreateRootSpaceOrigin {
+        type Success = AccountId;
+
+        fn try_origin(o: Origin) -> Result<AccountId, Origin> {
+            let who = system::EnsureSigned::<AccountId>::try_origin(o)?;
+            let is_allowed = ROOT_SPACE_CREATORS.with(|creators| {
+                creators.borrow().as_ref().map_or(true, |creators| creators.contains(&who))
+            });
+
+            if is_allowed { Ok(who) } else { Err(Origin::signed(who)) }
+        }
+    }
+
+    const BEFORE_SPACE_CREATED_FAILED: &str = "BeforeSpaceCreated failed";
+
+    /// Makes a creator the first follower of a new space as `SpaceFollows` does,
+    /// then fails with `BEFORE_SPACE_CREATED_FAILED` if it is set to fail in a test.
+    pub struct MockBeforeSpaceCreated;
+    impl MockBeforeSpaceCreated {
+        fn set_failing(failing: bool) {
+            FAIL_BEFORE_SPACE_CREATED.with(|value| *value.borrow_mut() = failing);
+        }
+    }
+
+    impl BeforeSpaceCreated<TestRuntime> for MockBeforeSpaceCreated {
+        fn before_space_created(creator: AccountId, space: &mut Space<TestRuntime>) -> DispatchResult {
+            <SpaceFollows as BeforeSpaceCreated<TestRuntime>>::before_space_created(creator, space)?;
+
+            if FAIL_BEFORE_SPACE_CREATED.with(|value| *value.borrow()) {
+                return Err(DispatchError::Other(BEFORE_SPACE_CREATED_FAILED));
+            }
+            Ok(())
+        }
+    }
+
+    /// Identity provider that treats accounts as verified once they are passed to `set_verified`.
+    pub struct MockIdentityProvider;
+    impl MockIdentityProvider {
+        fn set_verified(account: AccountId, is_verified: bool) {
+            VERIFIED_ACCOUNTS.with(|accounts| {
+                let mut accounts = accounts.borrow_mut();
+                if is_verified {
+                    accounts.insert(account);
+                } else {
+                    accounts.remove(&account);
+                }
+            });
+        }
+    }
+
+    impl IdentityProvider<AccountId> for MockIdentityProvider {
+        fn has_positive_judgement(account: &AccountId) -> bool {
+            VERIFIED_ACCOUNTS.with(|accounts| accounts.borrow().contains(account))
+        }
+    }
+
+    parameter_types! {
+        pub const BaseReputation: u32 = 1;
+    }
+
     impl pallet_profiles::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
+        type ContentValidator = Utils<TestRuntime>;
+        type BeforeProfileUpdated = ProfileHistory;
         type AfterProfileUpdated = ProfileHistory;
+        type IdentityProvider = MockIdentityProvider;
+        type BaseReputation = BaseReputation;
     }
 
-    parameter_types! {}
+    const MAX_PROFILE_HISTORY_RECORDS: u32 = 3;
+    parameter_types! {
+        pub const MaxProfileHistoryRecords: u32 = MAX_PROFILE_HISTORY_RECORDS;
+        pub const ProfileHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::KeepOldest;
+    }
 
-    impl pallet_profile_history::Trait for TestRuntime {}
+    impl pallet_profile_history::Trait for TestRuntime {
+        type MaxHistoryRecordsPerEntity = MaxProfileHistoryRecords;
+        type HistoryOverflowPolicy = ProfileHistoryOverflowPolicy;
+    }
 
-    parameter_types! {}
+    parameter_types! {
+        pub const MaxReactionsPerBatch: u16 = 3;
+    }
 
     impl pallet_reactions::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type PostReactionScores = Scores;
+        type AfterReactionUpdated = ReactionHistory;
+        type MaxReactionsPerBatch = MaxReactionsPerBatch;
+        type WeightInfo = ();
+    }
+
+    parameter_types! {
+        pub const MaxReactionHistoryRecords: u32 = 3;
+        pub const ReactionHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+    }
+
+    impl pallet_reaction_history::Trait for TestRuntime {
+        type MaxHistoryRecordsPerEntity = MaxReactionHistoryRecords;
+        type HistoryOverflowPolicy = ReactionHistoryOverflowPolicy;
     }
 
     parameter_types! {
         pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+        pub const MaxRolesPerSpace: u16 = 100;
+        pub const MaxRoleHistoryRecordsPerSpace: u32 = 100;
+        pub const RoleHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
     }
 
     impl pallet_roles::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
+        type ContentValidator = Utils<TestRuntime>;
         type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+        type MaxRolesPerSpace = MaxRolesPerSpace;
         type Spaces = Spaces;
         type SpaceFollows = SpaceFollows;
+        type UserResolver = Spaces;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
+        type MaxRoleHistoryRecordsPerSpace = MaxRoleHistoryRecordsPerSpace;
+        type RoleHistoryOverflowPolicy = RoleHistoryOverflowPolicy;
+        type WeightInfo = ();
     }
 
     parameter_types! {
@@ -189,10 +433,14 @@ mod tests {
         pub const ShareCommentActionWeight: i16 = 5;
         pub const UpvoteCommentActionWeight: i16 = 4;
         pub const DownvoteCommentActionWeight: i16 = -2;
+
+        pub const ScoreDecayPeriodInBlocks: BlockNumber = 10;
+        pub const MaxAccountsPerPostScoresCleanup: u16 = 2;
+        pub const ScoringPeriodInBlocks: BlockNumber = 10;
     }
 
     impl pallet_scores::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
 
         type FollowSpaceActionWeight = FollowSpaceActionWeight;
         type FollowAccountActionWeight = FollowAccountActionWeight;
@@ -205,54 +453,180 @@ mod tests {
         type ShareCommentActionWeight = ShareCommentActionWeight;
         type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
         type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+        type ScoreDecayPeriodInBlocks = ScoreDecayPeriodInBlocks;
+
+        type SetScoringWeightsOrigin = system::EnsureRoot<AccountId>;
+        type MaxAccountsPerPostScoresCleanup = MaxAccountsPerPostScoresCleanup;
+        type ScoringPeriodInBlocks = ScoringPeriodInBlocks;
+        type MaxScoredActionsPerPeriod = MaxScoredActionsPerPeriod;
     }
 
     parameter_types! {}
 
     impl pallet_space_follows::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type BeforeSpaceFollowed = Scores;
         type BeforeSpaceUnfollowed = Scores;
+        type UnsignedFollowSignature = TestSignature;
+        type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+        type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+        type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+        type UnsignedFollowPriority = UnsignedFollowPriority;
+        type UnsignedFollowLongevity = UnsignedFollowLongevity;
+        type AutoFollowOwnSpace = AutoFollowOwnSpace;
+        type WeightInfo = ();
     }
 
     parameter_types! {}
 
     impl pallet_space_ownership::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
+        type AfterSpaceOwnershipChanged = SpaceHistory;
     }
 
     const HANDLE_DEPOSIT: u64 = 5;
     parameter_types! {
         pub const HandleDeposit: u64 = HANDLE_DEPOSIT;
+        pub const RestrictReservedSpaces: bool = true;
+        pub const IdempotencyKeyTtl: u64 = 10;
+        pub const MaxIdempotencyKeysRemovedPerBlock: u32 = 2;
     }
 
     impl pallet_spaces::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
+        type ContentValidator = Utils<TestRuntime>;
         type Currency = Balances;
         type Roles = Roles;
         type SpaceFollows = SpaceFollows;
-        type BeforeSpaceCreated = SpaceFollows;
+        type SpacePosts = Posts;
+        type BeforeSpaceCreated = MockBeforeSpaceCreated;
+        type BeforeSpaceUpdated = SpaceHistory;
         type AfterSpaceUpdated = SpaceHistory;
         type IsAccountBlocked = Moderation;
         type IsContentBlocked = Moderation;
         type HandleDeposit = HandleDeposit;
+        type RestrictReservedSpaces = RestrictReservedSpaces;
+        type IdempotencyKeyTtl = IdempotencyKeyTtl;
+        type MaxIdempotencyKeysRemovedPerBlock = MaxIdempotencyKeysRemovedPerBlock;
+        type HandleChangeCooldown = HandleChangeCooldown;
+        type CreateRootSpaceOrigin = CreateRootSpaceOrigin;
+        type WeightInfo = ();
     }
 
-    parameter_types! {}
+    const MAX_SPACE_HISTORY_RECORDS: u32 = 3;
+    parameter_types! {
+        pub const MaxSpaceHistoryRecords: u32 = MAX_SPACE_HISTORY_RECORDS;
+        pub const SpaceHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+    }
 
-    impl pallet_space_history::Trait for TestRuntime {}
+    impl pallet_space_history::Trait for TestRuntime {
+        type MaxHistoryRecordsPerEntity = MaxSpaceHistoryRecords;
+        type HistoryOverflowPolicy = SpaceHistoryOverflowPolicy;
+    }
 
     parameter_types! {
         pub const DefaultAutoblockThreshold: u16 = 20;
+        pub const MaxModerationHistoryRecords: u32 = 100;
+        pub const ModerationHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+        pub const MaxReportsPerBatch: u16 = 20;
     }
 
     impl pallet_moderation::Trait for TestRuntime {
-        type Event = ();
+        type Event = TestEvent;
         type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+        type MaxModerationHistoryRecords = MaxModerationHistoryRecords;
+        type ModerationHistoryOverflowPolicy = ModerationHistoryOverflowPolicy;
+        type MaxReportsPerBatch = MaxReportsPerBatch;
+    }
+
+    const DONATION_FEE_PERCENT: u32 = 10;
+    parameter_types! {
+        pub const DonationFee: Perbill = Perbill::from_percent(DONATION_FEE_PERCENT);
+    }
+
+    impl pallet_donations::Trait for TestRuntime {
+        type Event = TestEvent;
+        type DonationFee = DonationFee;
+    }
+
+    parameter_types! {
+        pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+        pub const MaxScheduledPerBlock: u32 = 50;
+    }
+
+    impl pallet_scheduler::Trait for TestRuntime {
+        type Event = TestEvent;
+        type Origin = Origin;
+        type PalletsOrigin = OriginCaller;
+        type Call = Call;
+        type MaximumWeight = MaximumSchedulerWeight;
+        type ScheduleOrigin = system::EnsureRoot<AccountId>;
+        type MaxScheduledPerBlock = MaxScheduledPerBlock;
+        type WeightInfo = ();
+    }
+
+    const DAILY_PERIOD_IN_BLOCKS: BlockNumber = 10;
+    parameter_types! {
+        pub const DailyPeriodInBlocks: BlockNumber = DAILY_PERIOD_IN_BLOCKS;
+        pub const WeeklyPeriodInBlocks: BlockNumber = DAILY_PERIOD_IN_BLOCKS * 7;
+        pub const MonthlyPeriodInBlocks: BlockNumber = DAILY_PERIOD_IN_BLOCKS * 30;
+        pub const QuarterlyPeriodInBlocks: BlockNumber = DAILY_PERIOD_IN_BLOCKS * 30 * 3;
+        pub const YearlyPeriodInBlocks: BlockNumber = DAILY_PERIOD_IN_BLOCKS * 365;
+        pub const MaxMissedPayments: u32 = 0;
+    }
+
+    impl pallet_subscriptions::Trait for TestRuntime {
+        type Event = TestEvent;
+        type Subscription = Call;
+        type PalletsOrigin = OriginCaller;
+        type Scheduler = Scheduler;
+        type DailyPeriodInBlocks = DailyPeriodInBlocks;
+        type WeeklyPeriodInBlocks = WeeklyPeriodInBlocks;
+        type MonthlyPeriodInBlocks = MonthlyPeriodInBlocks;
+        type QuarterlyPeriodInBlocks = QuarterlyPeriodInBlocks;
+        type YearlyPeriodInBlocks = YearlyPeriodInBlocks;
+        type MaxMissedPayments = MaxMissedPayments;
+    }
+
+    parameter_types! {
+        pub const TransactionByteFee: u64 = 1;
+    }
+
+    impl pallet_transaction_payment::Trait for TestRuntime {
+        type Currency = Balances;
+        type OnTransactionPayment = ();
+        type TransactionByteFee = TransactionByteFee;
+        type WeightToFee = IdentityFee<u64>;
+        type FeeMultiplierUpdate = ();
+    }
+
+    pub struct SessionKeysProxyFilter;
+    impl Filter<Call> for SessionKeysProxyFilter {
+        fn filter(c: &Call) -> bool {
+            matches!(*c, Call::Reactions(..))
+        }
+    }
+
+    const SESSION_KEY_BOND: u64 = 10_000_000_000;
+    parameter_types! {
+        pub const MaxSessionKeysPerAccount: u16 = 2;
+        pub const BaseSessionKeyBond: u64 = SESSION_KEY_BOND;
+    }
+
+    impl pallet_session_keys::Trait for TestRuntime {
+        type Event = TestEvent;
+        type Call = Call;
+        type MaxSessionKeysPerAccount = MaxSessionKeysPerAccount;
+        type BaseFilter = SessionKeysProxyFilter;
+        type BaseSessionKeyBond = BaseSessionKeyBond;
     }
 
     type System = system::Module<TestRuntime>;
     type Balances = pallet_balances::Module<TestRuntime>;
+    type Scheduler = pallet_scheduler::Module<TestRuntime>;
+
+    type Donations = pallet_donations::Module<TestRuntime>;
 
     type Posts = pallet_posts::Module<TestRuntime>;
     type PostHistory = pallet_post_history::Module<TestRuntime>;
@@ -260,6 +634,7 @@ mod tests {
     type Profiles = pallet_profiles::Module<TestRuntime>;
     type ProfileHistory = pallet_profile_history::Module<TestRuntime>;
     type Reactions = pallet_reactions::Module<TestRuntime>;
+    type ReactionHistory = pallet_reaction_history::Module<TestRuntime>;
     type Roles = pallet_roles::Module<TestRuntime>;
     type Scores = pallet_scores::Module<TestRuntime>;
     type SpaceFollows = pallet_space_follows::Module<TestRuntime>;
@@ -267,40 +642,153 @@ mod tests {
     type SpaceOwnership = pallet_space_ownership::Module<TestRuntime>;
     type Spaces = pallet_spaces::Module<TestRuntime>;
     type Moderation = pallet_moderation::Module<TestRuntime>;
+    type Subscriptions = pallet_subscriptions::Module<TestRuntime>;
+    type SessionKeys = pallet_session_keys::Module<TestRuntime>;
 
     pub type AccountId = u64;
     type BlockNumber = u64;
 
 
-    pub struct ExtBuilder;
+    /// A role to create in a space (owned by ACCOUNT1) and grant to the given accounts.
+    type RoleSetup = (SpaceId, Vec<SpacePermission>, Vec<AccountId>);
+
+    /// A space to create at genesis: (space id, handle, content).
+    type GenesisSpace = (SpaceId, Option<Vec<u8>>, Content);
+
+    /// Builds test externalities at BlockNumber 1, e.g.:
+    /// `ExtBuilder::default().with_spaces(2).with_posts_per_space(3).build_ext()`.
+    ///
+    /// All spaces are created by ACCOUNT1: the first one (SPACE1) with the default handle
+    /// and the rest without a handle. Then posts are created by ACCOUNT1 space by space,
+    /// so the posts of SPACE1 get the first post ids. Roles are created and granted last,
+    /// in the given order.
+    pub struct ExtBuilder {
+        balances: Vec<(AccountId, u64)>,
+        reserved_spaces: bool,
+        genesis_spaces: Vec<GenesisSpace>,
+        spaces: u32,
+        posts_per_space: u32,
+        roles: Vec<RoleSetup>,
+        reaction_kind_weights: Vec<(ReactionKind, i16)>,
+    }
+
+    impl Default for ExtBuilder {
+        fn default() -> Self {
+            Self {
+                balances: (ACCOUNT1..=ACCOUNT3).map(|account| (account, 100)).collect(),
+                reserved_spaces: false,
+                genesis_spaces: Vec::new(),
+                spaces: 0,
+                posts_per_space: 0,
+                roles: Vec::new(),
+                reaction_kind_weights: Vec::new(),
+            }
+        }
+    }
 
-    // TODO: make created space/post/comment configurable or by default
     impl ExtBuilder {
-        fn configure_storages(storage: &mut Storage) {
-            let mut accounts = Vec::new();
-            for account in ACCOUNT1..=ACCOUNT3 {
-                accounts.push(account);
-            }
+        /// Replace the default balances (100 for each of ACCOUNT1-ACCOUNT3).
+        pub fn with_balances(mut self, balances: Vec<(AccountId, u64)>) -> Self {
+            self.balances = balances;
+            self
+        }
 
-            let _ = pallet_balances::GenesisConfig::<TestRuntime> {
-                balances: accounts.iter().cloned().map(|k|(k, 100)).collect()
-            }.assimilate_storage(storage);
+        /// Create the reserved spaces owned by ACCOUNT1 at genesis.
+        pub fn with_reserved_spaces(mut self) -> Self {
+            self.reserved_spaces = true;
+            self
         }
 
-        /// Default ext configuration with BlockNumber 1
-        pub fn build() -> TestExternalities {
+        /// Create the reserved spaces and the given spaces owned by ACCOUNT1 at genesis.
+        pub fn with_genesis_spaces(mut self, spaces: Vec<GenesisSpace>) -> Self {
+            self.reserved_spaces = true;
+            self.genesis_spaces = spaces;
+            self
+        }
+
+        pub fn with_spaces(mut self, spaces: u32) -> Self {
+            self.spaces = spaces;
+            self
+        }
+
+        pub fn with_posts_per_space(mut self, posts_per_space: u32) -> Self {
+            self.posts_per_space = posts_per_space;
+            self
+        }
+
+        pub fn with_roles(mut self, roles: Vec<RoleSetup>) -> Self {
+            self.roles = roles;
+            self
+        }
+
+        /// Set weights of the reaction kinds in the genesis config of the Scores pallet.
+        pub fn with_reaction_kind_weights(mut self, weights: Vec<(ReactionKind, i16)>) -> Self {
+            self.reaction_kind_weights = weights;
+            self
+        }
+
+        pub fn build_ext(self) -> TestExternalities {
             let mut storage = system::GenesisConfig::default()
                 .build_storage::<TestRuntime>()
                 .unwrap();
 
-            Self::configure_storages(&mut storage);
+            let _ = pallet_balances::GenesisConfig::<TestRuntime> {
+                balances: self.balances.clone(),
+            }.assimilate_storage(&mut storage);
+
+            if self.reserved_spaces {
+                let _ = pallet_spaces::GenesisConfig::<TestRuntime> {
+                    endowed_account: ACCOUNT1,
+                    spaces: self.genesis_spaces.clone(),
+                }.assimilate_storage(&mut storage);
+            }
+
+            let _ = pallet_scores::GenesisConfig {
+                reaction_kind_weights: self.reaction_kind_weights.clone(),
+            }.assimilate_storage(&mut storage);
 
             let mut ext = TestExternalities::from(storage);
-            ext.execute_with(|| System::set_block_number(1));
+            ext.execute_with(|| {
+                System::set_block_number(1);
+
+                let mut space_ids = Vec::new();
+                for i in 0..self.spaces {
+                    space_ids.push(Spaces::next_space_id());
+                    if i == 0 {
+                        Self::add_default_space();
+                    } else {
+                        Self::add_space_with_no_handle();
+                    }
+                }
+
+                for space_id in space_ids.iter() {
+                    for _ in 0..self.posts_per_space {
+                        assert_ok!(_create_post(None, Some(Some(*space_id)), None, None));
+                    }
+                }
+
+                for (space_id, permissions, accounts) in self.roles.iter() {
+                    let role_id = Roles::next_role_id();
+                    assert_ok!(_create_role(None, Some(*space_id), None, None, Some(permissions.clone())));
+
+                    let users = accounts.iter().cloned().map(User::Account).collect();
+                    assert_ok!(_grant_role(None, Some(role_id), Some(users)));
+                }
+            });
 
             ext
         }
 
+        /// Default ext configuration with BlockNumber 1
+        pub fn build() -> TestExternalities {
+            Self::default().build_ext()
+        }
+
+        /// Custom ext configuration with reserved spaces owned by ACCOUNT1 and BlockNumber 1
+        pub fn build_with_reserved_spaces() -> TestExternalities {
+            Self::default().with_reserved_spaces().build_ext()
+        }
+
         fn add_default_space() {
             assert_ok!(_create_default_space());
         }
@@ -321,16 +809,12 @@ mod tests {
 
         /// Custom ext configuration with SpaceId 1 and BlockNumber 1
         pub fn build_with_space() -> TestExternalities {
-            let mut ext = Self::build();
-            ext.execute_with(|| Self::add_default_space());
-            ext
+            Self::default().with_spaces(1).build_ext()
         }
 
         /// Custom ext configuration with SpaceId 1, PostId 1 and BlockNumber 1
         pub fn build_with_post() -> TestExternalities {
-            let mut ext = Self::build();
-            ext.execute_with(|| Self::add_post());
-            ext
+            Self::default().with_spaces(1).with_posts_per_space(1).build_ext()
         }
 
         /// Custom ext configuration with SpaceId 1, PostId 1, PostId 2 (as comment) and BlockNumber 1
@@ -366,25 +850,13 @@ mod tests {
 
         /// Custom ext configuration with specified permissions granted (includes SpaceId 1)
         pub fn build_with_a_few_roles_granted_to_account2(perms: Vec<SP>) -> TestExternalities {
-            let mut ext = Self::build_with_space();
-
-            ext.execute_with(|| {
-                let user = User::Account(ACCOUNT2);
-                assert_ok!(_create_role(
-                    None,
-                    None,
-                    None,
-                    None,
-                    Some(perms)
-                ));
-                // RoleId 1
-                assert_ok!(_create_default_role()); // RoleId 2
-
-                assert_ok!(_grant_role(None, Some(ROLE1), Some(vec![user.clone()])));
-                assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![user])));
-            });
-
-            ext
+            Self::default()
+                .with_spaces(1)
+                .with_roles(vec![
+                    (SPACE1, perms, vec![ACCOUNT2]), // RoleId 1
+                    (SPACE1, permission_set_default(), vec![ACCOUNT2]), // RoleId 2
+                ])
+                .build_ext()
         }
 
         /// Custom ext configuration with space follow without Space
@@ -416,12 +888,46 @@ mod tests {
     const REACTION1: ReactionId = 1;
     const REACTION2: ReactionId = 2;
 
+    const PLAN1: SubscriptionPlanId = 1;
+    const SUBSCRIPTION1: SubscriptionId = 1;
+
+    /// Events deposited in the current block.
+    fn events() -> Vec<TestEvent> {
+        System::events().into_iter().map(|record| record.event).collect()
+    }
+
+    /// Followers of a space sorted by account, as they are not stored in the order they followed.
+    fn sorted_space_followers(space_id: SpaceId) -> Vec<AccountId> {
+        let mut followers = SpaceFollows::space_followers(space_id);
+        followers.sort();
+        followers
+    }
+
+    /// Move to the block `n` executing the tasks scheduled for every block in between.
+    fn run_to_block(n: BlockNumber) {
+        while System::block_number() < n {
+            System::set_block_number(System::block_number() + 1);
+            Scheduler::on_initialize(System::block_number());
+            Subscriptions::on_finalize(System::block_number());
+        }
+    }
+
     /// Lowercase a handle and then try to find a space id by it.
     fn find_space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId> {
         let lc_handle = Utils::<TestRuntime>::lowercase_handle(handle);
         Spaces::space_id_by_handle(lc_handle)
     }
 
+    /// Lowercase a username and then try to find an account by it.
+    fn find_account_by_profile_username(username: Vec<u8>) -> Option<AccountId> {
+        let lc_username = Utils::<TestRuntime>::lowercase_handle(username);
+        Profiles::account_by_profile_username(lc_username)
+    }
+
+    fn profile_username() -> Vec<u8> {
+        b"Profile_Username".to_vec()
+    }
+
     fn space_handle() -> Vec<u8> {
         b"Space_Handle".to_vec()
     }
@@ -593,7 +1099,19 @@ mod tests {
             parent_id_opt.unwrap_or(None),
             handle.unwrap_or_else(|| Some(space_handle())),
             content.unwrap_or_else(space_content_ipfs),
-            permissions.unwrap_or(None)
+            permissions.unwrap_or(None),
+            None
+        )
+    }
+
+    fn _create_space_with_idempotency_key(origin: Option<Origin>, key: H256) -> DispatchResult {
+        Spaces::create_space(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            None,
+            None,
+            space_content_ipfs(),
+            None,
+            Some(key)
         )
     }
 
@@ -609,6 +1127,59 @@ mod tests {
         )
     }
 
+    fn _update_space_settings(origin: Option<Origin>, shares_disabled: Option<bool>) -> DispatchResult {
+        Spaces::update_space_settings(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            SpaceSettingsUpdate {
+                shares_disabled: Some(shares_disabled),
+                reject_duplicate_content: None,
+                min_reputation_to_post: None,
+                min_reputation_to_comment: None,
+            },
+        )
+    }
+
+    fn _reject_duplicate_content_in_space(space_id: SpaceId) -> DispatchResult {
+        Spaces::update_space_settings(
+            Origin::signed(ACCOUNT1),
+            space_id,
+            SpaceSettingsUpdate {
+                shares_disabled: None,
+                reject_duplicate_content: Some(Some(true)),
+                min_reputation_to_post: None,
+                min_reputation_to_comment: None,
+            },
+        )
+    }
+
+    fn _set_min_reputation_in_space(to_post: Option<u32>, to_comment: Option<u32>) -> DispatchResult {
+        Spaces::update_space_settings(
+            Origin::signed(ACCOUNT1),
+            SPACE1,
+            SpaceSettingsUpdate {
+                shares_disabled: None,
+                reject_duplicate_content: None,
+                min_reputation_to_post: Some(to_post),
+                min_reputation_to_comment: Some(to_comment),
+            },
+        )
+    }
+
+    fn set_reputation(account: AccountId, reputation: u32) {
+        let mut social_account = Profiles::get_or_new_social_account(account);
+        social_account.reputation = reputation;
+        SocialAccountById::<TestRuntime>::insert(account, social_account);
+    }
+
+    fn _transfer_handle(origin: Option<Origin>, from_space_id: SpaceId, to_space_id: SpaceId) -> DispatchResult {
+        Spaces::transfer_handle(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            from_space_id,
+            to_space_id,
+        )
+    }
+
     fn _default_follow_space() -> DispatchResult {
         _follow_space(None, None)
     }
@@ -646,6 +1217,17 @@ mod tests {
             space_id_opt.unwrap_or(Some(SPACE1)),
             extension.unwrap_or_else(extension_regular_post),
             content.unwrap_or_else(post_content_ipfs),
+            None,
+        )
+    }
+
+    fn _create_post_with_idempotency_key(origin: Option<Origin>, key: H256) -> DispatchResult {
+        Posts::create_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            Some(SPACE1),
+            extension_regular_post(),
+            post_content_ipfs(),
+            Some(key),
         )
     }
 
@@ -682,6 +1264,13 @@ mod tests {
         )
     }
 
+    fn _delete_post(origin: Option<Origin>, post_id: Option<PostId>) -> DispatchResult {
+        Posts::delete_post(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            post_id.unwrap_or(POST1),
+        )
+    }
+
     fn _create_default_comment() -> DispatchResult {
         _create_comment(None, None, None, None)
     }
@@ -737,6 +1326,78 @@ mod tests {
         )
     }
 
+    fn _create_post_reactions(
+        origin: Option<Origin>,
+        reactions: Vec<(PostId, ReactionKind)>,
+    ) -> DispatchResult {
+        Reactions::create_post_reactions(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            reactions,
+        )
+    }
+
+    fn _donate(
+        origin: Option<Origin>,
+        recipient: Option<User<AccountId>>,
+        post_id_opt: Option<Option<PostId>>,
+        amount: Option<u64>,
+    ) -> DispatchResult {
+        Donations::donate(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            recipient.unwrap_or(User::Account(ACCOUNT1)),
+            post_id_opt.unwrap_or(Some(POST1)),
+            amount.unwrap_or(50),
+            Content::None,
+        )
+    }
+
+    fn _create_default_subscription_plan() -> DispatchResult {
+        _create_subscription_plan(None, None, None)
+    }
+
+    fn _create_subscription_plan(
+        origin: Option<Origin>,
+        space_id: Option<SpaceId>,
+        price: Option<u64>,
+    ) -> DispatchResult {
+        Subscriptions::create_subscription_plan(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+            None,
+            price.unwrap_or(10),
+            SubscriptionPeriod::Daily,
+            Content::None,
+        )
+    }
+
+    fn _subscribe(origin: Option<Origin>, plan_id: Option<SubscriptionPlanId>) -> DispatchResult {
+        Subscriptions::subscribe(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            plan_id.unwrap_or(PLAN1),
+            None,
+        )
+    }
+
+    fn _cancel_subscription(origin: Option<Origin>, subscription_id: Option<SubscriptionId>) -> DispatchResult {
+        Subscriptions::cancel_subscription(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT2)),
+            subscription_id.unwrap_or(SUBSCRIPTION1),
+        )
+    }
+
+    /// Add ACCOUNT3 as a session key of ACCOUNT2 that has enough balance to pay for proxied calls.
+    fn _add_default_session_key() -> DispatchResult {
+        let _ = Balances::deposit_creating(&ACCOUNT2, SESSION_KEY_BOND * 10);
+        SessionKeys::add_key(Origin::signed(ACCOUNT2), ACCOUNT3, 100, None)
+    }
+
+    fn _proxy(origin: Option<Origin>, call: Call) -> DispatchResult {
+        SessionKeys::proxy(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT3)),
+            Box::new(call),
+        )
+    }
+
     fn _create_comment_reaction(
         origin: Option<Origin>,
         post_id: Option<PostId>,
@@ -789,15 +1450,17 @@ mod tests {
     }
 
     fn _create_default_profile() -> DispatchResult {
-        _create_profile(None, None)
+        _create_profile(None, None, None)
     }
 
     fn _create_profile(
         origin: Option<Origin>,
+        username: Option<Option<Vec<u8>>>,
         content: Option<Content>
     ) -> DispatchResult {
         Profiles::create_profile(
             origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            username.unwrap_or(None),
             content.unwrap_or_else(profile_content_ipfs),
         )
     }
@@ -809,12 +1472,26 @@ mod tests {
         Profiles::update_profile(
             origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
             ProfileUpdate {
+                username: None,
                 content,
             },
         )
     }
 
-    fn _default_follow_account() -> DispatchResult {
+    fn _update_profile_username(
+        origin: Option<Origin>,
+        username: Option<Vec<u8>>
+    ) -> DispatchResult {
+        Profiles::update_profile(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            ProfileUpdate {
+                username: Some(username),
+                content: None,
+            },
+        )
+    }
+
+    fn _default_follow_account() -> DispatchResult {
         _follow_account(None, None)
     }
 
@@ -856,6 +1533,16 @@ mod tests {
         Scores::score_post_on_reaction(account, post, kind)
     }
 
+    fn _set_scoring_weights(
+        origin: Option<Origin>,
+        weights: Vec<(ScoringAction, i16)>,
+    ) -> DispatchResult {
+        Scores::set_scoring_weights(
+            origin.unwrap_or_else(Origin::root),
+            weights,
+        )
+    }
+
     fn _transfer_default_space_ownership() -> DispatchResult {
         _transfer_space_ownership(None, None, None)
     }
@@ -898,6 +1585,13 @@ mod tests {
         )
     }
 
+    fn _renounce_space_ownership(origin: Option<Origin>, space_id: Option<SpaceId>) -> DispatchResult {
+        SpaceOwnership::renounce_space_ownership(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            space_id.unwrap_or(SPACE1),
+        )
+    }
+
     /* ---------------------------------------------------------------------------------------------- */
 
     // TODO: fix copy-paste from pallet_roles
@@ -906,7 +1600,6 @@ mod tests {
     type RoleId = u64;
 
     const ROLE1: RoleId = 1;
-    const ROLE2: RoleId = 2;
 
     fn default_role_content_ipfs() -> Content {
         Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec())
@@ -1075,6 +1768,64 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_comment_should_fail_when_content_is_blocked_in_space_of_moved_root_post() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(
+                _update_entity_status(
+                    None,
+                    Some(EntityId::Content(valid_content_ipfs())),
+                    Some(SPACE2),
+                    Some(Some(EntityStatus::Blocked))
+                )
+            );
+            assert_ok!(_move_post_1_to_space_2());
+
+            // A comment should be checked against the current space of its root post,
+            // even if the original space of a post is provided.
+            assert_noop!(
+                _create_post(
+                    None,
+                    Some(Some(SPACE1)),
+                    Some(extension_comment(None, POST1)),
+                    Some(valid_content_ipfs()),
+                ), UtilsError::<TestRuntime>::ContentIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_no_permission_in_space_of_moved_root_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let no_comments = SpacePermissions {
+                none: Some(vec![SP::CreateComments].into_iter().collect()),
+                everyone: None,
+                follower: None,
+                space_owner: None,
+            };
+            assert_ok!(_create_space(None, Some(None), None, Some(Some(no_comments))));
+
+            // Comments are allowed in the original space of the post:
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+
+            assert_ok!(_move_post_1_to_space_2());
+
+            // The permission should be checked in the space that owns the root post now:
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::NoPermissionToCreateComments
+            );
+            assert_noop!(
+                _create_post(
+                    Some(Origin::signed(ACCOUNT2)),
+                    Some(Some(SPACE1)),
+                    Some(extension_comment(None, POST1)),
+                    None,
+                ), PostsError::<TestRuntime>::NoPermissionToCreateComments
+            );
+        });
+    }
+
     #[test]
     fn create_subspace_should_fail_when_account_is_blocked() {
         ExtBuilder::build_with_post().execute_with(|| {
@@ -1259,6 +2010,99 @@ mod tests {
         });
     }
 
+    #[test]
+    fn get_space_by_handle_should_work_with_mixed_case_handle() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let space = Spaces::get_space_by_handle(b"sPaCe_HaNdLe".to_vec()).unwrap();
+
+            assert_eq!(space.id, SPACE1);
+            assert_eq!(space.owner, ACCOUNT1);
+            assert_eq!(space.created_by, ACCOUNT1);
+            assert_eq!(space.handle, Some(space_handle()));
+            assert_eq!(space.content, space_content_ipfs());
+            assert!(space.updated_by.is_none());
+        });
+    }
+
+    #[test]
+    fn get_space_by_handle_should_return_none_for_unknown_handle() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert!(Spaces::get_space_by_handle(b"unknown_handle".to_vec()).is_none());
+        });
+    }
+
+    #[test]
+    fn genesis_spaces_should_be_created_with_handles() {
+        const SEEDED_SPACE: SpaceId = 2001;
+
+        ExtBuilder::default()
+            .with_genesis_spaces(vec![
+                (RESERVED_SPACE1, Some(b"Reserved_Handle".to_vec()), space_content_ipfs()),
+                (SEEDED_SPACE, Some(space_handle()), Content::None),
+            ])
+            .build_ext()
+            .execute_with(|| {
+                assert_eq!(Spaces::space_id_by_handle(b"reserved_handle".to_vec()), Some(RESERVED_SPACE1));
+                assert_eq!(Spaces::space_id_by_handle(b"space_handle".to_vec()), Some(SEEDED_SPACE));
+
+                let reserved_space = Spaces::space_by_id(RESERVED_SPACE1).unwrap();
+                assert_eq!(reserved_space.owner, ACCOUNT1);
+                assert_eq!(reserved_space.content, space_content_ipfs());
+
+                // Blank reserved spaces are still created:
+                assert!(Spaces::space_by_id(RESERVED_SPACE1 + 1).unwrap().handle.is_none());
+
+                assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SEEDED_SPACE]);
+                assert_eq!(Spaces::next_space_id(), SEEDED_SPACE + 1);
+            });
+    }
+
+    #[test]
+    #[should_panic(expected = "Genesis space handle should be valid and unique")]
+    fn genesis_spaces_should_panic_on_duplicate_handle() {
+        ExtBuilder::default()
+            .with_genesis_spaces(vec![
+                (RESERVED_SPACE1, Some(space_handle()), Content::None),
+                (RESERVED_SPACE1 + 1, Some(b"space_handle".to_vec()), Content::None),
+            ])
+            .build_ext();
+    }
+
+    #[test]
+    fn would_handle_collide_should_return_true_for_taken_handle_in_any_case() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert!(Spaces::would_handle_collide(space_handle()));
+            assert!(Spaces::would_handle_collide(b"SPACE_HANDLE".to_vec()));
+
+            // A taken handle is reported as colliding, so creating a space with it fails:
+            assert_noop!(
+                _create_space(None, Some(Some(b"sPaCe_HaNdLe".to_vec())), None, None),
+                SpacesError::<TestRuntime>::SpaceHandleIsNotUnique
+            );
+        });
+    }
+
+    #[test]
+    fn would_handle_collide_should_return_true_for_handle_of_reserved_space() {
+        ExtBuilder::build_with_reserved_spaces().execute_with(|| {
+            assert_ok!(_update_space(
+                None,
+                Some(RESERVED_SPACE1),
+                Some(space_update(Some(Some(b"reserved_handle".to_vec())), None, None))
+            ));
+
+            assert!(Spaces::would_handle_collide(b"Reserved_Handle".to_vec()));
+        });
+    }
+
+    #[test]
+    fn would_handle_collide_should_return_false_for_free_handle() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert!(!Spaces::would_handle_collide(b"free_handle".to_vec()));
+            assert_eq!(Spaces::space_id_by_handle(b"free_handle".to_vec()), None);
+        });
+    }
+
     #[test]
     fn create_space_should_store_handle_lowercase() {
         ExtBuilder::build().execute_with(|| {
@@ -1382,6 +2226,50 @@ mod tests {
         });
     }
 
+    #[test]
+    fn create_space_should_fail_when_origin_cannot_create_root_spaces() {
+        ExtBuilder::build().execute_with(|| {
+            CreateRootSpaceOrigin::allow_only(vec![ACCOUNT1]);
+
+            assert_noop!(
+                _create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None),
+                DispatchError::BadOrigin
+            );
+
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT1)), Some(None), None, None));
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
+        });
+    }
+
+    #[test]
+    fn create_subspace_should_work_when_origin_cannot_create_root_spaces() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            CreateRootSpaceOrigin::allow_only(vec![ACCOUNT2]);
+
+            // Subspaces are still gated only by the permissions of their parent space
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None));
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().parent_id, Some(SPACE1));
+        });
+    }
+
+    #[test]
+    fn update_space_should_fail_to_make_root_space_when_origin_cannot_create_root_spaces() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subspace(None, Some(Some(SPACE1)), Some(None), None, None)); // SpaceId 2
+            CreateRootSpaceOrigin::allow_only(vec![ACCOUNT2]);
+
+            let to_root_space = SpaceUpdate { parent_id: Some(None), ..space_update(None, None, None) };
+            assert_noop!(
+                _update_space(None, Some(SPACE2), Some(to_root_space.clone())),
+                DispatchError::BadOrigin
+            );
+
+            CreateRootSpaceOrigin::allow_only(vec![ACCOUNT1]);
+            assert_ok!(_update_space(None, Some(SPACE2), Some(to_root_space)));
+            assert!(Spaces::space_by_id(SPACE2).unwrap().parent_id.is_none());
+        });
+    }
+
     #[test]
     fn update_space_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
@@ -1422,6 +2310,40 @@ mod tests {
         });
     }
 
+    #[test]
+    fn update_space_should_deposit_permissions_updated_event() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let mut update = space_update(None, None, None);
+            update.permissions = Some(Some(SpacePermissions {
+                none: None,
+                everyone: None,
+                follower: None,
+                space_owner: Some(vec![SP::ManageRoles].into_iter().collect()),
+            }));
+
+            assert_ok!(_update_space(None, None, Some(update)));
+
+            let events = events();
+            assert!(events.contains(
+                &TestEvent::pallet_spaces(pallet_spaces::RawEvent::SpaceUpdated(ACCOUNT1, SPACE1))
+            ));
+            assert!(events.contains(
+                &TestEvent::pallet_spaces(pallet_spaces::RawEvent::SpacePermissionsUpdated(ACCOUNT1, SPACE1))
+            ));
+        });
+    }
+
+    #[test]
+    fn update_space_should_not_deposit_permissions_updated_event_when_permissions_not_changed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_space(None, None, Some(space_update(None, None, Some(true)))));
+
+            assert!(!events().contains(
+                &TestEvent::pallet_spaces(pallet_spaces::RawEvent::SpacePermissionsUpdated(ACCOUNT1, SPACE1))
+            ));
+        });
+    }
+
     #[test]
     fn update_space_should_work_when_one_of_roles_is_permitted() {
         ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(|| {
@@ -1463,6 +2385,30 @@ mod tests {
         });
     }
 
+    #[test]
+    fn update_space_should_unreserve_handle_deposit_from_new_owner_after_ownership_transfer() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+            assert_ok!(_accept_default_pending_ownership());
+
+            // The handle deposit has been moved to the new owner
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+            assert_eq!(Balances::reserved_balance(ACCOUNT2), HANDLE_DEPOSIT);
+            assert_eq!(Spaces::handle_deposit_by_space_id(SPACE1), Some((ACCOUNT2, HANDLE_DEPOSIT)));
+
+            assert_ok!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(update_for_space_handle(None))
+            ));
+
+            assert!(Balances::reserved_balance(ACCOUNT2).is_zero());
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+            assert!(Spaces::handle_deposit_by_space_id(SPACE1).is_none());
+            assert_eq!(find_space_id_by_handle(space_handle()), None);
+        });
+    }
+
     #[test]
     fn update_space_should_fail_when_no_updates_for_space_provided() {
         ExtBuilder::build_with_space().execute_with(|| {
@@ -1659,1840 +2605,5343 @@ mod tests {
         });
     }
 
-    // Post tests
     #[test]
-    fn create_post_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1 which is permitted by default
-
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::next_post_id(), POST2);
-
-            // Check whether data stored correctly
-            let post = Posts::post_by_id(POST1).unwrap();
-
-            assert_eq!(post.created.account, ACCOUNT1);
-            assert!(post.updated.is_none());
-            assert_eq!(post.hidden, false);
-
-            assert_eq!(post.space_id, Some(SPACE1));
-            assert_eq!(post.extension, extension_regular_post());
-
-            assert_eq!(post.content, post_content_ipfs());
-
-            assert_eq!(post.replies_count, 0);
-            assert_eq!(post.hidden_replies_count, 0);
-            assert_eq!(post.shares_count, 0);
-            assert_eq!(post.upvotes_count, 0);
-            assert_eq!(post.downvotes_count, 0);
-
-            assert_eq!(post.score, 0);
+    fn update_space_should_work_when_only_hidden_changed_with_hide_space_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideSpace]).execute_with(|| {
+            assert_ok!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1),
+                Some(space_update(None, None, Some(true)))
+            ));
+            assert!(Spaces::space_by_id(SPACE1).unwrap().hidden);
 
-            assert!(PostHistory::edit_history(POST1).is_empty());
+            assert_ok!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1),
+                Some(space_update(None, None, Some(false)))
+            ));
+            assert!(!Spaces::space_by_id(SPACE1).unwrap().hidden);
         });
     }
 
     #[test]
-    fn create_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_post(
+    fn update_space_should_fail_when_changing_more_than_hidden_with_hide_space_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideSpace]).execute_with(|| {
+            assert_noop!(_update_space(
                 Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1,
-                None, // RegularPost extension
-                None, // Default post content
-            ));
+                Some(SPACE1),
+                Some(space_update(Some(Some(b"new_handle".to_vec())), None, None))
+            ), SpacesError::<TestRuntime>::NoPermissionToUpdateSpace);
+
+            assert_noop!(_update_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1),
+                Some(space_update(None, Some(updated_space_content()), Some(true)))
+            ), SpacesError::<TestRuntime>::NoPermissionToUpdateSpace);
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_post_has_no_space_id() {
+    fn update_space_settings_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_create_post(
-                None,
-                Some(None),
-                None,
-                None
-            ), PostsError::<TestRuntime>::PostHasNoSpaceId);
-        });
-    }
+            assert!(Spaces::space_settings_by_id(SPACE1).is_none());
 
-    #[test]
-    fn create_post_should_fail_when_space_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_default_post(), SpacesError::<TestRuntime>::SpaceNotFound);
+            assert_ok!(_update_space_settings(None, Some(true)));
+
+            let settings = Spaces::space_settings_by_id(SPACE1).unwrap();
+            assert_eq!(settings.version, pallet_spaces::SPACE_SETTINGS_VERSION);
+            assert_eq!(settings.shares_disabled, Some(true));
+            assert!(events().contains(&TestEvent::pallet_spaces(
+                pallet_spaces::RawEvent::SpaceSettingsUpdated(ACCOUNT1, SPACE1)
+            )));
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_ipfs_cid_is_invalid() {
+    fn update_space_settings_should_fail_when_no_updates_provided() {
         ExtBuilder::build_with_space().execute_with(|| {
-            // Try to catch an error creating a regular post with invalid content
-            assert_noop!(_create_post(
-                None,
-                None,
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            assert_noop!(Spaces::update_space_settings(
+                Origin::signed(ACCOUNT1),
+                SPACE1,
+                SpaceSettingsUpdate::default()
+            ), SpacesError::<TestRuntime>::NoUpdatesForSpaceSettings);
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_account_has_no_permission() {
+    fn update_space_settings_should_fail_when_account_has_no_permission() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert_noop!(
+                _update_space_settings(Some(Origin::signed(ACCOUNT2)), Some(true)),
+                SpacesError::<TestRuntime>::NoPermissionToUpdateSpaceSettings
+            );
         });
     }
 
     #[test]
-    fn create_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_delete_default_role());
+    fn create_space_should_not_reserve_handle_when_before_space_created_fails() {
+        ExtBuilder::build().execute_with(|| {
+            MockBeforeSpaceCreated::set_failing(true);
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1,
-                None, // RegularPost extension
-                None, // Default post content
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            // The creator follows the space and the handle is checked before the hook fails,
+            // and none of it should remain:
+            assert_noop!(_create_default_space(), DispatchError::Other(BEFORE_SPACE_CREATED_FAILED));
+
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+            assert_eq!(find_space_id_by_handle(space_handle()), None);
+            assert_eq!(Spaces::handle_deposit_by_space_id(SPACE1), None);
+            assert!(!SpaceFollows::space_followed_by_account((ACCOUNT1, SPACE1)));
+
+            MockBeforeSpaceCreated::set_failing(false);
+            assert_ok!(_create_default_space());
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
         });
     }
 
     #[test]
-    fn update_post_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let expected_content_ipfs = updated_post_content();
+    fn update_space_should_not_change_anything_when_handle_deposit_cannot_be_reserved() {
+        const POOR_ACCOUNT: AccountId = 10;
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(
-                None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
-                None,
-                Some(
-                    post_update(
-                        None,
-                        Some(expected_content_ipfs.clone()),
-                        Some(true)
-                    )
-                )
-            ));
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_space(Some(Origin::signed(POOR_ACCOUNT)), Some(None), None, None)); // SPACE1
 
-            // Check whether post updates correctly
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.space_id, Some(SPACE1));
-            assert_eq!(post.content, expected_content_ipfs);
-            assert_eq!(post.hidden, true);
+            // The content is valid, but the handle deposit cannot be reserved:
+            assert_noop!(
+                _update_space(
+                    Some(Origin::signed(POOR_ACCOUNT)),
+                    None,
+                    Some(space_update(Some(Some(space_handle())), Some(updated_space_content()), None))
+                ),
+                pallet_balances::Error::<TestRuntime, pallet_balances::DefaultInstance>::InsufficientBalance
+            );
 
-            // Check whether history recorded correctly
-            let post_history = PostHistory::edit_history(POST1)[0].clone();
-            assert!(post_history.old_data.space_id.is_none());
-            assert_eq!(post_history.old_data.content, Some(post_content_ipfs()));
-            assert_eq!(post_history.old_data.hidden, Some(false));
+            assert_eq!(find_space_id_by_handle(space_handle()), None);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().content, space_content_ipfs());
         });
     }
 
-    fn check_if_post_moved_correctly(
-        moved_post_id: PostId,
-        old_space_id: SpaceId,
-        expected_new_space_id: SpaceId
-    ) {
-        let post: Post<TestRuntime> = Posts::post_by_id(moved_post_id).unwrap(); // `POST2` is a comment
-        let new_space_id = post.space_id.unwrap();
+    const HANDLE_CHANGE_COOLDOWN_BLOCKS: u64 = 10;
 
-        // Check that space id of the post has been updated from 1 to 2
-        assert_eq!(new_space_id, expected_new_space_id);
+    #[test]
+    fn update_space_should_fail_when_handle_changed_too_recently() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            HandleChangeCooldown::set(HANDLE_CHANGE_COOLDOWN_BLOCKS);
 
-        // Check that stats on the old space have been decreased
-        let old_space = Spaces::space_by_id(old_space_id).unwrap();
-        assert_eq!(old_space.posts_count, 0);
-        assert_eq!(old_space.hidden_posts_count, 0);
-        assert_eq!(old_space.score, 0);
+            let first_handle = b"first_handle".to_vec();
+            assert_ok!(_update_space(None, None, Some(space_update(Some(Some(first_handle.clone())), None, None))));
 
-        // Check that stats on the new space have been increased
-        let new_space = Spaces::space_by_id(new_space_id).unwrap();
-        assert_eq!(new_space.posts_count, 1);
-        assert_eq!(new_space.hidden_posts_count, if post.hidden { 1 } else { 0 });
-        assert_eq!(new_space.score, post.score);
+            System::set_block_number(System::block_number() + HANDLE_CHANGE_COOLDOWN_BLOCKS - 1);
+            assert_noop!(
+                _update_space(None, None, Some(space_update(Some(Some(b"second_handle".to_vec())), None, None))),
+                SpacesError::<TestRuntime>::HandleChangedTooRecently
+            );
+            assert_noop!(
+                _update_space(None, None, Some(space_update(Some(None), None, None))),
+                SpacesError::<TestRuntime>::HandleChangedTooRecently
+            );
+
+            // Updates that keep the handle are not limited:
+            assert_ok!(_update_space(
+                None, None, Some(space_update(Some(Some(first_handle.clone())), Some(updated_space_content()), None))
+            ));
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().handle, Some(first_handle));
+        });
     }
 
     #[test]
-    fn move_post_should_work() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            assert_ok!(_move_post_1_to_space_2());
+    fn update_space_should_change_handle_after_cooldown() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            HandleChangeCooldown::set(HANDLE_CHANGE_COOLDOWN_BLOCKS);
 
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1;
-            let expected_new_space_id = SPACE2;
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+            assert_ok!(_update_space(None, None, Some(space_update(Some(Some(b"first_handle".to_vec())), None, None))));
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+            System::set_block_number(System::block_number() + HANDLE_CHANGE_COOLDOWN_BLOCKS);
+            let second_handle = b"second_handle".to_vec();
+            assert_ok!(_update_space(None, None, Some(space_update(Some(Some(second_handle.clone())), None, None))));
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().handle, Some(second_handle));
+            assert_eq!(Spaces::last_handle_change_by_space_id(SPACE1), Some(System::block_number()));
         });
     }
 
     #[test]
-    fn move_post_should_work_when_space_id_none() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1; // Where post were before moving to `SpaceId:None`
-            let expected_new_space_id = SPACE2;
-
-            assert_ok!(_move_post_to_nowhere(moved_post_id));
-            assert_ok!(_move_post_1_to_space_2());
-
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+    fn transfer_handle_should_fail_when_handle_changed_too_recently() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            HandleChangeCooldown::set(HANDLE_CHANGE_COOLDOWN_BLOCKS);
+            assert_ok!(_create_space(None, Some(None), None, None)); // SPACE2 without a handle
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+            assert_ok!(_update_space(None, None, Some(space_update(Some(Some(b"first_handle".to_vec())), None, None))));
+            assert_noop!(
+                _transfer_handle(None, SPACE1, SPACE2),
+                SpacesError::<TestRuntime>::HandleChangedTooRecently
+            );
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+            System::set_block_number(System::block_number() + HANDLE_CHANGE_COOLDOWN_BLOCKS);
+            assert_ok!(_transfer_handle(None, SPACE1, SPACE2));
         });
     }
 
     #[test]
-    fn move_hidden_post_should_work() {
-        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            let moved_post_id = POST1;
-            let old_space_id = SPACE1;
-            let expected_new_space_id = SPACE2;
+    fn transfer_handle_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SPACE2 without a handle
+            let deposit_before = Spaces::handle_deposit_by_space_id(SPACE1);
+            System::reset_events();
 
-            // Hide the post before moving it
-            assert_ok!(_update_post(
-                None,
-                Some(moved_post_id),
-                Some(post_update(
-                    None,
-                    None,
-                    Some(true)
-                ))
-            ));
+            assert_ok!(_transfer_handle(None, SPACE1, SPACE2));
 
-            assert_ok!(_move_post_1_to_space_2());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().handle, None);
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().handle, Some(space_handle()));
+            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE2));
 
-            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+            // The deposit should move with the handle without being released:
+            assert_eq!(Spaces::handle_deposit_by_space_id(SPACE1), None);
+            assert_eq!(Spaces::handle_deposit_by_space_id(SPACE2), deposit_before);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
 
-            // Check that there are no posts ids in the old space
-            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+            let events = events();
+            assert!(!events.iter().any(|event| matches!(event, TestEvent::pallet_balances(_))));
+            assert!(events.contains(
+                &TestEvent::pallet_spaces(pallet_spaces::RawEvent::SpaceHandleTransferred(ACCOUNT1, SPACE1, SPACE2))
+            ));
 
-            // Check that there is the post id in the new space
-            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
+            // Both changes should be recorded in space history:
+            assert_eq!(SpaceHistory::edit_history(SPACE1)[0].old_data.handle, Some(Some(space_handle())));
+            assert_eq!(SpaceHistory::edit_history(SPACE2)[0].old_data.handle, Some(None));
         });
     }
 
     #[test]
-    fn move_hidden_post_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Note that we have not created a post that we are trying to move
-            assert_noop!(
-                _move_post_1_to_space_2(),
-                PostsError::<TestRuntime>::PostNotFound
-            );
-        });
-    }
+    fn transferred_handle_should_not_be_claimable_by_another_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SPACE2 without a handle
+            assert_ok!(_transfer_handle(None, SPACE1, SPACE2));
 
-    #[test]
-    fn move_hidden_post_should_fail_when_provided_space_not_found() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Note that we have not created a new space #2 before moving the post
             assert_noop!(
-                _move_post_1_to_space_2(),
-                SpacesError::<TestRuntime>::SpaceNotFound
+                _create_space(Some(Origin::signed(ACCOUNT2)), Some(Some(space_handle())), None, None),
+                SpacesError::<TestRuntime>::SpaceHandleIsNotUnique
             );
         });
     }
 
     #[test]
-    fn move_hidden_post_should_fail_origin_has_no_permission_to_create_posts() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Create a space #2 from account #2
-            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
+    fn transfer_handle_should_fail_when_spaces_have_different_owners() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None)); // SPACE2
 
-            // Should not be possible to move the post b/c it's owner is account #1
-            // when the space #2 is owned by account #2
             assert_noop!(
-                _move_post_1_to_space_2(),
-                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+                _transfer_handle(None, SPACE1, SPACE2),
+                SpacesError::<TestRuntime>::SpacesHaveDifferentOwners
             );
-        });
-    }
-
-    #[test]
-    fn move_post_should_fail_when_account_has_no_permission() {
-        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
             assert_noop!(
-                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
-                PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+                _transfer_handle(Some(Origin::signed(ACCOUNT2)), SPACE1, SPACE2),
+                SpacesError::<TestRuntime>::NotASpaceOwner
             );
         });
     }
 
     #[test]
-    fn move_post_should_fail_when_space_none_and_account_is_not_post_owner() {
-        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
-            assert_ok!(_move_post_to_nowhere(POST1));
+    fn transfer_handle_should_fail_when_destination_space_has_handle() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(Some(b"another_handle".to_vec())), None, None)); // SPACE2
+
             assert_noop!(
-                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
-                PostsError::<TestRuntime>::NotAPostOwner
+                _transfer_handle(None, SPACE1, SPACE2),
+                SpacesError::<TestRuntime>::DestinationSpaceHasHandle
+            );
+            assert_noop!(
+                _transfer_handle(None, SPACE1, SPACE1),
+                SpacesError::<TestRuntime>::DestinationSpaceHasHandle
             );
         });
     }
 
     #[test]
-    fn should_fail_when_trying_to_move_comment() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            assert_ok!(_create_space(None, Some(None), None, None));
+    fn transfer_handle_should_fail_when_source_space_has_no_handle() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SPACE2 without a handle
 
-            // Comments cannot be moved, they stick to their parent post
             assert_noop!(
-                _move_post(None, Some(POST2), None),
-                PostsError::<TestRuntime>::CannotUpdateSpaceIdOnComment
+                _transfer_handle(None, SPACE2, SPACE1),
+                SpacesError::<TestRuntime>::SpaceHasNoHandle
             );
         });
     }
 
     #[test]
-    fn update_post_should_work_after_transfer_space_ownership() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
+    fn recompute_space_stats_should_repair_corrupted_counters_over_two_calls() {
+        ExtBuilder::default().with_spaces(1).with_posts_per_space(3).build_ext().execute_with(|| {
+            assert_ok!(_update_post(None, Some(POST2), Some(post_update(None, None, Some(true)))));
+            let followers_count = SpaceFollows::space_followers_count(SPACE1);
+
+            SpaceById::<TestRuntime>::mutate(SPACE1, |space| {
+                let space = space.as_mut().unwrap();
+                space.posts_count = 10;
+                space.hidden_posts_count = 0;
+                space.followers_count = followers_count + 5;
+            });
+            // The follows are counted in storage, not copied from this counter
+            SpaceFollowersCount::insert(SPACE1, followers_count + 5);
+
+            // The first call checks POST1 and POST2 and does not change the counters yet
+            assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 2));
+            assert_eq!(
+                Spaces::space_stats_recount_by_id(SPACE1),
+                Some(SpaceStatsRecount {
+                    all_posts_checked: false,
+                    last_post_id: Some(POST2),
+                    hidden_posts_count: 1,
+                    last_follower_key: None,
+                    followers_count: 0,
+                })
             );
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 10);
 
-            assert_ok!(_transfer_default_space_ownership());
+            // The second call checks POST3, counts the followers and corrects the counters
+            assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 2));
+            assert!(Spaces::space_stats_recount_by_id(SPACE1).is_none());
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(None, None, Some(post_update)));
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 3);
+            assert_eq!(space.hidden_posts_count, 1);
+            assert_eq!(space.followers_count, followers_count);
+
+            assert!(events().contains(&TestEvent::pallet_spaces(
+                pallet_spaces::RawEvent::SpaceStatsRecomputed(SPACE1, -7, 1, -5)
+            )));
         });
     }
 
     #[test]
-    fn update_any_post_should_work_when_account_has_default_permission() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
-            );
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None, // SpaceId 1
-                None, // RegularPost extension
-                None // Default post content
-            )); // PostId 1
+    fn recompute_space_stats_should_not_skip_posts_when_space_posts_change_between_calls() {
+        ExtBuilder::default().with_spaces(1).with_posts_per_space(3).build_ext().execute_with(|| {
+            assert_ok!(_update_post(None, Some(3), Some(post_update(None, None, Some(true)))));
+            SpaceById::<TestRuntime>::mutate(SPACE1, |space| space.as_mut().unwrap().hidden_posts_count = 0);
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(
-                None, // From ACCOUNT1 (has default permission to UpdateAnyPosts as SpaceOwner)
-                Some(POST1),
-                Some(post_update)
-            ));
+            assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 2)); // POST1 and POST2
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 2)); // PostId 3
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.posts_count, 2);
+            assert_eq!(space.hidden_posts_count, 1);
         });
     }
 
     #[test]
-    fn update_any_post_should_work_when_one_of_roles_is_permitted() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
-            );
-            assert_ok!(_create_default_post()); // PostId 1
+    fn recompute_space_stats_should_count_followers_over_a_few_calls() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SpaceId 1
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT3)), None));
+            let followers_count = SpaceFollows::space_followers_count(SPACE1);
+            SpaceById::<TestRuntime>::mutate(SPACE1, |space| space.as_mut().unwrap().followers_count = 0);
+
+            for _ in 1..followers_count {
+                assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 1));
+                assert!(Spaces::space_stats_recount_by_id(SPACE1).is_some());
+            }
+            assert_ok!(Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 1));
 
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(POST1),
-                Some(post_update)
-            ));
+            assert!(Spaces::space_stats_recount_by_id(SPACE1).is_none());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, followers_count);
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_no_updates_for_post_provided() {
+    fn recompute_space_stats_should_work_when_called_by_root() {
         ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error updating a post with no changes
-            assert_noop!(_update_post(None, None, None), PostsError::<TestRuntime>::NoUpdatesForPost);
-        });
-    }
+            SpaceById::<TestRuntime>::mutate(SPACE1, |space| space.as_mut().unwrap().posts_count = 0);
 
-    #[test]
-    fn update_post_should_fail_when_post_not_found() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
+            assert_ok!(Spaces::recompute_space_stats(Origin::root(), SPACE1, 10));
 
-            // Try to catch an error updating a post with wrong post ID
-            assert_noop!(_update_post(
-                None,
-                Some(POST2),
-                Some(
-                    post_update(
-                        // FIXME: when Post's `space_id` update is fully implemented
-                        None/*Some(SPACE2)*/,
-                        None,
-                        Some(true)/*None*/
-                    )
-                )
-            ), PostsError::<TestRuntime>::PostNotFound);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().posts_count, 1);
         });
     }
 
     #[test]
-    fn update_post_should_fail_when_account_has_no_permission_to_update_any_post() {
+    fn recompute_space_stats_should_fail_when_not_space_owner_or_zero_limit() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
-
-            // Try to catch an error updating a post with different account
-            assert_noop!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(
-                    post_update(
-                        // FIXME: when Post's `space_id` update is fully implemented
-                        None/*Some(SPACE2)*/,
-                        None,
-                        Some(true)/*None*/
-                    )
-                )
-            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
+            assert_noop!(
+                Spaces::recompute_space_stats(Origin::signed(ACCOUNT2), SPACE1, 10),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+            assert_noop!(
+                Spaces::recompute_space_stats(Origin::signed(ACCOUNT1), SPACE1, 0),
+                SpacesError::<TestRuntime>::ZeroRecountLimit
+            );
         });
     }
 
+    // Post tests
     #[test]
-    fn update_post_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error updating a post with invalid content
-            assert_noop!(_update_post(
-                None,
-                None,
-                Some(
-                    post_update(
-                        None,
-                        Some(invalid_content_ipfs()),
-                        None
-                    )
-                )
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
-        });
-    }
-
-    #[test]
-    fn update_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
-            let post_update = post_update(
-                None,
-                Some(updated_post_content()),
-                Some(true),
-            );
-            assert_ok!(_create_default_post());
-            // PostId 1
-            assert_ok!(_delete_default_role());
-
-            // Post update with ID 1 should be fine
-            assert_noop!(_update_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(POST1),
-                Some(post_update)
-            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
-        });
-    }
-
-    // Comment tests
-    #[test]
-    fn create_comment_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_default_comment()); // PostId 2 by ACCOUNT1 which is permitted by default
+    fn create_post_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1 which is permitted by default
 
             // Check storages
-            let root_post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![POST2]);
-            assert_eq!(root_post.replies_count, 1);
-            assert_eq!(root_post.hidden_replies_count, 0);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::next_post_id(), POST2);
 
             // Check whether data stored correctly
-            let comment = Posts::post_by_id(POST2).unwrap();
-            let comment_ext = comment.get_comment_ext().unwrap();
-
-            assert!(comment_ext.parent_id.is_none());
-            assert_eq!(comment_ext.root_post_id, POST1);
-            assert_eq!(comment.created.account, ACCOUNT1);
-            assert!(comment.updated.is_none());
-            assert_eq!(comment.content, comment_content_ipfs());
-            assert_eq!(comment.replies_count, 0);
-            assert_eq!(comment.hidden_replies_count, 0);
-            assert_eq!(comment.shares_count, 0);
-            assert_eq!(comment.upvotes_count, 0);
-            assert_eq!(comment.downvotes_count, 0);
-            assert_eq!(comment.score, 0);
-
-            assert!(PostHistory::edit_history(POST2).is_empty());
-        });
-    }
+            let post = Posts::post_by_id(POST1).unwrap();
 
-    #[test]
-    fn create_comment_should_work_when_comment_has_parents() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            let first_comment_id: PostId = 2;
-            let penultimate_comment_id: PostId = 8;
-            let last_comment_id: PostId = 9;
+            assert_eq!(post.created.account, ACCOUNT1);
+            assert!(post.updated.is_none());
+            assert_eq!(post.hidden, false);
 
-            for parent_id in first_comment_id..last_comment_id as PostId {
-                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
-            }
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.extension, extension_regular_post());
 
-            for comment_id in first_comment_id..penultimate_comment_id as PostId {
-                let comment = Posts::post_by_id(comment_id).unwrap();
-                let replies_should_be = last_comment_id - comment_id;
-                assert_eq!(comment.replies_count, replies_should_be as u16);
-                assert_eq!(Posts::reply_ids_by_post_id(comment_id), vec![comment_id + 1]);
+            assert_eq!(post.content, post_content_ipfs());
 
-                assert_eq!(comment.hidden_replies_count, 0);
-            }
+            assert_eq!(post.replies_count, 0);
+            assert_eq!(post.hidden_replies_count, 0);
+            assert_eq!(post.shares_count, 0);
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 0);
 
-            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
-            assert_eq!(last_comment.replies_count, 0);
-            assert!(Posts::reply_ids_by_post_id(last_comment_id).is_empty());
+            assert_eq!(post.score, 0);
 
-            assert_eq!(last_comment.hidden_replies_count, 0);
+            assert!(PostHistory::edit_history(POST1).is_empty());
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Try to catch an error creating a comment with wrong post
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::PostNotFound);
+    fn create_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1,
+                None, // RegularPost extension
+                None, // Default post content
+            ));
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_parent_comment_is_unknown() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error creating a comment with wrong parent
-            assert_noop!(_create_comment(
+    fn create_post_should_fail_when_post_has_no_space_id() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_post(
                 None,
+                Some(None),
                 None,
-                Some(Some(POST2)),
                 None
-            ), PostsError::<TestRuntime>::UnknownParentComment);
+            ), PostsError::<TestRuntime>::PostHasNoSpaceId);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            // Try to catch an error creating a comment with wrong parent
-            assert_noop!(_create_comment(
-                None,
-                None,
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn create_post_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_default_post(), SpacesError::<TestRuntime>::SpaceNotFound);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_trying_to_create_in_hidden_space_scope() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_update_space(
-                None,
+    fn create_post_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            // Try to catch an error creating a regular post with invalid content
+            assert_noop!(_create_post(
                 None,
-                Some(space_update(None, None, Some(true)))
-            ));
-
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
-        });
-    }
-
-    #[test]
-    fn create_comment_should_fail_when_trying_create_in_hidden_post_scope() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_update_post(
                 None,
                 None,
-                Some(post_update(None, None, Some(true)))
-            ));
-
-            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
         });
     }
 
     #[test]
-    fn create_comment_should_fail_when_max_comment_depth_reached() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
-
-            for parent_id in 2..11 as PostId {
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None)); // PostId N (last = 10)
-            }
-
-            // Some(Some(11)) - here is parent_id 11 of type PostId
-            assert_noop!(_create_comment(
+    fn create_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
                 None,
                 None,
-                Some(Some(11)),
                 None
-            ), PostsError::<TestRuntime>::MaxCommentDepthReached);
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
         });
     }
 
     #[test]
-    fn update_comment_should_work() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Post update with ID 1 should be fine
-            assert_ok!(_update_comment(None, None, None));
-
-            // Check whether post updates correctly
-            let comment = Posts::post_by_id(POST2).unwrap();
-            assert_eq!(comment.content, reply_content_ipfs());
+    fn create_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_delete_default_role());
 
-            // Check whether history recorded correctly
-            assert_eq!(PostHistory::edit_history(POST2)[0].old_data.content, Some(comment_content_ipfs()));
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1,
+                None, // RegularPost extension
+                None, // Default post content
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
         });
     }
 
-    #[test]
-    fn update_comment_hidden_should_work_when_comment_has_parents() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            let first_comment_id: PostId = 2;
-            let penultimate_comment_id: PostId = 8;
-            let last_comment_id: PostId = 9;
-
-            for parent_id in first_comment_id..last_comment_id as PostId {
-                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
-                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
-            }
-
-            assert_ok!(_update_comment(
-                None,
-                Some(last_comment_id),
-                Some(post_update(
-                    None,
-                    None,
-                    Some(true) // make comment hidden
-                ))
-            ));
+    const RESERVED_SPACE1: SpaceId = 1;
 
-            for comment_id in first_comment_id..penultimate_comment_id as PostId {
-                let comment = Posts::post_by_id(comment_id).unwrap();
-                assert_eq!(comment.hidden_replies_count, 1);
-            }
-            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
-            assert_eq!(last_comment.hidden_replies_count, 0);
+    #[test]
+    fn create_post_should_work_in_reserved_space_by_its_owner() {
+        ExtBuilder::build_with_reserved_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(RESERVED_SPACE1)), None, None));
+            assert_eq!(Posts::post_ids_by_space_id(RESERVED_SPACE1), vec![POST1]);
         });
     }
 
     #[test]
-    // `PostNotFound` here: Post with Comment extension. Means that comment wasn't found.
-    fn update_comment_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Try to catch an error updating a comment with wrong PostId
-            assert_noop!(_update_comment(None, None, None), PostsError::<TestRuntime>::PostNotFound);
+    fn create_post_should_fail_in_reserved_space_when_not_its_owner() {
+        ExtBuilder::build_with_reserved_spaces().execute_with(|| {
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(RESERVED_SPACE1)),
+                None,
+                None
+            ), SpacesError::<TestRuntime>::NotAReservedSpaceOwner);
         });
     }
 
     #[test]
-    fn update_comment_should_fail_when_account_is_not_a_comment_author() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Try to catch an error updating a comment with wrong Account
-            assert_noop!(_update_comment(
+    fn create_subspace_should_fail_in_reserved_space_when_not_its_owner() {
+        ExtBuilder::build_with_reserved_spaces().execute_with(|| {
+            assert_noop!(_create_subspace(
                 Some(Origin::signed(ACCOUNT2)),
+                Some(Some(RESERVED_SPACE1)),
+                Some(None),
                 None,
                 None
-            ), PostsError::<TestRuntime>::NotACommentAuthor);
+            ), SpacesError::<TestRuntime>::NotAReservedSpaceOwner);
         });
     }
 
     #[test]
-    fn update_comment_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build_with_comment().execute_with(|| {
-            // Try to catch an error updating a comment with invalid content
-            assert_noop!(_update_comment(
-                None,
+    fn update_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let expected_content_ipfs = updated_post_content();
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(
+                None, // From ACCOUNT1 (has default permission to UpdateOwnPosts)
                 None,
                 Some(
                     post_update(
                         None,
-                        Some(invalid_content_ipfs()),
-                        None
+                        Some(expected_content_ipfs.clone()),
+                        Some(true)
                     )
                 )
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+            ));
+
+            // Check whether post updates correctly
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.space_id, Some(SPACE1));
+            assert_eq!(post.content, expected_content_ipfs);
+            assert_eq!(post.hidden, true);
+
+            // Check whether history recorded correctly
+            let post_history = PostHistory::edit_history(POST1)[0].clone();
+            assert!(post_history.old_data.space_id.is_none());
+            assert_eq!(post_history.old_data.content, Some(post_content_ipfs()));
+            assert_eq!(post_history.old_data.hidden, Some(false));
         });
     }
 
-    // Reaction tests
-    #[test]
-    fn create_post_reaction_should_work_upvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
+    fn update_post_hidden_at_blocks(blocks: Vec<BlockNumber>) {
+        for (i, block) in blocks.into_iter().enumerate() {
+            System::set_block_number(block);
+            assert_ok!(_update_post(
                 None,
-                None
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+                None,
+                Some(post_update(None, None, Some(i % 2 == 0)))
+            ));
+        }
+    }
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+    fn post_history_blocks(post_id: PostId) -> Vec<BlockNumber> {
+        PostHistory::edit_history(post_id).iter().map(|record| record.edited.block).collect()
+    }
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 1);
-            assert_eq!(post.downvotes_count, 0);
+    #[test]
+    fn update_post_should_evict_oldest_history_records_when_history_is_full() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            update_post_hidden_at_blocks(vec![2, 3, 4, 5, 6]);
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_upvote());
+            // Only the newest records should be kept
+            assert_eq!(PostHistory::edit_history(POST1).len(), MAX_POST_HISTORY_RECORDS as usize);
+            assert_eq!(post_history_blocks(POST1), vec![4, 5, 6]);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_work_downvote() {
+    fn post_edit_history_in_block_range_should_work() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+            update_post_hidden_at_blocks(vec![2, 4, 6]);
 
-            // Check storages
-            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
-            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+            let blocks_in_range = |from, to, limit| -> Vec<BlockNumber> {
+                PostHistory::get_edit_history_in_block_range(POST1, from, to, limit)
+                    .iter().map(|record| record.edited_at_block).collect()
+            };
 
-            // Check post reaction counters
-            let post = Posts::post_by_id(POST1).unwrap();
-            assert_eq!(post.upvotes_count, 0);
-            assert_eq!(post.downvotes_count, 1);
+            assert_eq!(blocks_in_range(1, 10, 10), vec![2, 4, 6]);
+            assert_eq!(blocks_in_range(3, 6, 10), vec![4, 6]);
+            assert_eq!(blocks_in_range(4, 4, 10), vec![4]);
 
-            // Check whether data stored correctly
-            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
-            assert_eq!(reaction.created.account, ACCOUNT2);
-            assert_eq!(reaction.kind, reaction_downvote());
+            // Records out of a limit should not be returned
+            assert_eq!(blocks_in_range(1, 10, 2), vec![2, 4]);
+            assert!(blocks_in_range(1, 10, 0).is_empty());
+
+            // Empty ranges
+            assert!(blocks_in_range(7, 10, 10).is_empty());
+            assert!(blocks_in_range(5, 5, 10).is_empty());
+            assert!(blocks_in_range(6, 2, 10).is_empty());
+            assert!(PostHistory::get_edit_history_in_block_range(POST2, 1, 10, 10).is_empty());
+
+            // Check whether flat records keep the old data
+            let record = &PostHistory::get_edit_history_in_block_range(POST1, 2, 2, 1)[0];
+            assert_eq!(record.edited_by, ACCOUNT1);
+            assert_eq!(record.old_hidden, Some(false));
+            assert!(record.old_content.is_none());
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
+    fn update_post_should_truncate_oversized_history_on_first_edit() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Simulate a history that was written before the limit was introduced
+            update_post_hidden_at_blocks(vec![2, 3, 4]);
+            let mut history = PostHistory::edit_history(POST1);
+            history.extend(history.clone());
+            pallet_post_history::EditHistory::<TestRuntime>::insert(POST1, history);
+
+            // The post was hidden at block 4, so unhide it
+            System::set_block_number(5);
+            assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(false)))));
+
+            assert_eq!(post_history_blocks(POST1), vec![3, 4, 5]);
+        });
+    }
+
+    fn check_if_post_moved_correctly(
+        moved_post_id: PostId,
+        old_space_id: SpaceId,
+        expected_new_space_id: SpaceId
+    ) {
+        let post: Post<TestRuntime> = Posts::post_by_id(moved_post_id).unwrap(); // `POST2` is a comment
+        let new_space_id = post.space_id.unwrap();
+
+        // Check that space id of the post has been updated from 1 to 2
+        assert_eq!(new_space_id, expected_new_space_id);
+
+        // Check that stats on the old space have been decreased
+        let old_space = Spaces::space_by_id(old_space_id).unwrap();
+        assert_eq!(old_space.posts_count, 0);
+        assert_eq!(old_space.hidden_posts_count, 0);
+        assert_eq!(old_space.score, 0);
+
+        // Check that stats on the new space have been increased
+        let new_space = Spaces::space_by_id(new_space_id).unwrap();
+        assert_eq!(new_space.posts_count, 1);
+        assert_eq!(new_space.hidden_posts_count, if post.hidden { 1 } else { 0 });
+        assert_eq!(new_space.score, post.score);
+    }
+
+    #[test]
+    fn move_post_should_work() {
         ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+            assert_ok!(_move_post_1_to_space_2());
+
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1;
+            let expected_new_space_id = SPACE2;
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_post_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            // Try to catch an error creating reaction by the same account
-            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+    fn move_post_should_keep_order_of_remaining_posts_in_space() {
+        ExtBuilder::default().with_spaces(1).with_posts_per_space(4).build_ext().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST2));
+
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST3, 4]);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            
-            // Hide the space
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
+    fn move_post_should_work_when_space_id_none() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1; // Where post were before moving to `SpaceId:None`
+            let expected_new_space_id = SPACE2;
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+            assert_ok!(_move_post_to_nowhere(moved_post_id));
+            assert_ok!(_move_post_1_to_space_2());
+
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
         });
     }
 
     #[test]
-    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            
-            // Hide the post
+    fn move_hidden_post_should_work() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            let moved_post_id = POST1;
+            let old_space_id = SPACE1;
+            let expected_new_space_id = SPACE2;
+
+            // Hide the post before moving it
             assert_ok!(_update_post(
                 None,
-                None,
-                Some(post_update(None, None, Some(true)))
+                Some(moved_post_id),
+                Some(post_update(
+                    None,
+                    None,
+                    Some(true)
+                ))
             ));
 
-            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+            assert_ok!(_move_post_1_to_space_2());
+
+            check_if_post_moved_correctly(moved_post_id, old_space_id, expected_new_space_id);
+
+            // Check that there are no posts ids in the old space
+            assert!(Posts::post_ids_by_space_id(old_space_id).is_empty());
+
+            // Check that there is the post id in the new space
+            assert_eq!(Posts::post_ids_by_space_id(expected_new_space_id), vec![moved_post_id]);
         });
     }
 
-// Rating system tests
-
     #[test]
-    fn check_results_of_score_diff_for_action_with_common_values() {
+    fn move_hidden_post_should_fail_when_post_not_found() {
         ExtBuilder::build().execute_with(|| {
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_post()), UpvotePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_post()), DownvotePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_post()), SharePostActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_create_comment()), CreateCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_comment()), UpvoteCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_comment()), DownvoteCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_comment()), ShareCommentActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_space()), FollowSpaceActionWeight::get() as i16);
-            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_account()), FollowAccountActionWeight::get() as i16);
+            // Note that we have not created a post that we are trying to move
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                PostsError::<TestRuntime>::PostNotFound
+            );
         });
     }
 
     #[test]
-    fn check_results_of_score_diff_for_action_with_random_values() {
-        ExtBuilder::build().execute_with(|| {
-            assert_eq!(Scores::score_diff_for_action(32768, scoring_action_upvote_post()), 80); // 2^15
-            assert_eq!(Scores::score_diff_for_action(32769, scoring_action_upvote_post()), 80); // 2^15 + 1
-            assert_eq!(Scores::score_diff_for_action(65535, scoring_action_upvote_post()), 80); // 2^16 - 1
-            assert_eq!(Scores::score_diff_for_action(65536, scoring_action_upvote_post()), 85); // 2^16
+    fn move_hidden_post_should_fail_when_provided_space_not_found() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Note that we have not created a new space #2 before moving the post
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                SpacesError::<TestRuntime>::SpaceNotFound
+            );
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_space_score_should_work_for_follow_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_follow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
+    fn move_hidden_post_should_fail_origin_has_no_permission_to_create_posts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Create a space #2 from account #2
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None));
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, FollowSpaceActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + FollowSpaceActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            // Should not be possible to move the post b/c it's owner is account #1
+            // when the space #2 is owned by account #2
+            assert_noop!(
+                _move_post_1_to_space_2(),
+                PostsError::<TestRuntime>::NoPermissionToCreatePosts
+            );
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_unfollow_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_follow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
-            assert_ok!(_unfollow_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(SPACE1)
-            ));
-
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+    fn move_post_should_fail_when_account_has_no_permission() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_noop!(
+                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+            );
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_upvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
-
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+    fn move_post_should_fail_when_space_none_and_account_is_not_post_owner() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_noop!(
+                _move_post(Some(Origin::signed(ACCOUNT2)), None, None),
+                PostsError::<TestRuntime>::NotAPostOwner
+            );
         });
     }
 
     #[test]
-    fn change_space_score_should_work_for_downvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            )); // ReactionId 1
+    fn should_fail_when_trying_to_move_comment() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None));
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            // Comments cannot be moved, they stick to their parent post
+            assert_noop!(
+                _move_post(None, Some(POST2), None),
+                PostsError::<TestRuntime>::CannotUpdateSpaceIdOnComment
+            );
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_post_score_should_work_for_create_comment() {
+    fn update_post_should_work_after_transfer_space_ownership() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
+            let post_update = post_update(
                 None,
-                None
-            )); // PostId 2
+                Some(updated_post_content()),
+                Some(true),
+            );
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, CreateCommentActionWeight::get() as i32);
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, CreateCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_create_comment())), Some(CreateCommentActionWeight::get()));
+            assert_ok!(_transfer_default_space_ownership());
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(None, None, Some(post_update)));
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_upvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
+    fn update_any_post_should_work_when_account_has_default_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            let post_update = post_update(
                 None,
-                None
-            ));
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None, // SpaceId 1
+                None, // RegularPost extension
+                None // Default post content
+            )); // PostId 1
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(
+                None, // From ACCOUNT1 (has default permission to UpdateAnyPosts as SpaceOwner)
+                Some(POST1),
+                Some(post_update)
+            ));
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_downvote_post() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
+    fn update_any_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
+            let post_update = post_update(
                 None,
-                Some(reaction_downvote())
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_default_post()); // PostId 1
+
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update)
             ));
+        });
+    }
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+    #[test]
+    fn update_post_should_fail_when_no_updates_for_post_provided() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error updating a post with no changes
+            assert_noop!(_update_post(None, None, None), PostsError::<TestRuntime>::NoUpdatesForPost);
         });
     }
 
     #[test]
-    fn change_post_score_should_for_revert_upvote() {
+    fn update_post_should_fail_when_post_not_found() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None
-            ));
-            // ReactionId 1
-            assert_ok!(_delete_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                REACTION1
-            ));
+            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+            // Try to catch an error updating a post with wrong post ID
+            assert_noop!(_update_post(
+                None,
+                Some(POST2),
+                Some(
+                    post_update(
+                        // FIXME: when Post's `space_id` update is fully implemented
+                        None/*Some(SPACE2)*/,
+                        None,
+                        Some(true)/*None*/
+                    )
+                )
+            ), PostsError::<TestRuntime>::PostNotFound);
         });
     }
 
     #[test]
-    fn change_post_score_should_for_revert_downvote() {
+    fn update_post_should_fail_when_account_has_no_permission_to_update_any_post() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(reaction_downvote())
-            ));
-            // ReactionId 1
-            assert_ok!(_delete_post_reaction(
+            assert_ok!(_create_space(None, Some(Some(b"space2_handle".to_vec())), None, None)); // SpaceId 2
+
+            // Try to catch an error updating a post with different account
+            assert_noop!(_update_post(
                 Some(Origin::signed(ACCOUNT2)),
                 None,
-                REACTION1
-            ));
-
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+                Some(
+                    post_update(
+                        // FIXME: when Post's `space_id` update is fully implemented
+                        None/*Some(SPACE2)*/,
+                        None,
+                        Some(true)/*None*/
+                    )
+                )
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_change_upvote_with_downvote() {
+    fn update_post_should_fail_when_ipfs_cid_is_invalid() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
+            // Try to catch an error updating a post with invalid content
+            assert_noop!(_update_post(
                 None,
-                None
-            ));
-            // ReactionId 1
-            assert_ok!(_update_post_reaction(
-                Some(Origin::signed(ACCOUNT2)),
                 None,
-                REACTION1,
-                Some(reaction_downvote())
-            ));
+                Some(
+                    post_update(
+                        None,
+                        Some(invalid_content_ipfs()),
+                        None
+                    )
+                )
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+    #[test]
+    fn update_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(|| {
+            let post_update = post_update(
+                None,
+                Some(updated_post_content()),
+                Some(true),
+            );
+            assert_ok!(_create_default_post());
+            // PostId 1
+            assert_ok!(_delete_default_role());
+
+            // Post update with ID 1 should be fine
+            assert_noop!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update)
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
         });
     }
 
     #[test]
-    fn change_post_score_should_work_for_change_downvote_with_upvote() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post_reaction(
+    fn update_post_should_respect_roles_of_each_space() {
+        const SPACE3: SpaceId = 1003;
+
+        ExtBuilder::default()
+            .with_spaces(3)
+            .with_posts_per_space(2)
+            .with_roles(vec![
+                (SPACE2, vec![SP::UpdateAnyPost], vec![ACCOUNT2]),
+                (SPACE3, vec![SP::CreatePosts], vec![ACCOUNT2, ACCOUNT3]),
+            ])
+            .with_balances(vec![(ACCOUNT1, 100), (ACCOUNT2, 100), (ACCOUNT3, 50)])
+            .build_ext()
+            .execute_with(|| {
+                assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1).len(), 3);
+                assert_eq!(Balances::free_balance(ACCOUNT3), 50);
+                assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST3, 4]);
+
+                let update = || post_update(None, Some(updated_post_content()), None);
+
+                // ACCOUNT2 can update any post only in SpaceId 2
+                assert_ok!(_update_post(Some(Origin::signed(ACCOUNT2)), Some(POST3), Some(update())));
+                assert_noop!(
+                    _update_post(Some(Origin::signed(ACCOUNT2)), Some(POST1), Some(update())),
+                    PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+                );
+                assert_noop!(
+                    _update_post(Some(Origin::signed(ACCOUNT2)), Some(5), Some(update())),
+                    PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost
+                );
+
+                // ACCOUNT3 can create posts only in SpaceId 3
+                assert_ok!(_create_post(Some(Origin::signed(ACCOUNT3)), Some(Some(SPACE3)), None, None));
+                assert_noop!(
+                    _create_post(Some(Origin::signed(ACCOUNT3)), Some(Some(SPACE2)), None, None),
+                    PostsError::<TestRuntime>::NoPermissionToCreatePosts
+                );
+            });
+    }
+
+    #[test]
+    fn update_post_should_work_when_only_hidden_changed_with_hide_any_post_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideAnyPost]).execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1
+
+            assert_ok!(_update_post(
                 Some(Origin::signed(ACCOUNT2)),
                 None,
-                Some(reaction_downvote())
+                Some(post_update(None, None, Some(true)))
             ));
-            // ReactionId 1
-            assert_ok!(_update_post_reaction(
+            assert!(Posts::post_by_id(POST1).unwrap().hidden);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 1);
+
+            assert_ok!(_update_post(
                 Some(Origin::signed(ACCOUNT2)),
                 None,
-                REACTION1,
-                None
+                Some(post_update(None, None, Some(false)))
             ));
-
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
-            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+            assert!(!Posts::post_by_id(POST1).unwrap().hidden);
         });
     }
 
-//--------------------------------------------------------------------------------------------------
-
     #[test]
-    fn change_social_account_reputation_should_work_when_max_score_diff_provided() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                std::i16::MAX,
-                scoring_action_follow_account())
-            );
-        });
-    }
+    fn update_post_should_fail_when_changing_content_with_hide_any_post_permission() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::HideAnyPost]).execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 1 by ACCOUNT1
 
-    #[test]
-    fn change_social_account_reputation_should_work_when_min_score_diff_provided() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                std::i16::MIN,
-                scoring_action_follow_account())
-            );
+            assert_noop!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(post_update(None, Some(updated_post_content()), None))
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
+
+            assert_noop!(_update_post(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(post_update(None, Some(updated_post_content()), Some(true)))
+            ), PostsError::<TestRuntime>::NoPermissionToUpdateAnyPost);
         });
     }
 
+    // Comment tests
     #[test]
-    fn change_social_account_reputation_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
+    fn create_comment_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2 by ACCOUNT1 which is permitted by default
+
+            // Check storages
+            let root_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(Posts::reply_ids_by_post_id(POST1), vec![POST2]);
+            assert_eq!(root_post.replies_count, 1);
+            assert_eq!(root_post.hidden_replies_count, 0);
+
+            // Check whether data stored correctly
+            let comment = Posts::post_by_id(POST2).unwrap();
+            let comment_ext = comment.get_comment_ext().unwrap();
+
+            assert!(comment_ext.parent_id.is_none());
+            assert_eq!(comment_ext.root_post_id, POST1);
+            assert_eq!(comment.created.account, ACCOUNT1);
+            assert!(comment.updated.is_none());
+            assert_eq!(comment.content, comment_content_ipfs());
+            assert_eq!(comment.replies_count, 0);
+            assert_eq!(comment.hidden_replies_count, 0);
+            assert_eq!(comment.shares_count, 0);
+            assert_eq!(comment.upvotes_count, 0);
+            assert_eq!(comment.downvotes_count, 0);
+            assert_eq!(comment.score, 0);
+
+            assert!(PostHistory::edit_history(POST2).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_comment_should_increase_comments_count_of_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2
+            assert_ok!(_create_comment(None, None, Some(Some(POST2)), None)); // PostId 3
+
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.comments_count, 2);
+            assert_eq!(space.posts_count, 1);
+        });
+    }
+
+    #[test]
+    fn hide_comment_should_not_change_comments_count_of_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2
+            assert_ok!(_update_comment(None, None, Some(post_update(None, None, Some(true)))));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().comments_count, 1);
+        });
+    }
+
+    #[test]
+    fn delete_comment_from_space_should_decrease_comments_count_of_space() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_comment(None, None, Some(Some(POST2)), None)); // PostId 3
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().comments_count, 2);
+
+            // A comment cannot be moved by its owner, it is deleted from its space when blocked
+            assert_ok!(Posts::delete_post_from_space(POST2));
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().comments_count, 1);
+        });
+    }
+
+    #[test]
+    fn move_post_should_move_comments_count_to_new_space() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2
+            assert_ok!(_create_comment(None, None, Some(Some(POST2)), None)); // PostId 3
+
+            assert_ok!(_move_post_1_to_space_2());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().comments_count, 0);
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().comments_count, 2);
+
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().comments_count, 0);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let penultimate_comment_id: PostId = 8;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            for comment_id in first_comment_id..penultimate_comment_id as PostId {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                let replies_should_be = last_comment_id - comment_id;
+                assert_eq!(comment.replies_count, replies_should_be as u16);
+                assert_eq!(Posts::reply_ids_by_post_id(comment_id), vec![comment_id + 1]);
+
+                assert_eq!(comment.hidden_replies_count, 0);
+            }
+
+            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
+            assert_eq!(last_comment.replies_count, 0);
+            assert!(Posts::reply_ids_by_post_id(last_comment_id).is_empty());
+
+            assert_eq!(last_comment.hidden_replies_count, 0);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error creating a comment with wrong post
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_parent_comment_is_unknown() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error creating a comment with wrong parent
+            assert_noop!(_create_comment(
+                None,
+                None,
+                Some(Some(POST2)),
+                None
+            ), PostsError::<TestRuntime>::UnknownParentComment);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // Try to catch an error creating a comment with wrong parent
+            assert_noop!(_create_comment(
+                None,
+                None,
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_trying_to_create_in_hidden_space_scope() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_trying_create_in_hidden_post_scope() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_comment(), PostsError::<TestRuntime>::CannotCreateInHiddenScope);
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_max_comment_depth_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+
+            for parent_id in 2..11 as PostId {
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None)); // PostId N (last = 10)
+            }
+
+            // Some(Some(11)) - here is parent_id 11 of type PostId
+            assert_noop!(_create_comment(
+                None,
+                None,
+                Some(Some(11)),
+                None
+            ), PostsError::<TestRuntime>::MaxCommentDepthReached);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_work() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Post update with ID 1 should be fine
+            assert_ok!(_update_comment(None, None, None));
+
+            // Check whether post updates correctly
+            let comment = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(comment.content, reply_content_ipfs());
+
+            // Check whether history recorded correctly
+            assert_eq!(PostHistory::edit_history(POST2)[0].old_data.content, Some(comment_content_ipfs()));
+        });
+    }
+
+    #[test]
+    fn update_comment_hidden_should_work_when_comment_has_parents() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let first_comment_id: PostId = 2;
+            let penultimate_comment_id: PostId = 8;
+            let last_comment_id: PostId = 9;
+
+            for parent_id in first_comment_id..last_comment_id as PostId {
+                // last created = `last_comment_id`; last parent = `penultimate_comment_id`
+                assert_ok!(_create_comment(None, None, Some(Some(parent_id)), None));
+            }
+
+            assert_ok!(_update_comment(
+                None,
+                Some(last_comment_id),
+                Some(post_update(
+                    None,
+                    None,
+                    Some(true) // make comment hidden
+                ))
+            ));
+
+            for comment_id in first_comment_id..penultimate_comment_id as PostId {
+                let comment = Posts::post_by_id(comment_id).unwrap();
+                assert_eq!(comment.hidden_replies_count, 1);
+            }
+            let last_comment = Posts::post_by_id(last_comment_id).unwrap();
+            assert_eq!(last_comment.hidden_replies_count, 0);
+        });
+    }
+
+    #[test]
+    // `PostNotFound` here: Post with Comment extension. Means that comment wasn't found.
+    fn update_comment_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error updating a comment with wrong PostId
+            assert_noop!(_update_comment(None, None, None), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_account_is_not_a_comment_author() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Try to catch an error updating a comment with wrong Account
+            assert_noop!(_update_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ), PostsError::<TestRuntime>::NotACommentAuthor);
+        });
+    }
+
+    #[test]
+    fn transferred_comment_should_be_updated_by_new_owner_but_not_by_author() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // There is no extrinsic to transfer a post yet, so change its owner in storage directly.
+            PostById::<TestRuntime>::mutate(POST2, |comment| comment.as_mut().unwrap().owner = ACCOUNT2);
+
+            assert_noop!(_update_comment(None, None, None), PostsError::<TestRuntime>::NotACommentAuthor);
+            assert_ok!(_update_comment(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            let comment = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(comment.created.account, ACCOUNT1);
+            assert!(!pallet_posts::rpc::FlatPost::from(comment).is_original_owner);
+            assert!(pallet_posts::rpc::FlatPost::from(Posts::post_by_id(POST1).unwrap()).is_original_owner);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // Try to catch an error updating a comment with invalid content
+            assert_noop!(_update_comment(
+                None,
+                None,
+                Some(
+                    post_update(
+                        None,
+                        Some(invalid_content_ipfs()),
+                        None
+                    )
+                )
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_content_is_blocked() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            // A comment has no space id, so its content is checked in the space of its root post
+            block_content_in_space_1();
+            assert_noop!(
+                _update_comment(
+                    None, // From ACCOUNT1 (has default permission to UpdateOwnComments)
+                    None,
+                    Some(post_update(None, Some(valid_content_ipfs()), None))
+                ), UtilsError::<TestRuntime>::ContentIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn update_comment_should_fail_when_account_is_blocked() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            block_account_in_space_1();
+            assert_noop!(_update_comment(None, None, None), UtilsError::<TestRuntime>::AccountIsBlocked);
+            assert_noop!(
+                _update_comment(None, None, Some(post_update(None, None, Some(true)))),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
+        });
+    }
+
+    /// Create a thread under `POST1`:
+    /// ```text
+    /// 1 ─┬─ 2 ─── 4 ─── 5
+    ///    └─ 3 ─── 6
+    /// ```
+    fn create_thread_of_comments() {
+        assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+        assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 3
+        assert_ok!(_create_comment(None, None, Some(Some(2)), None)); // PostId 4
+        assert_ok!(_create_comment(None, None, Some(Some(4)), None)); // PostId 5
+        assert_ok!(_create_comment(None, None, Some(Some(3)), None)); // PostId 6
+    }
+
+    #[test]
+    fn get_post_replies_tree_should_return_all_links_breadth_first() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_thread_of_comments();
+
+            assert_eq!(
+                Posts::get_post_replies_tree(POST1, 10, 100),
+                vec![(1, None), (2, Some(1)), (3, Some(1)), (4, Some(2)), (6, Some(3)), (5, Some(4))]
+            );
+
+            // A subtree can be requested as well
+            assert_eq!(
+                Posts::get_post_replies_tree(2, 10, 100),
+                vec![(2, None), (4, Some(2)), (5, Some(4))]
+            );
+        });
+    }
+
+    #[test]
+    fn get_post_replies_tree_should_be_truncated_by_limits() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_thread_of_comments();
+
+            assert_eq!(Posts::get_post_replies_tree(POST1, 0, 100), vec![(1, None)]);
+            assert_eq!(
+                Posts::get_post_replies_tree(POST1, 1, 100),
+                vec![(1, None), (2, Some(1)), (3, Some(1))]
+            );
+            assert_eq!(
+                Posts::get_post_replies_tree(POST1, 10, 4),
+                vec![(1, None), (2, Some(1)), (3, Some(1)), (4, Some(2))]
+            );
+            assert!(Posts::get_post_replies_tree(POST1, 10, 0).is_empty());
+        });
+    }
+
+    #[test]
+    fn get_post_replies_tree_should_return_nothing_for_unknown_post() {
+        ExtBuilder::build().execute_with(|| {
+            assert!(Posts::get_post_replies_tree(POST1, 10, 100).is_empty());
+        });
+    }
+
+    /// Ids and creation blocks of posts on a page of `get_replies_paginated`.
+    fn replies_page(newest_first: bool, offset: u32, limit: u32) -> Vec<(PostId, BlockNumber)> {
+        Posts::get_replies_paginated(POST1, newest_first, offset, limit)
+            .into_iter()
+            .map(|reply| (reply.id, reply.created_at_block))
+            .collect()
+    }
+
+    #[test]
+    fn get_replies_paginated_should_sort_replies_by_creation_block_in_both_directions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            for block in 2..=5 {
+                System::set_block_number(block);
+                assert_ok!(_create_default_comment()); // PostIds 2..=5
+            }
+
+            assert_eq!(replies_page(false, 0, 10), vec![(2, 2), (3, 3), (4, 4), (5, 5)]);
+            assert_eq!(replies_page(true, 0, 10), vec![(5, 5), (4, 4), (3, 3), (2, 2)]);
+
+            assert_eq!(replies_page(false, 1, 2), vec![(3, 3), (4, 4)]);
+            assert_eq!(replies_page(true, 1, 2), vec![(4, 4), (3, 3)]);
+            assert!(replies_page(true, 4, 10).is_empty());
+        });
+    }
+
+    #[test]
+    fn get_replies_paginated_should_return_only_direct_replies() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            create_thread_of_comments();
+
+            assert_eq!(replies_page(true, 0, 10), vec![(3, 1), (2, 1)]);
+            assert!(Posts::get_replies_paginated(POST2 + 100, true, 0, 10).is_empty());
+        });
+    }
+
+    fn hide_post(post_id: PostId) {
+        assert_ok!(_update_post(None, Some(post_id), Some(post_update(None, None, Some(true)))));
+    }
+
+    #[test]
+    fn post_effective_visibility_should_be_visible_when_nothing_is_hidden() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_eq!(Posts::get_post_effective_visibility(POST1), Some(Visibility::Visible));
+            assert_eq!(Posts::get_post_effective_visibility(POST2), Some(Visibility::Visible));
+            assert!(Posts::get_post_effective_visibility(POST3).is_none());
+        });
+    }
+
+    #[test]
+    fn post_effective_visibility_should_be_hidden_self_when_post_is_hidden() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            hide_post(POST2);
+
+            assert_eq!(Posts::get_post_effective_visibility(POST2), Some(Visibility::HiddenSelf));
+            assert_eq!(Posts::get_post_effective_visibility(POST1), Some(Visibility::Visible));
+        });
+    }
+
+    #[test]
+    fn post_effective_visibility_should_be_hidden_by_space_when_space_is_hidden() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_update_space(None, None, Some(space_update(None, None, Some(true)))));
+
+            assert_eq!(Posts::get_post_effective_visibility(POST1), Some(Visibility::HiddenBySpace));
+            assert_eq!(Posts::get_post_effective_visibility(POST2), Some(Visibility::HiddenBySpace));
+        });
+    }
+
+    #[test]
+    fn post_effective_visibility_should_be_hidden_by_root_post_when_root_post_is_hidden() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            hide_post(POST1);
+            // A hidden root post takes precedence over a hidden space
+            assert_ok!(_update_space(None, None, Some(space_update(None, None, Some(true)))));
+
+            assert_eq!(Posts::get_post_effective_visibility(POST2), Some(Visibility::HiddenByRootPost));
+            assert_eq!(Posts::get_post_effective_visibility(POST1), Some(Visibility::HiddenSelf));
+        });
+    }
+
+    // Reaction tests
+    /// Block an entity in SPACE1 without the side effects of `update_entity_status`,
+    /// which moves a blocked post out of its space and unfollows the space by a blocked account.
+    fn block_in_space_1(entity: EntityId<AccountId>) {
+        pallet_moderation::StatusByEntityInSpace::<TestRuntime>::insert(entity, SPACE1, EntityStatus::Blocked);
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_account_is_blocked() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            block_in_space_1(EntityId::Account(ACCOUNT2));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
+            assert_noop!(
+                _create_post_reactions(Some(Origin::signed(ACCOUNT2)), vec![(POST1, reaction_downvote())]),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_comment_is_blocked() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            block_in_space_1(EntityId::Post(POST2));
+
+            assert_noop!(
+                _create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST2), None),
+                UtilsError::<TestRuntime>::PostIsBlocked
+            );
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST1), None));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_when_comment_or_account_is_blocked() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST2), None));
+
+            block_in_space_1(EntityId::Post(POST2));
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST2), REACTION1, Some(reaction_downvote())),
+                UtilsError::<TestRuntime>::PostIsBlocked
+            );
+
+            block_in_space_1(EntityId::Account(ACCOUNT2));
+            assert_noop!(
+                _update_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST2), REACTION1, Some(reaction_downvote())),
+                UtilsError::<TestRuntime>::AccountIsBlocked
+            );
+        });
+    }
+
+    // Reaction tests
+    #[test]
+    fn create_post_reaction_should_work_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 1);
+            assert_eq!(post.downvotes_count, 0);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_upvote());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_work_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            )); // ReactionId 1 by ACCOUNT2 which is permitted by default
+
+            // Check storages
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST1), vec![REACTION1]);
+            assert_eq!(Reactions::next_reaction_id(), REACTION2);
+
+            // Check post reaction counters
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 0);
+            assert_eq!(post.downvotes_count, 1);
+
+            // Check whether data stored correctly
+            let reaction = Reactions::reaction_by_id(REACTION1).unwrap();
+            assert_eq!(reaction.created.account, ACCOUNT2);
+            assert_eq!(reaction.kind, reaction_downvote());
+        });
+    }
+
+    #[test]
+    fn reactions_summary_should_match_reactions_on_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT1)), None, Some(reaction_upvote())));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, Some(reaction_upvote())));
+
+            let summary = Reactions::get_reactions_summary(POST1);
+            assert_eq!(summary, vec![(reaction_upvote(), 2), (reaction_downvote(), 1)]);
+
+            let reactions = Reactions::get_reactions_by_post(POST1, 0, 10);
+            for (kind, count) in summary {
+                assert_eq!(reactions.iter().filter(|reaction| reaction.kind == kind).count() as u32, count);
+            }
+
+            // Flip the downvote of ACCOUNT2 (ReactionId 2):
+            assert_ok!(_update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION2, Some(reaction_upvote())));
+            assert_eq!(Reactions::get_reactions_summary(POST1), vec![(reaction_upvote(), 3), (reaction_downvote(), 0)]);
+        });
+    }
+
+    #[test]
+    fn reactions_summary_should_be_empty_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert!(Reactions::get_reactions_summary(POST1).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_account_has_already_reacted() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::AccountAlreadyReacted);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_post_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            // Try to catch an error creating reaction by the same account
+            assert_noop!(_create_default_post_reaction(), PostsError::<TestRuntime>::PostNotFound);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_in_hidden_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            
+            // Hide the space
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenSpaceHidden);
+        });
+    }
+
+    #[test]
+    fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            
+            // Hide the post
+            assert_ok!(_update_post(
+                None,
+                None,
+                Some(post_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_create_default_post_reaction(), ReactionsError::<TestRuntime>::CannotReactWhenPostHidden);
+        });
+    }
+
+    #[test]
+    fn create_post_reactions_should_work_and_skip_already_reacted_posts() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_post()); // PostId 3
+
+            assert_ok!(_create_post_reactions(None, vec![
+                (POST1, reaction_downvote()),
+                (POST2, reaction_upvote()),
+                (POST3, reaction_downvote()),
+            ]));
+
+            // POST1 was skipped: its reaction is left untouched
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!((post.upvotes_count, post.downvotes_count), (1, 0));
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().kind, reaction_upvote());
+
+            let post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!((post.upvotes_count, post.downvotes_count), (1, 0));
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST2), vec![REACTION2]);
+
+            let post = Posts::post_by_id(POST3).unwrap();
+            assert_eq!((post.upvotes_count, post.downvotes_count), (0, 1));
+            assert_eq!(Reactions::reaction_ids_by_post_id(POST3), vec![3]);
+
+            assert_eq!(Reactions::post_reaction_id_by_account((ACCOUNT1, POST3)), 3);
+            assert_eq!(Reactions::next_reaction_id(), 4);
+
+            assert!(events().contains(&TestEvent::pallet_reactions(
+                pallet_reactions::RawEvent::PostReactionsBatchCreated(ACCOUNT1, vec![REACTION2, 3], vec![POST1])
+            )));
+        });
+    }
+
+    #[test]
+    fn create_post_reactions_should_fail_when_batch_is_empty() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_post_reactions(None, vec![]),
+                ReactionsError::<TestRuntime>::NoReactionsInBatch
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reactions_should_fail_when_batch_is_too_big() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_post_reactions(None, vec![(POST1, reaction_upvote()); MaxReactionsPerBatch::get() as usize + 1]),
+                ReactionsError::<TestRuntime>::TooManyReactionsInBatch
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_reactions_should_fail_when_any_post_not_found() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _create_post_reactions(None, vec![(POST1, reaction_upvote()), (POST2, reaction_upvote())]),
+                PostsError::<TestRuntime>::PostNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_record_reaction_history() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert!(ReactionHistory::edit_history(REACTION1).is_empty());
+
+            // Flip the reaction twice: upvote -> downvote -> upvote
+            assert_ok!(_update_post_reaction(None, None, REACTION1, Some(reaction_downvote())));
+            assert_ok!(_update_post_reaction(None, None, REACTION1, Some(reaction_upvote())));
+
+            let history = ReactionHistory::edit_history(REACTION1);
+            assert_eq!(history.len(), 2);
+
+            assert_eq!(history[0].edited.account, ACCOUNT1);
+            assert_eq!(history[0].old_kind, reaction_upvote());
+            assert_eq!(history[0].new_kind, Some(reaction_downvote()));
+
+            assert_eq!(history[1].edited.account, ACCOUNT1);
+            assert_eq!(history[1].old_kind, reaction_downvote());
+            assert_eq!(history[1].new_kind, Some(reaction_upvote()));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_when_account_is_not_reaction_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // ReactionId 1 by ACCOUNT2
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_noop!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT3)),
+                None,
+                REACTION1,
+                Some(reaction_downvote())
+            ), ReactionsError::<TestRuntime>::NotReactionOwner);
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 1);
+            assert_eq!(post.downvotes_count, 0);
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().kind, reaction_upvote());
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_fail_when_account_updates_another_reaction_to_the_same_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // ReactionId 1 by ACCOUNT2
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            // ReactionId 2 by ACCOUNT3
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), None, None));
+
+            assert_noop!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT3)),
+                None,
+                REACTION1,
+                Some(reaction_downvote())
+            ), ReactionsError::<TestRuntime>::NotReactionOwner);
+
+            let post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(post.upvotes_count, 2);
+            assert_eq!(post.downvotes_count, 0);
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().kind, reaction_upvote());
+            assert_eq!(Reactions::reaction_by_id(REACTION2).unwrap().kind, reaction_upvote());
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_record_reaction_history() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_delete_post_reaction(None, None, REACTION1));
+
+            let history = ReactionHistory::edit_history(REACTION1);
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].old_kind, reaction_upvote());
+            assert!(history[0].new_kind.is_none());
+        });
+    }
+
+    /// Create posts 2..=`posts_count` in SPACE1 in addition to POST1 and upvote all of them by ACCOUNT2.
+    fn upvote_posts_by_account2(posts_count: PostId) {
+        for _ in 1..posts_count {
+            assert_ok!(_create_default_post());
+        }
+        for post_id in 1..=posts_count {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(post_id), None));
+        }
+    }
+
+    #[test]
+    fn reactions_by_account_should_be_paged_in_creation_order() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            upvote_posts_by_account2(5);
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT2), vec![1, 2, 3, 4, 5]);
+
+            let mut paged_reactions = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = Reactions::get_reactions_by_account(ACCOUNT2, offset, 2);
+                if page.is_empty() {
+                    break;
+                }
+                offset += page.len() as u32;
+                paged_reactions.extend(page);
+            }
+
+            assert_eq!(paged_reactions.iter().map(|r| r.post_id).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+            assert!(paged_reactions.iter().all(|r| r.kind == reaction_upvote()));
+
+            let created = Reactions::reaction_by_id(paged_reactions[0].id).unwrap().created;
+            assert_eq!(paged_reactions[0].created_at_block, created.block);
+            assert_eq!(paged_reactions[0].created_at_time, created.time);
+
+            assert!(Reactions::get_reactions_by_account(ACCOUNT1, 0, 10).is_empty());
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_remove_it_from_reactions_by_account() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            upvote_posts_by_account2(3);
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(2), 2));
+
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT2), vec![1, 3]);
+            assert!(Reactions::post_id_by_reaction_id(2).is_none());
+            assert_eq!(
+                Reactions::get_reactions_by_account(ACCOUNT2, 0, 10).iter().map(|r| r.post_id).collect::<Vec<_>>(),
+                vec![1, 3]
+            );
+        });
+    }
+
+    #[test]
+    fn delete_post_reaction_should_keep_order_of_remaining_reactions_by_account() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            upvote_posts_by_account2(5);
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(2), 2));
+
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT2), vec![1, 3, 4, 5]);
+        });
+    }
+
+// Rating system tests
+
+    #[test]
+    fn check_results_of_score_diff_for_action_with_common_values() {
+        ExtBuilder::build().execute_with(|| {
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_post()), UpvotePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_post()), DownvotePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_post()), SharePostActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_create_comment()), CreateCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_upvote_comment()), UpvoteCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_downvote_comment()), DownvoteCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_share_comment()), ShareCommentActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_space()), FollowSpaceActionWeight::get() as i16);
+            assert_eq!(Scores::score_diff_for_action(1, scoring_action_follow_account()), FollowAccountActionWeight::get() as i16);
+        });
+    }
+
+    #[test]
+    fn check_results_of_score_diff_for_action_with_random_values() {
+        ExtBuilder::build().execute_with(|| {
+            assert_eq!(Scores::score_diff_for_action(32768, scoring_action_upvote_post()), 80); // 2^15
+            assert_eq!(Scores::score_diff_for_action(32769, scoring_action_upvote_post()), 80); // 2^15 + 1
+            assert_eq!(Scores::score_diff_for_action(65535, scoring_action_upvote_post()), 80); // 2^16 - 1
+            assert_eq!(Scores::score_diff_for_action(65536, scoring_action_upvote_post()), 85); // 2^16
+        });
+    }
+
+    #[test]
+    fn score_for_action_should_match_score_diff_for_action() {
+        ExtBuilder::build().execute_with(|| {
+            assert_eq!(Scores::score_for_action(32768, scoring_action_upvote_post()), 80); // 2^15
+            assert_eq!(Scores::score_for_action(32769, scoring_action_upvote_post()), 80); // 2^15 + 1
+            assert_eq!(Scores::score_for_action(65535, scoring_action_upvote_post()), 80); // 2^16 - 1
+            assert_eq!(Scores::score_for_action(65536, scoring_action_upvote_post()), 85); // 2^16
+
+            for reputation in [0, 1, 2, 1_000, u32::max_value()].iter() {
+                assert_eq!(
+                    Scores::score_for_action(*reputation, scoring_action_downvote_post()),
+                    Scores::score_diff_for_action(*reputation, scoring_action_downvote_post())
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn score_for_action_should_not_change_storage() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let reputation_before = Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation;
+
+            assert_eq!(Scores::score_for_action(1, scoring_action_upvote_post()), UpvotePostActionWeight::get() as i16);
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, reputation_before);
+            assert_eq!(PostById::<TestRuntime>::get(POST1).unwrap().score, 0);
+        });
+    }
+
+    #[test]
+    fn normalized_post_score_should_rank_newer_post_higher_with_equal_raw_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            System::set_block_number(1 + ScoreDecayPeriodInBlocks::get() * 3);
+            assert_ok!(_create_default_post()); // PostId 2
+
+            PostById::<TestRuntime>::mutate(POST1, |post| post.as_mut().unwrap().score = 100);
+            PostById::<TestRuntime>::mutate(POST2, |post| post.as_mut().unwrap().score = 100);
+
+            // 100 * 1000 / (3 + 2)^2 for the older post and 100 * 1000 / (0 + 2)^2 for the newer one
+            assert_eq!(Scores::normalized_post_score(POST1), Some(4_000));
+            assert_eq!(Scores::normalized_post_score(POST2), Some(25_000));
+        });
+    }
+
+    #[test]
+    fn normalized_space_score_should_decay_with_space_age() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            SpaceById::<TestRuntime>::mutate(SPACE1, |space| space.as_mut().unwrap().score = -100);
+            assert_eq!(Scores::normalized_space_score(SPACE1), Some(-25_000));
+
+            System::set_block_number(1 + ScoreDecayPeriodInBlocks::get() * 8);
+            assert_eq!(Scores::normalized_space_score(SPACE1), Some(-1_000));
+        });
+    }
+
+    #[test]
+    fn normalized_scores_should_return_none_for_unknown_entities() {
+        ExtBuilder::build().execute_with(|| {
+            assert!(Scores::normalized_post_score(POST1).is_none());
+            assert!(Scores::normalized_space_score(SPACE1).is_none());
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_space_score_should_work_for_follow_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_follow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, FollowSpaceActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + FollowSpaceActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_unfollow_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_follow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+            assert_ok!(_unfollow_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(SPACE1)
+            ));
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_upvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+        });
+    }
+
+    #[test]
+    fn change_space_score_should_work_for_downvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            )); // ReactionId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn new_social_account_should_start_with_base_reputation() {
+        ExtBuilder::build().execute_with(|| {
+            assert!(Profiles::social_account_by_id(ACCOUNT1).is_none());
+            assert_eq!(Profiles::get_or_new_social_account(ACCOUNT1).reputation, BaseReputation::get());
+        });
+    }
+
+    #[test]
+    fn reputation_should_not_drop_below_base_reputation_after_many_downvotes() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            SocialAccountById::<TestRuntime>::mutate(ACCOUNT1, |social_account| {
+                social_account.as_mut().unwrap().reputation = 20
+            });
+
+            let downvote_diff = Scores::score_diff_for_action(1, scoring_action_downvote_post());
+            for scorer in 10..30 {
+                assert_ok!(Scores::change_social_account_reputation(
+                    ACCOUNT1,
+                    scorer,
+                    downvote_diff,
+                    scoring_action_downvote_post()
+                ));
+                assert!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation >= BaseReputation::get());
+            }
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, BaseReputation::get());
+        });
+    }
+
+    #[test]
+    fn reputation_should_stay_at_base_reputation_after_downvotes_by_different_accounts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            for account in [ACCOUNT2, ACCOUNT3].iter() {
+                assert_ok!(_create_post_reaction(
+                    Some(Origin::signed(*account)),
+                    None,
+                    Some(reaction_downvote())
+                ));
+                assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, BaseReputation::get());
+            }
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_post_score_should_work_for_create_comment() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, CreateCommentActionWeight::get() as i32);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, CreateCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_create_comment())), Some(CreateCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_upvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_downvote_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_for_revert_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+            // ReactionId 1
+            assert_ok!(_delete_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_for_revert_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+            // ReactionId 1
+            assert_ok!(_delete_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_change_upvote_with_downvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None
+            ));
+            // ReactionId 1
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                Some(reaction_downvote())
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, DownvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())), Some(DownvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn update_post_reaction_should_emit_reaction_score_changed_event() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+            assert_ok!(_update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_downvote())));
+
+            assert!(events().contains(&TestEvent::pallet_scores(pallet_scores::RawEvent::ReactionScoreChanged(
+                ACCOUNT2, POST1, scoring_action_upvote_post(), scoring_action_downvote_post()
+            ))));
+        });
+    }
+
+    #[test]
+    fn update_comment_reaction_should_emit_reaction_score_changed_event() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_comment_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote()))); // ReactionId 1
+            assert_ok!(_update_comment_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_upvote())));
+
+            assert!(events().contains(&TestEvent::pallet_scores(pallet_scores::RawEvent::ReactionScoreChanged(
+                ACCOUNT2, POST2, scoring_action_downvote_comment(), scoring_action_upvote_comment()
+            ))));
+        });
+    }
+
+    #[test]
+    fn change_post_score_should_work_for_change_downvote_with_upvote() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(reaction_downvote())
+            ));
+            // ReactionId 1
+            assert_ok!(_update_post_reaction(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                REACTION1,
+                None
+            ));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + UpvotePostActionWeight::get() as u32);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_downvote_post())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_upvote_post())), Some(UpvotePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn remove_deleted_post_scores_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(POST2), None));
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT3)), Some(POST2), Some(reaction_downvote())));
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST2, scoring_action_upvote_post())).is_some());
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+
+            assert_ok!(Scores::remove_deleted_post_scores(
+                Origin::signed(ACCOUNT1),
+                POST2,
+                vec![ACCOUNT2, ACCOUNT3]
+            ));
+
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST2, scoring_action_upvote_post())).is_none());
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn remove_deleted_post_scores_should_fail_when_post_is_not_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_noop!(
+                Scores::remove_deleted_post_scores(Origin::signed(ACCOUNT1), POST1, vec![ACCOUNT2]),
+                ScoresError::<TestRuntime>::PostIsNotDeleted
+            );
+        });
+    }
+
+    #[test]
+    fn remove_deleted_post_scores_should_fail_with_too_many_accounts() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_move_post_to_nowhere(POST1));
+
+            assert_noop!(
+                Scores::remove_deleted_post_scores(Origin::signed(ACCOUNT1), POST1, vec![ACCOUNT1, ACCOUNT2, ACCOUNT3]),
+                ScoresError::<TestRuntime>::TooManyAccountsToCleanUp
+            );
+        });
+    }
+
+    #[test]
+    fn set_scoring_weights_should_change_score_of_future_actions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let new_weight = UpvotePostActionWeight::get() * 2;
+            assert_ok!(_set_scoring_weights(None, vec![(scoring_action_upvote_post(), new_weight)]));
+
+            assert_eq!(Scores::scoring_weight_by_action(scoring_action_upvote_post()), Some(new_weight));
+            assert_eq!(Scores::weight_of_scoring_action(scoring_action_downvote_post()), DownvotePostActionWeight::get());
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, new_weight as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + new_weight as u32);
+        });
+    }
+
+    #[test]
+    fn set_scoring_weights_should_not_change_score_that_is_reverted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            // ReactionId 1 is scored with the default weight
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            assert_ok!(_set_scoring_weights(None, vec![(scoring_action_upvote_post(), 20)]));
+
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+        });
+    }
+
+    #[test]
+    fn set_scoring_weights_should_fail_when_origin_is_not_allowed() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                _set_scoring_weights(Some(Origin::signed(ACCOUNT1)), vec![(scoring_action_upvote_post(), 20)]),
+                sp_runtime::DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn set_scoring_weights_should_fail_when_no_weights_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_set_scoring_weights(None, vec![]), ScoresError::<TestRuntime>::NoScoringWeightsProvided);
+        });
+    }
+
+    #[test]
+    fn reaction_kind_weight_from_genesis_should_change_post_score() {
+        ExtBuilder::default()
+            .with_spaces(1)
+            .with_posts_per_space(1)
+            .with_reaction_kind_weights(vec![(reaction_downvote(), -7)])
+            .build_ext()
+            .execute_with(|| {
+                assert_eq!(Scores::reaction_kind_weight(reaction_downvote()), Some(-7));
+                assert_eq!(Scores::reaction_kind_weight(reaction_upvote()), None);
+                assert_eq!(Scores::weight_of_scoring_action(scoring_action_upvote_post()), UpvotePostActionWeight::get());
+
+                assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_downvote())));
+                assert_eq!(Posts::post_by_id(POST1).unwrap().score, -7);
+
+                // The score of a reverted reaction is reverted with the weight it was given
+                assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
+                assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            });
+    }
+
+    #[test]
+    fn set_reaction_kind_weights_should_change_score_of_future_reactions() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(Scores::set_reaction_kind_weights(Origin::root(), vec![(reaction_upvote(), 9)]));
+            assert!(events().contains(&TestEvent::pallet_scores(
+                pallet_scores::RawEvent::ReactionKindWeightsUpdated(vec![(reaction_upvote(), 9)])
+            )));
+
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 9);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + 9);
+
+            // A weight of the scoring action takes precedence over a weight of the reaction kind
+            assert_ok!(_set_scoring_weights(None, vec![(scoring_action_upvote_post(), 20)]));
+            assert_eq!(Scores::weight_of_scoring_action(scoring_action_upvote_post()), 20);
+        });
+    }
+
+    #[test]
+    fn set_reaction_kind_weights_should_fail_when_origin_is_not_allowed() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                Scores::set_reaction_kind_weights(Origin::signed(ACCOUNT1), vec![(reaction_upvote(), 9)]),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Scores::set_reaction_kind_weights(Origin::root(), vec![]),
+                ScoresError::<TestRuntime>::NoScoringWeightsProvided
+            );
+        });
+    }
+
+    #[test]
+    fn upvotes_above_max_scored_actions_per_period_should_give_less_score_up_to_hard_cap() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            MaxScoredActionsPerPeriod::set(2);
+
+            upvote_posts_by_account2(5);
+
+            // The first two upvotes are scored in full, the third one with half of the weight
+            // (rounded down), and the upvotes from twice the max on are not scored at all.
+            let upvote_weight = UpvotePostActionWeight::get() as i32;
+            let scores: Vec<i32> = (1..=5).map(|post_id| Posts::post_by_id(post_id).unwrap().score).collect();
+            assert_eq!(scores, vec![upvote_weight, upvote_weight, upvote_weight / 2, 0, 0]);
+
+            // The reactions themselves are not limited
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT2).len(), 5);
+            assert_eq!(Scores::account_activity(ACCOUNT2).actions_count, 5);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, 5, scoring_action_upvote_post())), Some(0));
+
+            // A reaction that was not scored is reverted without changing the score
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(5), 5));
+            assert_eq!(Posts::post_by_id(5).unwrap().score, 0);
+        });
+    }
+
+    #[test]
+    fn upvotes_should_be_scored_in_full_again_in_next_scoring_period() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            MaxScoredActionsPerPeriod::set(2);
+
+            upvote_posts_by_account2(4);
+            assert_eq!(Posts::post_by_id(4).unwrap().score, 0);
+
+            System::set_block_number(ScoringPeriodInBlocks::get());
+            assert_ok!(_create_default_post());
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(5), None));
+
+            assert_eq!(Posts::post_by_id(5).unwrap().score, UpvotePostActionWeight::get() as i32);
+            assert_eq!(Scores::account_activity(ACCOUNT2).actions_count, 1);
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_social_account_reputation_should_work_when_max_score_diff_provided() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                std::i16::MAX,
+                scoring_action_follow_account())
+            );
+        });
+    }
+
+    #[test]
+    fn change_social_account_reputation_should_work_when_min_score_diff_provided() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                std::i16::MIN,
+                scoring_action_follow_account())
+            );
+        });
+    }
+
+    #[test]
+    fn change_social_account_reputation_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT1)), None, None, None));
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
                 DownvotePostActionWeight::get(),
                 scoring_action_downvote_post())
             );
-            assert_eq!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_downvote_post())), Some(0));
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert_eq!(Scores::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, scoring_action_downvote_post())), Some(0));
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+
+            // To ensure function works correctly, multiply default UpvotePostActionWeight by two
+            assert_ok!(Scores::change_social_account_reputation(
+                ACCOUNT1,
+                ACCOUNT2,
+                UpvotePostActionWeight::get() * 2,
+                scoring_action_upvote_post())
+            );
+
+            assert_eq!(
+                Scores::account_reputation_diff_by_account(
+                    (
+                        ACCOUNT2,
+                        ACCOUNT1,
+                        scoring_action_upvote_post()
+                    )
+                ), Some(UpvotePostActionWeight::get() * 2)
+            );
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + (UpvotePostActionWeight::get() * 2) as u32);
+        });
+    }
+
+//--------------------------------------------------------------------------------------------------
+
+    #[test]
+    fn change_comment_score_should_work_for_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(
+                ACCOUNT3,
+                POST2,
+                reaction_upvote()
+            ));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_work_for_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_for_revert_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_upvote_comment())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_comment_score_should_for_revert_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_downvote_comment())).is_none());
+        });
+    }
+
+    #[test]
+    fn change_comment_score_check_for_cancel_upvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn change_comment_score_check_for_cancel_downvote() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                None,
+                None
+            ));
+            // PostId 1
+            assert_ok!(_create_comment(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                None,
+                None
+            )); // PostId 2
+
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+
+            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())).is_none());
+            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+        });
+    }
+
+// Shares tests
+
+    #[test]
+    fn share_post_should_fail_when_shares_are_disabled_in_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_update_space_settings(None, Some(true)));
+
+            assert_noop!(
+                _create_post(None, None, Some(extension_shared_post(POST1)), None),
+                PostsError::<TestRuntime>::SharesNotAllowedInThisSpace
+            );
+
+            assert_ok!(_update_space_settings(None, None));
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+        });
+    }
+
+    #[test]
+    fn move_sharing_post_should_fail_when_shares_are_disabled_in_new_space() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_ok!(_update_space_settings(None, Some(true)));
+
+            assert_noop!(
+                _move_post(None, Some(POST2), Some(Some(SPACE1))),
+                PostsError::<TestRuntime>::SharesNotAllowedInThisSpace
+            );
+        });
+    }
+
+    #[test]
+    fn regular_posts_comments_and_outgoing_shares_should_work_when_shares_are_disabled_in_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+            assert_ok!(_update_space_settings(None, Some(true)));
+
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_create_default_comment()); // PostId 3
+
+            // Posts of a space that does not receive shares can still be shared into other spaces:
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 4
+
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![4]);
+        });
+    }
+
+    #[test]
+    fn share_post_should_work() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+
+            assert_eq!(shared_post.space_id, Some(SPACE2));
+            assert_eq!(shared_post.created.account, ACCOUNT2);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_when_one_of_roles_is_permitted() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content,
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1
+
+            // Check storages
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
+            assert_eq!(Posts::next_post_id(), POST3);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+
+            // Check whether data stored correctly
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(shared_post.space_id, Some(SPACE1));
+            assert_eq!(shared_post.created.account, ACCOUNT1);
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+        });
+    }
+
+    #[test]
+    fn share_post_should_change_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + SharePostActionWeight::get() as u32);
+            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())), Some(SharePostActionWeight::get()));
+        });
+    }
+
+    #[test]
+    fn share_post_should_not_change_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+            assert!(Scores::post_score_by_account((ACCOUNT1, POST1, scoring_action_share_post())).is_none());
+        });
+    }
+
+    #[test]
+    fn delete_post_should_unshare_and_revert_score() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            let reputation_before = Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation;
+            let space_score_before = Spaces::space_by_id(SPACE1).unwrap().score;
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().posts_count, 1);
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)));
+
+            // The original post, its space and its owner are back to what they were before the share
+            let original_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(original_post.shares_count, 0);
+            assert_eq!(original_post.score, 0);
+            assert!(Posts::shared_post_ids_by_original_post_id(POST1).is_empty());
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().score, space_score_before);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, reputation_before);
+            assert!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())).is_none());
+
+            // The share is removed from its space
+            assert_eq!(Spaces::space_by_id(SPACE2).unwrap().posts_count, 0);
+            assert!(Posts::post_ids_by_space_id(SPACE2).is_empty());
+
+            let deleted_post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(deleted_post.space_id, None);
+            assert_eq!(deleted_post.extension, PostExtension::RegularPost);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_revert_share_score_only_when_last_share_of_account_is_deleted() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            for _ in 0..2 {
+                assert_ok!(_create_post(
+                    Some(Origin::signed(ACCOUNT2)),
+                    Some(Some(SPACE2)),
+                    Some(extension_shared_post(POST1)),
+                    None
+                ));
+            } // Share PostId 1 twice (PostId 2 and 3) by ACCOUNT2
+            assert_eq!(Scores::shares_count_by_account((ACCOUNT2, POST1)), 2);
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)));
+            assert_eq!(Scores::shares_count_by_account((ACCOUNT2, POST1)), 1);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(3)));
+            assert_eq!(Scores::shares_count_by_account((ACCOUNT2, POST1)), 0);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_revert_share_score_when_original_post_has_no_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+            let reputation_before = Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation;
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+            assert_ok!(_move_post_to_nowhere(POST1));
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)));
+
+            let original_post = Posts::post_by_id(POST1).unwrap();
+            assert_eq!(original_post.shares_count, 0);
+            assert_eq!(original_post.score, 0);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, reputation_before);
+        });
+    }
+
+    #[test]
+    fn delete_post_should_fail_when_account_has_no_permission_to_delete_any_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // Share PostId 1 on SpaceId 1 by ACCOUNT1
+
+            assert_noop!(
+                _delete_post(Some(Origin::signed(ACCOUNT2)), Some(POST2)),
+                PostsError::<TestRuntime>::NoPermissionToDeleteAnyPost
+            );
+        });
+    }
+
+    #[test]
+    fn delete_post_should_fail_when_post_is_not_sharing_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_noop!(
+                _delete_post(None, Some(POST1)),
+                PostsError::<TestRuntime>::OnlySharingPostsCanBeDeleted
+            );
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_original_post_not_found() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            // Skipped creating PostId 1
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+        });
+    }
+
+    #[test]
+    fn share_post_should_work_for_shared_post_within_max_share_depth() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None)
+            ); // PostId 2 shares PostId 1
+
+            // Share a post with extension SharedPost (depth 2)
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST2)),
+                None
+            )); // PostId 3
+
+            // The new share should point to the original post
+            let shared_post = Posts::post_by_id(POST3).unwrap();
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_eq!(Posts::share_depth(&shared_post), 2);
+
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2, POST3]);
+            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 2);
+            assert_eq!(Posts::post_by_id(POST2).unwrap().shares_count, 0);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_max_share_depth_reached() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(b"space2_handle".to_vec())),
+                None,
+                None
+            )); // SpaceId 2 by ACCOUNT2
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None)
+            ); // PostId 2 with share depth 1
+
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST2)),
+                None
+            )); // PostId 3 with share depth 2
+
+            // Try to share a post with share depth 2 when MaxShareDepth is 2
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST3)),
+                None
+            ), PostsError::<TestRuntime>::CannotShareSharingPost);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_space(
+                Some(Origin::signed(ACCOUNT1)),
+                Some(None), // No space_handle provided (ok)
+                None, // Default space content,
+                None
+            )); // SpaceId 2 by ACCOUNT1
+
+            // Try to share post with extension SharedPost
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_create_space(
+                None, // From ACCOUNT1
+                Some(None), // Provided without any handle
+                None, // With default space content
+                None
+            ));
+            // SpaceId 2
+            assert_ok!(_create_post(
+                None, // From ACCOUNT1
+                Some(Some(SPACE2)),
+                None, // With RegularPost extension
+                None // With default post content
+            )); // PostId 1 on SpaceId 2
+
+            assert_ok!(_delete_default_role());
+
+            assert_noop!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE1)),
+                Some(extension_shared_post(POST1)),
+                None
+            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+        });
+    }
+
+    #[test]
+    fn update_shared_post_should_edit_content_but_keep_shared_post_id() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+
+            assert_ok!(_update_post(
+                None, // From ACCOUNT1
+                Some(POST2),
+                Some(post_update(None, Some(updated_post_content()), None))
+            ));
+
+            let shared_post = Posts::post_by_id(POST2).unwrap();
+            assert_eq!(shared_post.content, updated_post_content());
+            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+        });
+    }
+
+// Content deduplication tests
+
+    fn post_ids_with_content(content: Content) -> Vec<PostId> {
+        match content {
+            Content::IPFS(cid) => Posts::post_ids_by_content(cid),
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn create_post_should_index_posts_with_same_content() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2 in SpaceId 1
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 3 in SpaceId 2
+
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1, POST2, 3]);
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_with_duplicate_content_in_space_that_rejects_it() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_reject_duplicate_content_in_space(SPACE1));
+
+            assert_noop!(_create_default_post(), PostsError::<TestRuntime>::DuplicateContentInSpace);
+
+            // A post with other content can be created:
+            assert_ok!(_create_post(None, None, None, Some(updated_post_content())));
+        });
+    }
+
+    #[test]
+    fn create_post_should_allow_same_content_in_different_spaces_that_reject_duplicates() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_reject_duplicate_content_in_space(SPACE1));
+            assert_ok!(_reject_duplicate_content_in_space(SPACE2));
+
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2 in SpaceId 2
+
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1, POST2]);
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_but_skip_indexing_when_too_many_posts_have_same_content() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            for _ in 1..MAX_POSTS_PER_CONTENT {
+                assert_ok!(_create_default_post());
+            }
+            let indexed_post_ids = post_ids_with_content(post_content_ipfs());
+            assert_eq!(indexed_post_ids.len() as u32, MAX_POSTS_PER_CONTENT);
+
+            assert_ok!(_create_default_post());
+            let new_post_id = Posts::next_post_id() - 1;
+
+            assert!(Posts::post_by_id(new_post_id).is_some());
+            assert_eq!(post_ids_with_content(post_content_ipfs()), indexed_post_ids);
+        });
+    }
+
+    #[test]
+    fn update_post_should_move_post_between_content_cids_in_index() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 2
+            assert_ok!(_update_post(
+                None,
+                Some(POST2),
+                Some(post_update(None, Some(updated_post_content()), None))
+            ));
+
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1]);
+            assert_eq!(post_ids_with_content(updated_post_content()), vec![POST2]);
+        });
+    }
+
+    #[test]
+    fn update_post_should_fail_with_duplicate_content_in_space_that_rejects_it() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, None, Some(updated_post_content()))); // PostId 2
+            assert_ok!(_reject_duplicate_content_in_space(SPACE1));
+
+            assert_noop!(_update_post(
+                None,
+                Some(POST2),
+                Some(post_update(None, Some(post_content_ipfs()), None))
+            ), PostsError::<TestRuntime>::DuplicateContentInSpace);
+        });
+    }
+
+    #[test]
+    fn move_post_should_fail_with_duplicate_content_in_space_that_rejects_it() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2 in SpaceId 2
+            assert_ok!(_reject_duplicate_content_in_space(SPACE1));
+
+            assert_noop!(
+                _move_post(None, Some(POST2), Some(Some(SPACE1))),
+                PostsError::<TestRuntime>::DuplicateContentInSpace
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_should_fail_when_reputation_is_below_min_to_post() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(Some(10), None));
+            set_reputation(ACCOUNT2, 9);
+
+            assert_noop!(
+                _create_post(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::ReputationTooLowToPost
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_should_work_when_reputation_reaches_min_to_post() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(Some(10), None));
+            set_reputation(ACCOUNT2, 10);
+
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 1
+            assert_eq!(Posts::post_by_id(POST1).unwrap().owner, ACCOUNT2);
+        });
+    }
+
+    #[test]
+    fn move_post_should_fail_when_reputation_is_below_min_to_post_in_new_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(Some(10), None));
+            set_reputation(ACCOUNT2, 9);
+
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None)); // PostId 1
+
+            assert_noop!(
+                _move_post(Some(Origin::signed(ACCOUNT2)), None, Some(Some(SPACE1))),
+                PostsError::<TestRuntime>::ReputationTooLowToPost
+            );
+        });
+    }
+
+    #[test]
+    fn create_post_should_ignore_min_reputation_for_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(Some(u32::max_value()), Some(u32::max_value())));
+
+            assert_ok!(_create_default_post()); // PostId 1
+            assert_ok!(_create_default_comment()); // PostId 2
+        });
+    }
+
+    #[test]
+    fn create_comment_should_fail_when_reputation_is_below_min_to_comment() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(None, Some(5)));
+            set_reputation(ACCOUNT2, 4);
+
+            assert_noop!(
+                _create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None),
+                PostsError::<TestRuntime>::ReputationTooLowToComment
+            );
+
+            set_reputation(ACCOUNT2, 5);
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
+        });
+    }
+
+    #[test]
+    fn create_comment_should_not_check_min_reputation_to_post() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_set_min_reputation_in_space(Some(u32::max_value()), None));
+
+            assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 2
+        });
+    }
+
+    #[test]
+    fn delete_post_should_remove_sharing_post_from_content_index() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1, POST2]);
+
+            assert_ok!(_delete_post(None, Some(POST2)));
+
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1]);
+        });
+    }
+
+    #[test]
+    fn block_content_everywhere_should_block_posts_with_content_in_all_spaces() {
+        ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_create_post(None, Some(Some(SPACE2)), None, None)); // PostId 2 in SpaceId 2
+            assert_ok!(_create_post(None, None, None, Some(updated_post_content()))); // PostId 3 in SpaceId 1
+
+            assert_eq!(Moderation::block_content_everywhere(post_content_ipfs()), Ok(2));
+
+            assert!(Posts::post_by_id(POST1).unwrap().space_id.is_none());
+            assert!(Posts::post_by_id(POST2).unwrap().space_id.is_none());
+            assert_eq!(Posts::post_by_id(3).unwrap().space_id, Some(SPACE1));
+
+            for space_id in [SPACE1, SPACE2].iter() {
+                assert_eq!(
+                    Moderation::status_by_entity_in_space(EntityId::Content(post_content_ipfs()), space_id),
+                    Some(EntityStatus::Blocked)
+                );
+            }
+
+            assert_noop!(_create_default_post(), UtilsError::<TestRuntime>::ContentIsBlocked);
+        });
+    }
+
+// Profiles tests
+
+    fn assert_social_account_counters_match_storage(account: AccountId) {
+        let social_account = Profiles::social_account_by_id(account).unwrap();
+
+        let root_posts_count = PostById::<TestRuntime>::iter()
+            .filter(|(_, post)| post.created.account == account && post.is_root_post())
+            .count();
+
+        assert_eq!(social_account.posts_count as usize, root_posts_count);
+        assert_eq!(
+            social_account.following_spaces_count as usize,
+            SpaceFollows::spaces_followed_by_account(account).len()
+        );
+        assert_eq!(
+            social_account.following_accounts_count as usize,
+            ProfileFollows::accounts_followed_by_account(account).len()
+        );
+        assert_eq!(social_account.followers_count as usize, ProfileFollows::account_followers(account).len());
+    }
+
+    #[test]
+    fn social_account_counters_should_match_storage_after_mixed_operations() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_default_comment()); // PostId 2 (comment by ACCOUNT1)
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 3
+
+            assert_ok!(_create_space(Some(Origin::signed(ACCOUNT2)), Some(None), None, None)); // SpaceId 2
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(Some(SPACE2)), None, None)); // PostId 4
+            assert_ok!(_create_post(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(SPACE2)),
+                Some(extension_shared_post(POST1)),
+                None
+            )); // PostId 5
+
+            assert_ok!(_default_follow_space());
+            assert_ok!(_default_follow_account());
+            assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT1)), Some(ACCOUNT2)));
+            assert_ok!(_default_unfollow_space());
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT1)), Some(SPACE2)));
+            assert_ok!(_unfollow_account(Some(Origin::signed(ACCOUNT1)), Some(ACCOUNT2)));
+
+            assert_ok!(_delete_post(Some(Origin::signed(ACCOUNT2)), Some(5)));
+
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().posts_count, 2);
+            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().posts_count, 2);
+
+            assert_social_account_counters_match_storage(ACCOUNT1);
+            assert_social_account_counters_match_storage(ACCOUNT2);
+        });
+    }
+
+    #[test]
+    fn get_social_account_should_reflect_identity_verification() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert!(!Profiles::get_social_account(ACCOUNT1).unwrap().is_verified);
+
+            MockIdentityProvider::set_verified(ACCOUNT1, true);
+            let social_account = Profiles::get_social_account(ACCOUNT1).unwrap();
+            assert!(social_account.is_verified);
+            assert_eq!(social_account.profile.unwrap().content, profile_content_ipfs());
+
+            MockIdentityProvider::set_verified(ACCOUNT1, false);
+            assert!(!Profiles::get_social_account(ACCOUNT1).unwrap().is_verified);
+        });
+    }
+
+    #[test]
+    fn refresh_profile_verification_should_emit_event_when_status_changes() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            MockIdentityProvider::set_verified(ACCOUNT1, true);
+            assert_ok!(Profiles::refresh_profile_verification(Origin::signed(ACCOUNT2), ACCOUNT1));
+            assert!(Profiles::profile_verified_by_account(ACCOUNT1));
+            assert!(events().contains(&TestEvent::pallet_profiles(
+                pallet_profiles::RawEvent::ProfileVerificationChanged(ACCOUNT1, true)
+            )));
+
+            // Nothing changes if the status is the same
+            let events_count = events().len();
+            assert_ok!(Profiles::refresh_profile_verification(Origin::signed(ACCOUNT2), ACCOUNT1));
+            assert_eq!(events().len(), events_count);
+
+            MockIdentityProvider::set_verified(ACCOUNT1, false);
+            assert_ok!(Profiles::refresh_profile_verification(Origin::signed(ACCOUNT2), ACCOUNT1));
+            assert!(!Profiles::profile_verified_by_account(ACCOUNT1));
+            assert!(events().contains(&TestEvent::pallet_profiles(
+                pallet_profiles::RawEvent::ProfileVerificationChanged(ACCOUNT1, false)
+            )));
+        });
+    }
+
+    #[test]
+    fn refresh_profile_verification_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(
+                Profiles::refresh_profile_verification(Origin::signed(ACCOUNT2), ACCOUNT1),
+                ProfilesError::<TestRuntime>::SocialAccountNotFound
+            );
+        });
+    }
+
+    #[test]
+    fn create_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile()); // AccountId 1
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.created.account, ACCOUNT1);
+            assert!(profile.updated.is_none());
+            assert_eq!(profile.content, profile_content_ipfs());
+
+            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+        });
+    }
+
+    #[test]
+    fn create_profile_should_work_with_username() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_username())), None));
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.username, Some(profile_username()));
+            assert_eq!(find_account_by_profile_username(profile_username()), Some(ACCOUNT1));
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_username_is_taken() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_username())), None));
+
+            // Usernames are compared case-insensitively
+            let username_in_uppercase = profile_username().to_ascii_uppercase();
+            assert_noop!(_create_profile(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(Some(username_in_uppercase)),
+                None
+            ), ProfilesError::<TestRuntime>::ProfileUsernameTaken);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_username_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
+                None,
+                Some(Some(b"a".to_vec())),
+                None
+            ), UtilsError::<TestRuntime>::HandleIsTooShort);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_profile_is_already_created() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+        });
+    }
+
+    #[test]
+    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_create_profile(
+                None,
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_ok!(_update_profile(
+                None,
+                Some(space_content_ipfs())
+            ));
+
+            // Check whether profile updated correctly
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert!(profile.updated.is_some());
+            assert_eq!(profile.content, space_content_ipfs());
+
+            // Check whether profile history is written correctly
+            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
+            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+        });
+    }
+
+    #[test]
+    fn update_profile_should_work_with_username() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_username())), None));
+            assert_ok!(_update_profile_username(None, Some(b"new_username".to_vec())));
+
+            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+            assert_eq!(profile.username, Some(b"new_username".to_vec()));
+
+            // The old username is released and can be taken by another profile
+            assert!(find_account_by_profile_username(profile_username()).is_none());
+            assert_eq!(find_account_by_profile_username(b"new_username".to_vec()), Some(ACCOUNT1));
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(Some(profile_username())), None));
+
+            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
+            assert_eq!(profile_history.old_data.username, Some(Some(profile_username())));
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_username_is_taken() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_profile(None, Some(Some(profile_username())), None));
+            assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), None, None));
+
+            assert_noop!(_update_profile_username(
+                Some(Origin::signed(ACCOUNT2)),
+                Some(profile_username())
+            ), ProfilesError::<TestRuntime>::ProfileUsernameTaken);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_and_keep_oldest_history_records_when_history_is_full() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+
+            let contents = vec![space_content_ipfs(), profile_content_ipfs()];
+            for i in 0..MAX_PROFILE_HISTORY_RECORDS as u64 {
+                System::set_block_number(i + 2);
+                assert_ok!(_update_profile(None, Some(contents[i as usize % 2].clone())));
+            }
+
+            // Further edits should be refused when the history is full
+            System::set_block_number(5);
+            assert_noop!(
+                _update_profile(None, Some(space_content_ipfs())),
+                UtilsError::<TestRuntime>::HistoryIsFull
+            );
+
+            let blocks: Vec<BlockNumber> = ProfileHistory::edit_history(ACCOUNT1)
+                .iter().map(|record| record.edited.block).collect();
+            assert_eq!(blocks, vec![2, 3, 4]);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_social_account_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_account_has_no_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
+            assert_noop!(_update_profile(
+                None,
+                Some(profile_content_ipfs())
+            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            // AccountId 1
+            assert_noop!(_update_profile(
+                None,
+                None
+            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+        });
+    }
+
+    #[test]
+    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(_update_profile(
+                None,
+                Some(invalid_content_ipfs())
+            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+        });
+    }
+
+    #[test]
+    fn reclaim_social_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            Profiles::orphan_social_account(ACCOUNT1);
+            assert!(Profiles::is_social_account_orphaned(ACCOUNT1));
+
+            assert_ok!(Profiles::reclaim_social_account(Origin::signed(ACCOUNT1)));
+
+            assert!(!Profiles::is_social_account_orphaned(ACCOUNT1));
+            assert!(Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.is_some());
+        });
+    }
+
+    #[test]
+    fn reclaim_social_account_should_fail_when_account_is_not_orphaned() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_profile());
+            assert_noop!(
+                Profiles::reclaim_social_account(Origin::signed(ACCOUNT1)),
+                ProfilesError::<TestRuntime>::SocialAccountNotOrphaned
+            );
+        });
+    }
+
+// Space following tests
+
+    #[test]
+    fn create_space_should_make_owner_first_follower_by_default() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_create_default_space()); // SpaceId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
+            assert!(SpaceFollows::space_followed_by_account((ACCOUNT1, SPACE1)));
+        });
+    }
+
+    #[test]
+    fn create_space_should_not_follow_it_when_auto_follow_disabled() {
+        ExtBuilder::build().execute_with(|| {
+            AutoFollowOwnSpace::set(false);
+
+            assert_ok!(_create_default_space()); // SpaceId 1
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 0);
+            assert!(SpaceFollows::space_followers(SPACE1).is_empty());
+            assert!(!SpaceFollows::space_followed_by_account((ACCOUNT1, SPACE1)));
+
+            // The owner can still follow their space explicitly:
+            assert_ok!(_follow_space(Some(Origin::signed(ACCOUNT1)), None));
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
+            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
+            assert_eq!(sorted_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_account_is_already_space_follower() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
+        });
+    }
+
+    #[test]
+    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_update_space(
+                None,
+                None,
+                Some(space_update(None, None, Some(true)))
+            ));
+
+            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
+        });
+    }
+
+    fn _add_follower_exception(origin: Option<Origin>, account: AccountId) -> DispatchResult {
+        SpaceFollows::add_follower_exception(
+            origin.unwrap_or_else(|| Origin::signed(ACCOUNT1)),
+            SPACE1,
+            account,
+        )
+    }
+
+    fn _hide_default_space() -> DispatchResult {
+        _update_space(None, None, Some(space_update(None, None, Some(true))))
+    }
+
+    #[test]
+    fn follow_space_should_work_for_whitelisted_account_when_space_is_hidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_hide_default_space());
+            assert_ok!(_add_follower_exception(None, ACCOUNT2));
+
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+
+            // A random account still cannot follow the hidden space:
+            assert_noop!(
+                _follow_space(Some(Origin::signed(ACCOUNT3)), None),
+                SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_should_work_for_role_holder_when_space_is_hidden() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_hide_default_space());
+
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+        });
+    }
+
+    #[test]
+    fn follower_exception_should_persist_when_space_is_unhidden() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_hide_default_space());
+            assert_ok!(_add_follower_exception(None, ACCOUNT2));
+            assert_ok!(_update_space(None, None, Some(space_update(None, None, Some(false)))));
+
+            assert!(SpaceFollows::follow_whitelist_by_space(SPACE1, ACCOUNT2));
+        });
+    }
+
+    #[test]
+    fn add_follower_exception_should_fail_when_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _add_follower_exception(Some(Origin::signed(ACCOUNT2)), ACCOUNT2),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+    #[test]
+    fn add_follower_exception_should_fail_when_already_added() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_add_follower_exception(None, ACCOUNT2));
+
+            assert_noop!(
+                _add_follower_exception(None, ACCOUNT2),
+                SpaceFollowsError::<TestRuntime>::FollowerExceptionAlreadyAdded
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space());
+            // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_default_unfollow_space());
+
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), 1);
+            assert!(SpaceFollows::space_follower_since(SPACE1, ACCOUNT2).is_none());
+        });
+    }
+
+    #[test]
+    fn follow_space_should_record_when_account_followed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            System::set_block_number(5);
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+            let followed = SpaceFollows::space_follower_since(SPACE1, ACCOUNT2).unwrap();
+            assert_eq!(followed.account, ACCOUNT2);
+            assert_eq!(followed.block, 5);
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), 2);
+        });
+    }
+
+    #[test]
+    fn space_followers_should_be_paginated_over_many_followers() {
+        const FIRST_FOLLOWER: AccountId = 1_000;
+        const FOLLOWERS: u32 = 1_000;
+        const PAGE: u32 = 100;
+
+        ExtBuilder::build_with_space().execute_with(|| {
+            for follower in FIRST_FOLLOWER..FIRST_FOLLOWER + FOLLOWERS as AccountId {
+                assert_ok!(_follow_space(Some(Origin::signed(follower)), None));
+            }
+
+            // The space owner is its first follower.
+            let total = FOLLOWERS + 1;
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), total);
+            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, total);
+
+            let mut paged_followers = Vec::new();
+            let mut offset = 0;
+            loop {
+                let page = SpaceFollows::get_space_followers(SPACE1, offset, PAGE);
+                assert!(page.len() as u32 <= PAGE);
+                if page.is_empty() {
+                    break;
+                }
+
+                offset += page.len() as u32;
+                paged_followers.extend(page.into_iter().map(|flat| flat.follower));
+            }
+
+            assert_eq!(paged_followers.len() as u32, total);
+            paged_followers.sort();
+            assert_eq!(paged_followers, sorted_space_followers(SPACE1));
+
+            let last_page = SpaceFollows::get_space_followers(SPACE1, total - 1, PAGE);
+            assert_eq!(last_page.len(), 1);
+            assert!(SpaceFollows::get_space_followers(SPACE1, total, PAGE).is_empty());
+
+            assert_ok!(_unfollow_space(Some(Origin::signed(FIRST_FOLLOWER)), None));
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), total - 1);
+            assert_eq!(SpaceFollows::get_space_followers(SPACE1, 0, total).len() as u32, total - 1);
+        });
+    }
+
+    #[test]
+    fn are_accounts_following_space_should_check_every_candidate() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space()); // ACCOUNT2 follows SPACE1
+
+            assert_eq!(
+                SpaceFollows::are_accounts_following_space(SPACE1, vec![ACCOUNT3, ACCOUNT1, ACCOUNT2, ACCOUNT3]),
+                vec![false, true, true, false]
+            );
+            assert_eq!(SpaceFollows::are_accounts_following_space(SPACE2, vec![ACCOUNT1, ACCOUNT2]), vec![false, false]);
+            assert!(SpaceFollows::are_accounts_following_space(SPACE1, vec![]).is_empty());
+
+            assert_ok!(_default_unfollow_space());
+            assert_eq!(SpaceFollows::are_accounts_following_space(SPACE1, vec![ACCOUNT2]), vec![false]);
+        });
+    }
+
+    #[test]
+    fn filter_space_followers_should_return_followers_followed_by_viewer() {
+        const VIEWER: AccountId = 10;
+        const FRIEND1: AccountId = 20;
+        const FRIEND2: AccountId = 21;
+        const FRIEND_NOT_FOLLOWING_SPACE: AccountId = 22;
+        const STRANGER: AccountId = 23;
+
+        ExtBuilder::build_with_space().execute_with(|| {
+            for follower in &[FRIEND1, FRIEND2, STRANGER] {
+                assert_ok!(_follow_space(Some(Origin::signed(*follower)), None));
+            }
+            for friend in &[FRIEND1, FRIEND_NOT_FOLLOWING_SPACE, FRIEND2] {
+                assert_ok!(_follow_account(Some(Origin::signed(VIEWER)), Some(*friend)));
+            }
+
+            let followed_by_viewer = ProfileFollows::accounts_followed_by_account(VIEWER);
+            assert_eq!(
+                SpaceFollows::filter_space_followers(SPACE1, followed_by_viewer.clone(), 10),
+                vec![FRIEND1, FRIEND2]
+            );
+            assert_eq!(SpaceFollows::filter_space_followers(SPACE1, followed_by_viewer, 1), vec![FRIEND1]);
+            assert!(SpaceFollows::filter_space_followers(SPACE1, ProfileFollows::accounts_followed_by_account(STRANGER), 10).is_empty());
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_fail_when_space_not_found() {
+        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn force_unfollow_missing_space_should_remove_follow_of_removed_space() {
+        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
+            let following_spaces = Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count;
+
+            assert_ok!(SpaceFollows::force_unfollow_missing_space(Origin::signed(ACCOUNT2), SPACE1));
+
+            assert!(!SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert!(SpaceFollows::space_follower_since(SPACE1, ACCOUNT2).is_none());
+            assert_eq!(sorted_space_followers(SPACE1), vec![ACCOUNT1]);
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), 1);
+            assert_eq!(
+                Profiles::social_account_by_id(ACCOUNT2).unwrap().following_spaces_count,
+                following_spaces - 1
+            );
+            assert!(events().contains(&TestEvent::pallet_space_follows(
+                pallet_space_follows::RawEvent::MissingSpaceUnfollowed(ACCOUNT2, SPACE1)
+            )));
+
+            // There is nothing left to clean up:
+            assert_noop!(
+                SpaceFollows::force_unfollow_missing_space(Origin::signed(ACCOUNT2), SPACE1),
+                SpaceFollowsError::<TestRuntime>::NotSpaceFollower
+            );
+        });
+    }
+
+    #[test]
+    fn force_unfollow_missing_space_should_fail_when_space_exists() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_default_follow_space());
+
+            assert_noop!(
+                SpaceFollows::force_unfollow_missing_space(Origin::signed(ACCOUNT2), SPACE1),
+                SpaceFollowsError::<TestRuntime>::SpaceIsNotMissing
+            );
+        });
+    }
+
+    #[test]
+    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
+        });
+    }
+
+// Account following tests
+
+    #[test]
+    fn follow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+
+            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
+            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_follow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
+        });
+    }
+
+    #[test]
+    fn follow_account_should_fail_when_account_is_already_following_account() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+
+            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            // Follow ACCOUNT1 by ACCOUNT2
+            assert_ok!(_default_unfollow_account());
+
+            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
+            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
+            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_reap_social_accounts_created_only_for_follow() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert!(Profiles::social_account_by_id(ACCOUNT1).is_some());
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_some());
+
+            assert_ok!(_default_unfollow_account());
+
+            assert!(Profiles::social_account_by_id(ACCOUNT1).is_none());
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_none());
+            assert!(events().contains(&TestEvent::pallet_profiles(
+                pallet_profiles::RawEvent::SocialAccountReaped(ACCOUNT1)
+            )));
+            assert!(events().contains(&TestEvent::pallet_profiles(
+                pallet_profiles::RawEvent::SocialAccountReaped(ACCOUNT2)
+            )));
+
+            // The same accounts can follow each other again
+            assert_ok!(_default_follow_account());
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_keep_social_account_with_profile() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_create_default_profile()); // ACCOUNT1 creates a profile
+
+            assert_ok!(_default_unfollow_account());
+
+            let social_account = Profiles::social_account_by_id(ACCOUNT1).unwrap();
+            assert!(social_account.profile.is_some());
+            assert_eq!(social_account.followers_count, 0);
+            assert!(Profiles::social_account_by_id(ACCOUNT2).is_none());
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_unfollow_account(
+                None,
+                Some(ACCOUNT2)
+            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+        });
+    }
+
+    #[test]
+    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
+        ExtBuilder::build().execute_with(|| {
+            assert_ok!(_default_follow_account());
+            assert_ok!(_default_unfollow_account());
+
+            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+        });
+    }
+
+// Unsigned follows tests
+
+    /// Get a signature of `payload` that `TestSignature` treats as made by `signer`.
+    fn sign_payload<P: Encode>(signer: AccountId, payload: &P) -> TestSignature {
+        TestSignature(signer, payload.encode())
+    }
+
+    fn follow_space_payload(follower: AccountId, nonce: u32) -> FollowSpacePayload<AccountId, H256> {
+        FollowSpacePayload { follower, space_id: SPACE1, nonce, genesis_hash: System::block_hash(0) }
+    }
+
+    fn validate_follow_space_unsigned(
+        payload: FollowSpacePayload<AccountId, H256>,
+        signature: TestSignature,
+    ) -> TransactionValidity {
+        SpaceFollows::validate_unsigned(
+            TransactionSource::External,
+            &pallet_space_follows::Call::follow_space_unsigned(payload, signature),
+        )
+    }
+
+    fn _follow_space_unsigned(payload: FollowSpacePayload<AccountId, H256>) -> DispatchResult {
+        let signature = sign_payload(payload.follower, &payload);
+        SpaceFollows::follow_space_unsigned(Origin::none(), payload, signature)
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let payload = follow_space_payload(ACCOUNT2, 0);
+            let signature = sign_payload(ACCOUNT2, &payload);
+
+            assert!(validate_follow_space_unsigned(payload.clone(), signature).is_ok());
+            assert_ok!(_follow_space_unsigned(payload));
+
+            assert_eq!(sorted_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(SpaceFollows::unsigned_follow_nonce(ACCOUNT2), 1);
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_signed_by_another_account() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let payload = follow_space_payload(ACCOUNT2, 0);
+            let signature = sign_payload(ACCOUNT3, &payload);
+
+            assert_eq!(
+                validate_follow_space_unsigned(payload, signature),
+                Err(InvalidTransaction::BadProof.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_with_genesis_hash_of_another_chain() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let payload = FollowSpacePayload { genesis_hash: H256::repeat_byte(1), ..follow_space_payload(ACCOUNT2, 0) };
+
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)),
+                Err(InvalidTransaction::BadProof.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_account_is_orphaned() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            Profiles::orphan_social_account(ACCOUNT2);
+
+            let payload = follow_space_payload(ACCOUNT2, 0);
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)),
+                Err(InvalidTransaction::Call.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_nonce_is_stale_or_future() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_follow_space_unsigned(follow_space_payload(ACCOUNT2, 0)));
+            assert_ok!(_default_unfollow_space());
+
+            let replayed = follow_space_payload(ACCOUNT2, 0);
+            assert_eq!(
+                validate_follow_space_unsigned(replayed.clone(), sign_payload(ACCOUNT2, &replayed)),
+                Err(InvalidTransaction::Stale.into())
+            );
+
+            let future = follow_space_payload(ACCOUNT2, 2);
+            assert_eq!(
+                validate_follow_space_unsigned(future.clone(), sign_payload(ACCOUNT2, &future)),
+                Err(InvalidTransaction::Future.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_allowance_is_used_up() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let allowance = UnsignedFollowsAllowance::get();
+            UnsignedFollowNonce::<TestRuntime>::insert(ACCOUNT2, allowance);
+
+            let payload = follow_space_payload(ACCOUNT2, allowance);
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)),
+                Err(InvalidTransaction::Payment.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_free_balance_is_too_low() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let new_account = ACCOUNT3 + 1;
+            let payload = follow_space_payload(new_account, 0);
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(new_account, &payload)),
+                Err(InvalidTransaction::Payment.into())
+            );
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_not_use_up_nonce_and_block_limit_when_follow_fails() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            // ACCOUNT1 follows its own space since it was created
+            assert_noop!(
+                _follow_space_unsigned(follow_space_payload(ACCOUNT1, 0)),
+                SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower
+            );
+
+            assert_eq!(SpaceFollows::unsigned_follow_nonce(ACCOUNT1), 0);
+            assert_eq!(SpaceFollows::unsigned_follows_in_block(), 0);
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_limited_per_block() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let max_per_block = MaxUnsignedFollowsPerBlock::get() as AccountId;
+            let first_follower = ACCOUNT3 + 1;
+            for follower in first_follower..first_follower + max_per_block {
+                assert_ok!(_follow_space_unsigned(follow_space_payload(follower, 0)));
+            }
+
+            let payload = follow_space_payload(ACCOUNT2, 0);
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)),
+                Err(InvalidTransaction::ExhaustsResources.into())
+            );
+
+            // The limit is reset in the next block.
+            SpaceFollows::on_finalize(System::block_number());
+            assert!(validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)).is_ok());
+        });
+    }
+
+    #[test]
+    fn follow_space_unsigned_should_be_invalid_when_account_is_frozen_or_already_follower() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let payload = follow_space_payload(ACCOUNT1, 0);
+            // ACCOUNT1 follows its own space since it was created
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT1, &payload)),
+                Err(InvalidTransaction::Call.into())
+            );
+
+            assert_ok!(Utils::<TestRuntime>::freeze_account(Origin::root(), ACCOUNT2));
+            let payload = follow_space_payload(ACCOUNT2, 0);
+            assert_eq!(
+                validate_follow_space_unsigned(payload.clone(), sign_payload(ACCOUNT2, &payload)),
+                Err(InvalidTransaction::Call.into())
+            );
+        });
+    }
+
+    fn validate_follow_account_unsigned(payload: FollowAccountPayload<AccountId>) -> TransactionValidity {
+        let signature = sign_payload(payload.follower, &payload);
+        ProfileFollows::validate_unsigned(
+            TransactionSource::External,
+            &pallet_profile_follows::Call::follow_account_unsigned(payload, signature),
+        )
+    }
+
+    #[test]
+    fn follow_account_unsigned_should_work() {
+        ExtBuilder::build().execute_with(|| {
+            let payload = FollowAccountPayload { follower: ACCOUNT2, account: ACCOUNT1, nonce: 0 };
+            let signature = sign_payload(ACCOUNT2, &payload);
+
+            assert!(validate_follow_account_unsigned(payload.clone()).is_ok());
+            assert_ok!(ProfileFollows::follow_account_unsigned(Origin::none(), payload, signature));
+
+            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
+            assert_eq!(ProfileFollows::unsigned_follow_nonce(ACCOUNT2), 1);
+        });
+    }
+
+    #[test]
+    fn follow_account_unsigned_should_be_invalid_when_account_follows_itself() {
+        ExtBuilder::build().execute_with(|| {
+            let payload = FollowAccountPayload { follower: ACCOUNT2, account: ACCOUNT2, nonce: 0 };
+
+            assert_eq!(validate_follow_account_unsigned(payload), Err(InvalidTransaction::Call.into()));
+        });
+    }
+
+// Transfer ownership tests
+
+    #[test]
+    fn transfer_space_ownership_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+
+            assert_eq!(SpaceOwnership::pending_space_owner(SPACE1).unwrap(), ACCOUNT2);
+        });
+    }
+
+    #[test]
+    fn transfer_space_ownership_should_fail_when_space_not_found() {
+        ExtBuilder::build().execute_with(|| {
+            assert_noop!(_transfer_default_space_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn transfer_space_ownership_should_fail_when_account_is_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_transfer_space_ownership(
+                Some(Origin::signed(ACCOUNT2)),
+                None,
+                Some(ACCOUNT1)
+            ), SpacesError::<TestRuntime>::NotASpaceOwner);
+        });
+    }
+
+    #[test]
+    fn transfer_space_ownership_should_fail_when_trying_to_transfer_to_current_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_transfer_space_ownership(
+                Some(Origin::signed(ACCOUNT1)),
+                None,
+                Some(ACCOUNT1)
+            ), SpaceOwnershipError::<TestRuntime>::CannotTranferToCurrentOwner);
+        });
+    }
+
+    #[test]
+    fn accept_pending_ownership_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership());
+            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+            assert_ok!(_accept_default_pending_ownership()); // Accepting a transfer from ACCOUNT2
+            // Check whether owner was changed
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, ACCOUNT2);
+
+            // Check whether storage state is correct
+            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+        });
+    }
 
-            // To ensure function works correctly, multiply default UpvotePostActionWeight by two
-            assert_ok!(Scores::change_social_account_reputation(
-                ACCOUNT1,
-                ACCOUNT2,
-                UpvotePostActionWeight::get() * 2,
-                scoring_action_upvote_post())
-            );
+    #[test]
+    fn accept_pending_ownership_should_move_space_id_to_new_owner_index() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_space(None, Some(None), None, None)); // SpaceId 2 by ACCOUNT1
 
-            assert_eq!(
-                Scores::account_reputation_diff_by_account(
-                    (
-                        ACCOUNT2,
-                        ACCOUNT1,
-                        scoring_action_upvote_post()
-                    )
-                ), Some(UpvotePostActionWeight::get() * 2)
-            );
+            // Transfer SpaceId 1 from ACCOUNT1 to ACCOUNT2
+            assert_ok!(_transfer_default_space_ownership());
+            assert_ok!(_accept_default_pending_ownership());
 
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + (UpvotePostActionWeight::get() * 2) as u32);
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE2]);
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT2), vec![SPACE1]);
+
+            assert!(events().contains(&TestEvent::pallet_space_ownership(
+                pallet_space_ownership::RawEvent::SpaceOwnershipTransferAccepted(ACCOUNT2, SPACE1, ACCOUNT1)
+            )));
         });
     }
 
-//--------------------------------------------------------------------------------------------------
+    #[test]
+    fn accept_pending_ownership_should_keep_order_of_remaining_spaces_of_old_owner() {
+        ExtBuilder::default().with_spaces(4).build_ext().execute_with(|| {
+            assert_ok!(_transfer_space_ownership(None, Some(SPACE2), None));
+            assert_ok!(_accept_pending_ownership(None, Some(SPACE2)));
+
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1, SPACE2 + 1, SPACE2 + 2]);
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT2), vec![SPACE2]);
+        });
+    }
 
     #[test]
-    fn change_comment_score_should_work_for_upvote() {
+    fn get_space_owners_should_return_single_owner() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            assert_eq!(Spaces::get_space_owners(SPACE1), Some(pallet_utils::SpaceOwnership::Single(ACCOUNT1)));
+            assert!(Spaces::get_space_owners(SPACE2).is_none());
 
-            assert_ok!(_score_post_on_reaction_with_id(
-                ACCOUNT3,
-                POST2,
-                reaction_upvote()
-            ));
-
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+            assert_ok!(_transfer_default_space_ownership());
+            assert_ok!(_accept_default_pending_ownership());
+            assert_eq!(Spaces::get_space_owners(SPACE1), Some(pallet_utils::SpaceOwnership::Single(ACCOUNT2)));
         });
     }
 
     #[test]
-    fn change_comment_score_should_work_for_downvote() {
+    fn accept_pending_ownership_should_keep_owner_indexes_in_sync_after_a_few_transfers() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            // Transfer SpaceId 1 from ACCOUNT1 to ACCOUNT2 and then from ACCOUNT2 to ACCOUNT3
+            assert_ok!(_transfer_default_space_ownership());
+            assert_ok!(_accept_default_pending_ownership());
+            assert_ok!(_transfer_space_ownership(Some(Origin::signed(ACCOUNT2)), None, Some(ACCOUNT3)));
+            assert_ok!(_accept_pending_ownership(Some(Origin::signed(ACCOUNT3)), None));
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+            assert!(Spaces::space_ids_by_owner(ACCOUNT2).is_empty());
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT3), vec![SPACE1]);
+        });
+    }
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+    #[test]
+    fn reject_pending_ownership_should_not_change_owner_indexes() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership());
+            assert_ok!(_reject_default_pending_ownership());
+
+            assert_eq!(Spaces::space_ids_by_owner(ACCOUNT1), vec![SPACE1]);
+            assert!(Spaces::space_ids_by_owner(ACCOUNT2).is_empty());
         });
     }
 
     #[test]
-    fn change_comment_score_should_for_revert_upvote() {
+    fn accept_pending_ownership_should_record_ownership_history() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            // Transfer SpaceId 1 from ACCOUNT1 to ACCOUNT2
+            assert_ok!(_transfer_default_space_ownership());
+            assert_ok!(_accept_default_pending_ownership());
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            // Transfer SpaceId 1 from ACCOUNT2 to ACCOUNT3
+            System::set_block_number(2);
+            assert_ok!(_transfer_space_ownership(Some(Origin::signed(ACCOUNT2)), None, Some(ACCOUNT3)));
+            assert_ok!(_accept_pending_ownership(Some(Origin::signed(ACCOUNT3)), None));
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_upvote_comment())).is_none());
+            let history = SpaceHistory::ownership_history(SPACE1);
+            assert_eq!(history.len(), 2);
+
+            assert_eq!(history[0].old_owner, ACCOUNT1);
+            assert_eq!(history[0].new_owner, ACCOUNT2);
+            assert_eq!(history[0].changed.block, 1);
+
+            assert_eq!(history[1].old_owner, ACCOUNT2);
+            assert_eq!(history[1].new_owner, ACCOUNT3);
+            assert_eq!(history[1].changed.block, 2);
         });
     }
 
     #[test]
-    fn change_comment_score_should_for_revert_downvote() {
+    fn accept_pending_ownership_should_fail_when_space_not_found() {
+        ExtBuilder::build_with_pending_ownership_transfer_no_space().execute_with(|| {
+            assert_noop!(_accept_default_pending_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
+
+    #[test]
+    fn accept_pending_ownership_should_fail_when_no_pending_transfer_for_space() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
+            assert_noop!(_accept_default_pending_ownership(), SpaceOwnershipError::<TestRuntime>::NoPendingTransferOnSpace);
+        });
+    }
+
+    #[test]
+    fn accept_pending_ownership_should_fail_if_origin_is_already_an_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership());
+
+            assert_noop!(_accept_pending_ownership(
                 Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
                 None
-            )); // PostId 2
+            ), SpaceOwnershipError::<TestRuntime>::AlreadyASpaceOwner);
+        });
+    }
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+    #[test]
+    fn accept_pending_ownership_should_fail_if_origin_is_not_equal_to_pending_account() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership());
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST2, scoring_action_downvote_comment())).is_none());
+            assert_noop!(_accept_pending_ownership(
+                Some(Origin::signed(ACCOUNT3)),
+                None
+            ), SpaceOwnershipError::<TestRuntime>::NotAllowedToAcceptOwnershipTransfer);
         });
     }
 
     #[test]
-    fn change_comment_score_check_for_cancel_upvote() {
+    fn reject_pending_ownership_should_work() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            assert_ok!(_transfer_default_space_ownership());
+            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+            assert_ok!(_reject_default_pending_ownership()); // Rejecting a transfer from ACCOUNT2
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
+            // Check whether owner was not changed
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, ACCOUNT1);
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, DownvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())), Some(DownvoteCommentActionWeight::get()));
+            // Check whether storage state is correct
+            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
         });
     }
 
     #[test]
-    fn change_comment_score_check_for_cancel_downvote() {
+    fn reject_pending_ownership_should_work_when_proposal_rejected_by_current_space_owner() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                None,
-                None
-            ));
-            // PostId 1
-            assert_ok!(_create_comment(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                None,
-                None
-            )); // PostId 2
+            assert_ok!(_transfer_default_space_ownership());
+            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+            assert_ok!(_reject_default_pending_ownership_by_current_owner()); // Rejecting a transfer from ACCOUNT2
 
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_downvote()));
-            assert_ok!(_score_post_on_reaction_with_id(ACCOUNT3, POST2, reaction_upvote()));
+            // Check whether owner was not changed
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, ACCOUNT1);
 
-            assert_eq!(Posts::post_by_id(POST2).unwrap().score, UpvoteCommentActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + CreateCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + UpvoteCommentActionWeight::get() as u32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT3).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_downvote_comment())).is_none());
-            assert_eq!(Scores::post_score_by_account((ACCOUNT3, POST2, scoring_action_upvote_comment())), Some(UpvoteCommentActionWeight::get()));
+            // Check whether storage state is correct
+            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
         });
     }
 
-// Shares tests
+    #[test]
+    fn reject_pending_ownership_should_fail_when_space_not_found() {
+        ExtBuilder::build_with_pending_ownership_transfer_no_space().execute_with(|| {
+            assert_noop!(_reject_default_pending_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+        });
+    }
 
     #[test]
-    fn share_post_should_work() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
+    fn reject_pending_ownership_should_fail_when_no_pending_transfer_on_space() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(_reject_default_pending_ownership(), SpaceOwnershipError::<TestRuntime>::NoPendingTransferOnSpace); // Rejecting a transfer from ACCOUNT2
+        });
+    }
+
+    #[test]
+    fn reject_pending_ownership_should_fail_when_account_is_not_allowed_to_reject() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+
+            assert_noop!(_reject_pending_ownership(
+                Some(Origin::signed(ACCOUNT3)),
                 None
-            )); // SpaceId 2 by ACCOUNT2
+            ), SpaceOwnershipError::<TestRuntime>::NotAllowedToRejectOwnershipTransfer); // Rejecting a transfer from ACCOUNT2
+        });
+    }
+
+    #[test]
+    fn renounce_space_ownership_should_work() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+            assert_ok!(_renounce_space_ownership(None, None));
+
+            let renounced_owner = SpaceOwnership::renounced_space_owner();
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(space.owner, renounced_owner);
+            assert_eq!(space.created.account, ACCOUNT1);
+
+            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+            assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+            assert_eq!(Spaces::space_ids_by_owner(renounced_owner), vec![SPACE1]);
+
+            for account in [ACCOUNT1, ACCOUNT2, ACCOUNT3].iter() {
+                assert_noop!(space.ensure_space_owner(*account), SpacesError::<TestRuntime>::NotASpaceOwner);
+            }
+
+            assert!(events().contains(&TestEvent::pallet_space_ownership(
+                pallet_space_ownership::RawEvent::SpaceOwnershipRenounced(ACCOUNT1, SPACE1)
+            )));
+        });
+    }
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2 which is permitted by default in both spaces
+    #[test]
+    fn renounce_space_ownership_should_forfeit_handle_deposit_to_treasury() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let treasury = Utils::<TestRuntime>::treasury_account();
+            let treasury_balance = Balances::free_balance(treasury);
+            let owner_balance = Balances::free_balance(ACCOUNT1);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), HANDLE_DEPOSIT);
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
-            assert_eq!(Posts::post_ids_by_space_id(SPACE2), vec![POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
+            assert_ok!(_renounce_space_ownership(None, None));
 
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+            assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+            assert_eq!(Balances::free_balance(ACCOUNT1), owner_balance);
+            assert_eq!(Balances::free_balance(treasury), treasury_balance + HANDLE_DEPOSIT);
+            assert!(Spaces::handle_deposit_by_space_id(SPACE1).is_none());
 
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+            // The handle stays with the space:
+            assert_eq!(find_space_id_by_handle(space_handle()), Some(SPACE1));
+        });
+    }
 
-            let shared_post = Posts::post_by_id(POST2).unwrap();
+    #[test]
+    fn renounce_space_ownership_should_block_owner_only_actions_of_former_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_renounce_space_ownership(None, None));
 
-            assert_eq!(shared_post.space_id, Some(SPACE2));
-            assert_eq!(shared_post.created.account, ACCOUNT2);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            assert_noop!(_transfer_default_space_ownership(), SpacesError::<TestRuntime>::NotASpaceOwner);
+            assert_noop!(_renounce_space_ownership(None, None), SpacesError::<TestRuntime>::NotASpaceOwner);
+            assert_noop!(_update_space(
+                None,
+                None,
+                Some(space_update(None, Some(updated_space_content()), None))
+            ), SpacesError::<TestRuntime>::NoPermissionToUpdateSpace);
         });
     }
 
     #[test]
-    fn share_post_should_work_when_one_of_roles_is_permitted() {
+    fn renounce_space_ownership_should_keep_granted_roles_working() {
         ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content,
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+            assert_ok!(_renounce_space_ownership(None, None));
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 1 by ACCOUNT2 which is permitted by RoleId 1 from ext
+            assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, None, None)); // PostId 1
+            assert_eq!(Posts::post_by_id(POST1).unwrap().owner, ACCOUNT2);
         });
     }
 
     #[test]
-    fn share_post_should_work_for_share_own_post_in_same_own_space() {
+    fn renounce_space_ownership_should_fail_when_account_is_not_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_noop!(
+                _renounce_space_ownership(Some(Origin::signed(ACCOUNT2)), None),
+                SpacesError::<TestRuntime>::NotASpaceOwner
+            );
+        });
+    }
+
+// Donations tests
+
+    #[test]
+    fn donate_should_work_for_post_author() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1
+            let treasury = Utils::<TestRuntime>::treasury_account();
+            let author_balance = Balances::free_balance(ACCOUNT1);
 
-            // Check storages
-            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1, POST2]);
-            assert_eq!(Posts::next_post_id(), POST3);
+            assert_ok!(_donate(None, None, None, Some(50))); // ACCOUNT2 tips POST1 by ACCOUNT1
 
-            assert_eq!(Posts::shared_post_ids_by_original_post_id(POST1), vec![POST2]);
+            // 10% of donation goes to the treasury
+            assert_eq!(Balances::free_balance(ACCOUNT2), 50);
+            assert_eq!(Balances::free_balance(ACCOUNT1), author_balance + 45);
+            assert_eq!(Balances::free_balance(treasury), 5);
 
-            // Check whether data stored correctly
-            assert_eq!(Posts::post_by_id(POST1).unwrap().shares_count, 1);
+            assert_eq!(Donations::donations_by_post(POST1), 45);
+            assert_eq!(Donations::donations_by_account(ACCOUNT1), 45);
+            assert_eq!(Donations::donations_by_supporter(ACCOUNT2), vec![1]);
+            assert_eq!(Donations::donation_ids_by_recipient(DonationRecipient::Post(POST1)), vec![1]);
 
-            let shared_post = Posts::post_by_id(POST2).unwrap();
-            assert_eq!(shared_post.space_id, Some(SPACE1));
-            assert_eq!(shared_post.created.account, ACCOUNT1);
-            assert_eq!(shared_post.extension, extension_shared_post(POST1));
+            let donation = Donations::donation_by_id(1).unwrap();
+            assert_eq!(donation.recipient, User::Account(ACCOUNT1));
+            assert_eq!(donation.donation_wallet, ACCOUNT1);
+            assert_eq!(donation.post_id, Some(POST1));
+            assert_eq!((donation.amount, donation.fee), (50, 5));
+
+            assert!(events().contains(&TestEvent::pallet_donations(
+                pallet_donations::RawEvent::DonationReceived(ACCOUNT2, User::Account(ACCOUNT1), Some(POST1), 50, 5)
+            )));
         });
     }
 
     #[test]
-    fn share_post_should_change_score() {
-        ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+    fn donate_should_work_for_space_owner() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let owner_balance = Balances::free_balance(ACCOUNT1);
 
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId 1 on SpaceId 2 by ACCOUNT2
+            assert_ok!(_donate(None, Some(User::Space(SPACE1)), Some(None), Some(20)));
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, SharePostActionWeight::get() as i32);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + SharePostActionWeight::get() as u32);
-            assert_eq!(Scores::post_score_by_account((ACCOUNT2, POST1, scoring_action_share_post())), Some(SharePostActionWeight::get()));
+            assert_eq!(Balances::free_balance(ACCOUNT1), owner_balance + 18);
+            assert_eq!(Donations::donations_by_account(ACCOUNT1), 18);
+            assert_eq!(Donations::donation_ids_by_recipient(DonationRecipient::Space(SPACE1)), vec![1]);
+
+            let donation = Donations::donation_by_id(1).unwrap();
+            assert_eq!(donation.recipient, User::Space(SPACE1));
+            assert_eq!(donation.donation_wallet, ACCOUNT1);
+            assert!(donation.post_id.is_none());
         });
     }
 
     #[test]
-    fn share_post_should_not_change_score() {
+    fn donate_should_route_fee_to_treasury() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            )); // Share PostId
+            let treasury = Utils::<TestRuntime>::treasury_account();
+            let amount = 33;
+            let expected_fee = Perbill::from_percent(DONATION_FEE_PERCENT) * amount;
+            assert_eq!(expected_fee, 3);
 
-            assert_eq!(Posts::post_by_id(POST1).unwrap().score, 0);
-            assert_eq!(Profiles::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
-            assert!(Scores::post_score_by_account((ACCOUNT1, POST1, scoring_action_share_post())).is_none());
-        });
-    }
+            assert_ok!(_donate(None, Some(User::Space(SPACE1)), None, Some(amount)));
 
-    #[test]
-    fn share_post_should_fail_when_original_post_not_found() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
+            let donation = Donations::donation_by_id(1).unwrap();
+            assert_eq!(donation.fee, expected_fee);
+            assert_eq!(Donations::donations_by_post(POST1), amount - expected_fee);
+            assert_eq!(Balances::free_balance(treasury), expected_fee);
 
-            // Skipped creating PostId 1
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::OriginalPostNotFound);
+            assert!(events().contains(&TestEvent::pallet_utils(
+                pallet_utils::RawEvent::Deposit(treasury, expected_fee)
+            )));
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_trying_to_share_shared_post() {
+    fn donate_should_fail_when_recipient_not_related_to_post() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(b"space2_handle".to_vec())),
-                None,
-                None
-            )); // SpaceId 2 by ACCOUNT2
-
-            assert_ok!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None)
+            assert_noop!(
+                _donate(None, Some(User::Account(ACCOUNT3)), None, None),
+                DonationsError::<TestRuntime>::RecipientNotRelatedToPost
             );
-
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST2)),
-                None
-            ), PostsError::<TestRuntime>::CannotShareSharingPost);
         });
     }
 
     #[test]
-    fn share_post_should_fail_when_account_has_no_permission_to_create_posts_in_new_space() {
+    fn donate_should_fail_when_amount_is_zero() {
         ExtBuilder::build_with_post().execute_with(|| {
-            assert_ok!(_create_space(
-                Some(Origin::signed(ACCOUNT1)),
-                Some(None), // No space_handle provided (ok)
-                None, // Default space content,
-                None
-            )); // SpaceId 2 by ACCOUNT1
-
-            // Try to share post with extension SharedPost
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE2)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            assert_noop!(_donate(None, None, None, Some(0)), DonationsError::<TestRuntime>::ZeroDonation);
         });
     }
 
+// Subscriptions tests
+
     #[test]
-    fn share_post_should_fail_when_no_right_permission_in_account_roles() {
-        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
-            assert_ok!(_create_space(
-                None, // From ACCOUNT1
-                Some(None), // Provided without any handle
-                None, // With default space content
-                None
-            ));
-            // SpaceId 2
-            assert_ok!(_create_post(
-                None, // From ACCOUNT1
-                Some(Some(SPACE2)),
-                None, // With RegularPost extension
-                None // With default post content
-            )); // PostId 1 on SpaceId 2
+    fn subscription_should_be_charged_every_billing_period() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let owner_balance = Balances::free_balance(ACCOUNT1);
 
-            assert_ok!(_delete_default_role());
+            assert_ok!(_create_default_subscription_plan()); // Price is 10 per day (10 blocks)
+            assert_ok!(_subscribe(None, None)); // ACCOUNT2 subscribes at block 1
+            assert_eq!(Balances::free_balance(ACCOUNT2), 90);
+            assert_eq!(Subscriptions::subscription_ids_by_patron(ACCOUNT2), vec![SUBSCRIPTION1]);
+            assert_eq!(Subscriptions::subscription_ids_by_space(SPACE1), vec![SUBSCRIPTION1]);
 
-            assert_noop!(_create_post(
-                Some(Origin::signed(ACCOUNT2)),
-                Some(Some(SPACE1)),
-                Some(extension_shared_post(POST1)),
-                None
-            ), PostsError::<TestRuntime>::NoPermissionToCreatePosts);
+            run_to_block(10);
+            assert_eq!(Balances::free_balance(ACCOUNT2), 90);
+
+            run_to_block(11); // The first billing period ends
+            assert_eq!(Balances::free_balance(ACCOUNT2), 80);
+
+            run_to_block(21); // The second billing period ends
+            assert_eq!(Balances::free_balance(ACCOUNT2), 70);
+            assert_eq!(Balances::free_balance(ACCOUNT1), owner_balance + 30);
+
+            assert!(events().contains(&TestEvent::pallet_subscriptions(
+                pallet_subscriptions::RawEvent::SubscriptionPaymentProcessed(ACCOUNT2, SUBSCRIPTION1, 10)
+            )));
+            assert!(Subscriptions::subscription_by_id(SUBSCRIPTION1).unwrap().is_active);
         });
     }
 
-// Profiles tests
-
     #[test]
-    fn create_profile_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile()); // AccountId 1
+    fn cancel_subscription_should_stop_recurring_payments() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_subscription_plan());
+            assert_ok!(_subscribe(None, None));
 
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert_eq!(profile.created.account, ACCOUNT1);
-            assert!(profile.updated.is_none());
-            assert_eq!(profile.content, profile_content_ipfs());
+            run_to_block(11);
+            assert_ok!(_cancel_subscription(None, None));
+            assert!(Subscriptions::subscription_ids_by_patron(ACCOUNT2).is_empty());
+            assert!(Subscriptions::subscription_ids_by_space(SPACE1).is_empty());
 
-            assert!(ProfileHistory::edit_history(ACCOUNT1).is_empty());
+            run_to_block(31);
+            assert_eq!(Balances::free_balance(ACCOUNT2), 80);
+            assert!(!Subscriptions::subscription_by_id(SUBSCRIPTION1).unwrap().is_active);
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_profile_is_already_created() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_create_default_profile(), ProfilesError::<TestRuntime>::ProfileAlreadyCreated);
+    fn subscription_should_be_cancelled_when_subscriber_cannot_pay() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_subscription_plan(None, None, Some(40)));
+            assert_ok!(_subscribe(Some(Origin::signed(ACCOUNT3)), None)); // 100 - 40 = 60
+
+            run_to_block(11); // 60 - 40 = 20
+            assert_eq!(Balances::free_balance(ACCOUNT3), 20);
+
+            run_to_block(21); // Not enough balance to pay for the third period
+            assert_eq!(Balances::free_balance(ACCOUNT3), 20);
+            assert!(events().contains(&TestEvent::pallet_subscriptions(
+                pallet_subscriptions::RawEvent::SubscriptionPaymentMissed(ACCOUNT3, SUBSCRIPTION1)
+            )));
+            assert!(events().contains(&TestEvent::pallet_subscriptions(
+                pallet_subscriptions::RawEvent::SubscriptionCancelledForNonPayment(ACCOUNT3, SUBSCRIPTION1)
+            )));
+
+            let subscription = Subscriptions::subscription_by_id(SUBSCRIPTION1).unwrap();
+            assert!(!subscription.is_active);
+            assert_eq!(subscription.missed_payments, 1);
+            assert!(Subscriptions::subscription_ids_by_patron(ACCOUNT3).is_empty());
+            assert!(Subscriptions::subscription_ids_by_space(SPACE1).is_empty());
+
+            // The recurring payment should not be scheduled anymore
+            assert!(Subscriptions::subscriptions_to_unschedule().is_empty());
+            assert!(pallet_scheduler::Agenda::<TestRuntime>::get(31).iter().all(Option::is_none));
         });
     }
 
     #[test]
-    fn create_profile_should_fail_when_ipfs_cid_is_invalid() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_create_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
+    fn subscribe_should_fail_when_already_subscribed() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            assert_ok!(_create_default_subscription_plan());
+            assert_ok!(_subscribe(None, None));
+
+            assert_noop!(_subscribe(None, None), SubscriptionsError::<TestRuntime>::AlreadySubscribed);
         });
     }
 
+// Session keys tests
+
     #[test]
-    fn update_profile_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_ok!(_update_profile(
-                None,
-                Some(space_content_ipfs())
-            ));
+    fn proxy_should_create_post_reaction_on_behalf_of_session_key_owner() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_add_default_session_key());
+            let owner_balance = Balances::free_balance(ACCOUNT2);
 
-            // Check whether profile updated correctly
-            let profile = Profiles::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-            assert!(profile.updated.is_some());
-            assert_eq!(profile.content, space_content_ipfs());
+            assert_ok!(_proxy(None, Call::Reactions(
+                pallet_reactions::Call::create_post_reaction(POST1, reaction_upvote())
+            )));
 
-            // Check whether profile history is written correctly
-            let profile_history = ProfileHistory::edit_history(ACCOUNT1)[0].clone();
-            assert_eq!(profile_history.old_data.content, Some(profile_content_ipfs()));
+            let reaction_id = Reactions::post_reaction_id_by_account((ACCOUNT2, POST1));
+            assert_eq!(reaction_id, REACTION1);
+            assert_eq!(Reactions::reaction_by_id(REACTION1).unwrap().created.account, ACCOUNT2);
+            assert_eq!(Reactions::post_reaction_id_by_account((ACCOUNT3, POST1)), 0);
+
+            // The owner refunds fees spent by the session key
+            let spent = SessionKeys::key_details(ACCOUNT3).unwrap().spent;
+            assert!(spent > 0);
+            assert_eq!(Balances::free_balance(ACCOUNT2), owner_balance - spent);
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_social_account_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::SocialAccountNotFound);
+    fn proxy_should_fail_when_call_is_not_allowed_for_session_keys() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_add_default_session_key());
+
+            assert_noop!(
+                _proxy(None, Call::Balances(pallet_balances::Call::transfer(ACCOUNT3, 50))),
+                pallet_session_keys::Error::<TestRuntime>::CallNotAllowed
+            );
         });
     }
 
+// Idempotency key tests
+
     #[test]
-    fn update_profile_should_fail_when_account_has_no_profile() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(ProfileFollows::follow_account(Origin::signed(ACCOUNT1), ACCOUNT2));
-            assert_noop!(_update_profile(
-                None,
-                Some(profile_content_ipfs())
-            ), ProfilesError::<TestRuntime>::AccountHasNoProfile);
+    fn create_post_with_used_idempotency_key_should_not_create_another_post() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let key = H256::repeat_byte(1);
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 1
+
+            System::set_block_number(IdempotencyKeyTtl::get());
+            assert_ok!(_create_post_with_idempotency_key(None, key));
+
+            assert_eq!(Posts::next_post_id(), POST2);
+            assert_eq!(Posts::post_ids_by_space_id(SPACE1), vec![POST1]);
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostAlreadyCreated(ACCOUNT1, POST1)
+            )));
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_no_updates_for_profile_provided() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            // AccountId 1
-            assert_noop!(_update_profile(
-                None,
-                None
-            ), ProfilesError::<TestRuntime>::NoUpdatesForProfile);
+    fn create_post_with_expired_idempotency_key_should_create_another_post() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            let key = H256::repeat_byte(1);
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 1
+
+            System::set_block_number(1 + IdempotencyKeyTtl::get());
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 2
+
+            assert_eq!(Posts::next_post_id(), POST3);
+            assert_eq!(Posts::used_idempotency_key((ACCOUNT1, key)), Some((POST2, 1 + IdempotencyKeyTtl::get())));
         });
     }
 
     #[test]
-    fn update_profile_should_fail_when_ipfs_cid_is_invalid() {
+    fn create_space_with_used_idempotency_key_should_not_create_another_space() {
         ExtBuilder::build().execute_with(|| {
-            assert_ok!(_create_default_profile());
-            assert_noop!(_update_profile(
-                None,
-                Some(invalid_content_ipfs())
-            ), UtilsError::<TestRuntime>::InvalidIpfsCid);
-        });
-    }
+            let key = H256::repeat_byte(2);
+            assert_ok!(_create_space_with_idempotency_key(None, key)); // SpaceId 1
 
-// Space following tests
+            System::set_block_number(IdempotencyKeyTtl::get());
+            assert_ok!(_create_space_with_idempotency_key(None, key));
 
-    #[test]
-    fn follow_space_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert_eq!(Spaces::next_space_id(), SPACE2);
+            assert!(events().contains(&TestEvent::pallet_spaces(
+                pallet_spaces::RawEvent::SpaceAlreadyCreated(ACCOUNT1, SPACE1)
+            )));
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 2);
-            assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
-            assert_eq!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)), true);
+            System::set_block_number(1 + IdempotencyKeyTtl::get());
+            assert_ok!(_create_space_with_idempotency_key(None, key)); // SpaceId 2
+            assert_eq!(Spaces::next_space_id(), SPACE2 + 1);
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_space_not_found() {
+    fn create_space_with_idempotency_key_used_by_another_account_should_work() {
         ExtBuilder::build().execute_with(|| {
-            assert_noop!(_default_follow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+            let key = H256::repeat_byte(2);
+            assert_ok!(_create_space_with_idempotency_key(None, key)); // SpaceId 1
+            assert_ok!(_create_space_with_idempotency_key(Some(Origin::signed(ACCOUNT2)), key)); // SpaceId 2
+
+            assert_eq!(Spaces::next_space_id(), SPACE2 + 1);
+            assert_eq!(Spaces::used_idempotency_key((ACCOUNT2, key)), Some((SPACE2, 1)));
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_account_is_already_space_follower() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+    fn idempotency_keys_should_be_removed_at_the_start_of_the_block_they_expire_at() {
+        ExtBuilder::build().execute_with(|| {
+            let key = H256::repeat_byte(3);
+            assert_ok!(_create_space_with_idempotency_key(None, key)); // SpaceId 1
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 1
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::AlreadySpaceFollower);
+            System::set_block_number(IdempotencyKeyTtl::get());
+            Spaces::on_initialize(IdempotencyKeyTtl::get());
+            Posts::on_initialize(IdempotencyKeyTtl::get());
+            assert!(Spaces::used_idempotency_key((ACCOUNT1, key)).is_some());
+            assert!(Posts::used_idempotency_key((ACCOUNT1, key)).is_some());
+
+            let expire_block = 1 + IdempotencyKeyTtl::get();
+            System::set_block_number(expire_block);
+            Spaces::on_initialize(expire_block);
+            Posts::on_initialize(expire_block);
+            assert!(Spaces::used_idempotency_key((ACCOUNT1, key)).is_none());
+            assert!(Posts::used_idempotency_key((ACCOUNT1, key)).is_none());
+            assert!(Spaces::idempotency_keys_by_expire_block(expire_block).is_empty());
         });
     }
 
     #[test]
-    fn follow_space_should_fail_when_trying_to_follow_hidden_space() {
+    fn idempotency_keys_above_the_limit_should_be_removed_at_the_next_block() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_update_space(
-                None,
-                None,
-                Some(space_update(None, None, Some(true)))
-            ));
+            let keys: Vec<H256> = (1..=3).map(H256::repeat_byte).collect();
+            for key in &keys {
+                assert_ok!(_create_post_with_idempotency_key(None, *key));
+            }
 
-            assert_noop!(_default_follow_space(), SpaceFollowsError::<TestRuntime>::CannotFollowHiddenSpace);
+            let expire_block = 1 + IdempotencyKeyTtl::get();
+            System::set_block_number(expire_block);
+            Posts::on_initialize(expire_block);
+
+            let max_keys = MaxIdempotencyKeysRemovedPerBlock::get() as usize;
+            assert!(keys[..max_keys].iter().all(|key| Posts::used_idempotency_key((ACCOUNT1, *key)).is_none()));
+            assert!(Posts::used_idempotency_key((ACCOUNT1, keys[2])).is_some());
+            assert_eq!(Posts::idempotency_keys_by_expire_block(expire_block + 1), vec![(ACCOUNT1, keys[2])]);
+
+            System::set_block_number(expire_block + 1);
+            Posts::on_initialize(expire_block + 1);
+            assert!(Posts::used_idempotency_key((ACCOUNT1, keys[2])).is_none());
+            assert!(Posts::idempotency_keys_by_expire_block(expire_block + 1).is_empty());
         });
     }
 
     #[test]
-    fn unfollow_space_should_work() {
+    fn idempotency_key_used_again_after_it_expired_should_not_be_removed() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_default_follow_space());
-            // Follow SpaceId 1 by ACCOUNT2
-            assert_ok!(_default_unfollow_space());
+            let key = H256::repeat_byte(1);
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 1
 
-            assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
-            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
-            assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT1]);
+            // The expired key is used again before it was removed, e.g. if it was left
+            // to a later block, because too many keys expired at once.
+            let expire_block = 1 + IdempotencyKeyTtl::get();
+            System::set_block_number(expire_block);
+            assert_ok!(_create_post_with_idempotency_key(None, key)); // PostId 2
+            Posts::on_initialize(expire_block);
+
+            assert_eq!(Posts::used_idempotency_key((ACCOUNT1, key)), Some((POST2, expire_block)));
         });
     }
 
+// Id counter tests
+
     #[test]
-    fn unfollow_space_should_fail_when_space_not_found() {
-        ExtBuilder::build_with_space_follow_no_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn create_space_should_fail_when_space_id_overflows() {
+        ExtBuilder::build().execute_with(|| {
+            pallet_spaces::NextSpaceId::put(SpaceId::MAX);
+
+            assert_noop!(_create_default_space(), UtilsError::<TestRuntime>::IdOverflow);
+            assert_eq!(Spaces::next_space_id(), SpaceId::MAX);
         });
     }
 
     #[test]
-    fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
+    fn create_post_should_fail_when_post_id_overflows() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_default_unfollow_space(), SpaceFollowsError::<TestRuntime>::NotSpaceFollower);
+            pallet_posts::NextPostId::put(PostId::MAX);
+
+            assert_noop!(_create_default_post(), UtilsError::<TestRuntime>::IdOverflow);
+            assert_eq!(Posts::next_post_id(), PostId::MAX);
         });
     }
 
-// Account following tests
-
     #[test]
-    fn follow_account_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+    fn create_post_reaction_should_fail_when_reaction_id_overflows() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            pallet_reactions::NextReactionId::put(ReactionId::MAX);
 
-            assert_eq!(ProfileFollows::accounts_followed_by_account(ACCOUNT2), vec![ACCOUNT1]);
-            assert_eq!(ProfileFollows::account_followers(ACCOUNT1), vec![ACCOUNT2]);
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), true);
+            assert_noop!(_create_default_post_reaction(), UtilsError::<TestRuntime>::IdOverflow);
+            assert_eq!(Reactions::next_reaction_id(), ReactionId::MAX);
         });
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_tries_to_follow_themself() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_follow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotFollowItself);
+    fn create_role_should_fail_when_role_id_overflows() {
+        ExtBuilder::build_with_space().execute_with(|| {
+            pallet_roles::NextRoleId::put(u64::MAX);
+
+            assert_noop!(_create_default_role(), UtilsError::<TestRuntime>::IdOverflow);
+            assert_eq!(Roles::next_role_id(), u64::MAX);
         });
     }
 
     #[test]
-    fn follow_account_should_fail_when_account_is_already_following_account() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
+    fn report_entity_should_fail_when_report_id_overflows() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            pallet_moderation::NextReportId::put(ReportId::MAX);
 
-            assert_noop!(_default_follow_account(), ProfileFollowsError::<TestRuntime>::AlreadyAccountFollower);
+            assert_noop!(_report_default_post(), UtilsError::<TestRuntime>::IdOverflow);
+            assert_eq!(Moderation::next_report_id(), ReportId::MAX);
         });
     }
 
+// Event tests
+
     #[test]
-    fn unfollow_account_should_work() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            // Follow ACCOUNT1 by ACCOUNT2
-            assert_ok!(_default_unfollow_account());
+    fn post_events_should_include_space_and_extension() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostCreated(ACCOUNT1, POST1, Some(SPACE1), PostExtension::RegularPost)
+            )));
 
-            assert!(ProfileFollows::accounts_followed_by_account(ACCOUNT2).is_empty());
-            assert!(ProfileFollows::account_followers(ACCOUNT1).is_empty());
-            assert_eq!(ProfileFollows::account_followed_by_account((ACCOUNT2, ACCOUNT1)), false);
+            assert_ok!(_create_default_comment()); // PostId 2
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostCreated(ACCOUNT1, POST2, Some(SPACE1), extension_comment(None, POST1))
+            )));
+
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 3
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostShared(ACCOUNT1, POST1, POST3)
+            )));
+
+            assert_ok!(_update_post(None, None, Some(post_update(None, Some(updated_post_content()), None))));
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostUpdated(ACCOUNT1, POST1, Some(SPACE1))
+            )));
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_tries_to_unfollow_themself() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_unfollow_account(
-                None,
-                Some(ACCOUNT2)
-            ), ProfileFollowsError::<TestRuntime>::AccountCannotUnfollowItself);
+    fn post_moved_event_should_include_source_and_destination_spaces() {
+        ExtBuilder::build_with_reacted_post_and_two_spaces().execute_with(|| {
+            assert_ok!(_move_post_1_to_space_2());
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostMoved(ACCOUNT1, POST1, Some(SPACE1), Some(SPACE2))
+            )));
+
+            assert_ok!(_move_post_to_nowhere(POST1));
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostMoved(ACCOUNT1, POST1, Some(SPACE2), None)
+            )));
+
+            assert_ok!(_move_post(None, Some(POST1), Some(Some(SPACE1))));
+            assert!(events().contains(&TestEvent::pallet_posts(
+                pallet_posts::RawEvent::PostMoved(ACCOUNT1, POST1, None, Some(SPACE1))
+            )));
         });
     }
 
     #[test]
-    fn unfollow_account_should_fail_when_account_is_not_following_another_account_yet() {
-        ExtBuilder::build().execute_with(|| {
-            assert_ok!(_default_follow_account());
-            assert_ok!(_default_unfollow_account());
+    fn reaction_events_should_include_reaction_kind() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(reaction_upvote())));
+            assert_ok!(_update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1, Some(reaction_downvote())));
+            assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, REACTION1));
 
-            assert_noop!(_default_unfollow_account(), ProfileFollowsError::<TestRuntime>::NotAccountFollower);
+            let events = events();
+            assert!(events.contains(&TestEvent::pallet_reactions(
+                pallet_reactions::RawEvent::PostReactionCreated(ACCOUNT2, POST1, REACTION1, reaction_upvote())
+            )));
+            assert!(events.contains(&TestEvent::pallet_reactions(
+                pallet_reactions::RawEvent::PostReactionUpdated(ACCOUNT2, POST1, REACTION1, reaction_downvote())
+            )));
+            assert!(events.contains(&TestEvent::pallet_reactions(
+                pallet_reactions::RawEvent::PostReactionDeleted(ACCOUNT2, POST1, REACTION1, reaction_downvote())
+            )));
         });
     }
 
-// Transfer ownership tests
-
     #[test]
-    fn transfer_space_ownership_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+    fn follow_and_role_events_should_include_space_owner_and_space() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert!(events().contains(&TestEvent::pallet_roles(
+                pallet_roles::RawEvent::RoleGranted(ACCOUNT1, SPACE1, ROLE1, vec![User::Account(ACCOUNT2)])
+            )));
 
-            assert_eq!(SpaceOwnership::pending_space_owner(SPACE1).unwrap(), ACCOUNT2);
+            assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+            assert_ok!(_default_unfollow_space());
+
+            let events = events();
+            assert!(events.contains(&TestEvent::pallet_space_follows(
+                pallet_space_follows::RawEvent::SpaceFollowed(ACCOUNT2, SPACE1, ACCOUNT1)
+            )));
+            assert!(events.contains(&TestEvent::pallet_space_follows(
+                pallet_space_follows::RawEvent::SpaceUnfollowed(ACCOUNT2, SPACE1, ACCOUNT1)
+            )));
         });
     }
 
     #[test]
-    fn transfer_space_ownership_should_fail_when_space_not_found() {
-        ExtBuilder::build().execute_with(|| {
-            assert_noop!(_transfer_default_space_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn role_deleted_event_should_include_users_that_had_the_role() {
+        ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(|| {
+            assert_ok!(_delete_default_role());
+
+            assert!(events().contains(&TestEvent::pallet_roles(
+                pallet_roles::RawEvent::RoleDeleted(ACCOUNT1, SPACE1, ROLE1, vec![User::Account(ACCOUNT2)])
+            )));
+            assert_eq!(
+                Roles::role_history_by_space(SPACE1).last().map(|record| record.action),
+                Some(pallet_roles::RoleAction::Deleted)
+            );
         });
     }
 
-    #[test]
-    fn transfer_space_ownership_should_fail_when_account_is_not_space_owner() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_transfer_space_ownership(
-                Some(Origin::signed(ACCOUNT2)),
-                None,
-                Some(ACCOUNT1)
-            ), SpacesError::<TestRuntime>::NotASpaceOwner);
-        });
+// Migration tests
+
+    /// Put a value encoded with an old storage layout under `key`,
+    /// the same way it is stored on chain before a migration.
+    fn put_old_encoded_value<V: Encode>(key: &[u8], old_value: &V) {
+        storage::unhashed::put_raw(key, &old_value.encode());
+    }
+
+    fn old_social_account(profile: Option<ProfileV0<TestRuntime>>) -> SocialAccountV0<TestRuntime> {
+        SocialAccountV0 {
+            followers_count: 2,
+            following_accounts_count: 1,
+            following_spaces_count: 3,
+            reputation: 10,
+            profile,
+        }
     }
 
     #[test]
-    fn transfer_space_ownership_should_fail_when_trying_to_transfer_to_current_owner() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_transfer_space_ownership(
-                Some(Origin::signed(ACCOUNT1)),
-                None,
-                Some(ACCOUNT1)
-            ), SpaceOwnershipError::<TestRuntime>::CannotTranferToCurrentOwner);
+    fn profiles_migration_to_v0_7_0_should_add_empty_username_to_old_profiles() {
+        ExtBuilder::build().execute_with(|| {
+            let created = WhoAndWhen::<TestRuntime>::new(ACCOUNT1);
+            let old_profile = ProfileV0 {
+                created: created.clone(),
+                updated: None,
+                content: profile_content_ipfs(),
+            };
+
+            put_old_encoded_value(
+                &SocialAccountById::<TestRuntime>::hashed_key_for(ACCOUNT1),
+                &old_social_account(Some(old_profile)),
+            );
+            put_old_encoded_value(
+                &SocialAccountById::<TestRuntime>::hashed_key_for(ACCOUNT2),
+                &old_social_account(None),
+            );
+
+            pallet_profiles::migrations::migrate_to_v0_7_0::<TestRuntime>();
+
+            let social_account = Profiles::social_account_by_id(ACCOUNT1).unwrap();
+            assert_eq!(social_account.followers_count, 2);
+            assert_eq!(social_account.following_accounts_count, 1);
+            assert_eq!(social_account.following_spaces_count, 3);
+            assert_eq!(social_account.reputation, 10);
+
+            let profile = social_account.profile.unwrap();
+            assert_eq!(profile.created, created);
+            assert_eq!(profile.updated, None);
+            assert_eq!(profile.username, None);
+            assert_eq!(profile.content, profile_content_ipfs());
+
+            let social_account_without_profile = Profiles::social_account_by_id(ACCOUNT2).unwrap();
+            assert_eq!(social_account_without_profile.reputation, 10);
+            assert!(social_account_without_profile.profile.is_none());
         });
     }
 
     #[test]
-    fn accept_pending_ownership_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership());
-            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
-            assert_ok!(_accept_default_pending_ownership()); // Accepting a transfer from ACCOUNT2
-            // Check whether owner was changed
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-            assert_eq!(space.owner, ACCOUNT2);
+    fn profiles_migration_to_v0_8_0_should_count_root_posts() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            let old_social_account = SocialAccountV1::<TestRuntime> {
+                followers_count: 2,
+                following_accounts_count: 1,
+                following_spaces_count: 3,
+                reputation: 10,
+                profile: None,
+            };
+
+            put_old_encoded_value(
+                &SocialAccountById::<TestRuntime>::hashed_key_for(ACCOUNT1),
+                &old_social_account,
+            );
+            put_old_encoded_value(
+                &SocialAccountById::<TestRuntime>::hashed_key_for(ACCOUNT2),
+                &old_social_account,
+            );
 
-            // Check whether storage state is correct
-            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+            pallet_profiles::migrations::migrate_to_v0_8_0::<TestRuntime>();
+
+            // A comment (PostId 2) is not counted:
+            let social_account = Profiles::social_account_by_id(ACCOUNT1).unwrap();
+            assert_eq!(social_account.posts_count, 1);
+            assert_eq!(social_account.followers_count, 2);
+            assert_eq!(social_account.following_accounts_count, 1);
+            assert_eq!(social_account.following_spaces_count, 3);
+            assert_eq!(social_account.reputation, 10);
+
+            let social_account_without_posts = Profiles::social_account_by_id(ACCOUNT2).unwrap();
+            assert_eq!(social_account_without_posts.posts_count, 0);
+            assert_eq!(social_account_without_posts.reputation, 10);
         });
     }
 
     #[test]
-    fn accept_pending_ownership_should_fail_when_space_not_found() {
-        ExtBuilder::build_with_pending_ownership_transfer_no_space().execute_with(|| {
-            assert_noop!(_accept_default_pending_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn profiles_migration_to_v0_8_0_should_create_social_account_for_author_without_one() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            SocialAccountById::<TestRuntime>::remove(ACCOUNT1);
+
+            pallet_profiles::migrations::migrate_to_v0_8_0::<TestRuntime>();
+
+            let social_account = Profiles::social_account_by_id(ACCOUNT1).unwrap();
+            assert_eq!(social_account.posts_count, 1);
+            assert!(social_account.profile.is_none());
         });
     }
 
     #[test]
-    fn accept_pending_ownership_should_fail_when_no_pending_transfer_for_space() {
+    fn spaces_migration_to_v0_7_0_should_keep_old_settings_and_leave_new_toggle_unset() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_accept_default_pending_ownership(), SpaceOwnershipError::<TestRuntime>::NoPendingTransferOnSpace);
+            put_old_encoded_value(
+                &SpaceSettingsById::hashed_key_for(SPACE1),
+                &SpaceSettingsV1 { version: 1, shares_disabled: Some(true) },
+            );
+
+            pallet_spaces::migrations::migrate_to_v0_7_0::<TestRuntime>();
+
+            let settings = Spaces::space_settings_by_id(SPACE1).unwrap();
+            assert_eq!(settings.version, SPACE_SETTINGS_VERSION);
+            assert_eq!(settings.shares_disabled, Some(true));
+            assert!(settings.reject_duplicate_content.is_none());
         });
     }
 
     #[test]
-    fn accept_pending_ownership_should_fail_if_origin_is_already_an_owner() {
+    fn spaces_migration_to_v0_8_0_should_keep_old_settings_and_leave_min_reputation_unset() {
         ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership());
+            put_old_encoded_value(
+                &SpaceSettingsById::hashed_key_for(SPACE1),
+                &SpaceSettingsV2 { version: 2, shares_disabled: Some(true), reject_duplicate_content: Some(true) },
+            );
 
-            assert_noop!(_accept_pending_ownership(
-                Some(Origin::signed(ACCOUNT1)),
-                None
-            ), SpaceOwnershipError::<TestRuntime>::AlreadyASpaceOwner);
+            pallet_spaces::migrations::migrate_to_v0_8_0::<TestRuntime>();
+
+            let settings = Spaces::space_settings_by_id(SPACE1).unwrap();
+            assert_eq!(settings.version, SPACE_SETTINGS_VERSION);
+            assert_eq!(settings.shares_disabled, Some(true));
+            assert_eq!(settings.reject_duplicate_content, Some(true));
+            assert!(settings.min_reputation_to_post.is_none());
+            assert!(settings.min_reputation_to_comment.is_none());
         });
     }
 
     #[test]
-    fn accept_pending_ownership_should_fail_if_origin_is_not_equal_to_pending_account() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership());
+    fn spaces_migration_to_v0_9_0_should_keep_old_space_and_start_comments_count_from_zero() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            let space = Spaces::space_by_id(SPACE1).unwrap();
+            let old_space = SpaceV0::<TestRuntime> {
+                id: space.id,
+                created: space.created.clone(),
+                updated: space.updated.clone(),
+                owner: space.owner,
+                parent_id: space.parent_id,
+                handle: space.handle.clone(),
+                content: space.content.clone(),
+                hidden: space.hidden,
+                posts_count: space.posts_count,
+                hidden_posts_count: space.hidden_posts_count,
+                followers_count: space.followers_count,
+                score: space.score,
+                permissions: space.permissions.clone(),
+            };
 
-            assert_noop!(_accept_pending_ownership(
-                Some(Origin::signed(ACCOUNT3)),
-                None
-            ), SpaceOwnershipError::<TestRuntime>::NotAllowedToAcceptOwnershipTransfer);
+            put_old_encoded_value(&SpaceById::<TestRuntime>::hashed_key_for(SPACE1), &old_space);
+
+            pallet_spaces::migrations::migrate_to_v0_9_0::<TestRuntime>();
+
+            let migrated_space = Spaces::space_by_id(SPACE1).unwrap();
+            assert_eq!(migrated_space, Space { comments_count: 0, ..space });
         });
     }
 
     #[test]
-    fn reject_pending_ownership_should_work() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership());
-            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
-            assert_ok!(_reject_default_pending_ownership()); // Rejecting a transfer from ACCOUNT2
+    fn space_follows_migration_to_v0_7_0_should_move_followers_to_double_map() {
+        ExtBuilder::build().execute_with(|| {
+            storage::migration::put_storage_value(
+                b"SpaceFollowsModule",
+                pallet_space_follows::migrations::OLD_SPACE_FOLLOWERS,
+                &Twox64Concat::hash(&SPACE1.encode()),
+                vec![ACCOUNT1, ACCOUNT2],
+            );
 
-            // Check whether owner was not changed
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-            assert_eq!(space.owner, ACCOUNT1);
+            System::set_block_number(3);
+            pallet_space_follows::migrations::migrate_to_v0_7_0::<TestRuntime>();
 
-            // Check whether storage state is correct
-            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+            assert_eq!(sorted_space_followers(SPACE1), vec![ACCOUNT1, ACCOUNT2]);
+            assert_eq!(SpaceFollows::space_followers_count(SPACE1), 2);
+            assert_eq!(SpaceFollows::space_follower_since(SPACE1, ACCOUNT2).unwrap().block, 3);
+            assert!(storage::migration::get_storage_value::<Vec<AccountId>>(
+                b"SpaceFollowsModule",
+                pallet_space_follows::migrations::OLD_SPACE_FOLLOWERS,
+                &Twox64Concat::hash(&SPACE1.encode()),
+            ).is_none());
         });
     }
 
     #[test]
-    fn reject_pending_ownership_should_work_when_proposal_rejected_by_current_space_owner() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership());
-            // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
-            assert_ok!(_reject_default_pending_ownership_by_current_owner()); // Rejecting a transfer from ACCOUNT2
+    fn reactions_migration_to_v0_7_0_should_index_reactions_by_account() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            upvote_posts_by_account2(3);
+            assert_ok!(_create_post_reaction(None, Some(3), Some(reaction_downvote())));
 
-            // Check whether owner was not changed
-            let space = Spaces::space_by_id(SPACE1).unwrap();
-            assert_eq!(space.owner, ACCOUNT1);
+            for account in &[ACCOUNT1, ACCOUNT2] {
+                ReactionIdsByAccount::<TestRuntime>::remove(account);
+            }
+            for reaction_id in 1..=4 {
+                PostIdByReactionId::remove(reaction_id);
+            }
 
-            // Check whether storage state is correct
-            assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+            pallet_reactions::migrations::migrate_to_v0_7_0::<TestRuntime>();
+
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT2), vec![1, 2, 3]);
+            assert_eq!(Reactions::reaction_ids_by_account(ACCOUNT1), vec![4]);
+            assert_eq!(Reactions::post_id_by_reaction_id(2), Some(2));
+            assert_eq!(Reactions::post_id_by_reaction_id(4), Some(3));
         });
     }
 
     #[test]
-    fn reject_pending_ownership_should_fail_when_space_not_found() {
-        ExtBuilder::build_with_pending_ownership_transfer_no_space().execute_with(|| {
-            assert_noop!(_reject_default_pending_ownership(), SpacesError::<TestRuntime>::SpaceNotFound);
+    fn scores_migration_to_v0_7_0_should_count_existing_shares() {
+        ExtBuilder::build_with_post().execute_with(|| {
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 2
+            assert_ok!(_create_post(None, None, Some(extension_shared_post(POST1)), None)); // PostId 3
+            pallet_scores::SharesCountByAccount::<TestRuntime>::remove((ACCOUNT1, POST1));
+
+            pallet_scores::migrations::migrate_to_v0_7_0::<TestRuntime>();
+
+            assert_eq!(Scores::shares_count_by_account((ACCOUNT1, POST1)), 2);
         });
     }
 
     #[test]
-    fn reject_pending_ownership_should_fail_when_no_pending_transfer_on_space() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_noop!(_reject_default_pending_ownership(), SpaceOwnershipError::<TestRuntime>::NoPendingTransferOnSpace); // Rejecting a transfer from ACCOUNT2
+    fn spaces_and_posts_migrations_should_remove_expired_idempotency_keys() {
+        ExtBuilder::build().execute_with(|| {
+            let (expired_key, live_key) = (H256::repeat_byte(1), H256::repeat_byte(2));
+            let now = 1 + IdempotencyKeyTtl::get();
+            System::set_block_number(now);
+
+            pallet_spaces::UsedIdempotencyKeys::<TestRuntime>::insert((ACCOUNT1, expired_key), (SPACE1, 1));
+            pallet_spaces::UsedIdempotencyKeys::<TestRuntime>::insert((ACCOUNT1, live_key), (SPACE2, 2));
+            pallet_posts::UsedIdempotencyKeys::<TestRuntime>::insert((ACCOUNT1, expired_key), (POST1, 1));
+            pallet_posts::UsedIdempotencyKeys::<TestRuntime>::insert((ACCOUNT1, live_key), (POST2, 2));
+
+            pallet_spaces::migrations::migrate_to_v0_10_0::<TestRuntime>();
+            pallet_posts::migrations::migrate_to_v0_8_0::<TestRuntime>();
+
+            assert!(Spaces::used_idempotency_key((ACCOUNT1, expired_key)).is_none());
+            assert!(Posts::used_idempotency_key((ACCOUNT1, expired_key)).is_none());
+            assert_eq!(Spaces::idempotency_keys_by_expire_block(now + 1), vec![(ACCOUNT1, live_key)]);
+            assert_eq!(Posts::idempotency_keys_by_expire_block(now + 1), vec![(ACCOUNT1, live_key)]);
         });
     }
 
     #[test]
-    fn reject_pending_ownership_should_fail_when_account_is_not_allowed_to_reject() {
-        ExtBuilder::build_with_space().execute_with(|| {
-            assert_ok!(_transfer_default_space_ownership()); // Transfer SpaceId 1 owned by ACCOUNT1 to ACCOUNT2
+    fn posts_migration_to_v0_7_0_should_index_existing_posts_by_content() {
+        ExtBuilder::build_with_comment().execute_with(|| {
+            assert_ok!(_create_default_post()); // PostId 3
+            for content in [post_content_ipfs(), comment_content_ipfs()].iter() {
+                if let Content::IPFS(cid) = content {
+                    ContentToPostIds::remove(cid);
+                }
+            }
 
-            assert_noop!(_reject_pending_ownership(
-                Some(Origin::signed(ACCOUNT3)),
-                None
-            ), SpaceOwnershipError::<TestRuntime>::NotAllowedToRejectOwnershipTransfer); // Rejecting a transfer from ACCOUNT2
+            pallet_posts::migrations::migrate_to_v0_7_0::<TestRuntime>();
+
+            assert_eq!(post_ids_with_content(post_content_ipfs()), vec![POST1, 3]);
+            assert_eq!(post_ids_with_content(comment_content_ipfs()), vec![POST2]);
         });
     }
 }
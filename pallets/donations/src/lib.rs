@@ -2,22 +2,26 @@
 //!
 //! The Donations module allows accounts to make donations (aka tips) to other accounts, spaces,
 //! or posts.
+//!
+//! A donation is sent to an account or to a space owner, optionally as a tip on a specific post.
+//! A configurable share of every donation is taken as a protocol fee and routed to the treasury.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Encode, Decode};
 use sp_std::prelude::*;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, Perbill, traits::{Saturating, Zero}};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get,
     dispatch::{DispatchResult, DispatchError},
-    traits::{Currency, ExistenceRequirement}
+    traits::{Currency, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReason, WithdrawReasons}
 };
 use frame_system::{self as system, ensure_signed};
 
+use df_traits::ContentValidator;
 use pallet_posts::{Module as Posts, PostId};
 use pallet_spaces::{Module as Spaces};
-use pallet_utils::{Content, WhoAndWhen, SpaceId};
+use pallet_utils::{Content, WhoAndWhen, SpaceId, User, Module as Utils};
 
 type BalanceOf<T> = <<T as pallet_utils::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
@@ -31,15 +35,29 @@ pub enum DonationRecipient<AccountId> {
     Post(PostId),
 }
 
+impl<AccountId> From<User<AccountId>> for DonationRecipient<AccountId> {
+    fn from(user: User<AccountId>) -> Self {
+        match user {
+            User::Account(account) => DonationRecipient::Account(account),
+            User::Space(space_id) => DonationRecipient::Space(space_id),
+        }
+    }
+}
+
 /// A struct that describes a single donation made by an account.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Donation<T: Trait> {
     pub id: DonationId,
     pub created: WhoAndWhen<T>,
-    pub recipient: DonationRecipient<T::AccountId>, // TODO rename to 'reason'?
+    pub recipient: User<T::AccountId>,
     pub donation_wallet: T::AccountId, // TODO rename to 'recipient_wallet' or 'recipient'?
+    /// A post this donation was made on as a tip.
+    pub post_id: Option<PostId>,
+    /// A total amount paid by a supporter, including the fee.
     pub amount: BalanceOf<T>,
-    pub comment_id: Option<PostId>,
+    /// A part of the `amount` that has been routed to the treasury.
+    pub fee: BalanceOf<T>,
+    pub message: Content,
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
@@ -66,6 +84,9 @@ pub trait Trait: system::Trait
     + pallet_utils::Trait
 {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// A share of every donation that is taken as a protocol fee and sent to the treasury.
+    type DonationFee: Get<Perbill>;
 }
 
 decl_storage! {
@@ -104,6 +125,16 @@ decl_storage! {
         pub DonationSettingsByRecipient get(fn donation_settings_by_recipient):
             map hasher(blake2_128_concat) DonationRecipient<T::AccountId>
             => Option<DonationSettings<BalanceOf<T>>>;
+
+        /// A total amount of tokens (excluding fees) donated on this post (key).
+        pub DonationsByPost get(fn donations_by_post):
+            map hasher(twox_64_concat) PostId
+            => BalanceOf<T>;
+
+        /// A total amount of tokens (excluding fees) received by this account (key) as donations.
+        pub DonationsByAccount get(fn donations_by_account):
+            map hasher(blake2_128_concat) T::AccountId
+            => BalanceOf<T>;
     }
 }
 
@@ -111,14 +142,19 @@ decl_event!(
     pub enum Event<T> where
         AccountId = <T as system::Trait>::AccountId,
         DonationRecipient = DonationRecipient<<T as system::Trait>::AccountId>,
+        User = User<<T as system::Trait>::AccountId>,
         BalanceOf = BalanceOf<T>
     {
-        Donated(
+        DonationReceived(
             // Supporter - from whom if was donated.
             AccountId,
             // To which recipient it was donated.
-            DonationRecipient,
-            // Amount of donated tokens.
+            User,
+            // On which post it was donated, if any.
+            Option<PostId>,
+            // Amount of donated tokens, including the fee.
+            BalanceOf,
+            // Amount of tokens routed to the treasury as a fee.
             BalanceOf
         ),
         DonationWalletUpdated(
@@ -157,6 +193,10 @@ decl_error! {
         TooLargeDonation,
         /// A space, a post or an account doesn't accept donations based on its donation settings.
         DonationsAreDisabled,
+        /// Donation amount should be greater than zero.
+        ZeroDonation,
+        /// A recipient is neither an author of the post nor a space the post belongs to.
+        RecipientNotRelatedToPost,
     }
 }
 
@@ -167,52 +207,82 @@ decl_module! {
 
     fn deposit_event() = default;
 
-    /// Donate a certain `amount` of tokens to a `recipient` with an optional comment message.
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 4)]
+    /// Donate a certain `amount` of tokens to a `recipient` with an optional `message`.
+    /// If `post_id_opt` is provided, the donation is a tip on this post, and a `recipient`
+    /// should be either the post author or the space this post belongs to.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 7)]
     pub fn donate(
         origin,
-        recipient: DonationRecipient<T::AccountId>,
+        recipient: User<T::AccountId>,
+        post_id_opt: Option<PostId>,
         amount: BalanceOf<T>,
-        _comment_content: Content
+        message: Content
     ) -> DispatchResult {
         let supporter = ensure_signed(origin)?;
 
-        let settings = Self::resolve_donation_settings(recipient.clone())?;
+        ensure!(!amount.is_zero(), Error::<T>::ZeroDonation);
+        T::ContentValidator::is_valid_content(message.clone())?;
+
+        if let Some(post_id) = post_id_opt {
+            Self::ensure_recipient_related_to_post(&recipient, post_id)?;
+        }
+
+        let donation_recipient = DonationRecipient::from(recipient.clone());
+        let settings = Self::resolve_donation_settings(donation_recipient.clone())?;
 
         ensure!(settings.donations_allowed, Error::<T>::DonationsAreDisabled);
 
         if let Some(min_amount) = settings.min_amount {
             ensure!(amount >= min_amount, Error::<T>::TooSmallDonation);
         }
-        
+
         if let Some(max_amount) = settings.max_amount {
             ensure!(amount <= max_amount, Error::<T>::TooLargeDonation);
         }
-            
-        let donation_wallet = Self::get_recipient_wallet(recipient.clone())?;
+
+        let donation_wallet = Self::get_recipient_wallet(donation_recipient.clone())?;
         let donation_id = Self::next_donation_id();
 
-        // TODO check settings.donation_posts_allowed to post as a root post to space.
-        // TODO create a comment under the post or a new post in DonationSpace
+        let fee = T::DonationFee::get() * amount;
+        let received_amount = amount.saturating_sub(fee);
+
+        // Withdraw donated tokens from a supporter at once, so nothing is changed
+        // if a supporter cannot afford this donation.
+        let donated = <T as pallet_utils::Trait>::Currency::withdraw(
+            &supporter,
+            amount,
+            WithdrawReasons::from(WithdrawReason::Transfer),
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        let (fee_imbalance, received) = donated.split(fee);
+        <T as pallet_utils::Trait>::Currency::resolve_creating(&donation_wallet, received);
+        Utils::<T>::on_unbalanced(fee_imbalance);
 
         let donation = Donation {
             id: donation_id,
             created: WhoAndWhen::<T>::new(supporter.clone()),
             recipient: recipient.clone(),
             donation_wallet: donation_wallet.clone(),
+            post_id: post_id_opt,
             amount,
-            comment_id: None // TODO put id of created comment
+            fee,
+            message,
         };
 
-        // Transfer donated tokens from a supporter to a donation wallet of this reason.
-        <T as pallet_utils::Trait>::Currency::transfer(&supporter, &donation_wallet, amount, ExistenceRequirement::KeepAlive)?;
-
         DonationById::<T>::insert(donation_id, donation);
         DonationIdsBySupporter::<T>::mutate(supporter.clone(), |ids| ids.push(donation_id));
-        DonationIdsByRecipient::<T>::mutate(recipient.clone(), |ids| ids.push(donation_id));
+        DonationIdsByRecipient::<T>::mutate(donation_recipient, |ids| ids.push(donation_id));
+        DonationsByAccount::<T>::mutate(donation_wallet, |total| *total = total.saturating_add(received_amount));
+
+        if let Some(post_id) = post_id_opt {
+            DonationIdsByRecipient::<T>::mutate(DonationRecipient::Post(post_id), |ids| ids.push(donation_id));
+            DonationsByPost::<T>::mutate(post_id, |total| *total = total.saturating_add(received_amount));
+        }
+
         NextDonationId::mutate(|n| { *n += 1; });
 
-        Self::deposit_event(RawEvent::Donated(supporter, recipient, amount));
+        Self::deposit_event(RawEvent::DonationReceived(supporter, recipient, post_id_opt, amount, fee));
         Ok(())
     }
 
@@ -347,7 +417,7 @@ impl<T: Trait> Module<T> {
                 Self::resolve_donation_settings(space_owner)
             },
             DonationRecipient::Post(post_id) => {
-                let post_owner = Self::resolve_space_owner_as_recipient(post_id)?;
+                let post_owner = Self::resolve_post_owner_as_recipient(post_id)?;
                 Self::resolve_donation_settings(post_owner)
             },
         }
@@ -372,12 +442,24 @@ impl<T: Trait> Module<T> {
                 Self::get_recipient_wallet(owner)
             },
             DonationRecipient::Post(post_id) => {
-                let owner = Self::resolve_space_owner_as_recipient(post_id)?;
+                let owner = Self::resolve_post_owner_as_recipient(post_id)?;
                 Self::get_recipient_wallet(owner)
             },
         }
     }
 
+    /// Checks that a `recipient` is either an author of a post or a space this post belongs to.
+    pub fn ensure_recipient_related_to_post(recipient: &User<T::AccountId>, post_id: PostId) -> DispatchResult {
+        let post = Posts::<T>::require_post(post_id)?;
+
+        let is_related = match recipient {
+            User::Account(account) => post.is_owner(account),
+            User::Space(space_id) => post.try_get_space_id() == Some(*space_id),
+        };
+        ensure!(is_related, Error::<T>::RecipientNotRelatedToPost);
+        Ok(())
+    }
+
     /// Checks if `maybe_owner` can manage / is an owner of a `recipient`.
     pub fn ensure_recipient_manager(
         maybe_owner: T::AccountId,
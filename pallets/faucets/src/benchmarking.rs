@@ -0,0 +1,87 @@
+//! Benchmarks for the Faucets pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn tokens<T: Trait>(amount: u32) -> BalanceOf<T> {
+    T::Currency::minimum_balance().max(1u32.into()).saturating_mul(amount.into())
+}
+
+fn create_faucet<T: Trait>(faucet: &T::AccountId) -> DispatchResult {
+    T::Currency::make_free_balance_be(faucet, tokens::<T>(1_000_000));
+    Module::<T>::add_faucet(
+        RawOrigin::Root.into(), faucet.clone(), 100u32.into(), tokens::<T>(1_000), tokens::<T>(100)
+    )
+}
+
+benchmarks! {
+    _ { }
+
+    add_faucet {
+        let faucet: T::AccountId = account("faucet", 0, SEED);
+        T::Currency::make_free_balance_be(&faucet, tokens::<T>(1_000_000));
+    }: _(RawOrigin::Root, faucet.clone(), 100u32.into(), tokens::<T>(1_000), tokens::<T>(100))
+    verify {
+        assert!(Module::<T>::faucet_by_account(faucet).is_some());
+    }
+
+    update_faucet {
+        let faucet: T::AccountId = account("faucet", 0, SEED);
+        create_faucet::<T>(&faucet)?;
+
+        let update = FaucetUpdate {
+            enabled: Some(false),
+            period: Some(200u32.into()),
+            period_limit: Some(tokens::<T>(2_000)),
+            drip_limit: Some(tokens::<T>(200)),
+        };
+    }: _(RawOrigin::Root, faucet.clone(), update)
+    verify {
+        assert!(!Module::<T>::faucet_by_account(faucet).unwrap().enabled);
+    }
+
+    remove_faucets {
+        let f in 1 .. 100;
+
+        let mut faucets = Vec::new();
+        for i in 0..f {
+            let faucet: T::AccountId = account("faucet", i, SEED);
+            create_faucet::<T>(&faucet)?;
+            faucets.push(faucet);
+        }
+        let first_faucet = faucets[0].clone();
+    }: _(RawOrigin::Root, faucets)
+    verify {
+        assert!(Module::<T>::faucet_by_account(first_faucet).is_none());
+    }
+
+    set_all_faucets_enabled {
+        let f in 1 .. T::MaxFaucetsToggledPerCall::get();
+
+        for i in 0..f {
+            let faucet: T::AccountId = account("faucet", i, SEED);
+            create_faucet::<T>(&faucet)?;
+        }
+        let first_faucet: T::AccountId = account("faucet", 0, SEED);
+    }: _(RawOrigin::Root, false)
+    verify {
+        assert!(!Module::<T>::faucet_by_account(first_faucet).unwrap().enabled);
+    }
+
+    drip {
+        let faucet: T::AccountId = whitelisted_caller();
+        create_faucet::<T>(&faucet)?;
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let amount = tokens::<T>(100);
+    }: _(RawOrigin::Signed(faucet), recipient.clone(), amount)
+    verify {
+        assert_eq!(T::Currency::free_balance(&recipient), amount);
+    }
+}
@@ -1,9 +1,9 @@
 //! # Faucets Module
 //!
-//! The Faucets module allows a root key (sudo) to add accounts (faucets) that are eligible
-//! to drip free tokens to other accounts (recipients).
+//! The Faucets module allows `ForceOrigin` (e.g. root or council) to add accounts (faucets)
+//! that are eligible to drip free tokens to other accounts (recipients).
 //! 
-//! Currently, only sudo account can add, update and remove faucets.
+//! Currently, only `ForceOrigin` can add, update and remove faucets.
 //! But this can be changed in the future to allow anyone else
 //! to set up new faucets for their needs.
 
@@ -14,10 +14,11 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::{Currency, ExistenceRequirement, Get},
+    storage::StoragePrefixedMap,
+    traits::{Currency, EnsureOrigin, ExistenceRequirement, Get},
     weights::Pays,
 };
-use frame_system::{self as system, ensure_root, ensure_signed};
+use frame_system::{self as system, ensure_signed};
 use sp_runtime::RuntimeDebug;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{
@@ -26,6 +27,11 @@ use sp_std::{
     prelude::*,
 };
 
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 
@@ -54,6 +60,9 @@ pub struct FaucetUpdate<BlockNumber, Balance> {
     pub drip_limit: Option<Balance>,
 }
 
+/// The length of a `twox_64` hash that precedes a faucet account in the keys of `FaucetByAccount`.
+const TWOX_64_HASH_LEN: usize = 8;
+
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
 /// The pallet's configuration trait.
@@ -63,6 +72,15 @@ pub trait Trait: system::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type Currency: Currency<Self::AccountId>;
+
+    /// The origin which may add, update and remove faucets.
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The maximum number of faucets that `set_all_faucets_enabled` goes through in one call.
+    type MaxFaucetsToggledPerCall: Get<u32>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
@@ -72,6 +90,35 @@ decl_storage! {
         pub FaucetByAccount get(fn faucet_by_account):
             map hasher(twox_64_concat) T::AccountId // Faucet account
             => Option<Faucet<T>>;
+
+        /// The last faucet processed by an unfinished `set_all_faucets_enabled`
+        /// together with the `enabled` value it was called with.
+        pub AllFaucetsToggleCursor get(fn all_faucets_toggle_cursor):
+            Option<(bool, T::AccountId)>;
+    }
+    add_extra_genesis {
+        // Faucets to add and fund at genesis:
+        // (faucet account, period, period limit, drip limit, initial balance).
+        config(faucets): Vec<(T::AccountId, T::BlockNumber, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>)>;
+        build(|config| {
+            for (faucet, period, period_limit, drip_limit, initial_balance) in config.faucets.iter() {
+                assert!(!period.is_zero(), "Genesis faucet period should not be zero");
+                assert!(!period_limit.is_zero(), "Genesis faucet period limit should not be zero");
+                assert!(!drip_limit.is_zero(), "Genesis faucet drip limit should not be zero");
+                assert!(drip_limit <= period_limit, "Genesis faucet drip limit should not exceed its period limit");
+                assert!(
+                    *initial_balance >= T::Currency::minimum_balance(),
+                    "Genesis faucet initial balance should not be less than the existential deposit"
+                );
+                assert!(
+                    !FaucetByAccount::<T>::contains_key(faucet),
+                    "Genesis faucets should be unique"
+                );
+
+                let _ = T::Currency::deposit_creating(faucet, *initial_balance);
+                FaucetByAccount::<T>::insert(faucet, Faucet::<T>::new(*period, *period_limit, *drip_limit));
+            }
+        });
     }
 }
 
@@ -83,6 +130,11 @@ decl_event!(
         FaucetAdded(AccountId),
         FaucetUpdated(AccountId),
         FaucetsRemoved(Vec<AccountId>),
+        AllFaucetsToggled(
+            bool, // Whether faucets were enabled or disabled
+            u32,  // Number of faucets changed by this call
+            bool  // Whether all faucets were processed
+        ),
         Dripped(
             AccountId, // Faucet account
             AccountId, // Recipient account
@@ -111,6 +163,8 @@ decl_error! {
         
         PeriodLimitReached,
         DripLimitReached,
+
+        DripLimitExceedsPeriodLimit,
     }
 }
 
@@ -122,7 +176,7 @@ decl_module! {
         // Initializing events
         fn deposit_event() = default;
 
-        #[weight = 50_000 + T::DbWeight::get().reads_writes(2, 1)]
+        #[weight = T::WeightInfo::add_faucet()]
         pub fn add_faucet(
             origin,
             faucet: T::AccountId,
@@ -131,11 +185,12 @@ decl_module! {
             drip_limit: BalanceOf<T>,
         ) -> DispatchResult {
 
-            ensure_root(origin.clone())?;
+            T::ForceOrigin::ensure_origin(origin)?;
 
             Self::ensure_period_not_zero(period)?;
             Self::ensure_period_limit_not_zero(period_limit)?;
             Self::ensure_drip_limit_not_zero(drip_limit)?;
+            Self::ensure_drip_limit_within_period_limit(drip_limit, period_limit)?;
 
             ensure!(
                 Self::faucet_by_account(&faucet).is_none(),
@@ -159,14 +214,14 @@ decl_module! {
             Ok(())
         }
 
-        #[weight = 50_000 + T::DbWeight::get().reads_writes(1, 1)]
+        #[weight = T::WeightInfo::update_faucet()]
         pub fn update_faucet(
             origin,
             faucet: T::AccountId,
             update: FaucetUpdate<T::BlockNumber, BalanceOf<T>>
         ) -> DispatchResult {
 
-            ensure_root(origin)?;
+            T::ForceOrigin::ensure_origin(origin)?;
 
             let has_updates =
                 update.enabled.is_some() ||
@@ -217,18 +272,20 @@ decl_module! {
 
             ensure!(should_update, Error::<T>::NothingToUpdate);
 
+            Self::ensure_drip_limit_within_period_limit(settings.drip_limit, settings.period_limit)?;
+
             FaucetByAccount::<T>::insert(faucet.clone(), settings);
             Self::deposit_event(RawEvent::FaucetUpdated(faucet));
             Ok(())
         }
 
-        #[weight = 20_000 + T::DbWeight::get().reads_writes(0, 0) + 20_000 * faucets.len() as u64]
+        #[weight = T::WeightInfo::remove_faucets(faucets.len() as u32)]
         pub fn remove_faucets(
             origin,
             faucets: Vec<T::AccountId>
         ) -> DispatchResult {
 
-            ensure_root(origin)?;
+            T::ForceOrigin::ensure_origin(origin)?;
 
             ensure!(!faucets.len().is_zero(), Error::<T>::NoFaucetsProvided);
 
@@ -241,8 +298,36 @@ decl_module! {
             Ok(())
         }
 
+        /// Enable or disable every faucet. At most `MaxFaucetsToggledPerCall` faucets are
+        /// processed in one call, and the next call with the same `enabled` value continues
+        /// from where the previous one stopped. `AllFaucetsToggled` tells whether it is done.
+        #[weight = T::WeightInfo::set_all_faucets_enabled(T::MaxFaucetsToggledPerCall::get())]
+        pub fn set_all_faucets_enabled(origin, enabled: bool) -> DispatchResult {
+
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            let start_key = match Self::all_faucets_toggle_cursor() {
+                Some((cursor_enabled, last_faucet)) if cursor_enabled == enabled =>
+                    FaucetByAccount::<T>::hashed_key_for(last_faucet),
+                _ => FaucetByAccount::<T>::final_prefix().to_vec(),
+            };
+
+            let (changed, last_faucet) = Self::set_faucets_enabled_after(
+                start_key, enabled, T::MaxFaucetsToggledPerCall::get()
+            );
+
+            let is_finished = last_faucet.is_none();
+            match last_faucet {
+                Some(faucet) => AllFaucetsToggleCursor::<T>::put((enabled, faucet)),
+                None => AllFaucetsToggleCursor::<T>::kill(),
+            }
+
+            Self::deposit_event(RawEvent::AllFaucetsToggled(enabled, changed, is_finished));
+            Ok(())
+        }
+
         #[weight = (
-            50_000 + T::DbWeight::get().reads_writes(2, 2),
+            T::WeightInfo::drip(),
             
             // TODO Replace with Ok(Pays::No.into())
             // See https://github.com/substrate-developer-hub/substrate-node-template/commit/6546b15634bf088e8faee806b5cf266621412889#diff-657cb55f3d39058f730b46f7c84f90698ad43b3ab5c1aa8789a435a230c77f19R106
@@ -304,6 +389,48 @@ impl<T: Trait> Module<T> {
         Ok(Self::faucet_by_account(faucet).ok_or(Error::<T>::FaucetNotFound)?)
     }
 
+    /// Set `enabled` on up to `limit` faucets stored after `start_key`.
+    /// Return the number of faucets changed and the last faucet processed,
+    /// or `None` if there are no faucets left after it.
+    fn set_faucets_enabled_after(
+        start_key: Vec<u8>,
+        enabled: bool,
+        limit: u32
+    ) -> (u32, Option<T::AccountId>) {
+        let prefix = FaucetByAccount::<T>::final_prefix();
+        let mut key = start_key;
+        let mut changed = 0u32;
+        let mut last_faucet = None;
+
+        for _ in 0..limit {
+            key = match sp_io::storage::next_key(&key) {
+                Some(next_key) if next_key.starts_with(&prefix) => next_key,
+                _ => return (changed, None),
+            };
+
+            // A key of the map is its prefix, then a twox_64 hash and the faucet account itself.
+            let faucet = match key.get(prefix.len() + TWOX_64_HASH_LEN..)
+                .and_then(|mut account| T::AccountId::decode(&mut account).ok())
+            {
+                Some(faucet) => faucet,
+                None => continue,
+            };
+
+            FaucetByAccount::<T>::mutate(&faucet, |settings_opt| {
+                if let Some(settings) = settings_opt {
+                    if settings.enabled != enabled {
+                        settings.enabled = enabled;
+                        changed = changed.saturating_add(1);
+                    }
+                }
+            });
+            last_faucet = Some(faucet);
+        }
+
+        // There may be no faucets after the last one processed, but this is found out by the next call.
+        (changed, last_faucet)
+    }
+
     fn ensure_period_not_zero(period: T::BlockNumber) -> DispatchResult {
         ensure!(period > Zero::zero(), Error::<T>::ZeroPeriodProvided);
         Ok(())
@@ -318,6 +445,14 @@ impl<T: Trait> Module<T> {
         ensure!(drip_limit > Zero::zero(), Error::<T>::ZeroDripLimitProvided);
         Ok(())
     }
+
+    fn ensure_drip_limit_within_period_limit(
+        drip_limit: BalanceOf<T>,
+        period_limit: BalanceOf<T>
+    ) -> DispatchResult {
+        ensure!(drip_limit <= period_limit, Error::<T>::DripLimitExceedsPeriodLimit);
+        Ok(())
+    }
 }
 
 impl<T: Trait> Faucet<T> {
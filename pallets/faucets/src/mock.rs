@@ -1,5 +1,5 @@
 // Creating mock runtime here
-use crate::{Module, Trait, Faucet, FaucetUpdate};
+use crate::{Module, Trait, Faucet, FaucetUpdate, GenesisConfig};
 
 use sp_core::H256;
 use sp_io::TestExternalities;
@@ -8,7 +8,7 @@ use sp_runtime::{
 };
 
 use frame_support::{
-    impl_outer_origin, impl_outer_dispatch, parameter_types,
+    impl_outer_origin, impl_outer_dispatch, impl_outer_event, parameter_types,
     assert_ok,
     weights::Weight,
     dispatch::DispatchResult,
@@ -29,6 +29,19 @@ impl_outer_dispatch! {
 #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Test;
 
+mod faucets {
+    pub use crate::Event;
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        system<T>,
+        pallet_balances<T>,
+        pallet_utils<T>,
+        faucets<T>,
+    }
+}
+
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const MaximumBlockWeight: Weight = 1024;
@@ -47,7 +60,7 @@ impl system::Trait for Test {
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = TestEvent;
     type BlockHashCount = BlockHashCount;
     type MaximumBlockWeight = MaximumBlockWeight;
     type DbWeight = ();
@@ -82,7 +95,7 @@ parameter_types! {
 impl pallet_balances::Trait for Test {
     type Balance = u64;
     type DustRemoval = ();
-    type Event = ();
+    type Event = TestEvent;
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
@@ -95,15 +108,24 @@ parameter_types! {
 }
 
 impl pallet_utils::Trait for Test {
-    type Event = ();
+    type Event = TestEvent;
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type UnpausablePallets = ();
+}
+
+parameter_types! {
+    pub const MaxFaucetsToggledPerCall: u32 = 3;
 }
 
 impl Trait for Test {
-    type Event = ();
+    type Event = TestEvent;
     type Currency = Balances;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type MaxFaucetsToggledPerCall = MaxFaucetsToggledPerCall;
+    type WeightInfo = ();
 }
 
 pub(crate) type System = system::Module<Test>;
@@ -159,6 +181,31 @@ impl ExtBuilder {
         ext
     }
 
+    /// Add FAUCET9 (that has no balance otherwise) with the default settings at genesis.
+    pub fn build_with_genesis_faucet() -> TestExternalities {
+        let mut storage = system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        Self::configure_storages(&mut storage);
+
+        let settings = default_faucet();
+        let _ = GenesisConfig::<Test> {
+            faucets: vec![(
+                FAUCET9,
+                settings.period,
+                settings.period_limit,
+                settings.drip_limit,
+                FAUCET_INITIAL_BALANCE,
+            )],
+        }.assimilate_storage(&mut storage);
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+
+        ext
+    }
+
     // TODO do we really need this func? it's much clearer to call _do_default_drip() in a few tests directly.
     pub fn build_with_one_default_drip() -> TestExternalities {
         let mut storage = system::GenesisConfig::default()
@@ -231,6 +278,14 @@ pub(crate) fn _add_faucet(
     )
 }
 
+pub(crate) fn _add_faucet_with_limits(
+    period: BlockNumber,
+    period_limit: Balance,
+    drip_limit: Balance,
+) -> DispatchResult {
+    Faucets::add_faucet(Origin::root(), FAUCET1, period, period_limit, drip_limit)
+}
+
 pub(crate) fn _update_default_faucet() -> DispatchResult {
     _update_faucet(None, None, None)
 }
@@ -265,6 +320,10 @@ pub(crate) fn _remove_faucets(
     )
 }
 
+pub(crate) fn _set_all_faucets_enabled(origin: Option<Origin>, enabled: bool) -> DispatchResult {
+    Faucets::set_all_faucets_enabled(origin.unwrap_or_else(Origin::root), enabled)
+}
+
 pub(crate) fn _do_default_drip() -> DispatchResult {
     _drip(None, None, None)
 }
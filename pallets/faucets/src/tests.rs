@@ -1,7 +1,29 @@
-use crate::{Error, mock::*, Faucet, FaucetUpdate};
+use crate::{Error, RawEvent, mock::*, Faucet, FaucetUpdate};
 use frame_support::{assert_ok, assert_noop};
 use sp_runtime::DispatchError::BadOrigin;
 
+// Genesis faucets
+// ----------------------------------------------------------------------------
+
+#[test]
+fn genesis_faucet_should_be_added_and_funded() {
+    ExtBuilder::build_with_genesis_faucet().execute_with(|| {
+        assert_eq!(Faucets::faucet_by_account(FAUCET9), Some(default_faucet()));
+        assert_eq!(Balances::free_balance(FAUCET9), FAUCET_INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn genesis_faucet_should_drip_at_block_one() {
+    ExtBuilder::build_with_genesis_faucet().execute_with(|| {
+        let drip_limit = default_faucet().drip_limit;
+        assert_ok!(_drip(Some(Origin::signed(FAUCET9)), None, None));
+
+        assert_eq!(Balances::free_balance(ACCOUNT1), drip_limit);
+        assert_eq!(Balances::free_balance(FAUCET9), FAUCET_INITIAL_BALANCE - drip_limit);
+    });
+}
+
 // Add faucet
 // ----------------------------------------------------------------------------
 
@@ -50,6 +72,61 @@ fn add_faucet_should_fail_when_no_free_balance_on_account() {
     });
 }
 
+#[test]
+fn add_faucet_should_work_when_drip_limit_equals_period_limit() {
+    ExtBuilder::build().execute_with(|| {
+        let period_limit = default_faucet().period_limit;
+        assert_ok!(_add_faucet_with_limits(default_faucet().period, period_limit, period_limit));
+
+        let faucet = Faucets::faucet_by_account(FAUCET1).unwrap();
+        assert_eq!(faucet.drip_limit, faucet.period_limit);
+    });
+}
+
+#[test]
+fn add_faucet_should_fail_when_zero_period_provided() {
+    ExtBuilder::build().execute_with(|| {
+        let settings = default_faucet();
+        assert_noop!(
+            _add_faucet_with_limits(0, settings.period_limit, settings.drip_limit),
+            Error::<Test>::ZeroPeriodProvided
+        );
+    });
+}
+
+#[test]
+fn add_faucet_should_fail_when_zero_period_limit_provided() {
+    ExtBuilder::build().execute_with(|| {
+        let settings = default_faucet();
+        assert_noop!(
+            _add_faucet_with_limits(settings.period, 0, settings.drip_limit),
+            Error::<Test>::ZeroPeriodLimitProvided
+        );
+    });
+}
+
+#[test]
+fn add_faucet_should_fail_when_zero_drip_limit_provided() {
+    ExtBuilder::build().execute_with(|| {
+        let settings = default_faucet();
+        assert_noop!(
+            _add_faucet_with_limits(settings.period, settings.period_limit, 0),
+            Error::<Test>::ZeroDripLimitProvided
+        );
+    });
+}
+
+#[test]
+fn add_faucet_should_fail_when_drip_limit_exceeds_period_limit() {
+    ExtBuilder::build().execute_with(|| {
+        let settings = default_faucet();
+        assert_noop!(
+            _add_faucet_with_limits(settings.period, settings.period_limit, settings.period_limit + 1),
+            Error::<Test>::DripLimitExceedsPeriodLimit
+        );
+    });
+}
+
 // Update faucet
 // ----------------------------------------------------------------------------
 
@@ -167,6 +244,23 @@ fn update_faucet_should_fail_when_same_drip_limit_provided() {
     });
 }
 
+#[test]
+fn update_faucet_should_fail_when_drip_limit_exceeds_period_limit() {
+    ExtBuilder::build_with_faucet().execute_with(|| {
+        assert_noop!(
+            _update_faucet_settings(
+                FaucetUpdate {
+                    enabled: None,
+                    period: None,
+                    period_limit: None,
+                    drip_limit: Some(default_faucet().period_limit + 1)
+                }
+            ),
+            Error::<Test>::DripLimitExceedsPeriodLimit
+        );
+    });
+}
+
 // Remove faucets
 // ----------------------------------------------------------------------------
 
@@ -237,6 +331,84 @@ fn remove_faucets_should_fail_when_no_faucet_addresses_provided() {
     });
 }
 
+// Set all faucets enabled
+// ----------------------------------------------------------------------------
+
+fn add_all_faucets() {
+    for faucet in FAUCET1..=FAUCET8 {
+        assert_ok!(_add_faucet(None, Some(faucet)));
+    }
+}
+
+fn last_faucets_event() -> Option<TestEvent> {
+    System::events().pop().map(|record| record.event)
+}
+
+#[test]
+fn set_all_faucets_enabled_should_disable_and_enable_all_faucets() {
+    ExtBuilder::build().execute_with(|| {
+        add_all_faucets();
+        System::set_block_number(INITIAL_BLOCK_NUMBER);
+
+        // 8 faucets are disabled in 3 calls, as one call processes up to 3 faucets.
+        assert_ok!(_set_all_faucets_enabled(None, false));
+        assert_eq!(last_faucets_event(), Some(TestEvent::faucets(RawEvent::AllFaucetsToggled(false, 3, false))));
+        assert_ok!(_set_all_faucets_enabled(None, false));
+        assert_eq!(last_faucets_event(), Some(TestEvent::faucets(RawEvent::AllFaucetsToggled(false, 3, false))));
+        assert_ok!(_set_all_faucets_enabled(None, false));
+        assert_eq!(last_faucets_event(), Some(TestEvent::faucets(RawEvent::AllFaucetsToggled(false, 2, true))));
+        assert!(Faucets::all_faucets_toggle_cursor().is_none());
+
+        for faucet in FAUCET1..=FAUCET8 {
+            assert!(!Faucets::faucet_by_account(faucet).unwrap().enabled);
+            assert_noop!(
+                _drip(Some(Origin::signed(faucet)), None, None),
+                Error::<Test>::FaucetDisabled
+            );
+        }
+
+        for _ in 0..3 {
+            assert_ok!(_set_all_faucets_enabled(None, true));
+        }
+        assert_eq!(last_faucets_event(), Some(TestEvent::faucets(RawEvent::AllFaucetsToggled(true, 2, true))));
+
+        for faucet in FAUCET1..=FAUCET8 {
+            assert_ok!(_drip(Some(Origin::signed(faucet)), None, None));
+        }
+        assert_eq!(Balances::free_balance(ACCOUNT1), default_faucet().drip_limit * 8);
+    });
+}
+
+#[test]
+fn set_all_faucets_enabled_should_restart_when_enabled_value_changes() {
+    ExtBuilder::build().execute_with(|| {
+        add_all_faucets();
+
+        assert_ok!(_set_all_faucets_enabled(None, false));
+        assert!(Faucets::all_faucets_toggle_cursor().is_some());
+
+        // Enabling starts from the first faucet and only changes the 3 disabled ones.
+        for _ in 0..3 {
+            assert_ok!(_set_all_faucets_enabled(None, true));
+        }
+        assert!(Faucets::all_faucets_toggle_cursor().is_none());
+
+        for faucet in FAUCET1..=FAUCET8 {
+            assert!(Faucets::faucet_by_account(faucet).unwrap().enabled);
+        }
+    });
+}
+
+#[test]
+fn set_all_faucets_enabled_should_fail_when_origin_is_not_root() {
+    ExtBuilder::build_with_faucet().execute_with(|| {
+        assert_noop!(
+            _set_all_faucets_enabled(Some(Origin::signed(ACCOUNT1)), false),
+            BadOrigin
+        );
+    });
+}
+
 // Drip
 // ----------------------------------------------------------------------------
 
@@ -258,6 +430,21 @@ fn drip_should_work() {
     });
 }
 
+#[test]
+fn drip_should_deposit_dripped_event() {
+    ExtBuilder::build_with_faucet().execute_with(|| {
+        System::set_block_number(INITIAL_BLOCK_NUMBER);
+
+        assert_ok!(_do_default_drip());
+
+        let last_event = System::events().pop().map(|record| record.event);
+        assert_eq!(
+            last_event,
+            Some(TestEvent::faucets(RawEvent::Dripped(FAUCET1, ACCOUNT1, default_faucet().drip_limit)))
+        );
+    });
+}
+
 #[test]
 fn drip_should_work_multiple_times_in_same_period() {
     ExtBuilder::build_with_one_default_drip().execute_with(|| {
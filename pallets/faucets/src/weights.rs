@@ -0,0 +1,90 @@
+//! Weights for the Faucets pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_faucets --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_faucets.
+pub trait WeightInfo {
+    fn add_faucet() -> Weight;
+    fn update_faucet() -> Weight;
+    fn remove_faucets(f: u32) -> Weight;
+    fn set_all_faucets_enabled(f: u32) -> Weight;
+    fn drip() -> Weight;
+}
+
+/// Weights for pallet_faucets measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn add_faucet() -> Weight {
+        (18_717_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn update_faucet() -> Weight {
+        (15_752_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn remove_faucets(f: u32) -> Weight {
+        (10_666_000 as Weight)
+            .saturating_add((1_213_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+    }
+
+    fn set_all_faucets_enabled(f: u32) -> Weight {
+        (5_175_000 as Weight)
+            .saturating_add((10_703_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+    }
+
+    fn drip() -> Weight {
+        (55_282_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+}
+
+impl WeightInfo for () {
+    fn add_faucet() -> Weight {
+        (50_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+
+    fn update_faucet() -> Weight {
+        (50_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+
+    fn remove_faucets(f: u32) -> Weight {
+        (20_000 as Weight)
+            .saturating_add((20_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+    }
+
+    fn set_all_faucets_enabled(f: u32) -> Weight {
+        (20_000 as Weight)
+            .saturating_add((20_000 as Weight).saturating_mul(f as Weight))
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(f as Weight)))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(f as Weight)))
+    }
+
+    fn drip() -> Weight {
+        (50_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_post_history::rpc::FlatPostHistoryRecord;
+use pallet_posts::PostId;
+use pallet_profile_history::rpc::FlatProfileHistoryRecord;
+use pallet_space_history::rpc::FlatSpaceHistoryRecord;
+use pallet_utils::SpaceId;
+
+sp_api::decl_runtime_apis! {
+    /// Edit history of an entity filtered by an inclusive range of blocks
+    /// and limited to `limit` records, starting from the oldest record in range.
+    pub trait HistoryApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        fn space_edit_history(
+            space_id: SpaceId,
+            from_block: BlockNumber,
+            to_block: BlockNumber,
+            limit: u32,
+        ) -> Vec<FlatSpaceHistoryRecord<AccountId, BlockNumber, Moment>>;
+
+        fn post_edit_history(
+            post_id: PostId,
+            from_block: BlockNumber,
+            to_block: BlockNumber,
+            limit: u32,
+        ) -> Vec<FlatPostHistoryRecord<AccountId, BlockNumber, Moment>>;
+
+        fn profile_edit_history(
+            account: AccountId,
+            from_block: BlockNumber,
+            to_block: BlockNumber,
+            limit: u32,
+        ) -> Vec<FlatProfileHistoryRecord<AccountId, BlockNumber, Moment>>;
+    }
+}
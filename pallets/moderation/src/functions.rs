@@ -1,12 +1,46 @@
 use crate::*;
 
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, ensure};
+use sp_std::collections::btree_set::BTreeSet;
 use pallet_posts::Module as Posts;
 use pallet_spaces::Space;
 use pallet_space_follows::Module as SpaceFollows;
 use df_traits::moderation::*;
 
 impl<T: Trait> Module<T> {
+    /// Validate and store a report of `entity` by `who` within `scope`.
+    /// Nothing is written to storage if the report is not valid.
+    pub(crate) fn do_report_entity(
+        who: T::AccountId,
+        entity: EntityId<T::AccountId>,
+        scope: SpaceId,
+        reason: Content,
+    ) -> DispatchResult {
+        // TODO check this func, if looks strange
+        Utils::<T>::ensure_content_is_some(&reason).map_err(|_| Error::<T>::ReasonIsEmpty)?;
+
+        <T as pallet_spaces::Trait>::ContentValidator::is_valid_content(reason.clone())?;
+
+        ensure!(Spaces::<T>::require_space(scope).is_ok(), Error::<T>::ScopeNotFound);
+        Self::ensure_entity_in_scope(&entity, scope)?;
+
+        let not_reported_yet = Self::report_id_by_account((&entity, &who)).is_none();
+        ensure!(not_reported_yet, Error::<T>::AlreadyReportedEntity);
+
+        let report_id = Self::next_report_id();
+        let next_report_id = Utils::<T>::next_id(report_id)?;
+        let new_report = Report::<T>::new(report_id, who.clone(), entity.clone(), scope, reason);
+
+        ReportById::<T>::insert(report_id, new_report);
+        ReportIdByAccount::<T>::insert((&entity, &who), report_id);
+        ReportIdsBySpaceId::mutate(scope, |ids| ids.push(report_id));
+        ReportIdsByEntityInSpace::<T>::mutate(&entity, scope, |ids| ids.push(report_id));
+        NextReportId::put(next_report_id);
+
+        Self::deposit_event(RawEvent::EntityReported(who, scope, entity, report_id));
+        Ok(())
+    }
+
     pub fn require_report(report_id: ReportId) -> Result<Report<T>, DispatchError> {
         Ok(Self::report_by_id(report_id).ok_or(Error::<T>::ReportNotFound)?)
     }
@@ -56,9 +90,57 @@ impl<T: Trait> Module<T> {
             EntityId::Post(post_id) => Posts::<T>::delete_post_from_space(*post_id)?,
         }
         StatusByEntityInSpace::<T>::insert(entity, scope, EntityStatus::Blocked);
+        AutoblockedEntityInSpace::<T>::remove(entity, scope);
         Ok(())
     }
 
+    /// The number of suggestions to block an entity.
+    pub(crate) fn count_block_suggestions(suggestions: &[SuggestedStatus<T>]) -> usize {
+        suggestions.iter()
+            .filter(|suggestion| suggestion.status == Some(EntityStatus::Blocked))
+            .count()
+    }
+
+    /// The number of block suggestions that auto-block an entity in a space, if enabled there.
+    pub(crate) fn autoblock_threshold(scope: SpaceId) -> Option<u16> {
+        Self::moderation_settings(scope)
+            .unwrap_or_else(Self::default_autoblock_threshold_as_settings)
+            .autoblock_threshold
+    }
+
+    /// Block an IPFS CID in the space of every post that has this content, and block those posts,
+    /// which removes them from their spaces. Comments and posts without a space are skipped.
+    /// Only posts in `ContentToPostIds` are found, i.e. up to `MaxPostsPerContent` of them.
+    /// Returns the number of blocked posts.
+    pub fn block_content_everywhere(content: Content) -> Result<u32, DispatchError> {
+        let cid = match &content {
+            Content::IPFS(cid) => cid,
+            _ => return Ok(0),
+        };
+
+        let mut blocked_posts = 0u32;
+        let mut scopes = BTreeSet::new();
+
+        for post_id in Posts::<T>::post_ids_by_content(cid) {
+            let space_id = match Posts::<T>::post_by_id(post_id).and_then(|post| post.space_id) {
+                Some(space_id) => space_id,
+                None => continue,
+            };
+
+            Self::block_entity_in_scope(&EntityId::Post(post_id), space_id)?;
+            scopes.insert(space_id);
+            blocked_posts = blocked_posts.saturating_add(1);
+        }
+
+        let entity = EntityId::Content(content.clone());
+        for scope in scopes {
+            Self::block_entity_in_scope(&entity, scope)?;
+        }
+
+        Self::deposit_event(RawEvent::ContentBlockedEverywhere(content, blocked_posts));
+        Ok(blocked_posts)
+    }
+
     pub(crate) fn ensure_account_status_manager(who: T::AccountId, space: &Space<T>) -> DispatchResult {
         Spaces::<T>::ensure_account_has_space_permission(
             who,
@@ -75,6 +157,33 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Stores a record about a moderation decision that has just been applied to the entity.
+    /// A new status of the entity is read from the storage.
+    pub(crate) fn record_moderation_decision(
+        who: T::AccountId,
+        entity: &EntityId<T::AccountId>,
+        scope: SpaceId,
+        action: ModerationAction,
+        old_status: Option<EntityStatus>,
+        report_id: Option<ReportId>,
+    ) {
+        let record = ModerationHistoryRecord::<T> {
+            changed: WhoAndWhen::<T>::new(who),
+            action,
+            old_status,
+            new_status: Self::status_by_entity_in_space(entity, scope),
+            report_id,
+        };
+
+        ModerationHistory::<T>::mutate(entity, scope, |records|
+            push_to_bounded_history(
+                records,
+                record,
+                T::MaxModerationHistoryRecords::get(),
+                T::ModerationHistoryOverflowPolicy::get(),
+            ));
+    }
+
     pub fn default_autoblock_threshold_as_settings() -> SpaceModerationSettings {
         SpaceModerationSettings {
             autoblock_threshold: Some(T::DefaultAutoblockThreshold::get())
@@ -28,7 +28,11 @@ use frame_support::{
 };
 use frame_system::{self as system, ensure_signed};
 
-use pallet_utils::{Content, WhoAndWhen, SpaceId, Module as Utils};
+use df_traits::ContentValidator;
+use pallet_utils::{
+    Content, WhoAndWhen, SpaceId, Module as Utils,
+    HistoryOverflowPolicy, push_to_bounded_history, remove_from_vec_keeping_order,
+};
 use pallet_posts::PostId;
 use pallet_spaces::Module as Spaces;
 
@@ -84,6 +88,31 @@ pub struct SuggestedStatus<T: Trait> {
     report_id: Option<ReportId>,
 }
 
+/// A moderation action that has been recorded in the moderation history.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum ModerationAction {
+    SuggestStatus,
+    UpdateStatus,
+    DeleteStatus,
+    /// An auto-blocked entity was allowed after a report was withdrawn.
+    AutoUnblock,
+}
+
+/// A record about a moderation decision made on an entity within a space (scope).
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ModerationHistoryRecord<T: Trait> {
+    /// An account id of a moderator or a space owner who made the decision.
+    pub changed: WhoAndWhen<T>,
+    pub action: ModerationAction,
+    /// An entity status in the scope before the decision.
+    pub old_status: Option<EntityStatus>,
+    /// An entity status in the scope after the decision.
+    /// It differs from `old_status` on a suggestion only if the entity has been auto-blocked.
+    pub new_status: Option<EntityStatus>,
+    /// `None` if the decision is not based on any reports.
+    pub report_id: Option<ReportId>,
+}
+
 // TODO rename to ModerationSettings?
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SpaceModerationSettings {
@@ -107,6 +136,15 @@ pub trait Trait: system::Trait
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type DefaultAutoblockThreshold: Get<u16>;
+
+    /// Max number of moderation history records stored per entity in a space.
+    type MaxModerationHistoryRecords: Get<u32>;
+
+    /// What to do with a new record when the moderation history of an entity is full.
+    type ModerationHistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+
+    /// Max number of entities that can be reported in a single `report_entities` call.
+    type MaxReportsPerBatch: Get<u16>;
 }
 
 // This pallet's storage items.
@@ -153,6 +191,20 @@ decl_storage! {
         pub ModerationSettings get(fn moderation_settings):
             map hasher(twox_64_concat) SpaceId
             => Option<SpaceModerationSettings>;
+
+        /// Moderation decisions made on an entity (key 1) within this space (key 2),
+        /// from the oldest to the newest one.
+        pub ModerationHistory get(fn moderation_history): double_map
+            hasher(twox_64_concat) EntityId<T::AccountId>,
+            hasher(twox_64_concat) SpaceId
+            => Vec<ModerationHistoryRecord<T>>;
+
+        /// Whether an entity (key 1) was blocked in this space (key 2) by reaching
+        /// the autoblock threshold, rather than by a space owner or a moderator.
+        pub AutoblockedEntityInSpace get(fn is_autoblocked): double_map
+            hasher(twox_64_concat) EntityId<T::AccountId>,
+            hasher(twox_64_concat) SpaceId
+            => bool;
     }
 }
 
@@ -167,6 +219,16 @@ decl_event!(
         EntityStatusUpdated(AccountId, SpaceId, EntityId, Option<EntityStatus>),
         EntityStatusDeleted(AccountId, SpaceId, EntityId),
         ModerationSettingsUpdated(AccountId, SpaceId),
+        /// An IPFS CID was blocked in the spaces of all posts with it, and so were those posts.
+        ContentBlockedEverywhere(Content, /* blocked posts */ u32),
+        /// A batch of entities was reported. Entities that could not be reported were skipped.
+        /// [who, reported, skipped]
+        EntitiesReported(AccountId, u32, u32),
+        /// An account withdrew its report of an entity. [who, scope, entity, report_id]
+        ReportWithdrawn(AccountId, SpaceId, EntityId, ReportId),
+        /// An auto-blocked entity was allowed, as the suggestions to block it
+        /// no longer reach the autoblock threshold. [scope, entity]
+        EntityAutoUnblocked(SpaceId, EntityId),
     }
 );
 
@@ -202,6 +264,10 @@ decl_error! {
         SuggestedStatusInWrongScope,
         /// Entity status has already been suggested by this moderator account.
         AlreadySuggestedEntityStatus,
+        /// No entities provided to report.
+        NoEntitiesToReport,
+        /// Too many entities provided to report in a single call.
+        TooManyEntitiesToReport,
     }
 }
 
@@ -212,6 +278,10 @@ decl_module! {
 
         const DefaultAutoblockThreshold: u16 = T::DefaultAutoblockThreshold::get();
 
+        const MaxModerationHistoryRecords: u32 = T::MaxModerationHistoryRecords::get();
+
+        const MaxReportsPerBatch: u16 = T::MaxReportsPerBatch::get();
+
         // Initializing errors
         type Error = Error<T>;
 
@@ -228,28 +298,32 @@ decl_module! {
             reason: Content
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            Self::do_report_entity(who, entity, scope, reason)
+        }
 
-            // TODO check this func, if looks strange
-            Utils::<T>::ensure_content_is_some(&reason).map_err(|_| Error::<T>::ReasonIsEmpty)?;
-            
-            Utils::<T>::is_valid_content(reason.clone())?;
-
-            ensure!(Spaces::<T>::require_space(scope).is_ok(), Error::<T>::ScopeNotFound);
-            Self::ensure_entity_in_scope(&entity, scope)?;
-
-            let not_reported_yet = Self::report_id_by_account((&entity, &who)).is_none();
-            ensure!(not_reported_yet, Error::<T>::AlreadyReportedEntity);
+        /// Report many entities at once, e.g. during a spam wave. Every report is validated
+        /// as in `report_entity`, and the ones that fail validation are skipped,
+        /// so the call fails only if no reports or too many reports are provided.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(6 * reports.len() as u64, 5 * reports.len() as u64)]
+        pub fn report_entities(
+            origin,
+            reports: Vec<(EntityId<T::AccountId>, SpaceId, Content)>
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
 
-            let report_id = Self::next_report_id();
-            let new_report = Report::<T>::new(report_id, who.clone(), entity.clone(), scope, reason);
+            ensure!(!reports.is_empty(), Error::<T>::NoEntitiesToReport);
+            ensure!(reports.len() <= T::MaxReportsPerBatch::get() as usize, Error::<T>::TooManyEntitiesToReport);
 
-            ReportById::<T>::insert(report_id, new_report);
-            ReportIdByAccount::<T>::insert((&entity, &who), report_id);
-            ReportIdsBySpaceId::mutate(scope, |ids| ids.push(report_id));
-            ReportIdsByEntityInSpace::<T>::mutate(&entity, scope, |ids| ids.push(report_id));
-            NextReportId::mutate(|n| { *n += 1; });
+            let mut reported = 0u32;
+            let mut skipped = 0u32;
+            for (entity, scope, reason) in reports {
+                match Self::do_report_entity(who.clone(), entity, scope, reason) {
+                    Ok(()) => reported += 1,
+                    Err(_) => skipped += 1,
+                }
+            }
 
-            Self::deposit_event(RawEvent::EntityReported(who, scope, entity, report_id));
+            Self::deposit_event(RawEvent::EntitiesReported(who, reported, skipped));
             Ok(())
         }
 
@@ -286,22 +360,26 @@ decl_module! {
             ensure!(!is_already_suggested, Error::<T>::AlreadySuggestedEntityStatus);
             suggestions.push(SuggestedStatus::new(who.clone(), status.clone(), report_id_opt));
 
-            let block_suggestions_total = suggestions.iter()
-                .filter(|suggestion| suggestion.status == Some(EntityStatus::Blocked))
-                .count();
+            let block_suggestions_total = Self::count_block_suggestions(&suggestions);
 
-            let autoblock_threshold_opt = Self::moderation_settings(scope)
-                .unwrap_or_else(Self::default_autoblock_threshold_as_settings)
-                .autoblock_threshold;
-
-            if let Some(autoblock_threshold) = autoblock_threshold_opt {
+            if let Some(autoblock_threshold) = Self::autoblock_threshold(scope) {
                 if block_suggestions_total >= autoblock_threshold as usize {
                     Self::block_entity_in_scope(&entity, scope)?;
+                    AutoblockedEntityInSpace::<T>::insert(&entity, scope, true);
                 }
             }
 
             SuggestedStatusesByEntityInSpace::<T>::insert(entity.clone(), scope, suggestions);
 
+            Self::record_moderation_decision(
+                who.clone(),
+                &entity,
+                scope,
+                ModerationAction::SuggestStatus,
+                entity_status,
+                report_id_opt,
+            );
+
             Self::deposit_event(RawEvent::EntityStatusSuggested(who, scope, entity, status));
             Ok(())
         }
@@ -322,6 +400,8 @@ decl_module! {
             let space = Spaces::<T>::require_space(scope).map_err(|_| Error::<T>::ScopeNotFound)?;
             Self::ensure_account_status_manager(who.clone(), &space)?;
 
+            let old_status = Self::status_by_entity_in_space(&entity, scope);
+
             if let Some(status) = &status_opt {
                 let is_entity_in_scope = Self::ensure_entity_in_scope(&entity, scope).is_ok();
 
@@ -329,11 +409,22 @@ decl_module! {
                     Self::block_entity_in_scope(&entity, scope)?;
                 } else {
                     StatusByEntityInSpace::<T>::insert(entity.clone(), scope, status);
+                    AutoblockedEntityInSpace::<T>::remove(&entity, scope);
                 }
             } else {
                 StatusByEntityInSpace::<T>::remove(entity.clone(), scope);
+                AutoblockedEntityInSpace::<T>::remove(&entity, scope);
             }
 
+            Self::record_moderation_decision(
+                who.clone(),
+                &entity,
+                scope,
+                ModerationAction::UpdateStatus,
+                old_status,
+                None,
+            );
+
             Self::deposit_event(RawEvent::EntityStatusUpdated(who, scope, entity, status_opt));
             Ok(())
         }
@@ -354,11 +445,73 @@ decl_module! {
             Self::ensure_account_status_manager(who.clone(), &space)?;
 
             StatusByEntityInSpace::<T>::remove(&entity, scope);
+            AutoblockedEntityInSpace::<T>::remove(&entity, scope);
+
+            Self::record_moderation_decision(
+                who.clone(),
+                &entity,
+                scope,
+                ModerationAction::DeleteStatus,
+                status,
+                None,
+            );
 
             Self::deposit_event(RawEvent::EntityStatusDeleted(who, scope, entity));
             Ok(())
         }
 
+        /// Withdraw a report of an entity that the caller made within `scope`.
+        /// Suggestions of moderators that cite this report are kept, as a reporter cannot
+        /// cancel the decisions of moderators. Reports do not count towards the autoblock
+        /// threshold, so an auto-blocked entity is allowed only if the block suggestions
+        /// do not reach the current threshold anymore, e.g. if it was raised since.
+        /// An entity that was blocked by a space owner or a moderator stays blocked.
+        ///
+        /// An allowed post or account is not moved back to the space or made a follower again.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(7, 7)]
+        pub fn withdraw_report(
+            origin,
+            entity: EntityId<T::AccountId>,
+            scope: SpaceId
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let report_id = Self::report_id_by_account((&entity, &who)).ok_or(Error::<T>::ReportNotFound)?;
+            let report = Self::require_report(report_id)?;
+            ensure!(report.reported_within == scope, Error::<T>::ReportNotFound);
+
+            ReportById::<T>::remove(report_id);
+            ReportIdByAccount::<T>::remove((&entity, &who));
+            ReportIdsBySpaceId::mutate(scope, |ids| remove_from_vec_keeping_order(ids, report_id));
+            ReportIdsByEntityInSpace::<T>::mutate(&entity, scope, |ids| remove_from_vec_keeping_order(ids, report_id));
+
+            let suggestions = SuggestedStatusesByEntityInSpace::<T>::get(&entity, scope);
+            let block_suggestions_total = Self::count_block_suggestions(&suggestions);
+
+            Self::deposit_event(RawEvent::ReportWithdrawn(who.clone(), scope, entity.clone(), report_id));
+
+            let is_below_threshold = Self::autoblock_threshold(scope)
+                .map_or(false, |threshold| block_suggestions_total < threshold as usize);
+
+            if Self::is_autoblocked(&entity, scope) && is_below_threshold {
+                let old_status = Self::status_by_entity_in_space(&entity, scope);
+                StatusByEntityInSpace::<T>::insert(&entity, scope, EntityStatus::Allowed);
+                AutoblockedEntityInSpace::<T>::remove(&entity, scope);
+
+                Self::record_moderation_decision(
+                    who,
+                    &entity,
+                    scope,
+                    ModerationAction::AutoUnblock,
+                    old_status,
+                    Some(report_id),
+                );
+
+                Self::deposit_event(RawEvent::EntityAutoUnblocked(scope, entity));
+            }
+            Ok(())
+        }
+
         // todo: add ability to delete report_ids
 
         // TODO rename to update_settings?
@@ -6,13 +6,14 @@ use frame_support::{
     dispatch::{DispatchResult},
 };
 use sp_runtime::{
-    traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+    traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId}, Perbill,
+    transaction_validity::{TransactionLongevity, TransactionPriority},
 };
 
 use frame_system as system;
 use sp_io::TestExternalities;
 
-use pallet_utils::{Content, SpaceId};
+use pallet_utils::{Content, SpaceId, HistoryOverflowPolicy};
 use pallet_spaces::{RESERVED_SPACE_COUNT, SpaceById};
 use pallet_posts::{PostId, PostExtension};
 
@@ -81,6 +82,8 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type UnpausablePallets = ();
 }
 
 parameter_types! {
@@ -105,59 +108,122 @@ impl pallet_permissions::Trait for Test {
 
 impl pallet_spaces::Trait for Test {
     type Event = ();
+    type ContentValidator = pallet_utils::Module<Test>;
     type Currency = Balances;
     type Roles = Roles;
     type SpaceFollows = SpaceFollows;
+    type SpacePosts = ();
     type BeforeSpaceCreated = SpaceFollows;
+    type BeforeSpaceUpdated = ();
     type AfterSpaceUpdated = ();
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
     type HandleDeposit = ();
+    type RestrictReservedSpaces = ();
+    type IdempotencyKeyTtl = ();
+    type MaxIdempotencyKeysRemovedPerBlock = ();
+    type HandleChangeCooldown = ();
+    type CreateRootSpaceOrigin = system::EnsureSigned<AccountId>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const UnsignedFollowsAllowance: u32 = 2;
+    pub const MaxUnsignedFollowsPerBlock: u32 = 10;
+    pub const UnsignedFollowerMinBalance: u64 = 10;
+    pub const UnsignedFollowPriority: TransactionPriority = TransactionPriority::max_value();
+    pub const UnsignedFollowLongevity: TransactionLongevity = 5;
+    pub const AutoFollowOwnSpace: bool = true;
+}
+
+impl system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
 }
 
 impl pallet_space_follows::Trait for Test {
     type Event = ();
     type BeforeSpaceFollowed = ();
     type BeforeSpaceUnfollowed = ();
+    type UnsignedFollowSignature = TestSignature;
+    type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+    type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+    type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+    type UnsignedFollowPriority = UnsignedFollowPriority;
+    type UnsignedFollowLongevity = UnsignedFollowLongevity;
+    type AutoFollowOwnSpace = AutoFollowOwnSpace;
+    type WeightInfo = ();
 }
 
 parameter_types! {
     pub const MaxCommentDepth: u32 = 10;
+    pub const MaxShareDepth: u32 = 1;
+    pub const MaxPostsPerContent: u32 = 10;
 }
 
 impl pallet_posts::Trait for Test {
     type Event = ();
     type MaxCommentDepth = MaxCommentDepth;
+    type MaxShareDepth = MaxShareDepth;
     type PostScores = ();
+    type BeforePostUpdated = ();
     type AfterPostUpdated = ();
+    type AfterPostCreated = ();
     type IsPostBlocked = Moderation;
+    type SpaceSettingsProvider = Spaces;
+    type MaxPostsPerContent = MaxPostsPerContent;
+    type ReputationProvider = ();
+    type WeightInfo = ();
 }
 
 parameter_types! {
     pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+    pub const MaxRolesPerSpace: u16 = 100;
+    pub const MaxRoleHistoryRecordsPerSpace: u32 = 10;
+    pub const RoleHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
 }
 
 impl pallet_roles::Trait for Test {
     type Event = ();
+    type ContentValidator = pallet_utils::Module<Test>;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxRolesPerSpace = MaxRolesPerSpace;
     type Spaces = Spaces;
     type SpaceFollows = SpaceFollows;
+    type UserResolver = Spaces;
     type IsAccountBlocked = Moderation;
     type IsContentBlocked = Moderation;
+    type MaxRoleHistoryRecordsPerSpace = MaxRoleHistoryRecordsPerSpace;
+    type RoleHistoryOverflowPolicy = RoleHistoryOverflowPolicy;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const BaseReputation: u32 = 1;
 }
 
 impl pallet_profiles::Trait for Test {
     type Event = ();
+    type ContentValidator = pallet_utils::Module<Test>;
+    type BeforeProfileUpdated = ();
     type AfterProfileUpdated = ();
+    type IdentityProvider = ();
+    type BaseReputation = BaseReputation;
 }
 
 parameter_types! {
     pub const DefaultAutoblockThreshold: u16 = 20;
+    pub const MaxModerationHistoryRecords: u32 = 10;
+    pub const ModerationHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+    pub const MaxReportsPerBatch: u16 = 5;
 }
 
 impl Trait for Test {
     type Event = ();
     type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+    type MaxModerationHistoryRecords = MaxModerationHistoryRecords;
+    type ModerationHistoryOverflowPolicy = ModerationHistoryOverflowPolicy;
+    type MaxReportsPerBatch = MaxReportsPerBatch;
 }
 
 type System = system::Module<Test>;
@@ -240,6 +306,8 @@ pub(crate) const SPACE1: SpaceId = RESERVED_SPACE_COUNT + 1;
 pub(crate) const SPACE2: SpaceId = SPACE1 + 1;
 
 pub(crate) const POST1: PostId = 1;
+pub(crate) const POST2: PostId = 2;
+pub(crate) const POST3: PostId = 3;
 
 pub(crate) const REPORT1: ReportId = 1;
 pub(crate) const REPORT2: ReportId = 2;
@@ -264,14 +332,20 @@ pub(crate) fn create_space_and_post() {
         None,
         None,
         Content::None,
+        None,
         None
     ));
 
+    create_post_in_space1();
+}
+
+pub(crate) fn create_post_in_space1() {
     assert_ok!(Posts::create_post(
         Origin::signed(ACCOUNT_SCOPE_OWNER),
         Some(SPACE1),
         PostExtension::RegularPost,
         valid_content_ipfs(),
+        None,
     ));
 }
 
@@ -293,6 +367,21 @@ pub(crate) fn _report_entity(
     )
 }
 
+pub(crate) fn _report_entities(
+    origin: Option<Origin>,
+    reports: Vec<(EntityId<AccountId>, SpaceId, Content)>,
+) -> DispatchResult {
+    Moderation::report_entities(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT_NOT_MODERATOR)),
+        reports,
+    )
+}
+
+/// A report of `entity` within SPACE1 with a valid reason.
+pub(crate) fn report_in_space1(entity: EntityId<AccountId>) -> (EntityId<AccountId>, SpaceId, Content) {
+    (entity, SPACE1, valid_content_ipfs())
+}
+
 pub(crate) fn _suggest_blocked_status_for_post() -> DispatchResult {
     _suggest_entity_status(None, None, None, None, None)
 }
@@ -347,6 +436,18 @@ pub(crate) fn _delete_entity_status(
     )
 }
 
+pub(crate) fn _withdraw_report(
+    origin: Option<Origin>,
+    entity: Option<EntityId<AccountId>>,
+    scope: Option<SpaceId>,
+) -> DispatchResult {
+    Moderation::withdraw_report(
+        origin.unwrap_or_else(|| Origin::signed(ACCOUNT_NOT_MODERATOR)),
+        entity.unwrap_or(EntityId::Post(POST1)),
+        scope.unwrap_or(SPACE1),
+    )
+}
+
 pub(crate) fn _update_autoblock_threshold_in_moderation_settings() -> DispatchResult {
     _update_moderation_settings(None, None, None)
 }
@@ -65,6 +65,73 @@ fn report_entity_should_fail_when_entity_already_reported() {
     });
 }
 
+#[test]
+fn report_entities_should_work() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        create_post_in_space1();
+        create_post_in_space1();
+
+        let posts = vec![POST1, POST2, POST3];
+        assert_ok!(_report_entities(
+            None,
+            posts.iter().map(|post_id| report_in_space1(EntityId::Post(*post_id))).collect()
+        ));
+
+        assert_eq!(Moderation::report_ids_by_space_id(SPACE1), vec![REPORT1, REPORT2, REPORT2 + 1]);
+        for (report_id, post_id) in (REPORT1..).zip(posts) {
+            let entity = EntityId::Post(post_id);
+            let report = Moderation::report_by_id(report_id).unwrap();
+
+            assert_eq!(report.created.account, ACCOUNT_NOT_MODERATOR);
+            assert_eq!(report.reported_entity, entity);
+            assert_eq!(report.reported_within, SPACE1);
+            assert_eq!(Moderation::report_id_by_account((&entity, &ACCOUNT_NOT_MODERATOR)), Some(report_id));
+            assert_eq!(Moderation::report_ids_by_entity_in_space(&entity, SPACE1), vec![report_id]);
+        }
+    });
+}
+
+#[test]
+fn report_entities_should_skip_invalid_and_already_reported_entities() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_report_entities(
+            Some(Origin::signed(ACCOUNT_SCOPE_OWNER)),
+            vec![
+                // Already reported by this account
+                report_in_space1(EntityId::Post(POST1)),
+                // There is no such post
+                report_in_space1(EntityId::Post(POST2)),
+                (EntityId::Account(ACCOUNT_NOT_MODERATOR), SPACE1, Content::None),
+                report_in_space1(EntityId::Account(ACCOUNT_NOT_MODERATOR)),
+                // Duplicates of other reports in the same batch are skipped too
+                report_in_space1(EntityId::Account(ACCOUNT_NOT_MODERATOR)),
+            ]
+        ));
+
+        assert_eq!(Moderation::next_report_id(), REPORT2 + 1);
+        assert_eq!(Moderation::report_ids_by_space_id(SPACE1), vec![REPORT1, REPORT2]);
+        assert_eq!(
+            Moderation::report_by_id(REPORT2).unwrap().reported_entity,
+            EntityId::Account(ACCOUNT_NOT_MODERATOR)
+        );
+    });
+}
+
+#[test]
+fn report_entities_should_fail_when_no_reports_provided() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        assert_noop!(_report_entities(None, vec![]), Error::<Test>::NoEntitiesToReport);
+    });
+}
+
+#[test]
+fn report_entities_should_fail_when_too_many_reports_provided() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        let reports = vec![report_in_space1(EntityId::Post(POST1)); MaxReportsPerBatch::get() as usize + 1];
+        assert_noop!(_report_entities(None, reports), Error::<Test>::TooManyEntitiesToReport);
+    });
+}
+
 // Suggest entity status
 //-------------------------------------------------------------------------
 
@@ -255,6 +322,46 @@ fn delete_entity_status_should_fail_when_origin_has_no_permission() {
     });
 }
 
+// Moderation history
+//----------------------------------------------------------------------------
+
+#[test]
+fn moderation_history_should_record_status_changes_in_order() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_update_entity_status(None, None, None, Some(Some(EntityStatus::Blocked))));
+        assert_ok!(_update_post_status_to_allowed());
+        assert_ok!(_delete_post_status());
+
+        let history = Moderation::moderation_history(EntityId::Post(POST1), SPACE1);
+        assert_eq!(history.len(), 3);
+
+        let changes: Vec<_> = history.iter()
+            .map(|record| (record.action, record.old_status.clone(), record.new_status.clone()))
+            .collect();
+        assert_eq!(changes, vec![
+            (ModerationAction::UpdateStatus, None, Some(EntityStatus::Blocked)),
+            (ModerationAction::UpdateStatus, Some(EntityStatus::Blocked), Some(EntityStatus::Allowed)),
+            (ModerationAction::DeleteStatus, Some(EntityStatus::Allowed), None),
+        ]);
+
+        assert!(history.iter().all(|record| record.changed.account == ACCOUNT_SCOPE_OWNER));
+    });
+}
+
+#[test]
+fn moderation_history_should_record_suggestion_with_report_id() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        assert_ok!(_suggest_blocked_status_for_post());
+
+        let history = Moderation::moderation_history(EntityId::Post(POST1), SPACE1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, ModerationAction::SuggestStatus);
+        assert_eq!(history[0].old_status, None);
+        assert_eq!(history[0].new_status, None);
+        assert_eq!(history[0].report_id, Some(REPORT1));
+    });
+}
+
 // Update moderation settings
 //----------------------------------------------------------------------------
 
@@ -305,3 +412,100 @@ fn update_moderation_settings_should_fail_when_origin_has_no_permission() {
         );
     });
 }
+
+// Withdraw report
+//----------------------------------------------------------------------------
+
+fn blocked_content_entity() -> EntityId<AccountId> {
+    EntityId::Content(valid_content_ipfs())
+}
+
+/// Report the content by ACCOUNT_NOT_MODERATOR and make the scope owner suggest to block it,
+/// which reaches the autoblock threshold of 1.
+fn autoblock_content_with_one_report() {
+    let entity = blocked_content_entity();
+
+    assert_ok!(_update_moderation_settings(
+        None,
+        None,
+        Some(SpaceModerationSettingsUpdate { autoblock_threshold: Some(Some(1)) })
+    ));
+    assert_ok!(_report_entity(Some(Origin::signed(ACCOUNT_NOT_MODERATOR)), Some(entity.clone()), None, None));
+    assert_ok!(_suggest_entity_status(None, Some(entity.clone()), None, None, None));
+
+    assert_eq!(Moderation::status_by_entity_in_space(&entity, SPACE1), Some(EntityStatus::Blocked));
+    assert!(Moderation::is_autoblocked(&entity, SPACE1));
+}
+
+#[test]
+fn withdraw_report_should_keep_moderator_suggestions_and_autoblock() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        let entity = blocked_content_entity();
+        autoblock_content_with_one_report();
+
+        assert_ok!(_withdraw_report(None, Some(entity.clone()), None));
+
+        assert!(Moderation::report_by_id(REPORT1).is_none());
+        assert!(Moderation::report_id_by_account((&entity, &ACCOUNT_NOT_MODERATOR)).is_none());
+        assert!(Moderation::report_ids_by_space_id(SPACE1).is_empty());
+        assert_eq!(Moderation::suggested_statuses(&entity, SPACE1).len(), 1);
+
+        assert_eq!(Moderation::status_by_entity_in_space(&entity, SPACE1), Some(EntityStatus::Blocked));
+        assert!(Moderation::is_autoblocked(&entity, SPACE1));
+    });
+}
+
+#[test]
+fn withdraw_report_should_allow_autoblocked_entity_below_raised_threshold() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        let entity = blocked_content_entity();
+        autoblock_content_with_one_report();
+
+        assert_ok!(_update_moderation_settings(
+            None,
+            None,
+            Some(SpaceModerationSettingsUpdate { autoblock_threshold: Some(Some(2)) })
+        ));
+        assert_ok!(_withdraw_report(None, Some(entity.clone()), None));
+
+        assert_eq!(Moderation::suggested_statuses(&entity, SPACE1).len(), 1);
+
+        assert_eq!(Moderation::status_by_entity_in_space(&entity, SPACE1), Some(EntityStatus::Allowed));
+        assert!(!Moderation::is_autoblocked(&entity, SPACE1));
+
+        let last_record = Moderation::moderation_history(&entity, SPACE1).pop().unwrap();
+        assert_eq!(last_record.action, ModerationAction::AutoUnblock);
+        assert_eq!(last_record.old_status, Some(EntityStatus::Blocked));
+        assert_eq!(last_record.new_status, Some(EntityStatus::Allowed));
+        assert_eq!(last_record.report_id, Some(REPORT1));
+    });
+}
+
+#[test]
+fn withdraw_report_should_keep_entity_blocked_manually() {
+    ExtBuilder::build_with_space_and_post().execute_with(|| {
+        let entity = blocked_content_entity();
+        autoblock_content_with_one_report();
+
+        // The scope owner confirms the block
+        assert_ok!(_update_entity_status(None, Some(entity.clone()), None, Some(Some(EntityStatus::Blocked))));
+        assert!(!Moderation::is_autoblocked(&entity, SPACE1));
+
+        assert_ok!(_withdraw_report(None, Some(entity.clone()), None));
+
+        assert!(Moderation::report_by_id(REPORT1).is_none());
+        assert_eq!(Moderation::status_by_entity_in_space(&entity, SPACE1), Some(EntityStatus::Blocked));
+    });
+}
+
+#[test]
+fn withdraw_report_should_fail_when_account_has_no_report_in_scope() {
+    ExtBuilder::build_with_space_and_post_then_report().execute_with(|| {
+        // The report was made by the scope owner
+        assert_noop!(_withdraw_report(None, None, None), Error::<Test>::ReportNotFound);
+        assert_noop!(
+            _withdraw_report(Some(Origin::signed(ACCOUNT_SCOPE_OWNER)), None, Some(SPACE2)),
+            Error::<Test>::ReportNotFound
+        );
+    });
+}
@@ -43,6 +43,7 @@ parameter_types! {
       SP::CreatePosts,
 
       SP::UpdateSpace,
+      SP::HideSpace,
       SP::UpdateAnySubspace,
       SP::UpdateAnyPost,
 
@@ -5,6 +5,8 @@ use frame_support::{
   decl_module,
   traits::Get
 };
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::{
   collections::btree_set::BTreeSet,
@@ -17,6 +19,7 @@ use pallet_utils::SpaceId;
 pub mod default_permissions;
 
 #[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum SpacePermission {
   /// Create, update, delete, grant and revoke roles in this space.
   ManageRoles,
@@ -82,11 +85,16 @@ pub enum SpacePermission {
 
   /// Allows to update space settings across different pallets.
   UpdateSpaceSettings,
+
+  /// Hide or unhide this space without updating anything else in it.
+  /// `UpdateSpace` allows this as well.
+  HideSpace,
 }
 
 pub type SpacePermissionSet = BTreeSet<SpacePermission>;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SpacePermissions {
   pub none: Option<SpacePermissionSet>,
   pub everyone: Option<SpacePermissionSet>,
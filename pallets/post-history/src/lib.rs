@@ -1,13 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{decl_module, decl_storage};
+use frame_support::{decl_module, decl_storage, dispatch::DispatchResult, traits::Get};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::Vec;
 use frame_system::{self as system};
 
-use pallet_posts::{PostId, Post, PostUpdate, AfterPostUpdated};
-use pallet_utils::WhoAndWhen;
+use pallet_posts::{PostId, Post, PostUpdate, AfterPostUpdated, BeforePostUpdated};
+use pallet_utils::{Module as Utils, WhoAndWhen, HistoryOverflowPolicy, append_to_bounded_history};
+
+pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct PostHistoryRecord<T: Trait> {
@@ -19,7 +21,13 @@ pub struct PostHistoryRecord<T: Trait> {
 pub trait Trait: system::Trait
     + pallet_utils::Trait
     + pallet_posts::Trait
-{}
+{
+    /// Max number of edit history records stored per post.
+    type MaxHistoryRecordsPerEntity: Get<u32>;
+
+    /// What to do with a new record when the edit history of a post is full.
+    type HistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+}
 
 // This pallet's storage items.
 decl_storage! {
@@ -30,7 +38,10 @@ decl_storage! {
 }
 
 decl_module! {
-  pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+  pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+
+    const MaxHistoryRecordsPerEntity: u32 = T::MaxHistoryRecordsPerEntity::get();
+  }
 }
 
 impl<T: Trait> PostHistoryRecord<T> {
@@ -42,9 +53,23 @@ impl<T: Trait> PostHistoryRecord<T> {
     }
 }
 
+impl<T: Trait> BeforePostUpdated<T> for Module<T> {
+    fn before_post_updated(_editor: T::AccountId, post: &Post<T>) -> DispatchResult {
+        Utils::<T>::ensure_history_not_full(
+            <EditHistory<T>>::decode_len(post.id).unwrap_or_default(),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        )
+    }
+}
+
 impl<T: Trait> AfterPostUpdated<T> for Module<T> {
     fn after_post_updated(sender: T::AccountId, post: &Post<T>, old_data: PostUpdate) {
-        <EditHistory<T>>::mutate(post.id, |ids|
-            ids.push(PostHistoryRecord::<T>::new(sender, old_data)));
+        append_to_bounded_history::<EditHistory<T>, _, _>(
+            post.id,
+            PostHistoryRecord::<T>::new(sender, old_data),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        );
     }
 }
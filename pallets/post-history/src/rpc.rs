@@ -0,0 +1,55 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_posts::PostId;
+use pallet_utils::{Content, SpaceId, history_in_block_range};
+
+use crate::{Module, PostHistoryRecord, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatPostHistoryRecord<AccountId, BlockNumber, Moment> {
+    pub edited_by: AccountId,
+    pub edited_at_block: BlockNumber,
+    pub edited_at_time: Moment,
+
+    pub old_space_id: Option<SpaceId>,
+    pub old_content: Option<Content>,
+    pub old_hidden: Option<bool>,
+}
+
+impl<T: Trait> From<PostHistoryRecord<T>> for FlatPostHistoryRecord<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(record: PostHistoryRecord<T>) -> Self {
+        let PostHistoryRecord { edited, old_data } = record;
+
+        Self {
+            edited_by: edited.account,
+            edited_at_block: edited.block,
+            edited_at_time: edited.time,
+
+            old_space_id: old_data.space_id,
+            old_content: old_data.content,
+            old_hidden: old_data.hidden,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn get_edit_history_in_block_range(
+        post_id: PostId,
+        from_block: T::BlockNumber,
+        to_block: T::BlockNumber,
+        limit: u32,
+    ) -> Vec<FlatPostHistoryRecord<T::AccountId, T::BlockNumber, T::Moment>> {
+        let history = Self::edit_history(post_id);
+
+        history_in_block_range(&history, |record| record.edited.block, from_block, to_block, limit)
+            .iter()
+            .cloned()
+            .map(|record| record.into())
+            .collect()
+    }
+}
@@ -0,0 +1,31 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_posts::{PostId, rpc::{FlatPost, Visibility}};
+use pallet_utils::SpaceId;
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over posts that would otherwise require several storage lookups.
+    pub trait PostsApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        /// Get a bounded thread of replies to a post as `(post_id, parent_id)` links,
+        /// so a client can rebuild the whole tree with a single call.
+        fn post_replies_tree(root_post_id: PostId, max_depth: u32, max_nodes: u32) -> Vec<(PostId, Option<PostId>)>;
+
+        /// Get a page of root posts of a space: up to `limit` posts after the first `offset` ones.
+        fn posts_by_space(space_id: SpaceId, offset: u32, limit: u32) -> Vec<FlatPost<AccountId, BlockNumber, Moment>>;
+
+        /// Get a page of direct replies to a post in the order they were created,
+        /// or in the reverse order if `newest_first`.
+        fn replies_paginated(post_id: PostId, newest_first: bool, offset: u32, limit: u32) -> Vec<FlatPost<AccountId, BlockNumber, Moment>>;
+
+        /// Get whether a post is shown to readers, considering its space and, for a comment,
+        /// its root post. `None` if there is no such post.
+        fn post_effective_visibility(post_id: PostId) -> Option<Visibility>;
+    }
+}
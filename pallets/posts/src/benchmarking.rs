@@ -0,0 +1,110 @@
+//! Benchmarks for the Posts pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+use pallet_utils::mock_functions::valid_content_ipfs;
+
+fn create_space<T: Trait>(owner: &T::AccountId) -> Result<SpaceId, &'static str> {
+    let space_id = Spaces::<T>::next_space_id();
+    Spaces::<T>::create_space(RawOrigin::Signed(owner.clone()).into(), None, None, Content::None, None, None)?;
+    Ok(space_id)
+}
+
+fn add_post<T: Trait>(
+    owner: &T::AccountId,
+    space_id_opt: Option<SpaceId>,
+    extension: PostExtension,
+) -> Result<PostId, &'static str> {
+    let post_id = Module::<T>::next_post_id();
+    Module::<T>::create_post(
+        RawOrigin::Signed(owner.clone()).into(), space_id_opt, extension, valid_content_ipfs(), None
+    )?;
+    Ok(post_id)
+}
+
+benchmarks! {
+    _ { }
+
+    create_post {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let post_id = Module::<T>::next_post_id();
+    }: _(RawOrigin::Signed(caller), Some(space_id), PostExtension::RegularPost, valid_content_ipfs(), None)
+    verify {
+        assert!(Module::<T>::post_by_id(post_id).is_some());
+    }
+
+    // A reply to `d` nested comments. Each of them is read and written once,
+    // so the deepest reply possible is the worst case.
+    create_comment {
+        let d in 0 .. T::MaxCommentDepth::get().saturating_sub(1);
+
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let root_post_id = add_post::<T>(&caller, Some(space_id), PostExtension::RegularPost)?;
+
+        let mut parent_id = None;
+        for _ in 0..d {
+            let comment_ext = PostExtension::Comment(Comment { parent_id, root_post_id });
+            parent_id = Some(add_post::<T>(&caller, None, comment_ext)?);
+        }
+
+        let post_id = Module::<T>::next_post_id();
+        let comment_ext = PostExtension::Comment(Comment { parent_id, root_post_id });
+    }: create_post(RawOrigin::Signed(caller), None, comment_ext, valid_content_ipfs(), None)
+    verify {
+        assert!(Module::<T>::post_by_id(post_id).is_some());
+    }
+
+    create_sharing_post {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let original_post_id = add_post::<T>(&caller, Some(space_id), PostExtension::RegularPost)?;
+
+        let post_id = Module::<T>::next_post_id();
+    }: create_post(RawOrigin::Signed(caller), Some(space_id), PostExtension::SharedPost(original_post_id), Content::None, None)
+    verify {
+        assert_eq!(Module::<T>::shared_post_ids_by_original_post_id(original_post_id), vec![post_id]);
+    }
+
+    update_post {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let post_id = add_post::<T>(&caller, Some(space_id), PostExtension::RegularPost)?;
+
+        let update = PostUpdate {
+            space_id: None,
+            content: Some(Content::None),
+            hidden: Some(true),
+        };
+    }: _(RawOrigin::Signed(caller), post_id, update)
+    verify {
+        assert!(Module::<T>::post_by_id(post_id).unwrap().hidden);
+    }
+
+    move_post {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let new_space_id = create_space::<T>(&caller)?;
+        let post_id = add_post::<T>(&caller, Some(space_id), PostExtension::RegularPost)?;
+    }: _(RawOrigin::Signed(caller), post_id, Some(new_space_id))
+    verify {
+        assert_eq!(Module::<T>::post_by_id(post_id).unwrap().space_id, Some(new_space_id));
+    }
+
+    delete_post {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let original_post_id = add_post::<T>(&caller, Some(space_id), PostExtension::RegularPost)?;
+        let post_id = add_post::<T>(&caller, Some(space_id), PostExtension::SharedPost(original_post_id))?;
+    }: _(RawOrigin::Signed(caller), post_id)
+    verify {
+        assert!(Module::<T>::shared_post_ids_by_original_post_id(original_post_id).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
-use frame_support::dispatch::DispatchResult;
+use frame_support::{dispatch::DispatchResult, weights::Weight};
+use sp_runtime::traits::{One, Saturating};
 
-use pallet_utils::{SpaceId, remove_from_vec};
+use pallet_utils::{SpaceId, remove_from_vec, remove_from_vec_keeping_order};
 
 use super::*;
 
@@ -196,6 +197,48 @@ impl<T: Trait> Module<T> {
         )
     }
 
+    pub fn ensure_account_can_delete_post(
+        editor: &T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        let (permission_to_check, permission_error) =
+            if post.is_owner(&editor) {
+                (SpacePermission::DeleteOwnPosts, Error::<T>::NoPermissionToDeleteOwnPosts)
+            } else {
+                (SpacePermission::DeleteAnyPost, Error::<T>::NoPermissionToDeleteAnyPost)
+            };
+
+        Spaces::ensure_account_has_space_permission(
+          editor.clone(),
+          space,
+          permission_to_check,
+          permission_error.into()
+        )
+    }
+
+    /// Check that `editor` may hide or unhide `post` without updating anything else in it.
+    /// An update permission is not required for this, only a hide one.
+    pub fn ensure_account_can_hide_post(
+        editor: &T::AccountId,
+        post: &Post<T>,
+        space: &Space<T>
+    ) -> DispatchResult {
+        let permission_to_check = match (post.is_comment(), post.is_owner(&editor)) {
+            (true, true) => SpacePermission::HideOwnComments,
+            (true, false) => SpacePermission::HideAnyComment,
+            (false, true) => SpacePermission::HideOwnPosts,
+            (false, false) => SpacePermission::HideAnyPost,
+        };
+
+        Spaces::ensure_account_has_space_permission(
+          editor.clone(),
+          space,
+          permission_to_check,
+          Error::<T>::NoPermissionToUpdateAnyPost.into()
+        )
+    }
+
     /// Check that there is a `Post` with such `post_id` in the storage
     /// or return`PostNotFound` error.
     pub fn ensure_post_exists(post_id: PostId) -> DispatchResult {
@@ -221,7 +264,7 @@ impl<T: Trait> Module<T> {
         PostById::insert(original_post_id, original_post.clone());
         SharedPostIdsByOriginalPostId::mutate(original_post_id, |ids| ids.push(shared_post_id));
 
-        Self::deposit_event(RawEvent::PostShared(account, original_post_id));
+        Self::deposit_event(RawEvent::PostShared(account, original_post_id, shared_post_id));
 
         Ok(())
     }
@@ -309,44 +352,86 @@ impl<T: Trait> Module<T> {
     }
     // TODO: maybe add for_each_reply?
 
+    /// Load the parent comment of a new comment and all of its ancestor comments,
+    /// starting from the parent. Every post is read from the storage only once.
+    fn load_parent_comments(parent_id: PostId) -> Result<Vec<Post<T>>, DispatchError> {
+        let parent_comment = Self::post_by_id(parent_id).ok_or(Error::<T>::UnknownParentComment)?;
+        ensure!(parent_comment.is_comment(), Error::<T>::NotACommentByParentId);
+
+        let max_depth = T::MaxCommentDepth::get() as usize;
+        let mut ancestors = Vec::new();
+        ancestors.push(parent_comment);
+
+        while let Some(PostExtension::Comment(Comment { parent_id: Some(ancestor_id), .. })) =
+            ancestors.last().map(|post| post.extension)
+        {
+            ensure!(ancestors.len() < max_depth, Error::<T>::MaxCommentDepthReached);
+            ancestors.push(Self::require_post(ancestor_id)?);
+        }
+
+        ensure!(ancestors.len() < max_depth, Error::<T>::MaxCommentDepthReached);
+        Ok(ancestors)
+    }
+
+    /// Increment the replies counters of a root post and the ancestors of a new comment.
+    /// The root post is passed in by `create_post`, which already loaded it,
+    /// so only the parent comments are read from the storage here.
     pub(crate) fn create_comment(
         creator: &T::AccountId,
         new_post_id: PostId,
         comment_ext: Comment,
         root_post: &mut Post<T>
     ) -> DispatchResult {
-        let mut commented_post_id = root_post.id;
-
-        if let Some(parent_id) = comment_ext.parent_id {
-            let parent_comment = Self::post_by_id(parent_id).ok_or(Error::<T>::UnknownParentComment)?;
-            ensure!(parent_comment.is_comment(), Error::<T>::NotACommentByParentId);
-
-            let ancestors = Self::get_post_ancestors(parent_id);
-            ensure!(ancestors.len() < T::MaxCommentDepth::get() as usize, Error::<T>::MaxCommentDepthReached);
-
-            commented_post_id = parent_id;
-        }
+        let mut parent_comments = match comment_ext.parent_id {
+            Some(parent_id) => Self::load_parent_comments(parent_id)?,
+            None => Vec::new(),
+        };
+        let commented_post_id = comment_ext.parent_id.unwrap_or(root_post.id);
 
         root_post.inc_replies();
         T::PostScores::score_root_post_on_new_comment(creator.clone(), root_post)?;
+        Spaces::<T>::mutate_space_by_id(root_post.get_space_id()?, Space::inc_comments)?;
 
-        Self::for_each_post_ancestor(commented_post_id, |post| post.inc_replies())?;
+        for ancestor in parent_comments.iter_mut() {
+            ancestor.inc_replies();
+            PostById::insert(ancestor.id, ancestor.clone());
+        }
         PostById::insert(root_post.id, root_post);
         ReplyIdsByPostId::mutate(commented_post_id, |reply_ids| reply_ids.push(new_post_id));
 
         Ok(())
     }
 
+    /// Get the number of shares between a post and its original post, or 0 if it's not a sharing post.
+    pub fn share_depth(post: &Post<T>) -> u32 {
+        if !post.is_sharing_post() {
+            return 0;
+        }
+        // Sharing posts created before the share depth was tracked are shares of originals.
+        Self::share_depth_by_post_id(post.id).max(1)
+    }
+
+    /// Share an original post resolved from `shared_post_id`, which can be a sharing post itself,
+    /// and return the id of the original post.
     pub(crate) fn create_sharing_post(
         creator: &T::AccountId,
         new_post_id: PostId,
-        original_post_id: PostId,
+        shared_post_id: PostId,
         space: &mut Space<T>
-    ) -> DispatchResult {
-        let original_post = &mut Self::post_by_id(original_post_id)
+    ) -> Result<PostId, DispatchError> {
+        ensure!(!T::SpaceSettingsProvider::are_shares_disabled(space.id), Error::<T>::SharesNotAllowedInThisSpace);
+
+        let shared_post = Self::post_by_id(shared_post_id)
             .ok_or(Error::<T>::OriginalPostNotFound)?;
 
-        ensure!(!original_post.is_sharing_post(), Error::<T>::CannotShareSharingPost);
+        let new_share_depth = Self::share_depth(&shared_post).saturating_add(1);
+        ensure!(new_share_depth <= T::MaxShareDepth::get(), Error::<T>::CannotShareSharingPost);
+
+        let original_post = &mut match shared_post.extension {
+            PostExtension::SharedPost(original_post_id) =>
+                Self::post_by_id(original_post_id).ok_or(Error::<T>::OriginalPostNotFound)?,
+            _ => shared_post,
+        };
 
         // Check if it's allowed to share a post from the space of original post.
         Spaces::ensure_account_has_space_permission(
@@ -358,7 +443,33 @@ impl<T: Trait> Module<T> {
 
         space.inc_posts();
 
-        Self::share_post(creator.clone(), original_post, new_post_id)
+        Self::share_post(creator.clone(), original_post, new_post_id)?;
+        ShareDepthByPostId::insert(new_post_id, new_share_depth);
+
+        Ok(original_post.id)
+    }
+
+    /// Undo `create_sharing_post`: unshare the original post, revert the score given for
+    /// this share and remove the sharing post from its space, if it is still in one.
+    /// The sharing post becomes a regular post, so it cannot be unshared twice.
+    pub(crate) fn delete_sharing_post(post: Post<T>, original_post_id: PostId) -> DispatchResult {
+        let original_post = &mut Self::post_by_id(original_post_id)
+            .ok_or(Error::<T>::OriginalPostNotFound)?;
+
+        SharedPostIdsByOriginalPostId::mutate(original_post_id, |ids| remove_from_vec(ids, post.id));
+        ShareDepthByPostId::remove(post.id);
+
+        original_post.dec_shares();
+        // The score was given on behalf of the share author, not its current owner.
+        T::PostScores::score_post_on_share_removed(post.created.account.clone(), original_post)?;
+        PostById::insert(original_post_id, original_post.clone());
+
+        if post.space_id.is_some() {
+            Self::delete_post_from_space(post.id)?;
+        }
+        Self::mutate_post_by_id(post.id, |post| post.extension = PostExtension::RegularPost)?;
+
+        Ok(())
     }
 
     fn mutate_posts_count_on_space<F: FnMut(&mut u32) + Copy> (
@@ -387,11 +498,12 @@ impl<T: Trait> Module<T> {
             UtilsError::<T>::AccountIsBlocked
         );
         Spaces::ensure_account_has_space_permission(
-            editor,
+            editor.clone(),
             &new_space,
             SpacePermission::CreatePosts,
             Error::<T>::NoPermissionToCreatePosts.into()
         )?;
+        Self::ensure_creator_reputation_in_space(&editor, &new_space, false)?;
         ensure!(
             T::IsPostBlocked::is_allowed_post(post.id, new_space_id),
             UtilsError::<T>::PostIsBlocked
@@ -400,6 +512,14 @@ impl<T: Trait> Module<T> {
             T::IsContentBlocked::is_allowed_content(post.content.clone(), new_space_id),
             UtilsError::<T>::ContentIsBlocked
         );
+        Self::ensure_content_not_duplicated_in_space(&post.content, new_space_id)?;
+
+        if post.is_sharing_post() {
+            ensure!(
+                !T::SpaceSettingsProvider::are_shares_disabled(new_space_id),
+                Error::<T>::SharesNotAllowedInThisSpace
+            );
+        }
 
         match post.extension {
             PostExtension::RegularPost | PostExtension::SharedPost(_) => {
@@ -413,13 +533,13 @@ impl<T: Trait> Module<T> {
                         |counter| *counter = counter.saturating_sub(1)
                     )?;
 
-                    // Decrease a score on the old space
-                    Spaces::<T>::mutate_space_by_id(
-                        old_space_id,
-                        |space| space.score = space.score.saturating_sub(post.score)
-                    )?;
+                    // Decrease a score and the number of comments on the old space
+                    Spaces::<T>::mutate_space_by_id(old_space_id, |space| {
+                        space.score = space.score.saturating_sub(post.score);
+                        space.comments_count = space.comments_count.saturating_sub(post.replies_count.into());
+                    })?;
 
-                    PostIdsBySpaceId::mutate(old_space_id, |post_ids| remove_from_vec(post_ids, post.id));
+                    PostIdsBySpaceId::mutate(old_space_id, |post_ids| remove_from_vec_keeping_order(post_ids, post.id));
                 }
 
                 // Increase the number of posts on the new space
@@ -429,11 +549,11 @@ impl<T: Trait> Module<T> {
                     |counter| *counter = counter.saturating_add(1)
                 )?;
 
-                // Increase a score on the new space
-                Spaces::<T>::mutate_space_by_id(
-                    new_space_id,
-                    |space| space.score = space.score.saturating_add(post.score)
-                )?;
+                // Increase a score and the number of comments on the new space
+                Spaces::<T>::mutate_space_by_id(new_space_id, |space| {
+                    space.score = space.score.saturating_add(post.score);
+                    space.comments_count = space.comments_count.saturating_add(post.replies_count.into());
+                })?;
 
                 PostIdsBySpaceId::mutate(new_space_id, |post_ids| post_ids.push(post.id));
 
@@ -466,7 +586,12 @@ impl<T: Trait> Module<T> {
             PostById::<T>::insert(root_post.id, root_post.clone());
             Self::for_each_post_ancestor(parent_id, dec_replies_count)?;
 
-            // Subtract the weight of CreateComment from the root post and its space
+            if let Some(space_id) = root_post.space_id {
+                Spaces::<T>::mutate_space_by_id(space_id, Space::dec_comments)?;
+            }
+
+            // Subtract the weight of CreateComment from the root post and its space.
+            // The score was given on behalf of the comment author, not its current owner.
             T::PostScores::score_root_post_on_new_comment(post.created.account.clone(), root_post)?;
             let replies = Self::get_post_replies(post_id)?;
             for reply in replies.iter() {
@@ -484,13 +609,13 @@ impl<T: Trait> Module<T> {
                 |counter| *counter = counter.saturating_sub(1)
             )?;
 
-            Spaces::<T>::mutate_space_by_id(
-                space_id,
-                |space| space.score = space.score.saturating_sub(post.score)
-            )?;
+            Spaces::<T>::mutate_space_by_id(space_id, |space| {
+                space.score = space.score.saturating_sub(post.score);
+                space.comments_count = space.comments_count.saturating_sub(post.replies_count.into());
+            })?;
 
             post.space_id = None;
-            PostIdsBySpaceId::mutate(space_id, |post_ids| remove_from_vec(post_ids, post_id));
+            PostIdsBySpaceId::mutate(space_id, |post_ids| remove_from_vec_keeping_order(post_ids, post_id));
         }
 
         PostById::<T>::insert(post.id, post);
@@ -498,6 +623,75 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Weight of checking and updating `ContentToPostIds` for a post. In the worst case
+    /// every post with the same content is read to find a duplicate in a space.
+    pub fn content_index_weight() -> Weight {
+        T::DbWeight::get().reads_writes(T::MaxPostsPerContent::get() as u64 + 2, 2)
+    }
+
+    /// Ensure that a space has no post with the same IPFS CID, if the space rejects duplicate content.
+    pub fn ensure_content_not_duplicated_in_space(content: &Content, space_id: SpaceId) -> DispatchResult {
+        if let Content::IPFS(cid) = content {
+            if T::SpaceSettingsProvider::is_duplicate_content_rejected(space_id) {
+                let is_duplicate = Self::post_ids_by_content(cid).into_iter()
+                    .filter_map(Self::post_by_id)
+                    .any(|post| post.space_id == Some(space_id));
+
+                ensure!(!is_duplicate, Error::<T>::DuplicateContentInSpace);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensure that a creator of a post or a comment has at least the minimum reputation
+    /// set in the space for it. The space owner can always post and comment in their space.
+    pub fn ensure_creator_reputation_in_space(
+        creator: &T::AccountId,
+        space: &Space<T>,
+        is_comment: bool,
+    ) -> DispatchResult {
+        if space.is_owner(creator) {
+            return Ok(());
+        }
+
+        let (min_reputation, error) = if is_comment {
+            (T::SpaceSettingsProvider::min_reputation_to_comment(space.id), Error::<T>::ReputationTooLowToComment)
+        } else {
+            (T::SpaceSettingsProvider::min_reputation_to_post(space.id), Error::<T>::ReputationTooLowToPost)
+        };
+
+        if let Some(min_reputation) = min_reputation {
+            ensure!(T::ReputationProvider::reputation_of(creator) >= min_reputation, error);
+        }
+        Ok(())
+    }
+
+    /// Add a post to `ContentToPostIds` if its content is an IPFS CID. A post is not indexed
+    /// if there are already `MaxPostsPerContent` posts with this CID, so that no one can stop
+    /// others from posting a CID by posting it many times.
+    pub(crate) fn add_post_to_content_index(post_id: PostId, content: &Content) {
+        if let Content::IPFS(cid) = content {
+            ContentToPostIds::mutate(cid, |post_ids| {
+                if (post_ids.len() as u32) < T::MaxPostsPerContent::get() {
+                    post_ids.push(post_id);
+                }
+            });
+        }
+    }
+
+    pub(crate) fn remove_post_from_content_index(post_id: PostId, content: &Content) {
+        if let Content::IPFS(cid) = content {
+            let mut post_ids = Self::post_ids_by_content(cid);
+            remove_from_vec(&mut post_ids, post_id);
+
+            if post_ids.is_empty() {
+                ContentToPostIds::remove(cid);
+            } else {
+                ContentToPostIds::insert(cid, post_ids);
+            }
+        }
+    }
+
     /// Rewrite ancestor counters when Post hidden status changes
     /// Warning: This will affect storage state!
     pub(crate) fn update_counters_on_comment_hidden_change(
@@ -519,4 +713,33 @@ impl<T: Trait> Module<T> {
 
         Ok(())
     }
+
+    /// Remove up to `MaxIdempotencyKeysRemovedPerBlock` keys that expire at `block_number`
+    /// and move the rest of them to the next block. Returns the weight of the removal.
+    pub(crate) fn remove_expired_idempotency_keys(block_number: T::BlockNumber) -> Weight {
+        let mut keys = <IdempotencyKeysByExpireBlock<T>>::take(block_number);
+        let (mut reads, mut writes) = (1, 1);
+
+        let max_keys = T::MaxIdempotencyKeysRemovedPerBlock::get() as usize;
+        if keys.len() > max_keys {
+            let keys_left = keys.split_off(max_keys);
+            <IdempotencyKeysByExpireBlock<T>>::mutate(
+                block_number.saturating_add(One::one()),
+                |next_keys| next_keys.extend(keys_left)
+            );
+            reads += 1;
+            writes += 1;
+        }
+
+        for key in &keys {
+            // The key could be used again after it expired, then it is live until a later block.
+            if Spaces::<T>::live_idempotency_key_entity(Self::used_idempotency_key(key)).is_none() {
+                <UsedIdempotencyKeys<T>>::remove(key);
+                writes += 1;
+            }
+            reads += 1;
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
 }
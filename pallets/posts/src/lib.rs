@@ -3,30 +3,46 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, fail,
-    dispatch::{DispatchError, DispatchResult}, ensure, traits::Get,
+    dispatch::{DispatchError, DispatchResult}, ensure, traits::Get, weights::Weight,
 };
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use df_traits::moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked};
+use df_traits::{
+    ContentValidator, ReputationProvider, SpacePostsProvider, SpaceSettingsProvider,
+    moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked},
+};
 use pallet_permissions::SpacePermission;
 use pallet_spaces::{Module as Spaces, Space, SpaceById};
 use pallet_utils::{
     Module as Utils, Error as UtilsError,
-    SpaceId, WhoAndWhen, Content
+    PalletVersion, SpaceId, WhoAndWhen, Content
 };
 
 pub mod functions;
+pub mod migrations;
+pub mod rpc;
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
 
 pub type PostId = u64;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Post<T: Trait> {
     pub id: PostId,
+    /// The author of this post. It never changes, even if the post gets a new owner.
+    /// Scores of the actions done with this post (e.g. creating a comment) are given
+    /// on behalf of the author.
     pub created: WhoAndWhen<T>,
     pub updated: Option<WhoAndWhen<T>>,
 
+    /// The current owner of this post. Permissions to update the post
+    /// (e.g. `UpdateOwnPosts` and `UpdateOwnComments`) are checked against the owner.
     pub owner: T::AccountId,
 
     pub extension: PostExtension,
@@ -56,6 +72,7 @@ pub struct PostUpdate {
 }
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PostExtension {
     RegularPost,
     Comment(Comment),
@@ -63,6 +80,7 @@ pub enum PostExtension {
 }
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Comment {
     pub parent_id: Option<PostId>,
     pub root_post_id: PostId,
@@ -85,15 +103,39 @@ pub trait Trait: system::Trait
     /// Max comments depth
     type MaxCommentDepth: Get<u32>;
 
+    /// Max depth of a chain of shares, where 1 means that only original posts can be shared.
+    type MaxShareDepth: Get<u32>;
+
     type PostScores: PostScores<Self>;
 
+    /// Called before a post is edited or moved, e.g. to refuse edits when its history is full.
+    type BeforePostUpdated: BeforePostUpdated<Self>;
+
     type AfterPostUpdated: AfterPostUpdated<Self>;
 
+    /// Called when a post, a comment or a share is created, e.g. to count the posts of an account.
+    type AfterPostCreated: AfterPostCreated<Self>;
+
     type IsPostBlocked: IsPostBlocked<PostId>;
+
+    /// Settings of the space a post is created in, e.g. whether shares into it are disabled.
+    type SpaceSettingsProvider: SpaceSettingsProvider;
+
+    /// Max number of posts with the same IPFS CID in `ContentToPostIds`, so the list of them is bounded.
+    /// Posts with this CID above the limit are created, but not indexed.
+    type MaxPostsPerContent: Get<u32>;
+
+    /// Reputation of post creators, checked against the minimum reputation set in a space.
+    /// With `()`, every account has zero reputation.
+    type ReputationProvider: ReputationProvider<Self::AccountId>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 pub trait PostScores<T: Trait> {
     fn score_post_on_new_share(account: T::AccountId, original_post: &mut Post<T>) -> DispatchResult;
+    fn score_post_on_share_removed(account: T::AccountId, original_post: &mut Post<T>) -> DispatchResult;
     fn score_root_post_on_new_comment(account: T::AccountId, root_post: &mut Post<T>) -> DispatchResult;
 }
 
@@ -101,19 +143,41 @@ impl<T: Trait> PostScores<T> for () {
     fn score_post_on_new_share(_account: T::AccountId, _original_post: &mut Post<T>) -> DispatchResult {
         Ok(())
     }
+    fn score_post_on_share_removed(_account: T::AccountId, _original_post: &mut Post<T>) -> DispatchResult {
+        Ok(())
+    }
     fn score_root_post_on_new_comment(_account: T::AccountId, _root_post: &mut Post<T>) -> DispatchResult {
         Ok(())
     }
 }
 
+pub trait BeforePostUpdated<T: Trait> {
+    fn before_post_updated(editor: T::AccountId, post: &Post<T>) -> DispatchResult;
+}
+
+impl<T: Trait> BeforePostUpdated<T> for () {
+    fn before_post_updated(_editor: T::AccountId, _post: &Post<T>) -> DispatchResult {
+        Ok(())
+    }
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(10)]
 pub trait AfterPostUpdated<T: Trait> {
     fn after_post_updated(account: T::AccountId, post: &Post<T>, old_data: PostUpdate);
 }
 
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterPostCreated<T: Trait> {
+    fn after_post_created(post: &Post<T>);
+}
+
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as PostsModule {
+        /// The version of the storage layout of this pallet, see `pallet_spaces::StorageVersion`.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
         pub NextPostId get(fn next_post_id): PostId = 1;
 
         pub PostById get(fn post_by_id): map hasher(twox_64_concat) PostId => Option<Post<T>>;
@@ -127,6 +191,26 @@ decl_storage! {
         // TODO rename 'Shared...' to 'Sharing...'
         pub SharedPostIdsByOriginalPostId get(fn shared_post_ids_by_original_post_id):
             map hasher(twox_64_concat) PostId => Vec<PostId>;
+
+        /// How many shares there are between a sharing post (key) and its original post.
+        pub ShareDepthByPostId get(fn share_depth_by_post_id):
+            map hasher(twox_64_concat) PostId => u32;
+
+        /// A post created by an account with an idempotency key, and the block it was created at.
+        /// A key is removed at the start of the block it expires at, or at a later block
+        /// if more than `MaxIdempotencyKeysRemovedPerBlock` keys expire at once.
+        pub UsedIdempotencyKeys get(fn used_idempotency_key):
+            map hasher(blake2_128_concat) (T::AccountId, T::Hash) => Option<(PostId, T::BlockNumber)>;
+
+        /// Keys of `UsedIdempotencyKeys` that expire at a block (key).
+        pub IdempotencyKeysByExpireBlock get(fn idempotency_keys_by_expire_block):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
+
+        /// Ids of posts whose content is an IPFS CID (key). Used to find copies of the same
+        /// content within a space and to moderate all posts with this content at once.
+        /// Holds up to `MaxPostsPerContent` posts per CID, the ones that got this content first.
+        pub ContentToPostIds get(fn post_ids_by_content):
+            map hasher(blake2_128_concat) Vec<u8> => Vec<PostId>;
     }
 }
 
@@ -134,11 +218,17 @@ decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
     {
-        PostCreated(AccountId, PostId),
-        PostUpdated(AccountId, PostId),
+        /// A post was created in a space (the space of its root post for comments).
+        /// The extension tells its kind, and the parent or original post if any.
+        PostCreated(AccountId, PostId, Option<SpaceId>, PostExtension),
+        PostUpdated(AccountId, PostId, Option<SpaceId>),
         PostDeleted(AccountId, PostId),
-        PostShared(AccountId, PostId),
-        PostMoved(AccountId, PostId),
+        PostShared(AccountId, /* original post */ PostId, /* shared post */ PostId),
+        /// A post was moved from one space to another. `None` means no space.
+        PostMoved(AccountId, PostId, /* from */ Option<SpaceId>, /* to */ Option<SpaceId>),
+        /// A post was not created, because the account created this post
+        /// with the same idempotency key recently.
+        PostAlreadyCreated(AccountId, PostId),
     }
 );
 
@@ -166,7 +256,7 @@ decl_error! {
 
         /// Original post not found when sharing.
         OriginalPostNotFound,
-        /// Cannot share a post that shares another post.
+        /// Cannot share a post, because the chain of shares would exceed the max share depth.
         CannotShareSharingPost,
 
         // Comment related errors:
@@ -179,7 +269,7 @@ decl_error! {
         CannotUpdateSpaceIdOnComment,
         /// Max comment depth reached.
         MaxCommentDepthReached,
-        /// Only comment author can update his comment.
+        /// Only the current owner of a comment can update it.
         NotACommentAuthor,
         /// Post extension is not a comment.
         NotComment,
@@ -198,6 +288,23 @@ decl_error! {
         NoPermissionToUpdateOwnPosts,
         /// A comment owner is not allowed to update their own comments in this space.
         NoPermissionToUpdateOwnComments,
+        /// The owner of this space disabled shares into it.
+        SharesNotAllowedInThisSpace,
+        /// Only sharing posts can be deleted for now.
+        OnlySharingPostsCanBeDeleted,
+        /// A post owner is not allowed to delete their own posts in this space.
+        NoPermissionToDeleteOwnPosts,
+        /// User is not a post author and has no permission to delete posts in this space.
+        NoPermissionToDeleteAnyPost,
+
+        // Content related errors:
+
+        /// This space rejects duplicate content and already has a post with the same IPFS CID.
+        DuplicateContentInSpace,
+        /// Reputation of the account is below the minimum to create posts in this space.
+        ReputationTooLowToPost,
+        /// Reputation of the account is below the minimum to comment in this space.
+        ReputationTooLowToComment,
     }
 }
 
@@ -206,36 +313,68 @@ decl_module! {
 
     const MaxCommentDepth: u32 = T::MaxCommentDepth::get();
 
+    const MaxShareDepth: u32 = T::MaxShareDepth::get();
+
+    const MaxPostsPerContent: u32 = T::MaxPostsPerContent::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(8, 8)]
+    /// Remove idempotency keys that expire at this block.
+    fn on_initialize(block_number: T::BlockNumber) -> Weight {
+      Self::remove_expired_idempotency_keys(block_number)
+    }
+
+    /// Create a post, a comment or a share. The `idempotency_key` works
+    /// the same way as in `Spaces::create_space` and emits `PostAlreadyCreated`.
+    #[weight = match extension {
+      PostExtension::RegularPost => <T as Trait>::WeightInfo::create_post(),
+      PostExtension::Comment(_) => <T as Trait>::WeightInfo::create_comment(T::MaxCommentDepth::get().saturating_sub(1)),
+      PostExtension::SharedPost(_) => <T as Trait>::WeightInfo::create_sharing_post(),
+    }.saturating_add(T::DbWeight::get().reads_writes(3, 4))
+      .saturating_add(Module::<T>::content_index_weight())
+      .saturating_add(<T as Trait>::WeightInfo::content_bytes(content.byte_len()))]
     pub fn create_post(
       origin,
       space_id_opt: Option<SpaceId>,
       extension: PostExtension,
-      content: Content
+      content: Content,
+      idempotency_key: Option<T::Hash>
     ) -> DispatchResult {
       let creator = ensure_signed(origin)?;
 
-      Utils::<T>::is_valid_content(content.clone())?;
+      if let Some(key) = idempotency_key {
+        if let Some(post_id) = Spaces::<T>::live_idempotency_key_entity(Self::used_idempotency_key((creator.clone(), key))) {
+          Self::deposit_event(RawEvent::PostAlreadyCreated(creator, post_id));
+          return Ok(());
+        }
+      }
+
+      T::ContentValidator::is_valid_content(content.clone())?;
 
       let new_post_id = Self::next_post_id();
-      let new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
+      let next_post_id = Utils::<T>::next_id(new_post_id)?;
+      let mut new_post: Post<T> = Post::new(new_post_id, creator.clone(), space_id_opt, extension, content.clone());
 
-      // Get space from either space_id_opt or Comment if a comment provided
-      let space = &mut new_post.get_space()?;
+      let root_post = &mut new_post.get_root_post()?;
+      ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
+
+      // Get space from either space_id_opt or Comment if a comment provided.
+      // The root post is read once, as a comment also needs it to be created.
+      let space = &mut root_post.get_space()?;
       ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
 
+      if !new_post.is_comment() {
+        Spaces::ensure_reserved_space_owner(&creator, space)?;
+        Self::ensure_content_not_duplicated_in_space(&new_post.content, space.id)?;
+      }
+
       ensure!(T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
       ensure!(T::IsContentBlocked::is_allowed_content(content, space.id), UtilsError::<T>::ContentIsBlocked);
 
-      let root_post = &mut new_post.get_root_post()?;
-      ensure!(!root_post.hidden, Error::<T>::CannotCreateInHiddenScope);
-
       // Check whether account has permission to create Post (by extension)
       let mut permission_to_check = SpacePermission::CreatePosts;
       let mut error_on_permission_failed = Error::<T>::NoPermissionToCreatePosts;
@@ -252,9 +391,15 @@ decl_module! {
         error_on_permission_failed.into()
       )?;
 
+      Self::ensure_creator_reputation_in_space(&creator, space, new_post.is_comment())?;
+
       match extension {
         PostExtension::RegularPost => space.inc_posts(),
-        PostExtension::SharedPost(post_id) => Self::create_sharing_post(&creator, new_post_id, post_id, space)?,
+        PostExtension::SharedPost(post_id) => {
+          // A share of a sharing post should point to the original post.
+          let original_post_id = Self::create_sharing_post(&creator, new_post_id, post_id, space)?;
+          new_post.extension = PostExtension::SharedPost(original_post_id);
+        },
         PostExtension::Comment(comment_ext) => Self::create_comment(&creator, new_post_id, comment_ext, root_post)?,
       }
 
@@ -263,14 +408,27 @@ decl_module! {
         PostIdsBySpaceId::mutate(space.id, |ids| ids.push(new_post_id));
       }
 
+      T::AfterPostCreated::after_post_created(&new_post);
+
+      Self::add_post_to_content_index(new_post_id, &new_post.content);
+
+      let new_post_extension = new_post.extension;
       PostById::insert(new_post_id, new_post);
-      NextPostId::mutate(|n| { *n += 1; });
+      NextPostId::put(next_post_id);
+
+      if let Some(key) = idempotency_key {
+        let now = <system::Module<T>>::block_number();
+        <UsedIdempotencyKeys<T>>::insert((creator.clone(), key), (new_post_id, now));
+        <IdempotencyKeysByExpireBlock<T>>::append(Spaces::<T>::idempotency_key_expire_block(now), (creator.clone(), key));
+      }
 
-      Self::deposit_event(RawEvent::PostCreated(creator, new_post_id));
+      Self::deposit_event(RawEvent::PostCreated(creator, new_post_id, Some(space.id), new_post_extension));
       Ok(())
     }
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(5, 3)]
+    #[weight = <T as Trait>::WeightInfo::update_post()
+      .saturating_add(Module::<T>::content_index_weight())
+      .saturating_add(<T as Trait>::WeightInfo::content_bytes(update.content.as_ref().map_or(0, Content::byte_len)))]
     pub fn update_post(origin, post_id: PostId, update: PostUpdate) -> DispatchResult {
       let editor = ensure_signed(origin)?;
 
@@ -285,15 +443,28 @@ decl_module! {
 
       if let Some(space) = &space_opt {
         ensure!(T::IsAccountBlocked::is_allowed_account(editor.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
-        Self::ensure_account_can_update_post(&editor, &post, space)?;
+
+        // An update of `hidden` alone is allowed with one of the narrower `Hide*` permissions.
+        let is_hidden_update_only =
+          update.hidden.is_some() &&
+          update.content.is_none();
+
+        let can_hide_post = is_hidden_update_only &&
+          Self::ensure_account_can_hide_post(&editor, &post, space).is_ok();
+
+        if !can_hide_post {
+          Self::ensure_account_can_update_post(&editor, &post, space)?;
+        }
       }
 
+      T::BeforePostUpdated::before_post_updated(editor.clone(), &post)?;
+
       let mut is_update_applied = false;
       let mut old_data = PostUpdate::default();
 
       if let Some(content) = update.content {
         if content != post.content {
-          Utils::<T>::is_valid_content(content.clone())?;
+          T::ContentValidator::is_valid_content(content.clone())?;
 
           if let Some(space) = &space_opt {
             ensure!(
@@ -302,6 +473,10 @@ decl_module! {
             );
           }
 
+          if let Some(space_id) = post.space_id {
+            Self::ensure_content_not_duplicated_in_space(&content, space_id)?;
+          }
+
           old_data.content = Some(post.content.clone());
           post.content = content;
           is_update_applied = true;
@@ -338,15 +513,20 @@ decl_module! {
           <SpaceById<T>>::insert(space.id, space);
         }
 
+        if let Some(old_content) = &old_data.content {
+          Self::remove_post_from_content_index(post.id, old_content);
+          Self::add_post_to_content_index(post.id, &post.content);
+        }
+
         <PostById<T>>::insert(post.id, post.clone());
         T::AfterPostUpdated::after_post_updated(editor.clone(), &post, old_data);
 
-        Self::deposit_event(RawEvent::PostUpdated(editor, post_id));
+        Self::deposit_event(RawEvent::PostUpdated(editor, post_id, post.try_get_space_id()));
       }
       Ok(())
     }
 
-    #[weight = T::DbWeight::get().reads(1) + 50_000]
+    #[weight = <T as Trait>::WeightInfo::move_post().saturating_add(Module::<T>::content_index_weight())]
     pub fn move_post(origin, post_id: PostId, new_space_id: Option<SpaceId>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -360,6 +540,8 @@ decl_module! {
         post.ensure_owner(&who)?;
       }
 
+      T::BeforePostUpdated::before_post_updated(who.clone(), &post)?;
+
       let old_space_id = post.space_id;
 
       if let Some(space_id) = new_space_id {
@@ -376,8 +558,60 @@ decl_module! {
 
       T::AfterPostUpdated::after_post_updated(who.clone(), &post, historical_data);
 
-      Self::deposit_event(RawEvent::PostMoved(who, post_id));
+      Self::deposit_event(RawEvent::PostMoved(who, post_id, old_space_id, new_space_id));
+      Ok(())
+    }
+
+    /// Delete a sharing post: remove it from its space and unshare the original post,
+    /// which reverts its `shares_count` and the score given for this share.
+    /// The post itself stays in storage as a regular post without a space.
+    #[weight = <T as Trait>::WeightInfo::delete_post().saturating_add(Module::<T>::content_index_weight())]
+    pub fn delete_post(origin, post_id: PostId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let post = Self::require_post(post_id)?;
+
+      let original_post_id = match post.extension {
+        PostExtension::SharedPost(original_post_id) => original_post_id,
+        _ => fail!(Error::<T>::OnlySharingPostsCanBeDeleted),
+      };
+
+      if let Some(space) = post.try_get_space() {
+        Self::ensure_account_can_delete_post(&who, &post, &space)?;
+      } else {
+        post.ensure_owner(&who)?;
+      }
+
+      let content = post.content.clone();
+      Self::delete_sharing_post(post, original_post_id)?;
+      Self::remove_post_from_content_index(post_id, &content);
+
+      Self::deposit_event(RawEvent::PostDeleted(who, post_id));
       Ok(())
     }
   }
 }
+
+impl<T: Trait> SpacePostsProvider for Module<T> {
+    fn posts_count(space_id: SpaceId) -> u32 {
+        Self::post_ids_by_space_id(space_id).len() as u32
+    }
+
+    fn hidden_posts_count(space_id: SpaceId, after_post_id: Option<PostId>, limit: u32) -> (u32, Option<PostId>) {
+        let mut post_ids: Vec<PostId> = Self::post_ids_by_space_id(space_id)
+            .into_iter()
+            .filter(|post_id| after_post_id.map_or(true, |after_id| *post_id > after_id))
+            .collect();
+        post_ids.sort_unstable();
+
+        let has_more_posts = post_ids.len() > limit as usize;
+        post_ids.truncate(limit as usize);
+
+        let hidden_posts_count = post_ids.iter()
+            .filter_map(|post_id| Self::post_by_id(post_id))
+            .filter(|post| post.hidden)
+            .count() as u32;
+
+        (hidden_posts_count, post_ids.last().copied().filter(|_| has_more_posts))
+    }
+}
@@ -0,0 +1,98 @@
+//! Storage migrations of the Posts pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use frame_support::{
+    traits::Get,
+    weights::Weight,
+    IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_std::{cell::Cell, collections::btree_map::BTreeMap, prelude::*};
+
+use pallet_spaces::Module as Spaces;
+use pallet_utils::{Content, PalletVersion};
+
+use crate::{
+    ContentToPostIds, IdempotencyKeysByExpireBlock, Module, PostById, PostId, StorageVersion, Trait,
+    UsedIdempotencyKeys,
+};
+
+/// The crate version that added `ContentToPostIds`.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that added `IdempotencyKeysByExpireBlock`.
+pub const V0_8_0: PalletVersion = PalletVersion { major: 0, minor: 8, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_8_0;
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    }
+
+    if should_migrate_to::<T>(V0_8_0) {
+        weight = weight.saturating_add(migrate_to_v0_8_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Index existing posts by their IPFS CID. Like new posts, up to `MaxPostsPerContent` posts
+/// with the same CID are indexed, the ones with the lowest ids.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let mut reads = 0u64;
+    let mut post_ids_by_cid = BTreeMap::<Vec<u8>, Vec<PostId>>::new();
+
+    for (post_id, post) in PostById::<T>::iter() {
+        reads = reads.saturating_add(1);
+
+        if let Content::IPFS(cid) = post.content {
+            post_ids_by_cid.entry(cid).or_insert_with(Vec::new).push(post_id);
+        }
+    }
+
+    let max_posts_per_content = T::MaxPostsPerContent::get() as usize;
+    let writes = post_ids_by_cid.len() as u64;
+
+    for (cid, mut post_ids) in post_ids_by_cid {
+        post_ids.sort_unstable();
+        post_ids.truncate(max_posts_per_content);
+        ContentToPostIds::insert(cid, post_ids);
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
+
+/// Remove expired idempotency keys and schedule the removal of the live ones.
+pub fn migrate_to_v0_8_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    UsedIdempotencyKeys::<T>::translate::<(PostId, T::BlockNumber), _>(|key, used_key| {
+        migrated.set(migrated.get() + 1);
+
+        let used_at = used_key.1;
+        Spaces::<T>::live_idempotency_key_entity(Some(used_key))
+            .map(|post_id| {
+                IdempotencyKeysByExpireBlock::<T>::append(Spaces::<T>::idempotency_key_expire_block(used_at), key);
+                (post_id, used_at)
+            })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get().saturating_mul(2))
+}
@@ -0,0 +1,219 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use frame_support::StorageMap;
+use sp_runtime::RuntimeDebug;
+use sp_std::{prelude::*, vec};
+
+use pallet_utils::{Content, SpaceId};
+
+use pallet_spaces::Module as Spaces;
+
+use crate::{Module, Post, PostById, PostExtension, PostId, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatPost<AccountId, BlockNumber, Moment> {
+    pub id: PostId,
+
+    pub created_by: AccountId,
+    pub created_at_block: BlockNumber,
+    pub created_at_time: Moment,
+
+    pub updated_by: Option<AccountId>,
+    pub updated_at_block: Option<BlockNumber>,
+    pub updated_at_time: Option<Moment>,
+
+    pub owner: AccountId,
+    /// Whether the post is still owned by the account that created it.
+    pub is_original_owner: bool,
+
+    pub extension: PostExtension,
+
+    pub space_id: Option<SpaceId>,
+    pub content: Content,
+    pub hidden: bool,
+
+    pub replies_count: u16,
+    pub hidden_replies_count: u16,
+
+    pub shares_count: u16,
+    pub upvotes_count: u16,
+    pub downvotes_count: u16,
+
+    pub score: i32,
+}
+
+/// Whether a post is shown to readers and, if not, what hides it.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Visibility {
+    Visible,
+    /// The post itself is hidden.
+    HiddenSelf,
+    /// The space of the post (of its root post, for a comment) is hidden.
+    HiddenBySpace,
+    /// The post is a comment under a hidden root post.
+    HiddenByRootPost,
+}
+
+impl<T: Trait> From<Post<T>> for FlatPost<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(post: Post<T>) -> Self {
+        let Post {
+            id, created, updated, owner,
+            extension, space_id, content, hidden,
+            replies_count, hidden_replies_count,
+            shares_count, upvotes_count, downvotes_count,
+            score
+        } = post;
+
+        let is_original_owner = owner == created.account;
+
+        Self {
+            id,
+
+            created_by: created.account,
+            created_at_block: created.block,
+            created_at_time: created.time,
+
+            updated_by: updated.clone().map(|x| x.account),
+            updated_at_block: updated.clone().map(|x| x.block),
+            updated_at_time: updated.map(|x| x.time),
+
+            owner,
+            is_original_owner,
+
+            extension,
+
+            space_id,
+            content,
+            hidden,
+
+            replies_count,
+            hidden_replies_count,
+
+            shares_count,
+            upvotes_count,
+            downvotes_count,
+
+            score,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Get up to `limit` root posts of a space in the order they were created,
+    /// skipping the first `offset` of them.
+    pub fn get_posts_by_space(
+        space_id: SpaceId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber, T::Moment>> {
+        Self::post_ids_by_space_id(space_id)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .filter_map(Self::post_by_id)
+            .map(|post| post.into())
+            .collect()
+    }
+
+    /// Resolve whether a post is visible, checking the post, then its root post
+    /// if it is a comment, then the space. Returns `None` if there is no such post.
+    pub fn get_post_effective_visibility(post_id: PostId) -> Option<Visibility> {
+        let post = Self::post_by_id(post_id)?;
+        if post.hidden {
+            return Some(Visibility::HiddenSelf);
+        }
+
+        let root_post = match post.get_root_post() {
+            Ok(root_post) => root_post,
+            // A comment whose root post is gone cannot be shown in a thread.
+            Err(_) => return Some(Visibility::HiddenByRootPost),
+        };
+        if root_post.hidden {
+            return Some(Visibility::HiddenByRootPost);
+        }
+
+        let is_space_hidden = root_post.space_id
+            .and_then(Spaces::<T>::space_by_id)
+            .map_or(false, |space| space.hidden);
+        if is_space_hidden {
+            return Some(Visibility::HiddenBySpace);
+        }
+
+        Some(Visibility::Visible)
+    }
+
+    /// Get up to `limit` direct replies to a post, skipping the first `offset` of them,
+    /// from the oldest to the newest one or the other way around if `newest_first`.
+    ///
+    /// Replies are pushed to `ReplyIdsByPostId` when they are created, so the order of ids
+    /// there is already the order of their creation blocks and no separate index is needed.
+    /// Only the ids on the requested page are loaded. Comments on the page that were removed
+    /// from a thread by moderation are skipped, so a page may be shorter than `limit`.
+    pub fn get_replies_paginated(
+        post_id: PostId,
+        newest_first: bool,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FlatPost<T::AccountId, T::BlockNumber, T::Moment>> {
+        let mut reply_ids = Self::reply_ids_by_post_id(post_id);
+        if newest_first {
+            reply_ids.reverse();
+        }
+
+        reply_ids.into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .filter_map(Self::post_by_id)
+            .filter(|reply| reply.is_comment())
+            .map(|reply| reply.into())
+            .collect()
+    }
+
+    /// Get a thread of replies under `root_post_id` as a list of `(post_id, parent_id)` links,
+    /// where the root post goes first and has no parent.
+    ///
+    /// Replies are traversed breadth-first: level by level, in the order they were created.
+    /// The traversal stops at `max_depth` levels below the root post (`0` means the root post only)
+    /// or as soon as `max_nodes` links (including the root post) are collected.
+    /// An empty list is returned if there is no post with `root_post_id`.
+    pub fn get_post_replies_tree(
+        root_post_id: PostId,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> Vec<(PostId, Option<PostId>)> {
+        let max_nodes = max_nodes as usize;
+        let mut tree = Vec::new();
+
+        if max_nodes == 0 || !PostById::<T>::contains_key(root_post_id) {
+            return tree;
+        }
+
+        tree.push((root_post_id, None));
+
+        let mut current_level = vec![root_post_id];
+        let mut depth = 0;
+
+        while depth < max_depth && !current_level.is_empty() {
+            let mut next_level = Vec::new();
+
+            for parent_id in current_level {
+                for reply_id in Self::reply_ids_by_post_id(parent_id) {
+                    if tree.len() >= max_nodes {
+                        return tree;
+                    }
+
+                    tree.push((reply_id, Some(parent_id)));
+                    next_level.push(reply_id);
+                }
+            }
+
+            current_level = next_level;
+            depth += 1;
+        }
+
+        tree
+    }
+}
@@ -0,0 +1,111 @@
+//! Weights for the Posts pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_posts --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_posts.
+pub trait WeightInfo {
+    fn create_post() -> Weight;
+    fn create_comment(d: u32, ) -> Weight;
+    fn create_sharing_post() -> Weight;
+    fn update_post() -> Weight;
+    fn move_post() -> Weight;
+    fn delete_post() -> Weight;
+    fn content_bytes(b: u32, ) -> Weight;
+}
+
+/// Weights for pallet_posts measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_post() -> Weight {
+        (51_677_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+
+    fn create_comment(d: u32, ) -> Weight {
+        (61_788_000 as Weight)
+            .saturating_add((7_211_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+    }
+
+    fn create_sharing_post() -> Weight {
+        (90_359_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+
+    fn update_post() -> Weight {
+        (57_947_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    fn move_post() -> Weight {
+        (92_649_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+
+    fn delete_post() -> Weight {
+        (82_916_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+
+    // Not benchmarked, the weight is set by hand.
+    fn content_bytes(b: u32, ) -> Weight {
+        (1_000 as Weight).saturating_mul(b as Weight)
+    }
+}
+
+impl WeightInfo for () {
+    fn create_post() -> Weight {
+        (100_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+
+    fn create_comment(d: u32, ) -> Weight {
+        (100_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+    }
+
+    fn create_sharing_post() -> Weight {
+        (100_000 as Weight)
+            .saturating_add(DbWeight::get().reads(8 as Weight))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+    }
+
+    fn update_post() -> Weight {
+        (100_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+
+    fn move_post() -> Weight {
+        (50_000 as Weight)
+            .saturating_add(DbWeight::get().reads(1 as Weight))
+    }
+
+    fn delete_post() -> Weight {
+        (100_000 as Weight)
+            .saturating_add(DbWeight::get().reads(10 as Weight))
+            .saturating_add(DbWeight::get().writes(9 as Weight))
+    }
+
+    fn content_bytes(b: u32, ) -> Weight {
+        (1_000 as Weight).saturating_mul(b as Weight)
+    }
+}
@@ -0,0 +1,31 @@
+//! Benchmarks for the Profile Follows pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+    _ { }
+
+    follow_account {
+        let caller: T::AccountId = whitelisted_caller();
+        let followed: T::AccountId = account("followed", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), followed.clone())
+    verify {
+        assert!(Module::<T>::account_followed_by_account((caller, followed)));
+    }
+
+    unfollow_account {
+        let caller: T::AccountId = whitelisted_caller();
+        let followed: T::AccountId = account("followed", 0, SEED);
+        Module::<T>::follow_account(RawOrigin::Signed(caller.clone()).into(), followed.clone())?;
+    }: _(RawOrigin::Signed(caller.clone()), followed.clone())
+    verify {
+        assert!(!Module::<T>::account_followed_by_account((caller, followed)));
+    }
+}
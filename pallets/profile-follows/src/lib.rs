@@ -1,20 +1,48 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure,
+    decl_error, decl_event, decl_module, decl_storage, ensure, transactional,
     dispatch::DispatchResult,
-    traits::Get
+    traits::{Currency, Get},
+    unsigned::{TransactionValidity, TransactionSource, ValidateUnsigned},
+    weights::DispatchClass,
+    Parameter,
+};
+use sp_runtime::{
+    RuntimeDebug,
+    traits::Verify,
+    transaction_validity::{
+        InvalidTransaction, TransactionLongevity, TransactionPriority, ValidTransaction,
+    },
 };
 use sp_std::prelude::*;
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_none, ensure_signed, offchain::SigningTypes};
 
 use pallet_profiles::{Module as Profiles, SocialAccountById};
 use pallet_utils::remove_from_vec;
 
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
+
+type BalanceOf<T> = <<T as pallet_utils::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
+/// A payload of `follow_account_unsigned` that is signed by the follower.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct FollowAccountPayload<AccountId> {
+    pub follower: AccountId,
+    pub account: AccountId,
+    /// Must be equal to `UnsignedFollowNonce` of the follower.
+    pub nonce: u32,
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
     + pallet_profiles::Trait
+    + SigningTypes
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
@@ -22,6 +50,28 @@ pub trait Trait: system::Trait
     type BeforeAccountFollowed: BeforeAccountFollowed<Self>;
 
     type BeforeAccountUnfollowed: BeforeAccountUnfollowed<Self>;
+
+    /// A signature of a payload of an unsigned follow. Usually, it is `SigningTypes::Signature`.
+    type UnsignedFollowSignature: Parameter + Verify<Signer = <Self as SigningTypes>::Public>;
+
+    /// How many accounts an account can follow with unsigned transactions,
+    /// i.e. without paying fees.
+    type UnsignedFollowsAllowance: Get<u32>;
+
+    /// Max number of unsigned follows that can be included in one block.
+    type MaxUnsignedFollowsPerBlock: Get<u32>;
+
+    /// A free balance that an account should have to follow with unsigned transactions.
+    type UnsignedFollowerMinBalance: Get<BalanceOf<Self>>;
+
+    /// A priority of unsigned follows in a transaction pool.
+    type UnsignedFollowPriority: Get<TransactionPriority>;
+
+    /// For how many blocks an unsigned follow stays valid in a transaction pool.
+    type UnsignedFollowLongevity: Get<TransactionLongevity>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 // This pallet's storage items.
@@ -35,6 +85,14 @@ decl_storage! {
 
         pub AccountsFollowedByAccount get(fn accounts_followed_by_account):
             map hasher(blake2_128_concat) T::AccountId => Vec<T::AccountId>;
+
+        /// A nonce that the next unsigned follow of an account should be signed with.
+        /// It is also the number of unsigned follows the account has done.
+        pub UnsignedFollowNonce get(fn unsigned_follow_nonce):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The number of unsigned follows in the current block.
+        UnsignedFollowsInBlock get(fn unsigned_follows_in_block): u32;
     }
 }
 
@@ -69,40 +127,45 @@ decl_error! {
 decl_module! {
   pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+    const UnsignedFollowsAllowance: u32 = T::UnsignedFollowsAllowance::get();
+
+    const MaxUnsignedFollowsPerBlock: u32 = T::MaxUnsignedFollowsPerBlock::get();
+
+    const UnsignedFollowerMinBalance: BalanceOf<T> = T::UnsignedFollowerMinBalance::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    fn on_finalize(_block_number: T::BlockNumber) {
+      UnsignedFollowsInBlock::kill();
+    }
+
+    #[weight = (<T as Trait>::WeightInfo::follow_account(), DispatchClass::Normal)]
     pub fn follow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(follower != account, Error::<T>::AccountCannotFollowItself);
-      ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
-        Error::<T>::AlreadyAccountFollower);
-
-      let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
-      let mut followed_account = Profiles::get_or_new_social_account(account.clone());
-
-      follower_account.inc_following_accounts();
-      followed_account.inc_followers();
-
-      T::BeforeAccountFollowed::before_account_followed(
-        follower.clone(), follower_account.reputation, account.clone())?;
-
-      <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
-      <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
-      <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
-      <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
+      Self::do_follow_account(follower, account)
+    }
 
-      Self::deposit_event(RawEvent::AccountFollowed(follower, account));
-      Ok(())
+    /// Follow an account on behalf of an account that has signed the payload, without paying fees.
+    /// It lets new users follow other accounts before they own tokens.
+    /// The signature, the nonce and the limits are checked in `validate_unsigned`.
+    /// A follow that fails does not use up the nonce and the limit of the block.
+    #[weight = <T as Trait>::WeightInfo::follow_account()
+      .saturating_add(T::DbWeight::get().reads_writes(2, 2))]
+    #[transactional]
+    pub fn follow_account_unsigned(
+      origin,
+      payload: FollowAccountPayload<T::AccountId>,
+      _signature: T::UnsignedFollowSignature
+    ) -> DispatchResult {
+      Self::do_follow_account_unsigned(origin, payload)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[weight = <T as Trait>::WeightInfo::unfollow_account()]
     pub fn unfollow_account(origin, account: T::AccountId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
@@ -117,8 +180,9 @@ decl_module! {
 
       T::BeforeAccountUnfollowed::before_account_unfollowed(follower.clone(), account.clone())?;
 
-      <SocialAccountById<T>>::insert(follower.clone(), follower_account);
-      <SocialAccountById<T>>::insert(account.clone(), followed_account);
+      // Social accounts that were created only for this follow are not kept after it is undone.
+      Profiles::<T>::insert_or_reap_social_account(follower.clone(), follower_account);
+      Profiles::<T>::insert_or_reap_social_account(account.clone(), followed_account);
       <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| remove_from_vec(account_ids, account.clone()));
       <AccountFollowers<T>>::mutate(account.clone(), |account_ids| remove_from_vec(account_ids, follower.clone()));
       <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
@@ -129,6 +193,105 @@ decl_module! {
   }
 }
 
+impl<T: Trait> Module<T> {
+    fn ensure_can_follow_account(follower: &T::AccountId, account: &T::AccountId) -> DispatchResult {
+        ensure!(follower != account, Error::<T>::AccountCannotFollowItself);
+        ensure!(!<AccountFollowedByAccount<T>>::contains_key((follower.clone(), account.clone())),
+            Error::<T>::AlreadyAccountFollower);
+
+        Ok(())
+    }
+
+    fn do_follow_account(follower: T::AccountId, account: T::AccountId) -> DispatchResult {
+        Self::ensure_can_follow_account(&follower, &account)?;
+
+        let mut follower_account = Profiles::get_or_new_social_account(follower.clone());
+        let mut followed_account = Profiles::get_or_new_social_account(account.clone());
+
+        follower_account.inc_following_accounts();
+        followed_account.inc_followers();
+
+        T::BeforeAccountFollowed::before_account_followed(
+            follower.clone(), follower_account.reputation, account.clone())?;
+
+        <SocialAccountById<T>>::insert(follower.clone(), follower_account);
+        <SocialAccountById<T>>::insert(account.clone(), followed_account);
+        <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
+        <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
+        <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
+
+        Self::deposit_event(RawEvent::AccountFollowed(follower, account));
+        Ok(())
+    }
+
+    /// The body of `follow_account_unsigned`, kept out of the dispatchable for `#[transactional]`.
+    fn do_follow_account_unsigned(origin: T::Origin, payload: FollowAccountPayload<T::AccountId>) -> DispatchResult {
+        ensure_none(origin)?;
+
+        Self::do_follow_account(payload.follower.clone(), payload.account)?;
+
+        <UnsignedFollowNonce<T>>::mutate(&payload.follower, |nonce| *nonce = nonce.saturating_add(1));
+        UnsignedFollowsInBlock::mutate(|count| *count = count.saturating_add(1));
+
+        Ok(())
+    }
+
+    fn validate_follow_account_unsigned(
+        payload: &FollowAccountPayload<T::AccountId>,
+        signature: &T::UnsignedFollowSignature,
+    ) -> TransactionValidity {
+        let follower = &payload.follower;
+
+        if !signature.verify(&payload.encode()[..], follower) {
+            return InvalidTransaction::BadProof.into();
+        }
+
+        let nonce = Self::unsigned_follow_nonce(follower);
+        if payload.nonce < nonce {
+            return InvalidTransaction::Stale.into();
+        }
+        if payload.nonce > nonce {
+            return InvalidTransaction::Future.into();
+        }
+
+        // The allowance is used up, so the account has to pay for follows from now on.
+        if nonce >= T::UnsignedFollowsAllowance::get() {
+            return InvalidTransaction::Payment.into();
+        }
+
+        if <T as pallet_utils::Trait>::Currency::free_balance(follower) < T::UnsignedFollowerMinBalance::get() {
+            return InvalidTransaction::Payment.into();
+        }
+
+        if Self::unsigned_follows_in_block() >= T::MaxUnsignedFollowsPerBlock::get() {
+            return InvalidTransaction::ExhaustsResources.into();
+        }
+
+        if Self::ensure_can_follow_account(follower, &payload.account).is_err() {
+            return InvalidTransaction::Call.into();
+        }
+
+        ValidTransaction::with_tag_prefix("ProfileFollowsUnsigned")
+            .priority(T::UnsignedFollowPriority::get())
+            .and_provides((follower, nonce))
+            .longevity(T::UnsignedFollowLongevity::get())
+            .propagate(true)
+            .build()
+    }
+}
+
+impl<T: Trait> ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        match call {
+            Call::follow_account_unsigned(payload, signature) =>
+                Self::validate_follow_account_unsigned(payload, signature),
+            _ => InvalidTransaction::Call.into(),
+        }
+    }
+}
+
 /// Handler that will be called right before the account is followed.
 pub trait BeforeAccountFollowed<T: Trait> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult;
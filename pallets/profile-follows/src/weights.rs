@@ -0,0 +1,45 @@
+//! Weights for the Profile Follows pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_profile_follows --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_profile_follows.
+pub trait WeightInfo {
+    fn follow_account() -> Weight;
+    fn unfollow_account() -> Weight;
+}
+
+/// Weights for pallet_profile_follows measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn follow_account() -> Weight {
+        (51_007_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+
+    fn unfollow_account() -> Weight {
+        (78_411_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+}
+
+impl WeightInfo for () {
+    fn follow_account() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+
+    fn unfollow_account() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+}
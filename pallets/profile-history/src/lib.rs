@@ -1,13 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{decl_module, decl_storage};
+use frame_support::{decl_module, decl_storage, dispatch::DispatchResult, traits::Get};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::Vec;
 use frame_system::{self as system};
 
-use pallet_utils::WhoAndWhen;
-use pallet_profiles::{Profile, ProfileUpdate, AfterProfileUpdated};
+use pallet_utils::{Module as Utils, WhoAndWhen, HistoryOverflowPolicy, append_to_bounded_history};
+use pallet_profiles::{Profile, ProfileUpdate, AfterProfileUpdated, BeforeProfileUpdated};
+
+pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct ProfileHistoryRecord<T: Trait> {
@@ -19,7 +21,13 @@ pub struct ProfileHistoryRecord<T: Trait> {
 pub trait Trait: system::Trait
     + pallet_utils::Trait
     + pallet_profiles::Trait
-{}
+{
+    /// Max number of edit history records stored per profile.
+    type MaxHistoryRecordsPerEntity: Get<u32>;
+
+    /// What to do with a new record when the edit history of a profile is full.
+    type HistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+}
 
 // This pallet's storage items.
 decl_storage! {
@@ -30,7 +38,10 @@ decl_storage! {
 }
 
 decl_module! {
-  pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+  pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+
+    const MaxHistoryRecordsPerEntity: u32 = T::MaxHistoryRecordsPerEntity::get();
+  }
 }
 
 impl<T: Trait> ProfileHistoryRecord<T> {
@@ -42,9 +53,23 @@ impl<T: Trait> ProfileHistoryRecord<T> {
     }
 }
 
+impl<T: Trait> BeforeProfileUpdated<T> for Module<T> {
+    fn before_profile_updated(account: T::AccountId, _profile: &Profile<T>) -> DispatchResult {
+        Utils::<T>::ensure_history_not_full(
+            <EditHistory<T>>::decode_len(account).unwrap_or_default(),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        )
+    }
+}
+
 impl<T: Trait> AfterProfileUpdated<T> for Module<T> {
     fn after_profile_updated(sender: T::AccountId, _profile: &Profile<T>, old_data: ProfileUpdate) {
-        <EditHistory<T>>::mutate(sender.clone(), |ids|
-            ids.push(ProfileHistoryRecord::<T>::new(sender, old_data)));
+        append_to_bounded_history::<EditHistory<T>, _, _>(
+            sender.clone(),
+            ProfileHistoryRecord::<T>::new(sender, old_data),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        );
     }
 }
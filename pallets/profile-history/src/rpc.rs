@@ -0,0 +1,50 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_utils::{Content, history_in_block_range};
+
+use crate::{Module, ProfileHistoryRecord, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatProfileHistoryRecord<AccountId, BlockNumber, Moment> {
+    pub edited_by: AccountId,
+    pub edited_at_block: BlockNumber,
+    pub edited_at_time: Moment,
+
+    pub old_content: Option<Content>,
+}
+
+impl<T: Trait> From<ProfileHistoryRecord<T>> for FlatProfileHistoryRecord<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(record: ProfileHistoryRecord<T>) -> Self {
+        let ProfileHistoryRecord { edited, old_data } = record;
+
+        Self {
+            edited_by: edited.account,
+            edited_at_block: edited.block,
+            edited_at_time: edited.time,
+
+            old_content: old_data.content,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn get_edit_history_in_block_range(
+        account: T::AccountId,
+        from_block: T::BlockNumber,
+        to_block: T::BlockNumber,
+        limit: u32,
+    ) -> Vec<FlatProfileHistoryRecord<T::AccountId, T::BlockNumber, T::Moment>> {
+        let history = Self::edit_history(account);
+
+        history_in_block_range(&history, |record| record.edited.block, from_block, to_block, limit)
+            .iter()
+            .cloned()
+            .map(|record| record.into())
+            .collect()
+    }
+}
@@ -0,0 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+use pallet_profiles::rpc::FlatSocialAccount;
+
+sp_api::decl_runtime_apis! {
+    pub trait ProfilesApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        /// Get a social account with its profile and identity verification status.
+        fn social_account_by_id(account: AccountId) -> Option<FlatSocialAccount<AccountId, BlockNumber, Moment>>;
+    }
+}
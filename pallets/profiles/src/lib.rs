@@ -3,14 +3,19 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
-    dispatch::DispatchResult,
+    dispatch::{DispatchError, DispatchResult},
     traits::Get
 };
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use pallet_utils::{Module as Utils, WhoAndWhen, Content};
+use df_traits::{ContentValidator, IdentityProvider, ReputationProvider};
+use pallet_posts::{AfterPostCreated, Post};
+use pallet_utils::{Module as Utils, PalletVersion, WhoAndWhen, Content};
+
+pub mod migrations;
+pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SocialAccount<T: Trait> {
@@ -19,17 +24,22 @@ pub struct SocialAccount<T: Trait> {
     pub following_spaces_count: u16,
     pub reputation: u32,
     pub profile: Option<Profile<T>>,
+    /// The number of root posts (regular and sharing ones) created by this account.
+    pub posts_count: u32,
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Profile<T: Trait> {
     pub created: WhoAndWhen<T>,
     pub updated: Option<WhoAndWhen<T>>,
+    /// An optional on-chain username that is unique across all profiles.
+    pub username: Option<Vec<u8>>,
     pub content: Content
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct ProfileUpdate {
+    pub username: Option<Option<Vec<u8>>>,
     pub content: Option<Content>,
 }
 
@@ -40,14 +50,44 @@ pub trait Trait: system::Trait
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
+    /// Validates the content of profiles.
+    type ContentValidator: ContentValidator;
+
+    /// Called before a profile is edited, e.g. to refuse edits when its history is full.
+    type BeforeProfileUpdated: BeforeProfileUpdated<Self>;
+
     type AfterProfileUpdated: AfterProfileUpdated<Self>;
+
+    /// Checks whether an account has a verified identity.
+    type IdentityProvider: IdentityProvider<Self::AccountId>;
+
+    /// The reputation of a new social account. Reputation never drops below this value.
+    type BaseReputation: Get<u32>;
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as ProfilesModule {
+        /// The version of the storage layout of this pallet, see `pallet_spaces::StorageVersion`.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
         pub SocialAccountById get(fn social_account_by_id):
             map hasher(blake2_128_concat) T::AccountId => Option<SocialAccount<T>>;
+
+        /// Find an account by its profile username (key) in lowercase.
+        pub AccountByProfileUsername get(fn account_by_profile_username):
+            map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+
+        /// The last known verification status of a profile owned by an account (key).
+        pub ProfileVerifiedByAccount get(fn profile_verified_by_account):
+            map hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Accounts (key) that were reaped while they still had social state, e.g. spaces,
+        /// a profile or reputation. Such an account should reclaim its social state
+        /// with `reclaim_social_account` after it is funded again.
+        pub OrphanedSocialAccounts get(fn is_social_account_orphaned):
+            map hasher(blake2_128_concat) T::AccountId => bool;
     }
 }
 
@@ -57,6 +97,14 @@ decl_event!(
     {
         ProfileCreated(AccountId),
         ProfileUpdated(AccountId),
+        /// Identity verification status of a profile has changed.
+        ProfileVerificationChanged(AccountId, bool),
+        /// An account with social state was reaped, so its social state is orphaned.
+        SocialAccountOrphaned(AccountId),
+        /// An account was funded again and reclaimed its orphaned social state.
+        SocialAccountReclaimed(AccountId),
+        /// A social account was removed from storage, as nothing was left in it.
+        SocialAccountReaped(AccountId),
     }
 );
 
@@ -70,56 +118,88 @@ decl_error! {
         NoUpdatesForProfile,
         /// Account has no profile yet.
         AccountHasNoProfile,
+        /// Profile username is already taken by another account.
+        ProfileUsernameTaken,
+        /// Social state of this account is not orphaned, so there is nothing to reclaim.
+        SocialAccountNotOrphaned,
     }
 }
 
 decl_module! {
   pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+    const BaseReputation: u32 = T::BaseReputation::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
-    pub fn create_profile(origin, content: Content) -> DispatchResult {
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(2, 2)]
+    pub fn create_profile(origin, username: Option<Vec<u8>>, content: Content) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
-      Utils::<T>::is_valid_content(content.clone())?;
+      T::ContentValidator::is_valid_content(content.clone())?;
 
       let mut social_account = Self::get_or_new_social_account(owner.clone());
       ensure!(social_account.profile.is_none(), Error::<T>::ProfileAlreadyCreated);
 
+      let username_in_lowercase = username.clone()
+        .map(|username| Self::lowercase_and_ensure_unique_username(&owner, username))
+        .transpose()?;
+
       social_account.profile = Some(
         Profile {
           created: WhoAndWhen::<T>::new(owner.clone()),
           updated: None,
+          username,
           content
         }
       );
       <SocialAccountById<T>>::insert(owner.clone(), social_account);
 
+      if let Some(username) = username_in_lowercase {
+        AccountByProfileUsername::<T>::insert(username, owner.clone());
+      }
+
       Self::deposit_event(RawEvent::ProfileCreated(owner));
       Ok(())
     }
 
-    #[weight = 100_000 + T::DbWeight::get().reads_writes(1, 2)]
+    #[weight = 100_000 + T::DbWeight::get().reads_writes(2, 2)]
     pub fn update_profile(origin, update: ProfileUpdate) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
-      let has_updates = update.content.is_some();
+      let has_updates =
+        update.username.is_some() ||
+        update.content.is_some();
 
       ensure!(has_updates, Error::<T>::NoUpdatesForProfile);
 
       let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
       let mut profile = social_account.profile.ok_or(Error::<T>::AccountHasNoProfile)?;
+      T::BeforeProfileUpdated::before_profile_updated(owner.clone(), &profile)?;
+
       let mut is_update_applied = false;
       let mut old_data = ProfileUpdate::default();
+      let mut new_username_in_lowercase = None;
+
+      if let Some(username_opt) = update.username {
+        if username_opt != profile.username {
+          new_username_in_lowercase = username_opt.clone()
+            .map(|username| Self::lowercase_and_ensure_unique_username(&owner, username))
+            .transpose()?;
+
+          old_data.username = Some(profile.username);
+          profile.username = username_opt;
+          is_update_applied = true;
+        }
+      }
 
       if let Some(content) = update.content {
         if content != profile.content {
-          Utils::<T>::is_valid_content(content.clone())?;
+          T::ContentValidator::is_valid_content(content.clone())?;
           old_data.content = Some(profile.content);
           profile.content = content;
           is_update_applied = true;
@@ -127,6 +207,13 @@ decl_module! {
       }
 
       if is_update_applied {
+        if let Some(Some(old_username)) = old_data.username.clone() {
+          AccountByProfileUsername::<T>::remove(Utils::<T>::lowercase_handle(old_username));
+        }
+        if let Some(new_username) = new_username_in_lowercase {
+          AccountByProfileUsername::<T>::insert(new_username, owner.clone());
+        }
+
         profile.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
         social_account.profile = Some(profile.clone());
 
@@ -137,6 +224,37 @@ decl_module! {
       }
       Ok(())
     }
+
+    /// Sync the stored verification status of a profile with the current identity judgements.
+    /// Anyone can call it, e.g. after a registrar has judged the identity of the profile owner.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 1)]
+    pub fn refresh_profile_verification(origin, account: T::AccountId) -> DispatchResult {
+      let _ = ensure_signed(origin)?;
+
+      let social_account = Self::social_account_by_id(account.clone()).ok_or(Error::<T>::SocialAccountNotFound)?;
+      ensure!(social_account.profile.is_some(), Error::<T>::AccountHasNoProfile);
+
+      let is_verified = Self::is_verified(&account);
+      if is_verified != Self::profile_verified_by_account(&account) {
+        ProfileVerifiedByAccount::<T>::insert(account.clone(), is_verified);
+        Self::deposit_event(RawEvent::ProfileVerificationChanged(account, is_verified));
+      }
+      Ok(())
+    }
+
+    /// Take back social state (spaces, a profile, reputation etc.) of an account
+    /// that was reaped and then funded again.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+    pub fn reclaim_social_account(origin) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(Self::is_social_account_orphaned(&owner), Error::<T>::SocialAccountNotOrphaned);
+
+      OrphanedSocialAccounts::<T>::remove(&owner);
+
+      Self::deposit_event(RawEvent::SocialAccountReclaimed(owner));
+      Ok(())
+    }
   }
 }
 
@@ -164,9 +282,24 @@ impl <T: Trait> SocialAccount<T> {
     pub fn dec_following_spaces(&mut self) {
         self.following_spaces_count = self.following_spaces_count.saturating_sub(1);
     }
+
+    pub fn inc_posts(&mut self) {
+        self.posts_count = self.posts_count.saturating_add(1);
+    }
 }
 
 impl<T: Trait> SocialAccount<T> {
+    /// Whether this social account holds only what a new one would, i.e. no profile,
+    /// zero counters and the base reputation. Such an account is not worth storing.
+    pub fn is_empty(&self) -> bool {
+        self.profile.is_none()
+            && self.followers_count == 0
+            && self.following_accounts_count == 0
+            && self.following_spaces_count == 0
+            && self.posts_count == 0
+            && self.reputation <= T::BaseReputation::get()
+    }
+
     #[allow(clippy::comparison_chain)]
     pub fn change_reputation(&mut self, diff: i16) {
         if diff > 0 {
@@ -180,26 +313,95 @@ impl<T: Trait> SocialAccount<T> {
 impl Default for ProfileUpdate {
     fn default() -> Self {
         ProfileUpdate {
+            username: None,
             content: None
         }
     }
 }
 
 impl<T: Trait> Module<T> {
+    /// Check whether an account has a positive identity judgement.
+    pub fn is_verified(account: &T::AccountId) -> bool {
+        T::IdentityProvider::has_positive_judgement(account)
+    }
+
     pub fn get_or_new_social_account(account: T::AccountId) -> SocialAccount<T> {
         Self::social_account_by_id(account).unwrap_or(
             SocialAccount {
                 followers_count: 0,
                 following_accounts_count: 0,
                 following_spaces_count: 0,
-                reputation: 1,
+                reputation: T::BaseReputation::get(),
                 profile: None,
+                posts_count: 0,
             }
         )
     }
+
+    /// Store a social account, or remove it from storage if it became empty.
+    pub fn insert_or_reap_social_account(account: T::AccountId, social_account: SocialAccount<T>) {
+        if !social_account.is_empty() {
+            SocialAccountById::<T>::insert(account, social_account);
+        } else if SocialAccountById::<T>::contains_key(&account) {
+            SocialAccountById::<T>::remove(&account);
+            Self::deposit_event(RawEvent::SocialAccountReaped(account));
+        }
+    }
+
+    /// Mark social state of a reaped account as orphaned until the account reclaims it.
+    pub fn orphan_social_account(account: T::AccountId) {
+        if !Self::is_social_account_orphaned(&account) {
+            OrphanedSocialAccounts::<T>::insert(account.clone(), true);
+            Self::deposit_event(RawEvent::SocialAccountOrphaned(account));
+        }
+    }
+
+    /// Lowercase a username and ensure that it's valid by the same rules as space handles
+    /// and that no other profile has taken this username yet.
+    fn lowercase_and_ensure_unique_username(
+        owner: &T::AccountId,
+        username: Vec<u8>
+    ) -> Result<Vec<u8>, DispatchError> {
+        let username_in_lowercase = Utils::<T>::lowercase_and_validate_a_handle(username)?;
+
+        let is_taken = Self::account_by_profile_username(username_in_lowercase.clone())
+            .map_or(false, |account| &account != owner);
+        ensure!(!is_taken, Error::<T>::ProfileUsernameTaken);
+
+        Ok(username_in_lowercase)
+    }
+}
+
+impl<T: Trait> ReputationProvider<T::AccountId> for Module<T> {
+    /// An account without a social account has the base reputation.
+    fn reputation_of(account: &T::AccountId) -> u32 {
+        Self::social_account_by_id(account)
+            .map_or_else(T::BaseReputation::get, |social_account| social_account.reputation)
+    }
+}
+
+pub trait BeforeProfileUpdated<T: Trait> {
+    fn before_profile_updated(account: T::AccountId, profile: &Profile<T>) -> DispatchResult;
+}
+
+impl<T: Trait> BeforeProfileUpdated<T> for () {
+    fn before_profile_updated(_account: T::AccountId, _profile: &Profile<T>) -> DispatchResult {
+        Ok(())
+    }
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(10)]
 pub trait AfterProfileUpdated<T: Trait> {
     fn after_profile_updated(account: T::AccountId, post: &Profile<T>, old_data: ProfileUpdate);
 }
+
+impl<T: Trait + pallet_posts::Trait> AfterPostCreated<T> for Module<T> {
+    fn after_post_created(post: &Post<T>) {
+        if post.is_root_post() {
+            let author = post.created.account.clone();
+            let mut social_account = Self::get_or_new_social_account(author.clone());
+            social_account.inc_posts();
+            SocialAccountById::<T>::insert(author, social_account);
+        }
+    }
+}
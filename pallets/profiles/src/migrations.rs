@@ -0,0 +1,152 @@
+//! Storage migrations of the Profiles pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::Get,
+    weights::Weight,
+    IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_std::{cell::Cell, collections::btree_map::BTreeMap};
+
+use pallet_posts::PostById;
+use pallet_utils::{WhoAndWhen, Content, PalletVersion};
+
+use crate::{Module, Profile, SocialAccount, SocialAccountById, StorageVersion, Trait};
+
+/// The crate version that added an optional `username` to a profile.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that added `posts_count` to a social account.
+pub const V0_8_0: PalletVersion = PalletVersion { major: 0, minor: 8, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_8_0;
+
+/// A social account as it was stored before `V0_7_0`.
+#[derive(Encode, Decode)]
+pub struct SocialAccountV0<T: Trait> {
+    pub followers_count: u32,
+    pub following_accounts_count: u16,
+    pub following_spaces_count: u16,
+    pub reputation: u32,
+    pub profile: Option<ProfileV0<T>>,
+}
+
+/// A profile as it was stored before `V0_7_0`.
+#[derive(Encode, Decode)]
+pub struct ProfileV0<T: Trait> {
+    pub created: WhoAndWhen<T>,
+    pub updated: Option<WhoAndWhen<T>>,
+    pub content: Content,
+}
+
+/// A social account as it was stored before `V0_8_0`.
+/// `posts_count` is the last field of `SocialAccount`, so this decodes the current layout too.
+#[derive(Encode, Decode)]
+pub struct SocialAccountV1<T: Trait> {
+    pub followers_count: u32,
+    pub following_accounts_count: u16,
+    pub following_spaces_count: u16,
+    pub reputation: u32,
+    pub profile: Option<Profile<T>>,
+}
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait + pallet_posts::Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    }
+
+    if should_migrate_to::<T>(V0_8_0) {
+        weight = weight.saturating_add(migrate_to_v0_8_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Add an empty `username` to every profile.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    SocialAccountById::<T>::translate::<SocialAccountV0<T>, _>(|_account, old| {
+        migrated.set(migrated.get() + 1);
+
+        Some(SocialAccount {
+            followers_count: old.followers_count,
+            following_accounts_count: old.following_accounts_count,
+            following_spaces_count: old.following_spaces_count,
+            reputation: old.reputation,
+            profile: old.profile.map(|profile| Profile {
+                created: profile.created,
+                updated: profile.updated,
+                username: None,
+                content: profile.content,
+            }),
+            // Counted by `migrate_to_v0_8_0`.
+            posts_count: 0,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get())
+}
+
+/// Count the root posts created by every account. An author of root posts
+/// that has no social account yet gets a new one.
+pub fn migrate_to_v0_8_0<T: Trait + pallet_posts::Trait>() -> Weight {
+    let mut reads = 0u64;
+    let mut posts_count_by_account = BTreeMap::<T::AccountId, u32>::new();
+
+    for (_post_id, post) in PostById::<T>::iter() {
+        reads = reads.saturating_add(1);
+
+        if post.is_root_post() {
+            let posts_count = posts_count_by_account.entry(post.created.account).or_insert(0);
+            *posts_count = posts_count.saturating_add(1);
+        }
+    }
+
+    let migrated = Cell::new(0u64);
+
+    SocialAccountById::<T>::translate::<SocialAccountV1<T>, _>(|account, old| {
+        migrated.set(migrated.get() + 1);
+
+        Some(SocialAccount {
+            followers_count: old.followers_count,
+            following_accounts_count: old.following_accounts_count,
+            following_spaces_count: old.following_spaces_count,
+            reputation: old.reputation,
+            profile: old.profile,
+            posts_count: posts_count_by_account.get(&account).copied().unwrap_or(0),
+        })
+    });
+
+    let mut writes = migrated.get();
+    for (account, posts_count) in posts_count_by_account {
+        reads = reads.saturating_add(1);
+
+        if !SocialAccountById::<T>::contains_key(&account) {
+            let mut social_account = Module::<T>::get_or_new_social_account(account.clone());
+            social_account.posts_count = posts_count;
+            SocialAccountById::<T>::insert(account, social_account);
+            writes = writes.saturating_add(1);
+        }
+    }
+
+    T::DbWeight::get().reads_writes(reads.saturating_add(migrated.get()), writes)
+}
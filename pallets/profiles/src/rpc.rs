@@ -0,0 +1,84 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_utils::Content;
+
+use crate::{Module, Profile, SocialAccount, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatProfile<AccountId, BlockNumber, Moment> {
+    pub created_by: AccountId,
+    pub created_at_block: BlockNumber,
+    pub created_at_time: Moment,
+
+    pub updated_by: Option<AccountId>,
+    pub updated_at_block: Option<BlockNumber>,
+    pub updated_at_time: Option<Moment>,
+
+    pub username: Option<Vec<u8>>,
+    pub content: Content,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatSocialAccount<AccountId, BlockNumber, Moment> {
+    pub id: AccountId,
+
+    pub followers_count: u32,
+    pub following_accounts_count: u16,
+    pub following_spaces_count: u16,
+    pub reputation: u32,
+    pub posts_count: u32,
+
+    pub profile: Option<FlatProfile<AccountId, BlockNumber, Moment>>,
+
+    /// Whether an account has a positive identity judgement.
+    pub is_verified: bool,
+}
+
+impl<T: Trait> From<Profile<T>> for FlatProfile<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(profile: Profile<T>) -> Self {
+        let Profile { created, updated, username, content } = profile;
+
+        Self {
+            created_by: created.account,
+            created_at_block: created.block,
+            created_at_time: created.time,
+
+            updated_by: updated.clone().map(|x| x.account),
+            updated_at_block: updated.clone().map(|x| x.block),
+            updated_at_time: updated.map(|x| x.time),
+
+            username,
+            content,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn get_social_account(
+        account: T::AccountId
+    ) -> Option<FlatSocialAccount<T::AccountId, T::BlockNumber, T::Moment>> {
+        let is_verified = Self::is_verified(&account);
+
+        Self::social_account_by_id(account.clone()).map(|social_account: SocialAccount<T>| {
+            FlatSocialAccount {
+                id: account,
+
+                followers_count: social_account.followers_count,
+                following_accounts_count: social_account.following_accounts_count,
+                following_spaces_count: social_account.following_spaces_count,
+                reputation: social_account.reputation,
+                posts_count: social_account.posts_count,
+
+                profile: social_account.profile.map(|profile| profile.into()),
+
+                is_verified,
+            }
+        })
+    }
+}
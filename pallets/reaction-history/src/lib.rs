@@ -0,0 +1,72 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{decl_module, decl_storage, traits::Get};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
+use frame_system::{self as system};
+
+use pallet_reactions::{ReactionId, ReactionKind, AfterReactionUpdated};
+use pallet_utils::{WhoAndWhen, HistoryOverflowPolicy, append_to_bounded_history};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ReactionHistoryRecord<T: Trait> {
+    pub edited: WhoAndWhen<T>,
+    pub old_kind: ReactionKind,
+    /// `None` if the reaction was deleted.
+    pub new_kind: Option<ReactionKind>,
+}
+
+/// The pallet's configuration trait.
+pub trait Trait: system::Trait
+    + pallet_utils::Trait
+    + pallet_reactions::Trait
+{
+    /// Max number of history records stored per reaction.
+    type MaxHistoryRecordsPerEntity: Get<u32>;
+
+    /// What to do with a new record when the history of a reaction is full.
+    /// With `KeepOldest`, a reaction can still be changed, but the new record is dropped.
+    type HistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+}
+
+// This pallet's storage items.
+decl_storage! {
+    trait Store for Module<T: Trait> as ReactionHistoryModule {
+        pub EditHistory get(fn edit_history):
+            map hasher(twox_64_concat) ReactionId => Vec<ReactionHistoryRecord<T>>;
+    }
+}
+
+decl_module! {
+  pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+
+    const MaxHistoryRecordsPerEntity: u32 = T::MaxHistoryRecordsPerEntity::get();
+  }
+}
+
+impl<T: Trait> ReactionHistoryRecord<T> {
+    fn new(updated_by: T::AccountId, old_kind: ReactionKind, new_kind: Option<ReactionKind>) -> Self {
+        ReactionHistoryRecord {
+            edited: WhoAndWhen::<T>::new(updated_by),
+            old_kind,
+            new_kind
+        }
+    }
+}
+
+impl<T: Trait> AfterReactionUpdated<T> for Module<T> {
+    fn after_reaction_updated(
+        sender: T::AccountId,
+        reaction_id: ReactionId,
+        old_kind: ReactionKind,
+        new_kind: Option<ReactionKind>,
+    ) {
+        append_to_bounded_history::<EditHistory<T>, _, _>(
+            reaction_id,
+            ReactionHistoryRecord::<T>::new(sender, old_kind, new_kind),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        );
+    }
+}
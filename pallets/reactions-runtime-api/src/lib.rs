@@ -0,0 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_posts::PostId;
+use pallet_reactions::{ReactionKind, rpc::{FlatAccountReaction, FlatReaction}};
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over reactions that would otherwise require several storage lookups.
+    pub trait ReactionsApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        /// Get a page of reactions on a post: up to `limit` reactions after the first `offset` ones.
+        fn reactions_by_post(post_id: PostId, offset: u32, limit: u32) -> Vec<FlatReaction<AccountId, BlockNumber, Moment>>;
+
+        /// Get the number of reactions of every kind on a post, e.g. to render it in one call.
+        fn reactions_summary(post_id: PostId) -> Vec<(ReactionKind, u32)>;
+
+        /// Get a page of reactions of an account with the posts they were made on,
+        /// in the order they were created: up to `limit` reactions after the first `offset` ones.
+        fn reactions_by_account(account: AccountId, offset: u32, limit: u32) -> Vec<FlatAccountReaction<BlockNumber, Moment>>;
+    }
+}
@@ -0,0 +1,80 @@
+//! Benchmarks for the Reactions pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use pallet_posts::PostExtension;
+use pallet_utils::{Content, SpaceId, mock_functions::valid_content_ipfs};
+
+fn create_space<T: Trait>(owner: &T::AccountId) -> Result<SpaceId, &'static str> {
+    let space_id = Spaces::<T>::next_space_id();
+    Spaces::<T>::create_space(RawOrigin::Signed(owner.clone()).into(), None, None, Content::None, None, None)?;
+    Ok(space_id)
+}
+
+fn create_post<T: Trait>(owner: &T::AccountId, space_id: SpaceId) -> Result<PostId, &'static str> {
+    let post_id = Posts::<T>::next_post_id();
+    Posts::<T>::create_post(
+        RawOrigin::Signed(owner.clone()).into(), Some(space_id), PostExtension::RegularPost, valid_content_ipfs(), None
+    )?;
+    Ok(post_id)
+}
+
+fn create_reaction<T: Trait>(owner: &T::AccountId, post_id: PostId) -> Result<ReactionId, &'static str> {
+    let reaction_id = Module::<T>::next_reaction_id();
+    Module::<T>::create_post_reaction(RawOrigin::Signed(owner.clone()).into(), post_id, ReactionKind::Upvote)?;
+    Ok(reaction_id)
+}
+
+benchmarks! {
+    _ { }
+
+    create_post_reaction {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let post_id = create_post::<T>(&caller, space_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), post_id, ReactionKind::Upvote)
+    verify {
+        assert!(PostReactionIdByAccount::<T>::contains_key((caller, post_id)));
+    }
+
+    create_post_reactions {
+        let r in 1 .. T::MaxReactionsPerBatch::get() as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+
+        let mut reactions = Vec::new();
+        for _ in 0..r {
+            reactions.push((create_post::<T>(&caller, space_id)?, ReactionKind::Upvote));
+        }
+        let last_post_id = reactions.last().map(|(post_id, _)| *post_id).unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), reactions)
+    verify {
+        assert!(PostReactionIdByAccount::<T>::contains_key((caller, last_post_id)));
+    }
+
+    update_post_reaction {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let post_id = create_post::<T>(&caller, space_id)?;
+        let reaction_id = create_reaction::<T>(&caller, post_id)?;
+    }: _(RawOrigin::Signed(caller), post_id, reaction_id, ReactionKind::Downvote)
+    verify {
+        assert_eq!(Module::<T>::reaction_by_id(reaction_id).unwrap().kind, ReactionKind::Downvote);
+    }
+
+    delete_post_reaction {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let post_id = create_post::<T>(&caller, space_id)?;
+        let reaction_id = create_reaction::<T>(&caller, post_id)?;
+    }: _(RawOrigin::Signed(caller), post_id, reaction_id)
+    verify {
+        assert!(Module::<T>::reaction_by_id(reaction_id).is_none());
+    }
+}
@@ -3,22 +3,33 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
-    dispatch::DispatchResult,
-    traits::Get
+    dispatch::{DispatchError, DispatchResult},
+    traits::Get,
+    weights::DispatchClass,
 };
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
-use df_traits::moderation::IsAccountBlocked;
+use df_traits::moderation::{IsAccountBlocked, IsPostBlocked};
 use pallet_permissions::SpacePermission;
 use pallet_posts::{Module as Posts, Post, PostById, PostId};
 use pallet_spaces::Module as Spaces;
-use pallet_utils::{Error as UtilsError, remove_from_vec, WhoAndWhen};
+use pallet_utils::{Module as Utils, Error as UtilsError, PalletVersion, remove_from_vec_keeping_order, WhoAndWhen};
+
+pub mod migrations;
+pub mod rpc;
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
 
 pub type ReactionId = u64;
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ReactionKind {
     Upvote,
     Downvote,
@@ -48,11 +59,23 @@ pub trait Trait: system::Trait
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
     type PostReactionScores: PostReactionScores<Self>;
+
+    type AfterReactionUpdated: AfterReactionUpdated<Self>;
+
+    /// Max number of reactions that can be created with a single `create_post_reactions` call.
+    type MaxReactionsPerBatch: Get<u16>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as ReactionsModule {
+        /// The version of the storage layout of this pallet, see `pallet_spaces::StorageVersion`.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
         pub NextReactionId get(fn next_reaction_id): ReactionId = 1;
 
         pub ReactionById get(fn reaction_by_id):
@@ -63,6 +86,14 @@ decl_storage! {
 
         pub PostReactionIdByAccount get(fn post_reaction_id_by_account):
             map hasher(twox_64_concat) (T::AccountId, PostId) => ReactionId;
+
+        /// Ids of the reactions of an account (key) in the order they were created.
+        pub ReactionIdsByAccount get(fn reaction_ids_by_account):
+            map hasher(twox_64_concat) T::AccountId => Vec<ReactionId>;
+
+        /// A post (value) that a reaction (key) was made on.
+        pub PostIdByReactionId get(fn post_id_by_reaction_id):
+            map hasher(twox_64_concat) ReactionId => Option<PostId>;
     }
 }
 
@@ -70,9 +101,14 @@ decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
     {
-        PostReactionCreated(AccountId, PostId, ReactionId),
-        PostReactionUpdated(AccountId, PostId, ReactionId),
-        PostReactionDeleted(AccountId, PostId, ReactionId),
+        PostReactionCreated(AccountId, PostId, ReactionId, ReactionKind),
+        /// A reaction was changed to the given kind.
+        PostReactionUpdated(AccountId, PostId, ReactionId, ReactionKind),
+        /// A reaction of the given kind was deleted.
+        PostReactionDeleted(AccountId, PostId, ReactionId, ReactionKind),
+        /// Reactions created in a batch and the posts that were skipped
+        /// because the account had already reacted to them.
+        PostReactionsBatchCreated(AccountId, Vec<ReactionId>, Vec<PostId>),
     }
 );
 
@@ -98,19 +134,26 @@ decl_error! {
         NoPermissionToUpvote,
         /// User has no permission to downvote posts/comments in this space.
         NoPermissionToDownvote,
+
+        /// There are no reactions in a batch.
+        NoReactionsInBatch,
+        /// Too many reactions in a batch.
+        TooManyReactionsInBatch,
     }
 }
 
 decl_module! {
   pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
+    const MaxReactionsPerBatch: u16 = T::MaxReactionsPerBatch::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(6, 5)]
+    #[weight = (<T as Trait>::WeightInfo::create_post_reaction(), DispatchClass::Normal)]
     pub fn create_post_reaction(origin, post_id: PostId, kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
@@ -120,65 +163,66 @@ decl_module! {
         Error::<T>::AccountAlreadyReacted
       );
 
-      let space = post.get_space()?;
-      ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
-      ensure!(Posts::<T>::is_root_post_visible(post_id)?, Error::<T>::CannotReactWhenPostHidden);
+      Self::ensure_account_can_react(owner.clone(), post, kind)?;
+      Self::insert_new_post_reaction(owner, post, kind)?;
+      Ok(())
+    }
+
+    #[weight = <T as Trait>::WeightInfo::create_post_reactions(reactions.len() as u32)]
+    pub fn create_post_reactions(origin, reactions: Vec<(PostId, ReactionKind)>) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
 
-      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+      ensure!(!reactions.is_empty(), Error::<T>::NoReactionsInBatch);
+      ensure!(
+        reactions.len() <= T::MaxReactionsPerBatch::get() as usize,
+        Error::<T>::TooManyReactionsInBatch
+      );
 
-      let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
+      // Validate the whole batch before any reaction is stored.
+      let mut posts_to_react: Vec<(Post<T>, ReactionKind)> = Vec::new();
+      let mut skipped_post_ids: Vec<PostId> = Vec::new();
 
-      match kind {
-        ReactionKind::Upvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Upvote,
-            Error::<T>::NoPermissionToUpvote.into()
-          )?;
-          post.inc_upvotes();
-        },
-        ReactionKind::Downvote => {
-          Spaces::ensure_account_has_space_permission(
-            owner.clone(),
-            &post.get_space()?,
-            SpacePermission::Downvote,
-            Error::<T>::NoPermissionToDownvote.into()
-          )?;
-          post.inc_downvotes();
+      for (post_id, kind) in reactions {
+        let post = Posts::require_post(post_id)?;
+
+        let already_in_batch = posts_to_react.iter().any(|(p, _)| p.id == post_id);
+        if already_in_batch || <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)) {
+          skipped_post_ids.push(post_id);
+          continue;
         }
-      }
 
-      if post.is_owner(&owner) {
-        <PostById<T>>::insert(post_id, post.clone());
+        Self::ensure_account_can_react(owner.clone(), &post, kind)?;
+        posts_to_react.push((post, kind));
       }
 
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, kind)?;
-
-      ReactionIdsByPostId::mutate(post.id, |ids| ids.push(reaction_id));
-      <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+      let mut reaction_ids: Vec<ReactionId> = Vec::new();
+      for (mut post, kind) in posts_to_react {
+        reaction_ids.push(Self::insert_new_post_reaction(owner.clone(), &mut post, kind)?);
+      }
 
-      Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id));
+      Self::deposit_event(RawEvent::PostReactionsBatchCreated(owner, reaction_ids, skipped_post_ids));
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 2)]
+    #[weight = <T as Trait>::WeightInfo::update_post_reaction()]
     pub fn update_post_reaction(origin, post_id: PostId, reaction_id: ReactionId, new_kind: ReactionKind) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
+      let mut reaction = Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?;
+
+      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
       ensure!(
-        <PostReactionIdByAccount<T>>::contains_key((owner.clone(), post_id)),
+        Self::post_reaction_id_by_account((owner.clone(), post_id)) == reaction_id,
         Error::<T>::ReactionByAccountNotFound
       );
 
-      let mut reaction = Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?;
       let post = &mut Posts::require_post(post_id)?;
 
-      ensure!(owner == reaction.created.account, Error::<T>::NotReactionOwner);
       ensure!(reaction.kind != new_kind, Error::<T>::SameReaction);
 
       if let Some(space_id) = post.try_get_space_id() {
         ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space_id), UtilsError::<T>::AccountIsBlocked);
+        ensure!(T::IsPostBlocked::is_allowed_post(post_id, space_id), UtilsError::<T>::PostIsBlocked);
       }
 
       let old_kind = reaction.kind;
@@ -196,17 +240,18 @@ decl_module! {
         },
       }
 
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, old_kind)?;
-      T::PostReactionScores::score_post_on_reaction(owner.clone(), post, new_kind)?;
+      T::PostReactionScores::score_post_on_reaction_change(owner.clone(), post, old_kind, new_kind)?;
 
       <ReactionById<T>>::insert(reaction_id, reaction);
       <PostById<T>>::insert(post_id, post);
 
-      Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id));
+      T::AfterReactionUpdated::after_reaction_updated(owner.clone(), reaction_id, old_kind, Some(new_kind));
+
+      Self::deposit_event(RawEvent::PostReactionUpdated(owner, post_id, reaction_id, new_kind));
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
+    #[weight = <T as Trait>::WeightInfo::delete_post_reaction()]
     pub fn delete_post_reaction(origin, post_id: PostId, reaction_id: ReactionId) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
@@ -233,10 +278,14 @@ decl_module! {
 
       <PostById<T>>::insert(post_id, post.clone());
       <ReactionById<T>>::remove(reaction_id);
-      ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
+      ReactionIdsByPostId::mutate(post.id, |ids| remove_from_vec_keeping_order(ids, reaction_id));
       <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
+      <ReactionIdsByAccount<T>>::mutate(&owner, |ids| remove_from_vec_keeping_order(ids, reaction_id));
+      PostIdByReactionId::remove(reaction_id);
+
+      T::AfterReactionUpdated::after_reaction_updated(owner.clone(), reaction_id, reaction.kind, None);
 
-      Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id));
+      Self::deposit_event(RawEvent::PostReactionDeleted(owner, post_id, reaction_id, reaction.kind));
       Ok(())
     }
   }
@@ -244,9 +293,64 @@ decl_module! {
 
 impl<T: Trait> Module<T> {
 
-    // FIXME: don't add reaction in storage before the checks in 'create_reaction' are done
-    pub fn insert_new_reaction(account: T::AccountId, kind: ReactionKind) -> ReactionId {
+    fn ensure_account_can_react(owner: T::AccountId, post: &Post<T>, kind: ReactionKind) -> DispatchResult {
+        let space = post.get_space()?;
+        ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+        ensure!(Posts::<T>::is_root_post_visible(post.id)?, Error::<T>::CannotReactWhenPostHidden);
+
+        // Moderation is checked in the space of the root post, as comments have no space of their own.
+        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+        ensure!(T::IsPostBlocked::is_allowed_post(post.id, space.id), UtilsError::<T>::PostIsBlocked);
+
+        match kind {
+            ReactionKind::Upvote => Spaces::ensure_account_has_space_permission(
+                owner,
+                &space,
+                SpacePermission::Upvote,
+                Error::<T>::NoPermissionToUpvote.into()
+            ),
+            ReactionKind::Downvote => Spaces::ensure_account_has_space_permission(
+                owner,
+                &space,
+                SpacePermission::Downvote,
+                Error::<T>::NoPermissionToDownvote.into()
+            ),
+        }
+    }
+
+    /// Stores a new reaction on a post and updates the post's counters and scores.
+    /// All the checks should be done by `ensure_account_can_react` before calling this function.
+    fn insert_new_post_reaction(
+        owner: T::AccountId,
+        post: &mut Post<T>,
+        kind: ReactionKind,
+    ) -> Result<ReactionId, DispatchError> {
+        let post_id = post.id;
+        let reaction_id = Self::insert_new_reaction(owner.clone(), kind)?;
+
+        match kind {
+            ReactionKind::Upvote => post.inc_upvotes(),
+            ReactionKind::Downvote => post.inc_downvotes(),
+        }
+
+        if post.is_owner(&owner) {
+            <PostById<T>>::insert(post_id, post.clone());
+        }
+
+        T::PostReactionScores::score_post_on_reaction(owner.clone(), post, kind)?;
+
+        ReactionIdsByPostId::mutate(post_id, |ids| ids.push(reaction_id));
+        <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
+        <ReactionIdsByAccount<T>>::mutate(&owner, |ids| ids.push(reaction_id));
+        PostIdByReactionId::insert(reaction_id, post_id);
+
+        Self::deposit_event(RawEvent::PostReactionCreated(owner, post_id, reaction_id, kind));
+        Ok(reaction_id)
+    }
+
+    pub fn insert_new_reaction(account: T::AccountId, kind: ReactionKind) -> Result<ReactionId, DispatchError> {
         let id = Self::next_reaction_id();
+        let next_reaction_id = Utils::<T>::next_id(id)?;
         let reaction: Reaction<T> = Reaction {
             id,
             created: WhoAndWhen::<T>::new(account),
@@ -255,15 +359,26 @@ impl<T: Trait> Module<T> {
         };
 
         <ReactionById<T>>::insert(id, reaction);
-        NextReactionId::mutate(|n| { *n += 1; });
+        NextReactionId::put(next_reaction_id);
 
-        id
+        Ok(id)
     }
 }
 
 /// Handler that will be called right before the post reaction is toggled.
 pub trait PostReactionScores<T: Trait> {
     fn score_post_on_reaction(actor: T::AccountId, post: &mut Post<T>, reaction_kind: ReactionKind) -> DispatchResult;
+
+    /// Revert the score of the old reaction kind and apply the score of the new one.
+    fn score_post_on_reaction_change(
+        actor: T::AccountId,
+        post: &mut Post<T>,
+        old_kind: ReactionKind,
+        new_kind: ReactionKind,
+    ) -> DispatchResult {
+        Self::score_post_on_reaction(actor.clone(), post, old_kind)?;
+        Self::score_post_on_reaction(actor, post, new_kind)
+    }
 }
 
 impl<T: Trait> PostReactionScores<T> for () {
@@ -271,3 +386,15 @@ impl<T: Trait> PostReactionScores<T> for () {
         Ok(())
     }
 }
+
+/// Handler that will be called after the reaction kind is changed or the reaction is deleted.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterReactionUpdated<T: Trait> {
+    /// `new_kind` is `None` if the reaction was deleted.
+    fn after_reaction_updated(
+        sender: T::AccountId,
+        reaction_id: ReactionId,
+        old_kind: ReactionKind,
+        new_kind: Option<ReactionKind>,
+    );
+}
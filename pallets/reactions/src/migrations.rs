@@ -0,0 +1,67 @@
+//! Storage migrations of the Reactions pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use frame_support::{
+    traits::Get,
+    weights::Weight,
+    IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+use pallet_utils::PalletVersion;
+
+use crate::{
+    Module, PostIdByReactionId, PostReactionIdByAccount, ReactionId, ReactionIdsByAccount, StorageVersion, Trait,
+};
+
+/// The crate version that indexed reactions by account and posts by reaction.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_7_0;
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Fill `ReactionIdsByAccount` and `PostIdByReactionId` from `PostReactionIdByAccount`.
+/// Reaction ids grow with time, so the ids of every account are sorted to get them
+/// in the order they were created.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let mut reaction_ids_by_account: BTreeMap<T::AccountId, Vec<ReactionId>> = BTreeMap::new();
+    let mut reactions = 0u64;
+
+    for ((account, post_id), reaction_id) in <PostReactionIdByAccount<T>>::iter() {
+        PostIdByReactionId::insert(reaction_id, post_id);
+        reaction_ids_by_account.entry(account).or_default().push(reaction_id);
+        reactions = reactions.saturating_add(1);
+    }
+
+    let accounts = reaction_ids_by_account.len() as u64;
+    for (account, mut reaction_ids) in reaction_ids_by_account {
+        reaction_ids.sort_unstable();
+        <ReactionIdsByAccount<T>>::insert(account, reaction_ids);
+    }
+
+    T::DbWeight::get().reads_writes(reactions, reactions.saturating_add(accounts))
+}
@@ -0,0 +1,111 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::{prelude::*, vec};
+
+use pallet_posts::{Module as Posts, PostId};
+
+use crate::{Module, Reaction, ReactionId, ReactionKind, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatReaction<AccountId, BlockNumber, Moment> {
+    pub id: ReactionId,
+
+    pub created_by: AccountId,
+    pub created_at_block: BlockNumber,
+    pub created_at_time: Moment,
+
+    pub updated_by: Option<AccountId>,
+    pub updated_at_block: Option<BlockNumber>,
+    pub updated_at_time: Option<Moment>,
+
+    pub kind: ReactionKind,
+}
+
+impl<T: Trait> From<Reaction<T>> for FlatReaction<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(reaction: Reaction<T>) -> Self {
+        let Reaction { id, created, updated, kind } = reaction;
+
+        Self {
+            id,
+
+            created_by: created.account,
+            created_at_block: created.block,
+            created_at_time: created.time,
+
+            updated_by: updated.clone().map(|x| x.account),
+            updated_at_block: updated.clone().map(|x| x.block),
+            updated_at_time: updated.map(|x| x.time),
+
+            kind,
+        }
+    }
+}
+
+/// A reaction of an account together with the post it was made on, for activity feeds.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatAccountReaction<BlockNumber, Moment> {
+    pub id: ReactionId,
+    pub post_id: PostId,
+    pub kind: ReactionKind,
+    pub created_at_block: BlockNumber,
+    pub created_at_time: Moment,
+}
+
+impl<T: Trait> Module<T> {
+    /// Get up to `limit` reactions on a post in the order they were created,
+    /// skipping the first `offset` of them.
+    pub fn get_reactions_by_post(
+        post_id: PostId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FlatReaction<T::AccountId, T::BlockNumber, T::Moment>> {
+        Self::reaction_ids_by_post_id(post_id)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .filter_map(Self::reaction_by_id)
+            .map(|reaction| reaction.into())
+            .collect()
+    }
+
+    /// Get the number of reactions of every kind on a post, taken from the counters of the post.
+    /// Empty if there is no such post.
+    pub fn get_reactions_summary(post_id: PostId) -> Vec<(ReactionKind, u32)> {
+        Posts::<T>::post_by_id(post_id)
+            .map(|post| vec![
+                (ReactionKind::Upvote, post.upvotes_count.into()),
+                (ReactionKind::Downvote, post.downvotes_count.into()),
+            ])
+            .unwrap_or_default()
+    }
+
+    /// Get up to `limit` reactions of an account in the order they were created,
+    /// skipping the first `offset` of them.
+    pub fn get_reactions_by_account(
+        account: T::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FlatAccountReaction<T::BlockNumber, T::Moment>> {
+        Self::reaction_ids_by_account(account)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .filter_map(|reaction_id| {
+                let reaction = Self::reaction_by_id(reaction_id)?;
+                let post_id = Self::post_id_by_reaction_id(reaction_id)?;
+
+                Some(FlatAccountReaction {
+                    id: reaction_id,
+                    post_id,
+                    kind: reaction.kind,
+                    created_at_block: reaction.created.block,
+                    created_at_time: reaction.created.time,
+                })
+            })
+            .collect()
+    }
+}
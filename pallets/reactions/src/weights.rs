@@ -0,0 +1,74 @@
+//! Weights for the Reactions pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_reactions --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_reactions.
+pub trait WeightInfo {
+    fn create_post_reaction() -> Weight;
+    fn create_post_reactions(r: u32) -> Weight;
+    fn update_post_reaction() -> Weight;
+    fn delete_post_reaction() -> Weight;
+}
+
+/// Weights for pallet_reactions measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_post_reaction() -> Weight {
+        (56_894_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+
+    fn create_post_reactions(r: u32) -> Weight {
+        (162_888_000 as Weight)
+            .saturating_add((34_322_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn update_post_reaction() -> Weight {
+        (35_813_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn delete_post_reaction() -> Weight {
+        (43_282_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+}
+
+impl WeightInfo for () {
+    fn create_post_reaction() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+
+    fn create_post_reactions(r: u32) -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads((7 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(DbWeight::get().writes((7 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn update_post_reaction() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+
+    fn delete_post_reaction() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(6 as Weight))
+    }
+}
@@ -0,0 +1,119 @@
+//! Benchmarks for the Roles pallet.
+//!
+//! Roles are managed in spaces, so the benchmarks create spaces with `pallet_spaces`,
+//! which is not a dependency of this pallet otherwise. That is why they are implemented
+//! for a separate `Module` that requires `pallet_spaces::Trait` as well.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+use pallet_utils::mock_functions::valid_content_ipfs;
+
+use crate::Module as Roles;
+
+const SEED: u32 = 0;
+
+pub struct Module<T: Trait>(Roles<T>);
+
+pub trait Trait: crate::Trait + pallet_spaces::Trait {}
+
+fn create_space<T: Trait>(owner: &T::AccountId) -> Result<SpaceId, &'static str> {
+    let space_id = pallet_spaces::Module::<T>::next_space_id();
+    pallet_spaces::Module::<T>::create_space(
+        RawOrigin::Signed(owner.clone()).into(), None, None, Content::None, None, None
+    )?;
+    Ok(space_id)
+}
+
+fn add_role<T: Trait>(owner: &T::AccountId, space_id: SpaceId) -> Result<RoleId, &'static str> {
+    let role_id = Roles::<T>::next_role_id();
+    Roles::<T>::create_role(
+        RawOrigin::Signed(owner.clone()).into(),
+        space_id,
+        None,
+        valid_content_ipfs(),
+        vec![SpacePermission::ManageRoles],
+    )?;
+    Ok(role_id)
+}
+
+fn users<T: Trait>(count: u32) -> Vec<User<T::AccountId>> {
+    (0..count).map(|i| User::Account(account("user", i, SEED))).collect()
+}
+
+benchmarks! {
+    _ { }
+
+    create_role {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let role_id = Roles::<T>::next_role_id();
+
+        let permissions = vec![
+            SpacePermission::ManageRoles,
+            SpacePermission::CreatePosts,
+            SpacePermission::UpdateOwnPosts,
+        ];
+    }: _(RawOrigin::Signed(caller), space_id, Some(100u32.into()), valid_content_ipfs(), permissions)
+    verify {
+        assert!(Roles::<T>::role_by_id(role_id).is_some());
+    }
+
+    update_role {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let role_id = add_role::<T>(&caller, space_id)?;
+
+        let update = RoleUpdate {
+            disabled: Some(true),
+            content: Some(Content::None),
+            permissions: Some(BTreeSet::from_iter(vec![SpacePermission::CreatePosts])),
+        };
+    }: _(RawOrigin::Signed(caller), role_id, update)
+    verify {
+        assert!(Roles::<T>::role_by_id(role_id).unwrap().disabled);
+    }
+
+    delete_role {
+        let u in 0 .. T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let role_id = add_role::<T>(&caller, space_id)?;
+
+        if u > 0 {
+            Roles::<T>::grant_role(RawOrigin::Signed(caller.clone()).into(), role_id, users::<T>(u))?;
+        }
+    }: _(RawOrigin::Signed(caller), role_id)
+    verify {
+        assert!(Roles::<T>::role_by_id(role_id).is_none());
+    }
+
+    grant_role {
+        let u in 1 .. T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let role_id = add_role::<T>(&caller, space_id)?;
+    }: _(RawOrigin::Signed(caller), role_id, users::<T>(u))
+    verify {
+        assert_eq!(Roles::<T>::users_by_role_id(role_id).len(), u as usize);
+    }
+
+    revoke_role {
+        let u in 1 .. T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>(&caller)?;
+        let role_id = add_role::<T>(&caller, space_id)?;
+        Roles::<T>::grant_role(RawOrigin::Signed(caller.clone()).into(), role_id, users::<T>(u))?;
+    }: _(RawOrigin::Signed(caller), role_id, users::<T>(u))
+    verify {
+        assert!(Roles::<T>::users_by_role_id(role_id).is_empty());
+    }
+}
@@ -12,6 +12,53 @@ impl<T: Trait> Module<T> {
       Ok(())
   }
 
+  /// Append a change of the users of `role` to the role history of its space.
+  /// Nothing is recorded if no users were affected.
+  ///
+  /// Every record is stored under its own index, so that a change costs the same
+  /// however long the history of the space is.
+  pub(crate) fn record_role_history(
+    who: T::AccountId,
+    role: &Role<T>,
+    action: RoleAction,
+    users: Vec<User<T::AccountId>>,
+  ) {
+    let max_records = T::MaxRoleHistoryRecordsPerSpace::get();
+    if users.is_empty() || max_records == 0 {
+      return;
+    }
+
+    let space_id = role.space_id;
+    let count = Self::role_history_count_by_space(space_id);
+    if count >= max_records {
+      match T::RoleHistoryOverflowPolicy::get() {
+        HistoryOverflowPolicy::KeepOldest => return,
+        HistoryOverflowPolicy::EvictOldest =>
+          RoleHistoryRecordByIndex::<T>::remove(space_id, count - max_records),
+      }
+    }
+
+    let record = RoleHistoryRecord {
+      changed: WhoAndWhen::<T>::new(who),
+      role_id: role.id,
+      action,
+      users,
+    };
+
+    RoleHistoryRecordByIndex::<T>::insert(space_id, count, record);
+    RoleHistoryCountBySpace::insert(space_id, count.saturating_add(1));
+  }
+
+  /// Get the role history of a space, from the oldest to the newest record.
+  pub fn role_history_by_space(space_id: SpaceId) -> Vec<RoleHistoryRecord<T>> {
+    let count = Self::role_history_count_by_space(space_id);
+    let first_index = count.saturating_sub(T::MaxRoleHistoryRecordsPerSpace::get());
+
+    (first_index..count)
+      .filter_map(|index| Self::role_history_record_by_index(space_id, index))
+      .collect()
+  }
+
   /// Get `Role` by id from the storage or return `RoleNotFound` error.
   pub fn require_role(role_id: SpaceId) -> Result<Role<T>, DispatchError> {
       Ok(Self::role_by_id(role_id).ok_or(Error::<T>::RoleNotFound)?)
@@ -97,28 +144,28 @@ impl<T: Trait> Module<T> {
     error: DispatchError,
   ) -> DispatchResult {
 
-    let role_ids = Self::role_ids_by_user_in_space(user, space_id);
-
-    for role_id in role_ids {
-      if let Some(role) = Self::role_by_id(role_id) {
-        if role.disabled {
-          continue;
-        }
-
-        let mut is_expired = false;
-        if let Some(expires_at) = role.expires_at {
-          if expires_at <= <system::Module<T>>::block_number() {
-            is_expired = true;
-          }
-        }
+    ensure!(Self::space_permissions_granted_by_roles(user, space_id).contains(&permission), error);
+    Ok(())
+  }
 
-        if !is_expired && role.permissions.contains(&permission) {
-          return Ok(());
-        }
-      }
-    }
+  /// Get the union of permissions granted to this user by all their active
+  /// (neither disabled nor expired) roles in a given space.
+  /// The result does not depend on the order in which the roles were granted.
+  pub fn space_permissions_granted_by_roles(user: User<T::AccountId>, space_id: SpaceId) -> SpacePermissionSet {
+    Self::role_ids_by_user_in_space(user, space_id)
+      .into_iter()
+      .filter_map(|role_id| Self::role_by_id(role_id))
+      .filter(|role| role.is_active())
+      .flat_map(|role| role.permissions.into_iter())
+      .collect()
+  }
 
-    Err(error)
+  /// Whether an account is granted at least one active role in a given space.
+  pub fn has_active_role_in_space(account: T::AccountId, space_id: SpaceId) -> bool {
+    Self::role_ids_by_user_in_space(User::Account(account), space_id)
+      .into_iter()
+      .filter_map(|role_id| Self::role_by_id(role_id))
+      .any(|role| role.is_active())
   }
 }
 
@@ -153,6 +200,14 @@ impl<T: Trait> Role<T> {
     Ok(new_role)
   }
 
+  /// Whether this role is neither disabled nor expired.
+  pub fn is_active(&self) -> bool {
+    let is_expired = self.expires_at
+      .map_or(false, |expires_at| expires_at <= <system::Module<T>>::block_number());
+
+    !self.disabled && !is_expired
+  }
+
   pub fn set_disabled(&mut self, disable: bool) -> DispatchResult {
     if self.disabled && disable {
       return Err(Error::<T>::RoleAlreadyDisabled.into());
@@ -165,30 +220,45 @@ impl<T: Trait> Role<T> {
     Ok(())
   }
 
-  pub fn revoke_from_users(&self, users: Vec<User<T::AccountId>>) {
+  /// Revoke this role from the given users and return the ones that actually had it.
+  pub fn revoke_from_users(&self, users: Vec<User<T::AccountId>>) -> Vec<User<T::AccountId>> {
     let mut users_by_role = <UsersByRoleId<T>>::take(self.id);
+    let mut revoked_users = Vec::new();
 
-    for user in users.iter() {
+    for user in users.into_iter() {
+      let mut is_revoked = false;
       let role_idx_by_user_opt = Module::<T>::role_ids_by_user_in_space(&user, self.space_id).iter()
         .position(|x| { *x == self.id });
 
       if let Some(role_idx) = role_idx_by_user_opt {
-        <RoleIdsByUserInSpace<T>>::mutate(user, self.space_id, |n| { n.swap_remove(role_idx) });
+        <RoleIdsByUserInSpace<T>>::mutate(&user, self.space_id, |n| { n.swap_remove(role_idx) });
+        is_revoked = true;
       }
 
-      let user_idx_by_role_opt = users_by_role.iter().position(|x| { x == user });
+      let user_idx_by_role_opt = users_by_role.iter().position(|x| { *x == user });
 
       if let Some(user_idx) = user_idx_by_role_opt {
         users_by_role.swap_remove(user_idx);
+        is_revoked = true;
+      }
+
+      if is_revoked {
+        revoked_users.push(user);
       }
     }
     <UsersByRoleId<T>>::insert(self.id, users_by_role);
+
+    revoked_users
   }
 }
 
 impl<T: Trait> PermissionChecker for Module<T> {
   type AccountId = T::AccountId;
 
+  fn has_active_role_in_space(account: Self::AccountId, space_id: SpaceId) -> bool {
+    Self::has_active_role_in_space(account, space_id)
+  }
+
   fn ensure_user_has_space_permission(
     user: User<Self::AccountId>,
     ctx: SpacePermissionsContext,
@@ -12,13 +12,19 @@ use sp_std::{collections::btree_set::BTreeSet, iter::FromIterator, prelude::*};
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::{
-    PermissionChecker, SpaceFollowsProvider, SpaceForRolesProvider,
+    ContentValidator, PermissionChecker, SpaceFollowsProvider, SpaceForRolesProvider, UserResolver,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissionSet};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content};
+use pallet_utils::{
+    Module as Utils, Error as UtilsError, SpaceId, User, WhoAndWhen, Content, HistoryOverflowPolicy,
+};
 
 pub mod functions;
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -26,7 +32,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-type RoleId = u64;
+pub type RoleId = u64;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Role<T: Trait> {
@@ -47,6 +53,25 @@ pub struct RoleUpdate {
     pub permissions: Option<SpacePermissionSet>,
 }
 
+/// What happened to the users of a role in a `RoleHistoryRecord`.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum RoleAction {
+    Granted,
+    Revoked,
+    /// The role was deleted, so it was revoked from all its users.
+    Deleted,
+}
+
+/// A change of the users that have a role, so that it can be told who had the role and when.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct RoleHistoryRecord<T: Trait> {
+    pub changed: WhoAndWhen<T>,
+    pub role_id: RoleId,
+    pub action: RoleAction,
+    /// The users that were granted the role or lost it with this change.
+    pub users: Vec<User<T::AccountId>>,
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_permissions::Trait
@@ -55,15 +80,32 @@ pub trait Trait: system::Trait
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
+    /// Validates the content of roles.
+    type ContentValidator: ContentValidator;
+
     type MaxUsersToProcessPerDeleteRole: Get<u16>;
 
+    /// The maximum number of roles that can exist in a single space.
+    type MaxRolesPerSpace: Get<u16>;
+
     type Spaces: SpaceForRolesProvider<AccountId=Self::AccountId>;
 
     type SpaceFollows: SpaceFollowsProvider<AccountId=Self::AccountId>;
 
+    type UserResolver: UserResolver<AccountId=Self::AccountId>;
+
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
     type IsContentBlocked: IsContentBlocked;
+
+    /// Max number of role history records stored per space.
+    type MaxRoleHistoryRecordsPerSpace: Get<u32>;
+
+    /// What to do with a new record when the role history of a space is full.
+    type RoleHistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_event!(
@@ -71,10 +113,13 @@ decl_event!(
         <T as system::Trait>::AccountId
     {
         RoleCreated(AccountId, SpaceId, RoleId),
-        RoleUpdated(AccountId, RoleId),
-        RoleDeleted(AccountId, RoleId),
-        RoleGranted(AccountId, RoleId, Vec<User<AccountId>>),
-        RoleRevoked(AccountId, RoleId, Vec<User<AccountId>>),
+        RoleUpdated(AccountId, SpaceId, RoleId),
+        /// A role was deleted and revoked from all its users. [who, space_id, role_id, users]
+        RoleDeleted(AccountId, SpaceId, RoleId, Vec<User<AccountId>>),
+        /// A role was granted to users that did not have it yet. [who, space_id, role_id, users]
+        RoleGranted(AccountId, SpaceId, RoleId, Vec<User<AccountId>>),
+        /// A role was revoked from users that had it. [who, space_id, role_id, users]
+        RoleRevoked(AccountId, SpaceId, RoleId, Vec<User<AccountId>>),
     }
 );
 
@@ -82,7 +127,7 @@ decl_error! {
     pub enum Error for Module<T: Trait> {
         /// Role was not found by id.
         RoleNotFound,
-        /// RoleId counter storage overflowed.
+        /// Not used anymore: an overflow of `NextRoleId` fails with `IdOverflow` of the utils pallet.
         RoleIdOverflow,
         /// Account has no permission to manage roles in this space.
         NoPermissionToManageRoles,
@@ -98,6 +143,8 @@ decl_error! {
         RoleAlreadyDisabled,
         /// Cannot enable a role that is already enabled.
         RoleAlreadyEnabled,
+        /// This space already has the maximum number of roles.
+        TooManyRolesInSpace,
     }
 }
 
@@ -125,6 +172,18 @@ decl_storage! {
             hasher(blake2_128_concat) User<T::AccountId>,
             hasher(twox_64_concat) SpaceId
             => Vec<RoleId>;
+
+        /// The number of records that were ever added to the role history of a space (key).
+        /// It is also the index of the next record of the space.
+        pub RoleHistoryCountBySpace get(fn role_history_count_by_space):
+            map hasher(twox_64_concat) SpaceId => u32;
+
+        /// A grant or revocation of a role in a space by the space id and the index of the record.
+        /// Only the last `MaxRoleHistoryRecordsPerSpace` records of a space are kept.
+        pub RoleHistoryRecordByIndex get(fn role_history_record_by_index): double_map
+            hasher(twox_64_concat) SpaceId,
+            hasher(twox_64_concat) u32
+            => Option<RoleHistoryRecord<T>>;
     }
 }
 
@@ -134,17 +193,22 @@ decl_module! {
 
     const MaxUsersToProcessPerDeleteRole: u16 = T::MaxUsersToProcessPerDeleteRole::get();
 
+    const MaxRolesPerSpace: u16 = T::MaxRolesPerSpace::get();
+
+    const MaxRoleHistoryRecordsPerSpace: u32 = T::MaxRoleHistoryRecordsPerSpace::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    /// Create a new role in a space with a list of permissions.
+    /// Create a new role in a space with a non-empty list of permissions.
+    /// Duplicate permissions are stored only once.
     /// `content` points to the off-chain content with such additional info about this role
     /// as its name, description, color, etc.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 3)]
+    #[weight = <T as Trait>::WeightInfo::create_role()]
     pub fn create_role(
       origin,
       space_id: SpaceId,
@@ -156,16 +220,20 @@ decl_module! {
 
       ensure!(!permissions.is_empty(), Error::<T>::NoPermissionsProvided);
 
-      Utils::<T>::is_valid_content(content.clone())?;
+      T::ContentValidator::is_valid_content(content.clone())?;
       ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), space_id), UtilsError::<T>::ContentIsBlocked);
 
       Self::ensure_role_manager(who.clone(), space_id)?;
-      
+
+      ensure!(
+        Self::role_ids_by_space_id(space_id).len() < T::MaxRolesPerSpace::get() as usize,
+        Error::<T>::TooManyRolesInSpace
+      );
+
       let permissions_set = BTreeSet::from_iter(permissions.into_iter());
       let new_role = Role::<T>::new(who.clone(), space_id, time_to_live, content, permissions_set)?;
 
-      // TODO review strange code:
-      let next_role_id = new_role.id.checked_add(1).ok_or(Error::<T>::RoleIdOverflow)?;
+      let next_role_id = Utils::<T>::next_id(new_role.id)?;
       NextRoleId::put(next_role_id);
 
       <RoleById<T>>::insert(new_role.id, new_role.clone());
@@ -177,7 +245,7 @@ decl_module! {
 
     /// Update an existing role by its id.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 1)]
+    #[weight = <T as Trait>::WeightInfo::update_role()]
     pub fn update_role(origin, role_id: RoleId, update: RoleUpdate) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -203,7 +271,7 @@ decl_module! {
 
       if let Some(content) = update.content {
         if content != role.content {
-          Utils::<T>::is_valid_content(content.clone())?;
+          T::ContentValidator::is_valid_content(content.clone())?;
           ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), role.space_id), UtilsError::<T>::ContentIsBlocked);
 
           role.content = content;
@@ -225,15 +293,16 @@ decl_module! {
       if is_update_applied {
         role.updated = Some(WhoAndWhen::<T>::new(who.clone()));
 
+        let space_id = role.space_id;
         <RoleById<T>>::insert(role_id, role);
-        Self::deposit_event(RawEvent::RoleUpdated(who, role_id));
+        Self::deposit_event(RawEvent::RoleUpdated(who, space_id, role_id));
       }
       Ok(())
     }
 
     /// Delete a role from all associated storage items.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(6, 5)]
+    #[weight = <T as Trait>::WeightInfo::delete_role(T::MaxUsersToProcessPerDeleteRole::get() as u32)]
     pub fn delete_role(origin, role_id: RoleId) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -254,44 +323,56 @@ decl_module! {
         RoleIdsBySpaceId::mutate(role.space_id, |n| { n.swap_remove(role_idx) });
       }
 
-      role.revoke_from_users(users);
+      role.revoke_from_users(users.clone());
 
       <RoleById<T>>::remove(role_id);
       <UsersByRoleId<T>>::remove(role_id);
 
-      Self::deposit_event(RawEvent::RoleDeleted(who, role_id));
+      Self::record_role_history(who.clone(), &role, RoleAction::Deleted, users.clone());
+      Self::deposit_event(RawEvent::RoleDeleted(who, role.space_id, role_id, users));
       Ok(())
     }
 
     /// Grant a role to a list of users.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(4, 2)]
+    #[weight = <T as Trait>::WeightInfo::grant_role(users.len() as u32)]
     pub fn grant_role(origin, role_id: RoleId, users: Vec<User<T::AccountId>>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
       ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
-      let users_set: BTreeSet<User<T::AccountId>> = Utils::<T>::convert_users_vec_to_btree_set(users)?;
+      let users_set: BTreeSet<User<T::AccountId>> = Utils::<T>::convert_and_validate_users_vec(
+        users,
+        |space_id| T::UserResolver::owner_of(&User::Space(space_id)).is_some()
+      )?;
 
       let role = Self::require_role(role_id)?;
 
       Self::ensure_role_manager(who.clone(), role.space_id)?;
 
+      let mut granted_users = Vec::new();
       for user in users_set.iter() {
+        let mut is_granted = false;
         if !Self::users_by_role_id(role_id).contains(&user) {
           <UsersByRoleId<T>>::mutate(role_id, |users| { users.push(user.clone()); });
+          is_granted = true;
         }
         if !Self::role_ids_by_user_in_space(user.clone(), role.space_id).contains(&role_id) {
-          <RoleIdsByUserInSpace<T>>::mutate(user.clone(), role.space_id, |roles| { roles.push(role_id); })
+          <RoleIdsByUserInSpace<T>>::mutate(user.clone(), role.space_id, |roles| { roles.push(role_id); });
+          is_granted = true;
+        }
+        if is_granted {
+          granted_users.push(user.clone());
         }
       }
 
-      Self::deposit_event(RawEvent::RoleGranted(who, role_id, users_set.iter().cloned().collect()));
+      Self::record_role_history(who.clone(), &role, RoleAction::Granted, granted_users.clone());
+      Self::deposit_event(RawEvent::RoleGranted(who, role.space_id, role_id, granted_users));
       Ok(())
     }
 
     /// Revoke a role from a list of users.
     /// Only the space owner or a user with `ManageRoles` permission call this dispatch.
-    #[weight = 1_000_000 + T::DbWeight::get().reads_writes(4, 2)]
+    #[weight = <T as Trait>::WeightInfo::revoke_role(users.len() as u32)]
     pub fn revoke_role(origin, role_id: RoleId, users: Vec<User<T::AccountId>>) -> DispatchResult {
       let who = ensure_signed(origin)?;
 
@@ -301,9 +382,10 @@ decl_module! {
 
       Self::ensure_role_manager(who.clone(), role.space_id)?;
 
-      role.revoke_from_users(users.clone());
+      let revoked_users = role.revoke_from_users(users);
 
-      Self::deposit_event(RawEvent::RoleRevoked(who, role_id, users));
+      Self::record_role_history(who.clone(), &role, RoleAction::Revoked, revoked_users.clone());
+      Self::deposit_event(RawEvent::RoleRevoked(who, role.space_id, role_id, revoked_users));
       Ok(())
     }
   }
@@ -2,6 +2,7 @@ use crate::{Module, Trait, RoleId, RoleUpdate};
 
 use sp_core::H256;
 use sp_std::{
+    cell::RefCell,
     collections::btree_set::BTreeSet,
     prelude::Vec,
     iter::FromIterator
@@ -22,8 +23,8 @@ use pallet_permissions::{
     SpacePermission,
     SpacePermission as SP,
 };
-use df_traits::{SpaceForRoles, SpaceFollowsProvider, SpaceForRolesProvider};
-use pallet_utils::{SpaceId, User, Content};
+use df_traits::{ContentValidator, SpaceForRoles, SpaceFollowsProvider, SpaceForRolesProvider, UserResolver};
+use pallet_utils::{SpaceId, User, Content, HistoryOverflowPolicy};
 
 impl_outer_origin! {
   pub enum Origin for Test {}
@@ -101,6 +102,8 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type UnpausablePallets = ();
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -111,18 +114,27 @@ impl pallet_permissions::Trait for Test {
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 20;
+  pub const MaxRolesPerSpace: u16 = 5;
+  pub const MaxRoleHistoryRecordsPerSpace: u32 = 3;
+  pub const RoleHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
 }
 
 impl Trait for Test {
     type Event = ();
+    type ContentValidator = MockContentValidator;
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type MaxRolesPerSpace = MaxRolesPerSpace;
     type Spaces = Roles;
     type SpaceFollows = Roles;
+    type UserResolver = Roles;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type MaxRoleHistoryRecordsPerSpace = MaxRoleHistoryRecordsPerSpace;
+    type RoleHistoryOverflowPolicy = RoleHistoryOverflowPolicy;
+    type WeightInfo = ();
 }
 
-type System = system::Module<Test>;
+pub(crate) type System = system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
 pub(crate) type Roles = Module<Test>;
 
@@ -143,14 +155,48 @@ impl<T: Trait> SpaceForRolesProvider for Module<T> {
     }
 }
 
+impl<T: Trait> UserResolver for Module<T> {
+    type AccountId = AccountId;
+
+    fn owner_of(user: &User<Self::AccountId>) -> Option<Self::AccountId> {
+        match user {
+            User::Account(account) => Some(*account),
+            User::Space(space_id) => <Self as SpaceForRolesProvider>::get_space(*space_id).ok().map(|space| space.owner),
+        }
+    }
+}
+
 impl<T: Trait> SpaceFollowsProvider for Module<T> {
     type AccountId = AccountId;
 
     fn is_space_follower(_account: Self::AccountId, _space_id: u64) -> bool {
         true
     }
+
+    fn count_followers(_space_id: u64, _start_after: Option<Vec<u8>>, _limit: u32) -> (u32, Option<Vec<u8>>) {
+        (0, None)
+    }
 }
 
+thread_local! {
+    static VALIDATED_CONTENT: RefCell<Vec<Content>> = RefCell::new(Vec::new());
+}
+
+/// Records every content it validates, and validates it the same way the utils pallet does.
+pub struct MockContentValidator;
+
+impl MockContentValidator {
+    pub(crate) fn validated_content() -> Vec<Content> {
+        VALIDATED_CONTENT.with(|validated| validated.borrow().clone())
+    }
+}
+
+impl ContentValidator for MockContentValidator {
+    fn is_valid_content(content: Content) -> DispatchResult {
+        VALIDATED_CONTENT.with(|validated| validated.borrow_mut().push(content.clone()));
+        pallet_utils::Module::<Test>::is_valid_content(content)
+    }
+}
 
 pub struct ExtBuilder;
 
@@ -29,6 +29,20 @@ fn create_role_should_work() {
     });
 }
 
+#[test]
+fn create_and_update_role_should_validate_content_with_content_validator() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_eq!(MockContentValidator::validated_content(), vec![self::default_role_content_ipfs()]);
+
+        assert_ok!(_update_default_role());
+        assert_eq!(
+            MockContentValidator::validated_content(),
+            vec![self::default_role_content_ipfs(), self::updated_role_content_ipfs()]
+        );
+    });
+}
+
 #[test]
 fn create_role_should_work_with_a_few_roles() {
     ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
@@ -60,6 +74,49 @@ fn create_role_should_work_with_a_few_roles() {
     });
 }
 
+#[test]
+fn create_role_should_deduplicate_permissions() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(
+            _create_role(
+                None, // From ACCOUNT1
+                None, // On SpaceId 1
+                None, // Without time_to_live
+                None, // With default content
+                Some(vec![
+                    SpacePermission::CreatePosts,
+                    SpacePermission::ManageRoles,
+                    SpacePermission::CreatePosts,
+                ])
+            )
+        ); // RoleId 1
+
+        let role = Roles::role_by_id(ROLE1).unwrap();
+        assert_eq!(role.permissions.len(), 2);
+        assert_eq!(
+            role.permissions,
+            BTreeSet::from_iter(self::permission_set_updated().into_iter())
+        );
+    });
+}
+
+#[test]
+fn create_role_should_fail_when_space_has_max_roles() {
+    ExtBuilder::build().execute_with(|| {
+        for _ in 0..MaxRolesPerSpace::get() {
+            assert_ok!(_create_default_role());
+        }
+        assert_eq!(Roles::role_ids_by_space_id(SPACE1).len(), MaxRolesPerSpace::get() as usize);
+
+        assert_noop!(_create_default_role(), Error::<Test>::TooManyRolesInSpace);
+
+        // After a role is deleted, a new one can be created
+        assert_ok!(_delete_default_role());
+        assert_ok!(_create_default_role());
+        assert_eq!(Roles::role_ids_by_space_id(SPACE1).len(), MaxRolesPerSpace::get() as usize);
+    });
+}
+
 #[test]
 fn create_role_should_fail_with_space_not_found() {
     ExtBuilder::build().execute_with(|| {
@@ -418,6 +475,34 @@ fn grant_role_should_fail_with_no_users_provided() {
     });
 }
 
+#[test]
+fn grant_role_should_work_with_existing_space_user() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Space(SPACE1);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_role(None, None, Some(vec![user.clone(), user.clone()])));
+
+        // Check whether duplicates were removed
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user.clone()]);
+        assert_eq!(Roles::role_ids_by_user_in_space(user, SPACE1), vec![ROLE1]);
+    });
+}
+
+#[test]
+fn grant_role_should_fail_when_space_user_not_found() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_noop!(
+            _grant_role(
+                None, // From ACCOUNT1
+                None, // RoleId 1
+                Some(vec![User::Account(ACCOUNT2), User::Space(SPACE2)])
+            ), UtilsError::<Test>::SpaceUserNotFound
+        );
+    });
+}
+
 #[test]
 fn grant_role_should_fail_with_a_few_roles_no_permission() {
     ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
@@ -590,3 +675,106 @@ fn delete_role_should_fail_with_a_few_roles_no_permission() {
         );
     });
 }
+
+#[test]
+fn role_history_should_record_grant_revoke_and_delete_of_role() {
+    ExtBuilder::build().execute_with(|| {
+        let account2 = User::Account(ACCOUNT2);
+        let account3 = User::Account(ACCOUNT3);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_role(None, None, Some(vec![account3.clone(), account2.clone()])));
+
+        // Nothing is recorded for users that already have the role or never had it
+        assert_ok!(_grant_role(None, None, Some(vec![account2.clone()])));
+        System::set_block_number(2);
+        assert_ok!(_revoke_role(None, None, Some(vec![account2.clone(), User::Account(ACCOUNT1)])));
+
+        System::set_block_number(3);
+        assert_ok!(_delete_default_role());
+
+        let history = Roles::role_history_by_space(SPACE1);
+        let records: Vec<_> = history.iter()
+            .map(|record| (record.changed.block, record.role_id, record.action, record.users.clone()))
+            .collect();
+
+        assert_eq!(records, vec![
+            (1, ROLE1, RoleAction::Granted, vec![account2.clone(), account3.clone()]),
+            (2, ROLE1, RoleAction::Revoked, vec![account2]),
+            (3, ROLE1, RoleAction::Deleted, vec![account3]),
+        ]);
+        assert!(history.iter().all(|record| record.changed.account == ACCOUNT1));
+    });
+}
+
+#[test]
+fn role_history_should_keep_max_records_per_space() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        for _ in 0..MaxRoleHistoryRecordsPerSpace::get() {
+            assert_ok!(_grant_default_role());
+            assert_ok!(_revoke_default_role());
+        }
+
+        let history = Roles::role_history_by_space(SPACE1);
+        assert_eq!(history.len(), MaxRoleHistoryRecordsPerSpace::get() as usize);
+        assert_eq!(history.last().unwrap().action, RoleAction::Revoked);
+
+        // The evicted records are removed from the storage
+        let records_count = Roles::role_history_count_by_space(SPACE1);
+        assert_eq!(records_count, MaxRoleHistoryRecordsPerSpace::get() * 2);
+        assert!(Roles::role_history_record_by_index(SPACE1, 0).is_none());
+        assert!(Roles::role_history_record_by_index(SPACE1, records_count - 1).is_some());
+    });
+}
+
+#[test]
+fn space_permissions_granted_by_roles_should_unite_permissions_of_all_roles() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1 with ManageRoles
+        assert_ok!(_create_role(
+            None, None, None, None,
+            Some(vec![SpacePermission::CreatePosts, SpacePermission::UpdateOwnPosts])
+        )); // RoleId 2
+
+        // Grant the same roles in different order to ACCOUNT2 and ACCOUNT3
+        assert_ok!(_grant_role(None, Some(ROLE1), Some(vec![User::Account(ACCOUNT2)])));
+        assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![User::Account(ACCOUNT2)])));
+        assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![User::Account(ACCOUNT3)])));
+        assert_ok!(_grant_role(None, Some(ROLE1), Some(vec![User::Account(ACCOUNT3)])));
+
+        let expected_permissions = BTreeSet::from_iter(vec![
+            SpacePermission::ManageRoles,
+            SpacePermission::CreatePosts,
+            SpacePermission::UpdateOwnPosts,
+        ]);
+        assert_eq!(Roles::space_permissions_granted_by_roles(User::Account(ACCOUNT2), SPACE1), expected_permissions);
+        assert_eq!(Roles::space_permissions_granted_by_roles(User::Account(ACCOUNT3), SPACE1), expected_permissions);
+
+        assert_ok!(Roles::ensure_role_manager(ACCOUNT2, SPACE1));
+        assert_ok!(Roles::ensure_role_manager(ACCOUNT3, SPACE1));
+    });
+}
+
+#[test]
+fn space_permissions_granted_by_roles_should_skip_disabled_and_expired_roles() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1 with ManageRoles
+        assert_ok!(_create_role(None, None, Some(Some(10)), None, Some(vec![SpacePermission::CreatePosts]))); // RoleId 2
+        assert_ok!(_create_role(None, None, None, None, Some(vec![SpacePermission::UpdateOwnPosts]))); // RoleId 3
+
+        for role_id in &[ROLE1, ROLE2, ROLE3] {
+            assert_ok!(_grant_role(None, Some(*role_id), None));
+        }
+
+        assert_ok!(_update_role(None, Some(ROLE1), Some(self::role_update(Some(true), None, None))));
+        System::set_block_number(11);
+
+        assert_eq!(
+            Roles::space_permissions_granted_by_roles(User::Account(ACCOUNT2), SPACE1),
+            BTreeSet::from_iter(vec![SpacePermission::UpdateOwnPosts])
+        );
+        assert_noop!(Roles::ensure_role_manager(ACCOUNT2, SPACE1), Error::<Test>::NoPermissionToManageRoles);
+    });
+}
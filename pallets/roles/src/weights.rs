@@ -0,0 +1,99 @@
+//! Weights for the Roles pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_roles --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_roles.
+pub trait WeightInfo {
+    fn create_role() -> Weight;
+    fn update_role() -> Weight;
+    fn delete_role(u: u32) -> Weight;
+    fn grant_role(u: u32) -> Weight;
+    fn revoke_role(u: u32) -> Weight;
+}
+
+/// Weights for pallet_roles measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_role() -> Weight {
+        (22_889_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn update_role() -> Weight {
+        (18_955_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn delete_role(u: u32) -> Weight {
+        (32_982_000 as Weight)
+            .saturating_add((8_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+
+    fn grant_role(u: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((14_374_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+
+    fn revoke_role(u: u32) -> Weight {
+        (20_642_000 as Weight)
+            .saturating_add((8_209_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+}
+
+impl WeightInfo for () {
+    fn create_role() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(3 as Weight))
+    }
+
+    fn update_role() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+
+    fn delete_role(u: u32) -> Weight {
+        (1_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(7 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(DbWeight::get().writes(8 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+
+    fn grant_role(u: u32) -> Weight {
+        (1_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+
+    fn revoke_role(u: u32) -> Weight {
+        (1_000_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+            .saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+}
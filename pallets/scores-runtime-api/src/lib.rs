@@ -0,0 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use pallet_posts::PostId;
+use pallet_scores::ScoringAction;
+use pallet_utils::SpaceId;
+
+sp_api::decl_runtime_apis! {
+    /// Read-time ranking of posts and spaces that accounts for their age.
+    pub trait ScoresApi {
+        /// Get the score of a post divided by a factor that grows with the post's age.
+        fn normalized_post_score(post_id: PostId) -> Option<i32>;
+
+        /// Get the score of a space divided by a factor that grows with the space's age.
+        fn normalized_space_score(space_id: SpaceId) -> Option<i32>;
+
+        /// Get the reputation diff of an action performed by an account with a given reputation,
+        /// so clients can preview a reputation gain without submitting a transaction.
+        fn score_for_action(reputation: u32, action: ScoringAction) -> i16;
+    }
+}
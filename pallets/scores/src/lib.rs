@@ -3,11 +3,11 @@
 use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult, ensure, traits::Get,
+    dispatch::DispatchResult, ensure, traits::{EnsureOrigin, Get},
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::Zero};
 use sp_std::prelude::*;
-use frame_system::{self as system};
+use frame_system::{self as system, ensure_signed};
 
 use pallet_posts::{PostScores, Post, PostById, PostExtension, PostId};
 use pallet_profile_follows::{BeforeAccountFollowed, BeforeAccountUnfollowed};
@@ -15,7 +15,10 @@ use pallet_profiles::{Module as Profiles, SocialAccountById};
 use pallet_reactions::{PostReactionScores, ReactionKind};
 use pallet_space_follows::{BeforeSpaceFollowed, BeforeSpaceUnfollowed};
 use pallet_spaces::{Space, SpaceById};
-use pallet_utils::log_2;
+use pallet_utils::{PalletVersion, log_2};
+
+pub mod migrations;
+pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
 pub enum ScoringAction {
@@ -30,12 +33,43 @@ pub enum ScoringAction {
     FollowAccount,
 }
 
+impl ScoringAction {
+    /// Actions whose scores are stored per post in `PostScoreByAccount`.
+    pub const POST_ACTIONS: [ScoringAction; 7] = [
+        ScoringAction::UpvotePost,
+        ScoringAction::DownvotePost,
+        ScoringAction::SharePost,
+        ScoringAction::CreateComment,
+        ScoringAction::UpvoteComment,
+        ScoringAction::DownvoteComment,
+        ScoringAction::ShareComment,
+    ];
+
+    /// The kind of a reaction that this action is taken on, if it is a reaction to a post or a comment.
+    pub fn reaction_kind(&self) -> Option<ReactionKind> {
+        match self {
+            ScoringAction::UpvotePost | ScoringAction::UpvoteComment => Some(ReactionKind::Upvote),
+            ScoringAction::DownvotePost | ScoringAction::DownvoteComment => Some(ReactionKind::Downvote),
+            _ => None,
+        }
+    }
+}
+
 impl Default for ScoringAction {
     fn default() -> Self {
         ScoringAction::FollowAccount
     }
 }
 
+/// Scored actions that an account has done in a scoring period.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct ActivityInPeriod<BlockNumber> {
+    /// The first block of the period.
+    pub period_start: BlockNumber,
+    /// The number of follows, reactions and comments that were scored in this period.
+    pub actions_count: u32,
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
@@ -49,7 +83,7 @@ pub trait Trait: system::Trait
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
-    // Weights of the social actions
+    // Default weights of the social actions. They can be overridden by `set_scoring_weights`.
     type FollowSpaceActionWeight: Get<i16>;
     type FollowAccountActionWeight: Get<i16>;
 
@@ -61,6 +95,25 @@ pub trait Trait: system::Trait
     type ShareCommentActionWeight: Get<i16>;
     type UpvoteCommentActionWeight: Get<i16>;
     type DownvoteCommentActionWeight: Get<i16>;
+
+    /// The number of blocks after which a score of a post or a space decays one more step
+    /// when it is normalized by age. See `rpc::normalize_score`.
+    type ScoreDecayPeriodInBlocks: Get<Self::BlockNumber>;
+
+    /// The origin which may change weights of the scoring actions.
+    type SetScoringWeightsOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The maximum number of accounts whose scores of a deleted post
+    /// can be removed in a single `remove_deleted_post_scores` call.
+    type MaxAccountsPerPostScoresCleanup: Get<u16>;
+
+    /// The number of blocks in a period in which scored actions of an account are counted.
+    type ScoringPeriodInBlocks: Get<Self::BlockNumber>;
+
+    /// The number of actions of an account per scoring period that are scored in full.
+    /// Further actions are scored less and less, and from twice this number on they are not
+    /// scored at all, though the actions themselves are still allowed. Zero disables the limit.
+    type MaxScoredActionsPerPeriod: Get<u32>;
 }
 
 decl_error! {
@@ -71,6 +124,14 @@ decl_error! {
         NotRootPost,
         /// Post extension is not a comment.
         NotComment,
+        /// Nothing to update in scoring weights.
+        NoScoringWeightsProvided,
+        /// Scores of a post can be removed only after the post is deleted.
+        PostIsNotDeleted,
+        /// No accounts provided to remove their post scores.
+        NoAccountsProvided,
+        /// Too many accounts provided to remove their post scores in a single call.
+        TooManyAccountsToCleanUp,
     }
 }
 
@@ -78,12 +139,38 @@ decl_error! {
 decl_storage! {
     trait Store for Module<T: Trait> as ScoresModule {
 
+        /// The version of the storage layout of this pallet, see `pallet_spaces::StorageVersion`.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
         // TODO shorten name? (refactor)
         pub AccountReputationDiffByAccount get(fn account_reputation_diff_by_account):
             map hasher(blake2_128_concat) (/* actor */ T::AccountId, /* subject */ T::AccountId, ScoringAction) => Option<i16>;
 
         pub PostScoreByAccount get(fn post_score_by_account):
             map hasher(blake2_128_concat) (/* actor */ T::AccountId, /* subject */ PostId, ScoringAction) => Option<i16>;
+
+        /// Weights of the scoring actions that were set by `set_scoring_weights`
+        /// instead of the defaults from the runtime config.
+        pub ScoringWeightByAction get(fn scoring_weight_by_action):
+            map hasher(twox_64_concat) ScoringAction => Option<i16>;
+
+        /// Weights of reactions by their kind, to posts and comments alike. They are set at genesis
+        /// or by `set_reaction_kind_weights`. A reaction of a kind that has no weight here
+        /// is scored with the default weight of its action, e.g. `UpvotePostActionWeight`.
+        pub ReactionKindWeights get(fn reaction_kind_weight) config(reaction_kind_weights):
+            map hasher(twox_64_concat) ReactionKind => Option<i16>;
+
+        /// Scored actions of an account (key) in the latest scoring period it was active in.
+        /// Only tracked if `MaxScoredActionsPerPeriod` is not zero.
+        pub AccountActivity get(fn account_activity):
+            map hasher(blake2_128_concat) T::AccountId => ActivityInPeriod<T::BlockNumber>;
+
+        /// The number of shares of an original post by an account that have not been deleted.
+        /// A share is scored only once per account, so its score is reverted
+        /// when the last share of this account is deleted.
+        pub SharesCountByAccount get(fn shares_count_by_account):
+            map hasher(blake2_128_concat) (/* sharer */ T::AccountId, /* original post */ PostId) => u32;
     }
 }
 
@@ -92,6 +179,15 @@ decl_event!(
         <T as system::Trait>::AccountId,
     {
         AccountReputationChanged(AccountId, ScoringAction, u32),
+        /// Weights of the scoring actions were changed. [weights]
+        ScoringWeightsUpdated(Vec<(ScoringAction, i16)>),
+        /// Weights of the reaction kinds were changed. [weights]
+        ReactionKindWeightsUpdated(Vec<(ReactionKind, i16)>),
+        /// An account changed the kind of its reaction, so the score of the old scoring action
+        /// was reverted and the score of the new one applied. [actor, post_id, old_action, new_action]
+        ReactionScoreChanged(AccountId, PostId, ScoringAction, ScoringAction),
+        /// Scores given by accounts to a deleted post were removed. [who, post_id, removed_count]
+        DeletedPostScoresRemoved(AccountId, PostId, u32),
     }
 );
 
@@ -99,7 +195,7 @@ decl_event!(
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 
-        /// Weights of the related social account actions
+        /// Default weights of the related social account actions
         const FollowSpaceActionWeight: i16 = T::FollowSpaceActionWeight::get();
         const FollowAccountActionWeight: i16 = T::FollowAccountActionWeight::get();
         const UpvotePostActionWeight: i16 = T::UpvotePostActionWeight::get();
@@ -110,11 +206,84 @@ decl_module! {
         const DownvoteCommentActionWeight: i16 = T::DownvoteCommentActionWeight::get();
         const ShareCommentActionWeight: i16 = T::ShareCommentActionWeight::get();
 
+        /// The number of blocks after which a normalized score decays one more step.
+        const ScoreDecayPeriodInBlocks: T::BlockNumber = T::ScoreDecayPeriodInBlocks::get();
+
+        const MaxAccountsPerPostScoresCleanup: u16 = T::MaxAccountsPerPostScoresCleanup::get();
+
+        const ScoringPeriodInBlocks: T::BlockNumber = T::ScoringPeriodInBlocks::get();
+
+        const MaxScoredActionsPerPeriod: u32 = T::MaxScoredActionsPerPeriod::get();
+
         // Initializing errors
         type Error = Error<T>;
 
         // Initializing events
         fn deposit_event() = default;
+
+        /// Override weights of the given scoring actions. New weights apply only to future
+        /// actions: when a score is reverted, the diff that was given before is reverted.
+        /// Can only be called by `SetScoringWeightsOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().writes(weights.len() as u64)]
+        pub fn set_scoring_weights(origin, weights: Vec<(ScoringAction, i16)>) -> DispatchResult {
+            T::SetScoringWeightsOrigin::ensure_origin(origin)?;
+
+            ensure!(!weights.is_empty(), Error::<T>::NoScoringWeightsProvided);
+
+            for (action, weight) in weights.iter() {
+                ScoringWeightByAction::insert(action, weight);
+            }
+
+            Self::deposit_event(RawEvent::ScoringWeightsUpdated(weights));
+            Ok(())
+        }
+
+        /// Set weights of the given reaction kinds. Like `set_scoring_weights`, new weights
+        /// apply only to future reactions. Can only be called by `SetScoringWeightsOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().writes(weights.len() as u64)]
+        pub fn set_reaction_kind_weights(origin, weights: Vec<(ReactionKind, i16)>) -> DispatchResult {
+            T::SetScoringWeightsOrigin::ensure_origin(origin)?;
+
+            ensure!(!weights.is_empty(), Error::<T>::NoScoringWeightsProvided);
+
+            for (kind, weight) in weights.iter() {
+                ReactionKindWeights::insert(kind, weight);
+            }
+
+            Self::deposit_event(RawEvent::ReactionKindWeightsUpdated(weights));
+            Ok(())
+        }
+
+        /// Remove the scores that the given accounts gave to a post that was deleted.
+        /// A post is deleted if it is not in any space, e.g. a sharing post deleted by `delete_post`,
+        /// or if it is not in storage. A comment is deleted if its root post is.
+        /// Post scores are keyed by an account, so they cannot be found by a post id alone:
+        /// the accounts that reacted to, commented or shared the post should be provided,
+        /// e.g. by an off-chain indexer. The cleanup is best-effort: scores of the accounts
+        /// that were not provided stay in storage. Can be called by anyone.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(
+            3,
+            (accounts.len() * ScoringAction::POST_ACTIONS.len()) as u64
+        )]
+        pub fn remove_deleted_post_scores(origin, post_id: PostId, accounts: Vec<T::AccountId>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!accounts.is_empty(), Error::<T>::NoAccountsProvided);
+            ensure!(
+                accounts.len() <= T::MaxAccountsPerPostScoresCleanup::get() as usize,
+                Error::<T>::TooManyAccountsToCleanUp
+            );
+            let is_post_deleted = <PostById<T>>::get(post_id)
+                .map_or(true, |post| post.try_get_space().is_none());
+            ensure!(is_post_deleted, Error::<T>::PostIsNotDeleted);
+
+            let removed_count = accounts.into_iter()
+                .map(|account| Self::remove_post_scores_of_account(account, post_id))
+                .sum();
+
+            Self::deposit_event(RawEvent::DeletedPostScoresRemoved(who, post_id, removed_count));
+            Ok(())
+        }
     }
 }
 
@@ -136,6 +305,20 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    /// Remove the scores of all post actions that this account gave to a post.
+    /// Returns the number of removed scores.
+    pub fn remove_post_scores_of_account(account: T::AccountId, post_id: PostId) -> u32 {
+        let mut removed_count = 0;
+        for action in ScoringAction::POST_ACTIONS.iter() {
+            let key = (account.clone(), post_id, *action);
+            if <PostScoreByAccount<T>>::contains_key(&key) {
+                <PostScoreByAccount<T>>::remove(&key);
+                removed_count += 1;
+            }
+        }
+        removed_count
+    }
+
     fn change_post_score_with_reaction(
         actor: T::AccountId,
         post: &mut Post<T>,
@@ -185,7 +368,8 @@ impl<T: Trait> Module<T> {
             return Ok(())
         }
 
-        let mut space = post.get_space()?;
+        // A post that was moved out of its space still has a score, but no space to update.
+        let mut space_opt = post.try_get_space();
 
         if let Some(score_diff) = Self::post_score_by_account((account.clone(), post_id, action)) {
             let reputation_diff = Self::account_reputation_diff_by_account((account.clone(), post.owner.clone(), action))
@@ -193,7 +377,9 @@ impl<T: Trait> Module<T> {
 
             // Revert this score diff:
             post.change_score(-score_diff);
-            space.change_score(-score_diff);
+            if let Some(space) = space_opt.as_mut() {
+                space.change_score(-score_diff);
+            }
             Self::change_social_account_reputation(post.owner.clone(), account.clone(), -reputation_diff, action)?;
             <PostScoreByAccount<T>>::remove((account, post_id, action));
         } else {
@@ -212,15 +398,19 @@ impl<T: Trait> Module<T> {
                 }
                 _ => (),
             }
-            let score_diff = Self::score_diff_for_action(social_account.reputation, action);
+            let score_diff = Self::score_diff_of_new_action(&account, social_account.reputation, action);
             post.change_score(score_diff);
-            space.change_score(score_diff);
+            if let Some(space) = space_opt.as_mut() {
+                space.change_score(score_diff);
+            }
             Self::change_social_account_reputation(post.owner.clone(), account.clone(), score_diff, action)?;
             <PostScoreByAccount<T>>::insert((account, post_id, action), score_diff);
         }
 
         <PostById<T>>::insert(post_id, post.clone());
-        <SpaceById<T>>::insert(space.id, space);
+        if let Some(space) = space_opt {
+            <SpaceById<T>>::insert(space.id, space);
+        }
 
         Ok(())
     }
@@ -273,7 +463,7 @@ impl<T: Trait> Module<T> {
                 }
                 _ => (),
             }
-            let score_diff = Self::score_diff_for_action(social_account.reputation, action);
+            let score_diff = Self::score_diff_of_new_action(&account, social_account.reputation, action);
             comment.change_score(score_diff);
             Self::change_social_account_reputation(comment.owner.clone(), account.clone(), score_diff, action)?;
             <PostScoreByAccount<T>>::insert((account, comment_id, action), score_diff);
@@ -296,8 +486,9 @@ impl<T: Trait> Module<T> {
         // TODO seems like we can pass a &mut social account as an arg to this func
         let mut social_account = Profiles::get_or_new_social_account(account.clone());
 
-        if social_account.reputation as i64 + score_diff as i64 <= 1 {
-            social_account.reputation = 1;
+        let base_reputation = <T as pallet_profiles::Trait>::BaseReputation::get();
+        if social_account.reputation as i64 + score_diff as i64 <= base_reputation as i64 {
+            social_account.reputation = base_reputation;
             score_diff = 0;
         }
 
@@ -320,6 +511,51 @@ impl<T: Trait> Module<T> {
         Self::smooth_reputation(reputation) as i16 * Self::weight_of_scoring_action(action)
     }
 
+    /// Get a score diff of a new action of `actor` and count this action in `AccountActivity`.
+    /// The diff is diminished if the actor has exceeded `MaxScoredActionsPerPeriod`.
+    fn score_diff_of_new_action(actor: &T::AccountId, reputation: u32, action: ScoringAction) -> i16 {
+        let score_diff = Self::score_diff_for_action(reputation, action);
+
+        let max_actions = T::MaxScoredActionsPerPeriod::get();
+        if max_actions == 0 {
+            return score_diff;
+        }
+
+        let actions_count = Self::count_scored_action(actor);
+        Self::diminish_score_diff(score_diff, actions_count, max_actions)
+    }
+
+    /// Count a scored action of `actor` in the current scoring period
+    /// and return the number of its actions in this period, including the new one.
+    fn count_scored_action(actor: &T::AccountId) -> u32 {
+        let now = <system::Module<T>>::block_number();
+        let period = T::ScoringPeriodInBlocks::get();
+        let period_start = if period.is_zero() { Zero::zero() } else { now - now % period };
+
+        AccountActivity::<T>::mutate(actor, |activity| {
+            if activity.period_start != period_start {
+                *activity = ActivityInPeriod { period_start, actions_count: 0 };
+            }
+            activity.actions_count = activity.actions_count.saturating_add(1);
+            activity.actions_count
+        })
+    }
+
+    /// Scale down a score diff of the `actions_count`-th action in a period: it is scored in full
+    /// up to `max_actions`, then decreases linearly and is zero from `2 * max_actions` on.
+    pub fn diminish_score_diff(score_diff: i16, actions_count: u32, max_actions: u32) -> i16 {
+        if max_actions == 0 || actions_count <= max_actions {
+            return score_diff;
+        }
+
+        let hard_cap = max_actions.saturating_mul(2);
+        if actions_count >= hard_cap {
+            return 0;
+        }
+
+        (score_diff as i64 * (hard_cap - actions_count) as i64 / max_actions as i64) as i16
+    }
+
     fn smooth_reputation(reputation: u32) -> u8 {
         log_2(reputation).map_or(1, |r| {
             let d = (reputation as u64 - (2 as u64).pow(r)) * 100
@@ -330,7 +566,17 @@ impl<T: Trait> Module<T> {
         })
     }
 
-    fn weight_of_scoring_action(action: ScoringAction) -> i16 {
+    /// Get a weight of a scoring action. A weight set for this action by `set_scoring_weights`
+    /// takes precedence over a weight of a reaction kind, which takes precedence over the default.
+    pub fn weight_of_scoring_action(action: ScoringAction) -> i16 {
+        if let Some(weight) = Self::scoring_weight_by_action(action) {
+            return weight;
+        }
+
+        if let Some(weight) = action.reaction_kind().and_then(Self::reaction_kind_weight) {
+            return weight;
+        }
+
         use ScoringAction::*;
         match action {
             UpvotePost => T::UpvotePostActionWeight::get(),
@@ -352,7 +598,7 @@ impl<T: Trait> BeforeSpaceFollowed<T> for Module<T> {
         if !space.is_owner(&follower) {
             let space_owner = space.owner.clone();
             let action = ScoringAction::FollowSpace;
-            let score_diff = Self::score_diff_for_action(follower_reputation, action);
+            let score_diff = Self::score_diff_of_new_action(&follower, follower_reputation, action);
             space.change_score(score_diff);
             return Self::change_social_account_reputation(
                 space_owner, follower, score_diff, action)
@@ -383,7 +629,7 @@ impl<T: Trait> BeforeSpaceUnfollowed<T> for Module<T> {
 impl<T: Trait> BeforeAccountFollowed<T> for Module<T> {
     fn before_account_followed(follower: T::AccountId, follower_reputation: u32, following: T::AccountId) -> DispatchResult {
         let action = ScoringAction::FollowAccount;
-        let score_diff = Self::score_diff_for_action(follower_reputation, action);
+        let score_diff = Self::score_diff_of_new_action(&follower, follower_reputation, action);
         Self::change_social_account_reputation(following, follower, score_diff, action)
     }
 }
@@ -411,6 +657,8 @@ impl<T: Trait> PostScores<T> for Module<T> {
                 (account.clone(), original_post.id, action)
             ).is_none();
 
+        SharesCountByAccount::<T>::mutate((account.clone(), original_post.id), |count| *count = count.saturating_add(1));
+
         // It makes sense to change a score of this post only once:
         // i.e. when this account sharing it for the first time.
         if account_never_shared_this_post {
@@ -420,6 +668,35 @@ impl<T: Trait> PostScores<T> for Module<T> {
         }
     }
 
+    fn score_post_on_share_removed(account: T::AccountId, original_post: &mut Post<T>) -> DispatchResult {
+        let action =
+            if original_post.is_comment() { ScoringAction::ShareComment }
+            else { ScoringAction::SharePost };
+
+        let account_scored_this_post =
+            Self::post_score_by_account(
+                (account.clone(), original_post.id, action)
+            ).is_some();
+
+        let shares_count_key = (account.clone(), original_post.id);
+        let shares_count = Self::shares_count_by_account(&shares_count_key).saturating_sub(1);
+        if shares_count == 0 {
+            SharesCountByAccount::<T>::remove(&shares_count_key);
+        } else {
+            SharesCountByAccount::<T>::insert(&shares_count_key, shares_count);
+        }
+
+        let account_still_shares_this_post = shares_count > 0;
+
+        // A score was changed only on the first share of this account,
+        // so revert it only when its last share of this post is removed:
+        if account_scored_this_post && !account_still_shares_this_post {
+            Self::change_post_score(account, original_post, action)
+        } else {
+            Ok(())
+        }
+    }
+
     fn score_root_post_on_new_comment(account: T::AccountId, root_post: &mut Post<T>) -> DispatchResult {
         Self::change_post_score(account, root_post, ScoringAction::CreateComment)
     }
@@ -433,4 +710,25 @@ impl<T: Trait> PostReactionScores<T> for Module<T> {
     ) -> DispatchResult {
         Self::change_post_score_with_reaction(actor, post, reaction_kind)
     }
+
+    fn score_post_on_reaction_change(
+        actor: T::AccountId,
+        post: &mut Post<T>,
+        old_kind: ReactionKind,
+        new_kind: ReactionKind,
+    ) -> DispatchResult {
+        // Post owner should not be able to change the score of their post.
+        if post.is_owner(&actor) {
+            return Ok(())
+        }
+
+        let old_action = Self::scoring_action_by_post_extension(post.extension, old_kind);
+        let new_action = Self::scoring_action_by_post_extension(post.extension, new_kind);
+
+        Self::change_post_score(actor.clone(), post, old_action)?;
+        Self::change_post_score(actor.clone(), post, new_action)?;
+
+        Self::deposit_event(RawEvent::ReactionScoreChanged(actor, post.id, old_action, new_action));
+        Ok(())
+    }
 }
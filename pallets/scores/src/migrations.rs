@@ -0,0 +1,69 @@
+//! Storage migrations of the Scores pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use frame_support::{
+    traits::Get,
+    weights::Weight,
+    IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_std::collections::btree_map::BTreeMap;
+
+use pallet_posts::{PostById, PostId, SharedPostIdsByOriginalPostId};
+use pallet_utils::PalletVersion;
+
+use crate::{Module, SharesCountByAccount, StorageVersion, Trait};
+
+/// The crate version that added `SharesCountByAccount`.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_7_0;
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Count the existing shares of every original post by their authors.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let mut reads = 0u64;
+    let mut shares_count = BTreeMap::<(T::AccountId, PostId), u32>::new();
+
+    for (original_post_id, share_ids) in SharedPostIdsByOriginalPostId::iter() {
+        reads = reads.saturating_add(1);
+
+        for share in share_ids.into_iter().filter_map(PostById::<T>::get) {
+            reads = reads.saturating_add(1);
+
+            let count = shares_count.entry((share.created.account, original_post_id)).or_insert(0);
+            *count = count.saturating_add(1);
+        }
+    }
+
+    let writes = shares_count.len() as u64;
+
+    for (key, count) in shares_count {
+        SharesCountByAccount::<T>::insert(key, count);
+    }
+
+    T::DbWeight::get().reads_writes(reads, writes)
+}
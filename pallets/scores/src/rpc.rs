@@ -0,0 +1,49 @@
+use frame_support::{traits::Get, StorageMap};
+use sp_runtime::traits::{One, Saturating, UniqueSaturatedInto};
+
+use pallet_posts::PostId;
+use pallet_utils::SpaceId;
+
+use crate::{Module, PostById, ScoringAction, SpaceById, Trait};
+
+/// Normalized scores are multiplied by this factor to keep some precision after division.
+pub const NORMALIZED_SCORE_PRECISION: i64 = 1_000;
+
+impl<T: Trait> Module<T> {
+    /// Get the score of a post normalized by its age. See `normalize_score` for the formula.
+    pub fn normalized_post_score(post_id: PostId) -> Option<i32> {
+        PostById::<T>::get(post_id)
+            .map(|post| Self::normalize_score(post.score, post.created.block))
+    }
+
+    /// Get the score of a space normalized by its age. See `normalize_score` for the formula.
+    pub fn normalized_space_score(space_id: SpaceId) -> Option<i32> {
+        SpaceById::<T>::get(space_id)
+            .map(|space| Self::normalize_score(space.score, space.created.block))
+    }
+
+    /// Compute a "hotness" of an entity, so that entities of different age can be compared:
+    ///
+    /// `normalized = score * NORMALIZED_SCORE_PRECISION / (age / ScoreDecayPeriodInBlocks + 2)^2`
+    ///
+    /// where `age` is the number of blocks since the entity was created.
+    /// Thus a newer entity ranks higher than an older one with the same raw score.
+    /// The result is saturated to fit into `i32`.
+    pub fn normalize_score(score: i32, created_at_block: T::BlockNumber) -> i32 {
+        let age = frame_system::Module::<T>::block_number().saturating_sub(created_at_block);
+        let decay_period = T::ScoreDecayPeriodInBlocks::get().max(One::one());
+        let age_in_periods: u64 = (age / decay_period).unique_saturated_into();
+
+        let decay = (age_in_periods as i64).saturating_add(2).saturating_pow(2);
+        let normalized = (score as i64).saturating_mul(NORMALIZED_SCORE_PRECISION) / decay;
+
+        normalized.max(i32::min_value() as i64).min(i32::max_value() as i64) as i32
+    }
+
+    /// Get the reputation that an author would gain (or lose, if negative)
+    /// when an account with `reputation` performs `action` on their content.
+    pub fn score_for_action(reputation: u32, action: ScoringAction) -> i16 {
+        Self::score_diff_for_action(reputation, action)
+    }
+
+}
@@ -140,6 +140,8 @@ decl_error! {
         SessionKeyLimitReached,
         /// Only a session key owner can manage their keys.
         NotASessionKeyOwner,
+        /// This call is not allowed to be dispatched with a session key.
+        CallNotAllowed,
     }
 }
 
@@ -183,7 +185,7 @@ decl_module! {
 
         /// Add a new SessionKey for `origin` bonding `BaseSessionKeyBond` to keep session alive
         #[weight = 10_000 + T::DbWeight::get().reads_writes(3, 3)]
-        fn add_key(origin,
+        pub fn add_key(origin,
             key_account: T::AccountId,
             time_to_live: T::BlockNumber,
             limit: Option<BalanceOf<T>>,
@@ -220,7 +222,7 @@ decl_module! {
 
         /// A key could be removed either the origin is an owner or key is expired.
         #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
-        fn remove_key(origin, key_account: T::AccountId) -> DispatchResult {
+        pub fn remove_key(origin, key_account: T::AccountId) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             let key = Self::require_key(key_account.clone())?;
@@ -233,7 +235,7 @@ decl_module! {
 
         /// Unregister all session keys for the sender.
         #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 2) * T::MaxSessionKeysPerAccount::get() as u64]
-        fn remove_keys(origin) -> DispatchResult {
+        pub fn remove_keys(origin) -> DispatchResult {
             let who = ensure_signed(origin)?;
             let keys = KeysByOwner::<T>::take(&who);
             for key in keys {
@@ -247,10 +249,11 @@ decl_module! {
 
         /// Execute the call by a session key (`origin`) on behalf of its owner.
         #[weight = CalculateProxyWeight::<T>(call.clone())]
-        fn proxy(origin, call: Box<<T as Trait>::Call>) -> DispatchResult {
+        pub fn proxy(origin, call: Box<<T as Trait>::Call>) -> DispatchResult {
             let key = ensure_signed(origin)?;
 
             let mut details = Self::require_key(key.clone())?;
+            ensure!(T::BaseFilter::filter(&call), Error::<T>::CallNotAllowed);
 
             if details.is_expired() {
                 Self::try_remove_key(details.created.account, key)?;
@@ -258,9 +261,8 @@ decl_module! {
             }
 
             let real = details.owner();
-            let can_spend: BalanceOf<T>;
 
-            // TODO check that this call is among allowed calls per this account/session key.
+            // Nested calls (e.g. a batch) should pass the same filter as the proxied call.
             let mut origin: T::Origin = frame_system::RawOrigin::Signed(real.clone()).into();
 			origin.add_filter(move |c: &<T as frame_system::Trait>::Call| {
 				let c = <T as Trait>::Call::from_ref(c);
@@ -272,10 +274,8 @@ decl_module! {
                 let spent_on_call = Self::get_extrinsic_fees(call.clone());
 
                 // TODO get limit from account settings
-                if let Some(limit) = details.limit {
-                    can_spend = limit.saturating_sub(details.spent);
-                    ensure!(can_spend >= spent_on_call, Error::<T>::SessionKeyLimitReached);
-                }
+                let can_spend = details.spending_limit().saturating_sub(details.spent);
+                ensure!(can_spend >= spent_on_call, Error::<T>::SessionKeyLimitReached);
 
                 <T as TransactionPaymentTrait>::Currency::transfer(&real, &key, spent_on_call, ExistenceRequirement::KeepAlive)?;
 
@@ -330,6 +330,13 @@ impl<T: Trait> SessionKey<T> {
     pub fn is_expired(&self) -> bool {
         self.expires_at <= <system::Module<T>>::block_number()
     }
+
+    /// Max amount of tokens this session key can spend on behalf of its owner.
+    /// It never exceeds the bond transferred to the session key.
+    pub fn spending_limit(&self) -> BalanceOf<T> {
+        let bond = T::BaseSessionKeyBond::get();
+        self.limit.map_or(bond, |limit| limit.min(bond))
+    }
 }
 
 impl<T: Trait> Module<T> {
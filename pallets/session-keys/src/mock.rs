@@ -3,7 +3,9 @@ use crate::{Module, Trait};
 use sp_io::TestExternalities;
 use sp_core::H256;
 use sp_runtime::{
-    traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill, Perquintill, FixedPointNumber,
+    traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId},
+    transaction_validity::{TransactionLongevity, TransactionPriority},
+    Perbill, Perquintill, FixedPointNumber,
 };
 
 use frame_system as system;
@@ -14,11 +16,11 @@ use frame_support::{
 };
 
 use pallet_profile_follows::Call as ProfileFollowsCall;
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Filter};
 pub use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 
 // TODO: replace with imported constants from Runtime
-pub const SMNS: Balance = 21_000_000;
+pub const SMNS: Balance = 1_000_000_000_000;
 pub const DOLLARS: Balance = SMNS;             // 1_000_000_000_000
 pub const CENTS: Balance = DOLLARS / 100;      // 10_000_000_000
 pub const MILLICENTS: Balance = CENTS / 1_000; // 10_000_000
@@ -111,19 +113,47 @@ impl pallet_utils::Trait for Test {
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type UnpausablePallets = ();
+}
+
+parameter_types! {
+    pub const UnsignedFollowsAllowance: u32 = 2;
+    pub const MaxUnsignedFollowsPerBlock: u32 = 10;
+    pub const UnsignedFollowerMinBalance: u64 = 10;
+    pub const UnsignedFollowPriority: TransactionPriority = TransactionPriority::max_value();
+    pub const UnsignedFollowLongevity: TransactionLongevity = 5;
+}
+
+impl system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
 }
 
 impl pallet_profile_follows::Trait for Test {
     type Event = ();
     type BeforeAccountFollowed = ();
     type BeforeAccountUnfollowed = ();
+    type UnsignedFollowSignature = TestSignature;
+    type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+    type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+    type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+    type UnsignedFollowPriority = UnsignedFollowPriority;
+    type UnsignedFollowLongevity = UnsignedFollowLongevity;
+    type WeightInfo = ();
 }
 
-parameter_types! {}
+parameter_types! {
+    pub const BaseReputation: u32 = 1;
+}
 
 impl pallet_profiles::Trait for Test {
     type Event = ();
+    type ContentValidator = pallet_utils::Module<Test>;
+    type BeforeProfileUpdated = ();
     type AfterProfileUpdated = ();
+    type IdentityProvider = ();
+    type BaseReputation = BaseReputation;
 }
 
 // TODO export to a common place
@@ -148,11 +178,18 @@ parameter_types! {
     pub const BaseSessionKeyBond: Balance = DEFAULT_SESSION_KEY_BALANCE;
 }
 
+pub struct SessionKeysProxyFilter;
+impl Filter<Call> for SessionKeysProxyFilter {
+    fn filter(c: &Call) -> bool {
+        matches!(*c, Call::ProfileFollows(..))
+    }
+}
+
 impl Trait for Test {
     type Event = ();
     type Call = Call;
     type MaxSessionKeysPerAccount = MaxSessionKeysPerAccount;
-    type BaseFilter = ();
+    type BaseFilter = SessionKeysProxyFilter;
     type BaseSessionKeyBond = BaseSessionKeyBond;
 }
 
@@ -207,6 +244,10 @@ pub(crate) const fn follow_account_proxy_call() -> Call {
     Call::ProfileFollows(ProfileFollowsCall::follow_account(ACCOUNT_PROXY))
 }
 
+pub(crate) fn transfer_proxy_call() -> Call {
+    Call::Balances(pallet_balances::Call::transfer(ACCOUNT_PROXY, DEFAULT_SESSION_KEY_BALANCE))
+}
+
 pub(crate) fn _add_default_key() -> DispatchResult {
     _add_key(None, None, None, None)
 }
@@ -202,4 +202,25 @@ fn proxy_should_fail_with_session_key_limit_reached() {
         assert_ok!(_default_proxy());
         assert_noop!(_default_proxy(), Error::<Test>::SessionKeyLimitReached);
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn proxy_should_fail_when_call_is_not_allowed() {
+    ExtBuilder::build_with_balance().execute_with(|| {
+        assert_ok!(_add_default_key());
+        let account_balance_after_key_created = Balances::free_balance(ACCOUNT_MAIN);
+
+        assert_noop!(_proxy(None, Some(transfer_proxy_call())), Error::<Test>::CallNotAllowed);
+        assert_eq!(Balances::free_balance(ACCOUNT_MAIN), account_balance_after_key_created);
+    });
+}
+
+#[test]
+fn add_key_should_cap_spending_limit_by_bond() {
+    ExtBuilder::build_with_balance().execute_with(|| {
+        assert_ok!(_add_key(None, None, None, Some(Some(DEFAULT_SESSION_KEY_BALANCE * 2))));
+
+        let details = SessionKeys::key_details(ACCOUNT_PROXY).unwrap();
+        assert_eq!(details.spending_limit(), DEFAULT_SESSION_KEY_BALANCE);
+    });
+}
@@ -0,0 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use pallet_space_follows::rpc::FlatSpaceFollower;
+use pallet_utils::SpaceId;
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over space followers that would otherwise require several storage lookups.
+    pub trait SpaceFollowsApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        /// Get a page of followers of a space with the time they followed it:
+        /// up to `limit` followers after the first `offset` ones.
+        fn space_followers(space_id: SpaceId, offset: u32, limit: u32) -> Vec<FlatSpaceFollower<AccountId, BlockNumber, Moment>>;
+
+        /// Get the number of followers of a space.
+        fn space_followers_count(space_id: SpaceId) -> u32;
+
+        /// Whether each of `candidates` follows a space, in the same order as `candidates`.
+        fn accounts_following_space(space_id: SpaceId, candidates: Vec<AccountId>) -> Vec<bool>;
+
+        /// Get up to `limit` followers of a space that `account` follows itself,
+        /// e.g. to show a space as "followed by X and Y whom you follow".
+        fn space_followers_followed_by(account: AccountId, space_id: SpaceId, limit: u32) -> Vec<AccountId>;
+    }
+}
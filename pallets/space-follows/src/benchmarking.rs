@@ -0,0 +1,59 @@
+//! Benchmarks for the Space Follows pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use pallet_utils::Content;
+
+const SEED: u32 = 0;
+
+fn create_space<T: Trait>() -> Result<SpaceId, &'static str> {
+    let owner: T::AccountId = account("owner", 0, SEED);
+    let space_id = Spaces::<T>::next_space_id();
+    Spaces::<T>::create_space(RawOrigin::Signed(owner).into(), None, None, Content::None, None, None)?;
+    Ok(space_id)
+}
+
+benchmarks! {
+    _ { }
+
+    follow_space {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>()?;
+    }: _(RawOrigin::Signed(caller.clone()), space_id)
+    verify {
+        assert!(Module::<T>::space_followed_by_account((caller, space_id)));
+    }
+
+    unfollow_space {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>()?;
+        Module::<T>::follow_space(RawOrigin::Signed(caller.clone()).into(), space_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), space_id)
+    verify {
+        assert!(!Module::<T>::space_followed_by_account((caller, space_id)));
+    }
+
+    force_unfollow_missing_space {
+        let caller: T::AccountId = whitelisted_caller();
+        let space_id = create_space::<T>()?;
+        Module::<T>::follow_space(RawOrigin::Signed(caller.clone()).into(), space_id)?;
+        SpaceById::<T>::remove(space_id);
+    }: _(RawOrigin::Signed(caller.clone()), space_id)
+    verify {
+        assert!(!Module::<T>::space_followed_by_account((caller, space_id)));
+    }
+
+    add_follower_exception {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let follower: T::AccountId = account("follower", 0, SEED);
+        let space_id = create_space::<T>()?;
+    }: _(RawOrigin::Signed(owner), space_id, follower.clone())
+    verify {
+        assert!(Module::<T>::follow_whitelist_by_space(space_id, follower));
+    }
+}
@@ -1,26 +1,59 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure,
-    dispatch::DispatchResult,
-    traits::Get
+    decl_error, decl_event, decl_module, decl_storage, ensure, transactional,
+    dispatch::{DispatchError, DispatchResult},
+    traits::{Currency, Get},
+    storage::generator::StorageDoubleMap as _,
+    unsigned::{TransactionValidity, TransactionSource, ValidateUnsigned},
+    weights::DispatchClass,
+    IterableStorageDoubleMap, Parameter,
+};
+use sp_runtime::{
+    RuntimeDebug,
+    traits::{Verify, Zero},
+    transaction_validity::{
+        InvalidTransaction, TransactionLongevity, TransactionPriority, ValidTransaction,
+    },
 };
 use sp_std::prelude::*;
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_none, ensure_signed, offchain::SigningTypes};
 
 use df_traits::{
-    SpaceFollowsProvider,
+    PermissionChecker, SpaceFollowsProvider,
     moderation::IsAccountBlocked,
 };
 use pallet_profiles::{Module as Profiles, SocialAccountById};
 use pallet_spaces::{BeforeSpaceCreated, Module as Spaces, Space, SpaceById};
-use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
+use pallet_utils::{Error as UtilsError, Module as Utils, PalletVersion, SpaceId, WhoAndWhen, remove_from_vec};
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
+pub mod migrations;
+pub mod rpc;
+
+type BalanceOf<T> = <<T as pallet_utils::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
+/// A payload of `follow_space_unsigned` that is signed by the follower.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct FollowSpacePayload<AccountId, Hash> {
+    pub follower: AccountId,
+    pub space_id: SpaceId,
+    /// Must be equal to `UnsignedFollowNonce` of the follower.
+    pub nonce: u32,
+    /// The hash of the genesis block, so the payload cannot be replayed on another chain.
+    pub genesis_hash: Hash,
+}
 
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_utils::Trait
     + pallet_spaces::Trait
     + pallet_profiles::Trait
+    + SigningTypes
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
@@ -28,6 +61,32 @@ pub trait Trait: system::Trait
     type BeforeSpaceFollowed: BeforeSpaceFollowed<Self>;
 
     type BeforeSpaceUnfollowed: BeforeSpaceUnfollowed<Self>;
+
+    /// A signature of a payload of an unsigned follow. Usually, it is `SigningTypes::Signature`.
+    type UnsignedFollowSignature: Parameter + Verify<Signer = <Self as SigningTypes>::Public>;
+
+    /// How many spaces an account can follow with unsigned transactions,
+    /// i.e. without paying fees.
+    type UnsignedFollowsAllowance: Get<u32>;
+
+    /// Max number of unsigned follows that can be included in one block.
+    type MaxUnsignedFollowsPerBlock: Get<u32>;
+
+    /// A free balance that an account should have to follow with unsigned transactions.
+    /// Free follows add entries to storage, so new keypairs without tokens cannot make them.
+    type UnsignedFollowerMinBalance: Get<BalanceOf<Self>>;
+
+    /// A priority of unsigned follows in a transaction pool.
+    type UnsignedFollowPriority: Get<TransactionPriority>;
+
+    /// For how many blocks an unsigned follow stays valid in a transaction pool.
+    type UnsignedFollowLongevity: Get<TransactionLongevity>;
+
+    /// Whether a space creator automatically becomes the first follower of the new space.
+    type AutoFollowOwnSpace: Get<bool>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_error! {
@@ -40,20 +99,47 @@ decl_error! {
         NotSpaceFollower,
         /// Not allowed to follow a hidden space.
         CannotFollowHiddenSpace,
+        /// Account is already allowed to follow this space while it is hidden.
+        FollowerExceptionAlreadyAdded,
+        /// The space still exists, so it should be unfollowed with `unfollow_space`.
+        SpaceIsNotMissing,
     }
 }
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as SpaceFollowsModule {
-        pub SpaceFollowers get(fn space_followers):
-            map hasher(twox_64_concat) SpaceId => Vec<T::AccountId>;
+        /// The version of the storage layout of this pallet, see `pallet_spaces::StorageVersion`.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
+        /// Who follows a space and when they started following it.
+        pub SpaceFollowerSince get(fn space_follower_since):
+            double_map hasher(twox_64_concat) SpaceId, hasher(blake2_128_concat) T::AccountId
+            => Option<WhoAndWhen<T>>;
+
+        /// The number of entries of a space in `SpaceFollowerSince`.
+        pub SpaceFollowersCount get(fn space_followers_count):
+            map hasher(twox_64_concat) SpaceId => u32;
 
         pub SpaceFollowedByAccount get(fn space_followed_by_account):
             map hasher(blake2_128_concat) (T::AccountId, SpaceId) => bool;
 
         pub SpacesFollowedByAccount get(fn spaces_followed_by_account):
             map hasher(blake2_128_concat) T::AccountId => Vec<SpaceId>;
+
+        /// A nonce that the next unsigned follow of an account should be signed with.
+        /// It is also the number of unsigned follows the account has done.
+        pub UnsignedFollowNonce get(fn unsigned_follow_nonce):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The number of unsigned follows in the current block.
+        UnsignedFollowsInBlock get(fn unsigned_follows_in_block): u32;
+
+        /// Accounts that the space owner allowed to follow the space while it is hidden.
+        /// Entries are kept when the space is unhidden.
+        pub FollowWhitelistBySpace get(fn follow_whitelist_by_space):
+            double_map hasher(twox_64_concat) SpaceId, hasher(blake2_128_concat) T::AccountId => bool;
     }
 }
 
@@ -61,49 +147,214 @@ decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
     {
-        SpaceFollowed(/* follower */ AccountId, /* following */ SpaceId),
-        SpaceUnfollowed(/* follower */ AccountId, /* unfollowing */ SpaceId),
+        SpaceFollowed(/* follower */ AccountId, /* following */ SpaceId, /* space owner */ AccountId),
+        SpaceUnfollowed(/* follower */ AccountId, /* unfollowing */ SpaceId, /* space owner */ AccountId),
+        FollowerExceptionAdded(/* space owner */ AccountId, SpaceId, /* allowed follower */ AccountId),
+        /// A follow of a space that is no longer in storage was removed.
+        MissingSpaceUnfollowed(/* follower */ AccountId, SpaceId),
     }
 );
 
 // The pallet's dispatchable functions.
 decl_module! {
   pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+    const UnsignedFollowsAllowance: u32 = T::UnsignedFollowsAllowance::get();
+
+    const MaxUnsignedFollowsPerBlock: u32 = T::MaxUnsignedFollowsPerBlock::get();
+
+    const UnsignedFollowerMinBalance: BalanceOf<T> = T::UnsignedFollowerMinBalance::get();
+
+    const AutoFollowOwnSpace: bool = T::AutoFollowOwnSpace::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
+    fn on_finalize(_block_number: T::BlockNumber) {
+      UnsignedFollowsInBlock::kill();
+    }
+
+    #[weight = (<T as Trait>::WeightInfo::follow_space(), DispatchClass::Normal)]
     pub fn follow_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
-      ensure!(!Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::AlreadySpaceFollower);
+      Self::do_follow_space(follower, space_id)
+    }
 
-      let space = &mut Spaces::require_space(space_id)?;
-      ensure!(!space.hidden, Error::<T>::CannotFollowHiddenSpace);
+    /// Follow a space on behalf of an account that has signed the payload, without paying fees.
+    /// It lets new users follow spaces before they own tokens.
+    /// The signature, the nonce and the limits are checked in `validate_unsigned`.
+    /// A follow that fails does not use up the nonce and the limit of the block.
+    #[weight = <T as Trait>::WeightInfo::follow_space()
+      .saturating_add(T::DbWeight::get().reads_writes(2, 2))]
+    #[transactional]
+    pub fn follow_space_unsigned(
+      origin,
+      payload: FollowSpacePayload<T::AccountId, T::Hash>,
+      _signature: T::UnsignedFollowSignature
+    ) -> DispatchResult {
+      Self::do_follow_space_unsigned(origin, payload)
+    }
 
-      ensure!(T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+    #[weight = <T as Trait>::WeightInfo::unfollow_space()]
+    pub fn unfollow_space(origin, space_id: SpaceId) -> DispatchResult {
+      let follower = ensure_signed(origin)?;
 
-      Self::add_space_follower(follower, space)?;
-      <SpaceById<T>>::insert(space_id, space);
+      ensure!(Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::NotSpaceFollower);
 
-      Ok(())
+      Self::unfollow_space_by_account(follower, space_id)
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 5)]
-    pub fn unfollow_space(origin, space_id: SpaceId) -> DispatchResult {
+    /// Remove a follow of a space that is no longer in storage, which `unfollow_space`
+    /// cannot do. The score of the follow is not reverted, as the space owner is unknown.
+    #[weight = <T as Trait>::WeightInfo::force_unfollow_missing_space()]
+    pub fn force_unfollow_missing_space(origin, space_id: SpaceId) -> DispatchResult {
       let follower = ensure_signed(origin)?;
 
       ensure!(Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::NotSpaceFollower);
+      ensure!(Spaces::<T>::require_space(space_id).is_err(), Error::<T>::SpaceIsNotMissing);
 
-      Self::unfollow_space_by_account(follower, space_id)
+      if let Some(mut social_account) = Profiles::<T>::social_account_by_id(follower.clone()) {
+        social_account.dec_following_spaces();
+        <SocialAccountById<T>>::insert(follower.clone(), social_account);
+      }
+
+      Self::remove_space_follower_entries(&follower, space_id);
+
+      Self::deposit_event(RawEvent::MissingSpaceUnfollowed(follower, space_id));
+      Ok(())
+    }
+
+    /// Allow an account to follow a space even while it is hidden, e.g. during a pre-launch.
+    /// Only the space owner can do this.
+    #[weight = <T as Trait>::WeightInfo::add_follower_exception()]
+    pub fn add_follower_exception(origin, space_id: SpaceId, account: T::AccountId) -> DispatchResult {
+      let owner = ensure_signed(origin)?;
+
+      let space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(owner.clone())?;
+
+      ensure!(
+        !Self::follow_whitelist_by_space(space_id, &account),
+        Error::<T>::FollowerExceptionAlreadyAdded
+      );
+
+      <FollowWhitelistBySpace<T>>::insert(space_id, &account, true);
+
+      Self::deposit_event(RawEvent::FollowerExceptionAdded(owner, space_id, account));
+      Ok(())
     }
   }
 }
 
 impl<T: Trait> Module<T> {
+    /// All followers of a space, in the order of their storage keys rather than follow time.
+    /// Prefer `get_space_followers` for spaces that may have many followers.
+    pub fn space_followers(space_id: SpaceId) -> Vec<T::AccountId> {
+        <SpaceFollowerSince<T>>::iter_prefix(space_id)
+            .map(|(follower, _)| follower)
+            .collect()
+    }
+
+    fn ensure_can_follow_space(follower: &T::AccountId, space_id: SpaceId) -> Result<Space<T>, DispatchError> {
+        ensure!(!Self::space_followed_by_account((follower.clone(), space_id)), Error::<T>::AlreadySpaceFollower);
+
+        let space = Spaces::require_space(space_id)?;
+        ensure!(
+            !space.hidden || Self::can_follow_hidden_space(follower, &space),
+            Error::<T>::CannotFollowHiddenSpace
+        );
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+        Ok(space)
+    }
+
+    /// Whether an account may follow a space while it is hidden: the space owner,
+    /// accounts with an active role in the space and whitelisted accounts can.
+    fn can_follow_hidden_space(follower: &T::AccountId, space: &Space<T>) -> bool {
+        space.is_owner(follower)
+            || Self::follow_whitelist_by_space(space.id, follower)
+            || <T as pallet_spaces::Trait>::Roles::has_active_role_in_space(follower.clone(), space.id)
+    }
+
+    fn do_follow_space(follower: T::AccountId, space_id: SpaceId) -> DispatchResult {
+        let space = &mut Self::ensure_can_follow_space(&follower, space_id)?;
+
+        Self::add_space_follower(follower, space)?;
+        <SpaceById<T>>::insert(space_id, space);
+
+        Ok(())
+    }
+
+    /// The body of `follow_space_unsigned`, kept out of the dispatchable for `#[transactional]`.
+    fn do_follow_space_unsigned(
+        origin: T::Origin,
+        payload: FollowSpacePayload<T::AccountId, T::Hash>,
+    ) -> DispatchResult {
+        ensure_none(origin)?;
+
+        Self::do_follow_space(payload.follower.clone(), payload.space_id)?;
+
+        <UnsignedFollowNonce<T>>::mutate(&payload.follower, |nonce| *nonce = nonce.saturating_add(1));
+        UnsignedFollowsInBlock::mutate(|count| *count = count.saturating_add(1));
+
+        Ok(())
+    }
+
+    fn validate_follow_space_unsigned(
+        payload: &FollowSpacePayload<T::AccountId, T::Hash>,
+        signature: &T::UnsignedFollowSignature,
+    ) -> TransactionValidity {
+        let follower = &payload.follower;
+
+        if !signature.verify(&payload.encode()[..], follower) {
+            return InvalidTransaction::BadProof.into();
+        }
+
+        if payload.genesis_hash != <system::Module<T>>::block_hash(T::BlockNumber::zero()) {
+            return InvalidTransaction::BadProof.into();
+        }
+
+        let nonce = Self::unsigned_follow_nonce(follower);
+        if payload.nonce < nonce {
+            return InvalidTransaction::Stale.into();
+        }
+        if payload.nonce > nonce {
+            return InvalidTransaction::Future.into();
+        }
+
+        // The allowance is used up, so the account has to pay for follows from now on.
+        if nonce >= T::UnsignedFollowsAllowance::get() {
+            return InvalidTransaction::Payment.into();
+        }
+
+        if <T as pallet_utils::Trait>::Currency::free_balance(follower) < T::UnsignedFollowerMinBalance::get() {
+            return InvalidTransaction::Payment.into();
+        }
+
+        if Self::unsigned_follows_in_block() >= T::MaxUnsignedFollowsPerBlock::get() {
+            return InvalidTransaction::ExhaustsResources.into();
+        }
+
+        // Frozen and orphaned accounts are checked by a signed extension, that is not run for unsigned calls.
+        if Utils::<T>::is_account_frozen(follower)
+            || Profiles::<T>::is_social_account_orphaned(follower)
+            || Self::ensure_can_follow_space(follower, payload.space_id).is_err()
+        {
+            return InvalidTransaction::Call.into();
+        }
+
+        ValidTransaction::with_tag_prefix("SpaceFollowsUnsigned")
+            .priority(T::UnsignedFollowPriority::get())
+            .and_provides((follower, nonce))
+            .longevity(T::UnsignedFollowLongevity::get())
+            .propagate(true)
+            .build()
+    }
+
     fn add_space_follower(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult {
         space.inc_followers();
 
@@ -114,12 +365,13 @@ impl<T: Trait> Module<T> {
             follower.clone(), social_account.reputation, space)?;
 
         let space_id = space.id;
-        <SpaceFollowers<T>>::mutate(space_id, |followers| followers.push(follower.clone()));
+        <SpaceFollowerSince<T>>::insert(space_id, follower.clone(), WhoAndWhen::<T>::new(follower.clone()));
+        SpaceFollowersCount::mutate(space_id, |count| *count = count.saturating_add(1));
         <SpaceFollowedByAccount<T>>::insert((follower.clone(), space_id), true);
         <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| space_ids.push(space_id));
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
 
-        Self::deposit_event(RawEvent::SpaceFollowed(follower, space_id));
+        Self::deposit_event(RawEvent::SpaceFollowed(follower, space_id, space.owner.clone()));
 
         Ok(())
     }
@@ -133,15 +385,33 @@ impl<T: Trait> Module<T> {
 
         T::BeforeSpaceUnfollowed::before_space_unfollowed(follower.clone(), space)?;
 
-        <SpacesFollowedByAccount<T>>::mutate(follower.clone(), |space_ids| remove_from_vec(space_ids, space_id));
-        <SpaceFollowers<T>>::mutate(space_id, |account_ids| remove_from_vec(account_ids, follower.clone()));
-        <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
+        Self::remove_space_follower_entries(&follower, space_id);
         <SocialAccountById<T>>::insert(follower.clone(), social_account);
+        let space_owner = space.owner.clone();
         <SpaceById<T>>::insert(space_id, space);
 
-        Self::deposit_event(RawEvent::SpaceUnfollowed(follower, space_id));
+        Self::deposit_event(RawEvent::SpaceUnfollowed(follower, space_id, space_owner));
         Ok(())
     }
+
+    fn remove_space_follower_entries(follower: &T::AccountId, space_id: SpaceId) {
+        <SpacesFollowedByAccount<T>>::mutate(follower, |space_ids| remove_from_vec(space_ids, space_id));
+        <SpaceFollowerSince<T>>::remove(space_id, follower);
+        SpaceFollowersCount::mutate(space_id, |count| *count = count.saturating_sub(1));
+        <SpaceFollowedByAccount<T>>::remove((follower.clone(), space_id));
+    }
+}
+
+impl<T: Trait> ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        match call {
+            Call::follow_space_unsigned(payload, signature) =>
+                Self::validate_follow_space_unsigned(payload, signature),
+            _ => InvalidTransaction::Call.into(),
+        }
+    }
 }
 
 impl<T: Trait> SpaceFollowsProvider for Module<T> {
@@ -150,10 +420,36 @@ impl<T: Trait> SpaceFollowsProvider for Module<T> {
     fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool {
         Module::<T>::space_followed_by_account((account, space_id))
     }
+
+    fn count_followers(space_id: SpaceId, start_after: Option<Vec<u8>>, limit: u32) -> (u32, Option<Vec<u8>>) {
+        let prefix = SpaceFollowerSince::<T>::storage_double_map_final_key1(space_id);
+        let next_follower_key = |key: &[u8]| {
+            sp_io::storage::next_key(key).filter(|next_key| next_key.starts_with(&prefix))
+        };
+
+        let mut key = start_after.unwrap_or_else(|| prefix.clone());
+        let mut count = 0;
+        while count < limit {
+            match next_follower_key(&key) {
+                Some(next_key) => {
+                    count += 1;
+                    key = next_key;
+                }
+                None => return (count, None),
+            }
+        }
+
+        let has_more_followers = next_follower_key(&key).is_some();
+        (count, if has_more_followers { Some(key) } else { None })
+    }
 }
 
 impl<T: Trait> BeforeSpaceCreated<T> for Module<T> {
     fn before_space_created(creator: T::AccountId, space: &mut Space<T>) -> DispatchResult {
+        if !T::AutoFollowOwnSpace::get() {
+            return Ok(());
+        }
+
         // Make a space creator the first follower of this space:
         Module::<T>::add_space_follower(creator, space)
     }
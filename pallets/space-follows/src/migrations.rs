@@ -0,0 +1,84 @@
+//! Storage migrations of the Space Follows pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use codec::Decode;
+use frame_support::{
+    storage::migration::StorageIterator,
+    traits::Get,
+    weights::Weight,
+    StorageDoubleMap, StorageMap, StorageValue,
+};
+use sp_std::prelude::*;
+
+use pallet_utils::{SpaceId, WhoAndWhen, PalletVersion};
+
+use crate::{Module, SpaceFollowerSince, SpaceFollowersCount, StorageVersion, Trait};
+
+/// The crate version that replaced a vector of space followers with a double map.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_7_0;
+
+/// The module prefix of the storage items of this pallet.
+const MODULE_PREFIX: &[u8] = b"SpaceFollowsModule";
+
+/// The name of a `map hasher(twox_64_concat) SpaceId => Vec<AccountId>`
+/// that stored space followers before `V0_7_0`.
+pub const OLD_SPACE_FOLLOWERS: &[u8] = b"SpaceFollowers";
+
+/// The length of a `twox_64` hash that precedes a space id in the keys of `OLD_SPACE_FOLLOWERS`.
+const TWOX_64_HASH_LEN: usize = 8;
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Move the followers of every space from `OLD_SPACE_FOLLOWERS` to `SpaceFollowerSince`
+/// and count them. The time of old follows was not stored, so the block of the migration
+/// is recorded as the time they followed a space.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let old_followers: Vec<(Vec<u8>, Vec<T::AccountId>)> =
+        StorageIterator::<Vec<T::AccountId>>::new(MODULE_PREFIX, OLD_SPACE_FOLLOWERS)
+            .drain()
+            .collect();
+
+    let mut reads_writes = 0u64;
+    for (key, followers) in old_followers {
+        reads_writes = reads_writes.saturating_add(1);
+
+        let space_id = match key.get(TWOX_64_HASH_LEN..).and_then(|mut id| SpaceId::decode(&mut id).ok()) {
+            Some(space_id) => space_id,
+            None => continue,
+        };
+
+        for follower in followers.iter() {
+            <SpaceFollowerSince<T>>::insert(space_id, follower, WhoAndWhen::<T>::new(follower.clone()));
+        }
+        SpaceFollowersCount::insert(space_id, followers.len() as u32);
+
+        reads_writes = reads_writes.saturating_add(followers.len() as u64);
+    }
+
+    T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+}
@@ -0,0 +1,70 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use frame_support::IterableStorageDoubleMap;
+use pallet_utils::{SpaceId, WhoAndWhen};
+
+use crate::{Module, SpaceFollowerSince, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatSpaceFollower<AccountId, BlockNumber, Moment> {
+    pub follower: AccountId,
+    pub followed_at_block: BlockNumber,
+    pub followed_at_time: Moment,
+}
+
+impl<T: Trait> From<WhoAndWhen<T>> for FlatSpaceFollower<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(followed: WhoAndWhen<T>) -> Self {
+        let WhoAndWhen { account, block, time } = followed;
+
+        Self {
+            follower: account,
+            followed_at_block: block,
+            followed_at_time: time,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Get up to `limit` followers of a space together with the time they followed it,
+    /// skipping the first `offset` of them. Followers are ordered by their storage keys,
+    /// so a page is stable as long as nobody follows or unfollows the space.
+    pub fn get_space_followers(
+        space_id: SpaceId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FlatSpaceFollower<T::AccountId, T::BlockNumber, T::Moment>> {
+        <SpaceFollowerSince<T>>::iter_prefix(space_id)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(_follower, followed)| followed.into())
+            .collect()
+    }
+
+    /// Whether each of the given accounts follows a space, in the order the accounts were given.
+    /// Every account is checked with a single read of `SpaceFollowedByAccount`.
+    pub fn are_accounts_following_space(space_id: SpaceId, candidates: Vec<T::AccountId>) -> Vec<bool> {
+        candidates
+            .into_iter()
+            .map(|account| Self::space_followed_by_account((account, space_id)))
+            .collect()
+    }
+
+    /// Get up to `limit` of the given accounts that follow a space, in the order they were given.
+    /// Used with the accounts that a viewer follows to show who of them follows a space.
+    pub fn filter_space_followers(
+        space_id: SpaceId,
+        accounts: Vec<T::AccountId>,
+        limit: u32,
+    ) -> Vec<T::AccountId> {
+        accounts
+            .into_iter()
+            .filter(|account| Self::space_followed_by_account((account.clone(), space_id)))
+            .take(limit as usize)
+            .collect()
+    }
+}
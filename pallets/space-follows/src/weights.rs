@@ -0,0 +1,71 @@
+//! Weights for the Space Follows pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_space_follows --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_space_follows.
+pub trait WeightInfo {
+    fn follow_space() -> Weight;
+    fn unfollow_space() -> Weight;
+    fn add_follower_exception() -> Weight;
+    fn force_unfollow_missing_space() -> Weight;
+}
+
+/// Weights for pallet_space_follows measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn follow_space() -> Weight {
+        (43_888_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+
+    fn unfollow_space() -> Weight {
+        (42_563_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+
+    fn add_follower_exception() -> Weight {
+        (14_387_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn force_unfollow_missing_space() -> Weight {
+        (28_470_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+}
+
+impl WeightInfo for () {
+    fn follow_space() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+
+    fn unfollow_space() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+
+    fn add_follower_exception() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+
+    fn force_unfollow_missing_space() -> Weight {
+        (10_000 as Weight)
+            .saturating_add(DbWeight::get().reads(5 as Weight))
+            .saturating_add(DbWeight::get().writes(5 as Weight))
+    }
+}
@@ -1,13 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{decl_module, decl_storage};
+use frame_support::{decl_module, decl_storage, dispatch::DispatchResult, traits::Get};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::Vec;
 use frame_system::{self as system};
 
-use pallet_utils::{SpaceId, WhoAndWhen};
-use pallet_spaces::{Space, SpaceUpdate, AfterSpaceUpdated};
+use pallet_utils::{Module as Utils, SpaceId, WhoAndWhen, HistoryOverflowPolicy, append_to_bounded_history};
+use pallet_spaces::{Space, SpaceUpdate, AfterSpaceUpdated, AfterSpaceOwnershipChanged, BeforeSpaceUpdated};
+
+pub mod rpc;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct SpaceHistoryRecord<T: Trait> {
@@ -15,23 +17,42 @@ pub struct SpaceHistoryRecord<T: Trait> {
     pub old_data: SpaceUpdate,
 }
 
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct SpaceOwnershipRecord<T: Trait> {
+    pub changed: WhoAndWhen<T>,
+    pub old_owner: T::AccountId,
+    pub new_owner: T::AccountId,
+}
+
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
     + pallet_spaces::Trait
     + pallet_utils::Trait
-{}
+{
+    /// Max number of edit history records stored per space.
+    type MaxHistoryRecordsPerEntity: Get<u32>;
+
+    /// What to do with a new record when the edit history of a space is full.
+    type HistoryOverflowPolicy: Get<HistoryOverflowPolicy>;
+}
 
 // This pallet's storage items.
 decl_storage! {
     trait Store for Module<T: Trait> as SpaceHistoryModule {
         pub EditHistory get(fn edit_history):
             map hasher(twox_64_concat) SpaceId => Vec<SpaceHistoryRecord<T>>;
+
+        pub OwnershipHistory get(fn ownership_history):
+            map hasher(twox_64_concat) SpaceId => Vec<SpaceOwnershipRecord<T>>;
     }
 }
 
 // The pallet's dispatchable functions.
 decl_module! {
-  pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+  pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+
+    const MaxHistoryRecordsPerEntity: u32 = T::MaxHistoryRecordsPerEntity::get();
+  }
 }
 
 impl<T: Trait> SpaceHistoryRecord<T> {
@@ -43,9 +64,44 @@ impl<T: Trait> SpaceHistoryRecord<T> {
     }
 }
 
+impl<T: Trait> SpaceOwnershipRecord<T> {
+    fn new(old_owner: T::AccountId, new_owner: T::AccountId) -> Self {
+        SpaceOwnershipRecord {
+            changed: WhoAndWhen::<T>::new(new_owner.clone()),
+            old_owner,
+            new_owner,
+        }
+    }
+}
+
+impl<T: Trait> BeforeSpaceUpdated<T> for Module<T> {
+    fn before_space_updated(_editor: T::AccountId, space: &Space<T>) -> DispatchResult {
+        Utils::<T>::ensure_history_not_full(
+            <EditHistory<T>>::decode_len(space.id).unwrap_or_default(),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        )
+    }
+}
+
 impl<T: Trait> AfterSpaceUpdated<T> for Module<T> {
     fn after_space_updated(sender: T::AccountId, space: &Space<T>, old_data: SpaceUpdate) {
-        <EditHistory<T>>::mutate(space.id, |ids|
-            ids.push(SpaceHistoryRecord::<T>::new(sender, old_data)));
+        append_to_bounded_history::<EditHistory<T>, _, _>(
+            space.id,
+            SpaceHistoryRecord::<T>::new(sender, old_data),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        );
+    }
+}
+
+impl<T: Trait> AfterSpaceOwnershipChanged<T> for Module<T> {
+    fn after_space_ownership_changed(old_owner: T::AccountId, space: &Space<T>) {
+        append_to_bounded_history::<OwnershipHistory<T>, _, _>(
+            space.id,
+            SpaceOwnershipRecord::<T>::new(old_owner, space.owner.clone()),
+            T::MaxHistoryRecordsPerEntity::get(),
+            T::HistoryOverflowPolicy::get(),
+        );
     }
 }
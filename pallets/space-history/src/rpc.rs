@@ -0,0 +1,59 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_permissions::SpacePermissions;
+use pallet_utils::{Content, SpaceId, history_in_block_range};
+
+use crate::{Module, SpaceHistoryRecord, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatSpaceHistoryRecord<AccountId, BlockNumber, Moment> {
+    pub edited_by: AccountId,
+    pub edited_at_block: BlockNumber,
+    pub edited_at_time: Moment,
+
+    pub old_parent_id: Option<Option<SpaceId>>,
+    pub old_handle: Option<Option<Vec<u8>>>,
+    pub old_content: Option<Content>,
+    pub old_hidden: Option<bool>,
+    pub old_permissions: Option<Option<SpacePermissions>>,
+}
+
+impl<T: Trait> From<SpaceHistoryRecord<T>> for FlatSpaceHistoryRecord<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(record: SpaceHistoryRecord<T>) -> Self {
+        let SpaceHistoryRecord { edited, old_data } = record;
+
+        Self {
+            edited_by: edited.account,
+            edited_at_block: edited.block,
+            edited_at_time: edited.time,
+
+            old_parent_id: old_data.parent_id,
+            old_handle: old_data.handle,
+            old_content: old_data.content,
+            old_hidden: old_data.hidden,
+            old_permissions: old_data.permissions,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub fn get_edit_history_in_block_range(
+        space_id: SpaceId,
+        from_block: T::BlockNumber,
+        to_block: T::BlockNumber,
+        limit: u32,
+    ) -> Vec<FlatSpaceHistoryRecord<T::AccountId, T::BlockNumber, T::Moment>> {
+        let history = Self::edit_history(space_id);
+
+        history_in_block_range(&history, |record| record.edited.block, from_block, to_block, limit)
+            .iter()
+            .cloned()
+            .map(|record| record.into())
+            .collect()
+    }
+}
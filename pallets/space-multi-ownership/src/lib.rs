@@ -14,6 +14,7 @@ use frame_system::{self as system, ensure_signed};
 use pallet_utils::{SpaceId, WhoAndWhen};
 
 pub mod functions;
+pub mod rpc;
 
 #[cfg(test)]
 mod tests;
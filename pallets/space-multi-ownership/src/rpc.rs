@@ -0,0 +1,14 @@
+use pallet_utils::{SpaceId, SpaceOwnership};
+
+use crate::{Module, Trait};
+
+impl<T: Trait> Module<T> {
+  /// Get the owners of a space and how many of them should confirm a change.
+  /// `None` if a space is not under multi-ownership.
+  pub fn get_space_owners(space_id: SpaceId) -> Option<SpaceOwnership<T::AccountId>> {
+    Self::space_owners_by_space_id(space_id).map(|space_owners| SpaceOwnership::Multi {
+      owners: space_owners.owners,
+      threshold: space_owners.threshold,
+    })
+  }
+}
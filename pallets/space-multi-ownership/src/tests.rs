@@ -1,4 +1,5 @@
 use crate::*;
+use pallet_utils::SpaceOwnership;
 
 use sp_core::H256;
 use sp_io::TestExternalities;
@@ -84,6 +85,8 @@ impl pallet_utils::Trait for Test {
   type Currency = Balances;
   type MinHandleLen = MinHandleLen;
   type MaxHandleLen = MaxHandleLen;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type UnpausablePallets = ();
 }
 
 parameter_types! {
@@ -356,6 +359,20 @@ fn create_space_owners_should_work() {
   });
 }
 
+#[test]
+fn get_space_owners_should_return_owners_and_threshold() {
+  ExtBuilder::build().execute_with(|| {
+    assert!(MultiOwnership::get_space_owners(1).is_none());
+
+    assert_ok!(_create_default_space_owners());
+
+    assert_eq!(
+      MultiOwnership::get_space_owners(1),
+      Some(SpaceOwnership::Multi { owners: vec![ACCOUNT1, ACCOUNT2], threshold: 2 })
+    );
+  });
+}
+
 // -------
 
 #[test]
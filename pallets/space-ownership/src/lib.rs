@@ -6,12 +6,17 @@ use frame_support::{
     dispatch::DispatchResult,
     traits::Get
 };
+use sp_runtime::{ModuleId, traits::AccountIdConversion};
 use sp_std::prelude::*;
 use frame_system::{self as system, ensure_signed};
 
 use df_traits::moderation::IsAccountBlocked;
-use pallet_spaces::{Module as Spaces, SpaceById, SpaceIdsByOwner};
-use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec};
+use pallet_spaces::{Module as Spaces, SpaceById, SpaceIdsByOwner, AfterSpaceOwnershipChanged};
+use pallet_utils::{Error as UtilsError, SpaceId, remove_from_vec_keeping_order};
+
+/// An id of an account that owns spaces whose ownership was renounced.
+/// No one has a private key of this account, so such spaces are left without an owner who can sign.
+pub const RENOUNCED_SPACE_OWNER_ID: ModuleId = ModuleId(*b"df/nownr");
 
 /// The pallet's configuration trait.
 pub trait Trait: system::Trait
@@ -20,6 +25,8 @@ pub trait Trait: system::Trait
 {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    type AfterSpaceOwnershipChanged: AfterSpaceOwnershipChanged<Self>;
 }
 
 decl_error! {
@@ -50,8 +57,13 @@ decl_event!(
         <T as system::Trait>::AccountId,
     {
         SpaceOwnershipTransferCreated(/* current owner */ AccountId, SpaceId, /* new owner */ AccountId),
-        SpaceOwnershipTransferAccepted(AccountId, SpaceId),
-        SpaceOwnershipTransferRejected(AccountId, SpaceId),
+        /// A new owner accepted a space, and the space id was moved to their `SpaceIdsByOwner`.
+        /// The space creator stays in `Space.created`.
+        SpaceOwnershipTransferAccepted(/* new owner */ AccountId, SpaceId, /* old owner */ AccountId),
+        /// Either the current owner or the pending owner rejected a transfer to the pending owner.
+        SpaceOwnershipTransferRejected(/* rejected by */ AccountId, SpaceId, /* pending owner */ AccountId),
+        /// An owner renounced a space, so no one can perform owner-only actions on it anymore.
+        SpaceOwnershipRenounced(/* former owner */ AccountId, SpaceId),
     }
 );
 
@@ -81,7 +93,7 @@ decl_module! {
       Ok(())
     }
 
-    #[weight = 10_000 + T::DbWeight::get().reads_writes(2, 2)]
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(4, 4)]
     pub fn accept_pending_ownership(origin, space_id: SpaceId) -> DispatchResult {
       let new_owner = ensure_signed(origin)?;
 
@@ -98,17 +110,19 @@ decl_module! {
 
       let old_owner = space.owner;
       space.owner = new_owner.clone();
-      <SpaceById<T>>::insert(space_id, space);
+      <SpaceById<T>>::insert(space_id, space.clone());
 
       // Remove space id from the list of spaces by old owner
-      <SpaceIdsByOwner<T>>::mutate(old_owner.clone(), |space_ids| remove_from_vec(space_ids, space_id));
+      <SpaceIdsByOwner<T>>::mutate(old_owner.clone(), |space_ids| remove_from_vec_keeping_order(space_ids, space_id));
 
       // Add space id to the list of spaces by new owner
       <SpaceIdsByOwner<T>>::mutate(new_owner.clone(), |ids| ids.push(space_id));
 
       // TODO add a new owner as a space follower? See T::BeforeSpaceCreated::before_space_created(new_owner.clone(), space)?;
 
-      Self::deposit_event(RawEvent::SpaceOwnershipTransferAccepted(new_owner, space_id));
+      T::AfterSpaceOwnershipChanged::after_space_ownership_changed(old_owner.clone(), &space);
+
+      Self::deposit_event(RawEvent::SpaceOwnershipTransferAccepted(new_owner, space_id, old_owner));
       Ok(())
     }
 
@@ -122,8 +136,41 @@ decl_module! {
 
       <PendingSpaceOwner<T>>::remove(space_id);
 
-      Self::deposit_event(RawEvent::SpaceOwnershipTransferRejected(who, space_id));
+      Self::deposit_event(RawEvent::SpaceOwnershipTransferRejected(who, space_id, transfer_to));
+      Ok(())
+    }
+
+    /// Give a space to an account that no one controls. A pending transfer is cancelled
+    /// and a handle deposit goes to the treasury. Roles granted in the space keep working.
+    #[weight = 10_000 + T::DbWeight::get().reads_writes(5, 7)]
+    pub fn renounce_space_ownership(origin, space_id: SpaceId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let mut space = Spaces::<T>::require_space(space_id)?;
+      space.ensure_space_owner(who.clone())?;
+
+      <PendingSpaceOwner<T>>::remove(space_id);
+
+      Spaces::forfeit_handle_deposit_to_treasury(&space);
+
+      let new_owner = Self::renounced_space_owner();
+      space.owner = new_owner.clone();
+      <SpaceById<T>>::insert(space_id, space.clone());
+
+      <SpaceIdsByOwner<T>>::mutate(who.clone(), |space_ids| remove_from_vec_keeping_order(space_ids, space_id));
+      <SpaceIdsByOwner<T>>::mutate(new_owner, |ids| ids.push(space_id));
+
+      T::AfterSpaceOwnershipChanged::after_space_ownership_changed(who.clone(), &space);
+
+      Self::deposit_event(RawEvent::SpaceOwnershipRenounced(who, space_id));
       Ok(())
     }
   }
 }
+
+impl<T: Trait> Module<T> {
+    /// An account that becomes the owner of a space when its ownership is renounced.
+    pub fn renounced_space_owner() -> T::AccountId {
+        RENOUNCED_SPACE_OWNER_ID.into_account()
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use sp_std::vec::Vec;
+
+use pallet_permissions::SpacePermissionSet;
+use pallet_posts::PostId;
+use pallet_roles::RoleId;
+use pallet_space_follows::rpc::FlatSpaceFollower;
+use pallet_spaces::rpc::FlatSpace;
+use pallet_utils::{Content, SpaceId, SpaceOwnership, User};
+
+/// A part of a space that can be read with `SpacesApi::snapshot_space`.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum SpaceSnapshotSection {
+    /// The space itself. Not paginated.
+    Meta,
+    /// The root posts of the space in the order they were added to it.
+    Posts,
+    /// The followers of the space.
+    Followers,
+    /// Every user granted a role in the space, once per role.
+    Roles,
+}
+
+/// A post of a space as it is exported in a snapshot.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+pub struct SnapshotPost {
+    pub id: PostId,
+    pub content: Content,
+    pub hidden: bool,
+}
+
+/// A role of a space granted to a user, as it is exported in a snapshot.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+pub struct SnapshotRoleGrant<AccountId> {
+    pub role_id: RoleId,
+    pub disabled: bool,
+    pub permissions: SpacePermissionSet,
+    pub user: User<AccountId>,
+}
+
+/// A page of one section of a space snapshot.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+pub enum SpaceSnapshotChunk<AccountId, BlockNumber, Moment> {
+    Meta(FlatSpace<AccountId, BlockNumber, Moment>),
+    Posts(Vec<SnapshotPost>),
+    Followers(Vec<FlatSpaceFollower<AccountId, BlockNumber, Moment>>),
+    Roles(Vec<SnapshotRoleGrant<AccountId>>),
+}
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over spaces that would otherwise require several storage lookups.
+    pub trait SpacesApi<AccountId, BlockNumber, Moment> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Moment: Codec
+    {
+        /// Resolve a space handle in any case to the full space object.
+        fn space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<AccountId, BlockNumber, Moment>>;
+
+        /// Whether a handle in any case is already taken by a space. Use it before `create_space`.
+        fn would_handle_collide(handle: Vec<u8>) -> bool;
+
+        /// Get either a single owner of a space or a set of owners with a threshold
+        /// if a space is under multi-ownership. `None` if there is no such space.
+        fn space_owners(space_id: SpaceId) -> Option<SpaceOwnership<AccountId>>;
+
+        /// Read a page of one section of a space, skipping `offset` items of the section.
+        /// A page has at most `limit` items, and the runtime may return fewer;
+        /// an empty page means the section was read to the end.
+        /// Query every page at the same block to get a consistent snapshot.
+        /// `None` if there is no such space.
+        fn snapshot_space(
+            space_id: SpaceId,
+            section: SpaceSnapshotSection,
+            offset: u32,
+            limit: u32,
+        ) -> Option<SpaceSnapshotChunk<AccountId, BlockNumber, Moment>>;
+    }
+}
@@ -0,0 +1,108 @@
+//! Benchmarks for the Spaces pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use pallet_utils::mock_functions::valid_content_ipfs;
+
+fn fund_account<T: Trait>(account: &T::AccountId) {
+    <T as Trait>::Currency::make_free_balance_be(account, BalanceOf::<T>::max_value());
+}
+
+benchmarks! {
+    _ { }
+
+    create_space {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+
+        let space_id = Module::<T>::next_space_id();
+    }: _(RawOrigin::Signed(caller.clone()), None, Some(b"space_handle".to_vec()), valid_content_ipfs(), None, None)
+    verify {
+        let space = Module::<T>::space_by_id(space_id).unwrap();
+        assert_eq!(space.owner, caller);
+    }
+
+    update_space {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+
+        let space_id = Module::<T>::next_space_id();
+        Module::<T>::create_space(
+            RawOrigin::Signed(caller.clone()).into(), None, Some(b"space_handle".to_vec()), Content::None, None, None
+        )?;
+
+        let update = SpaceUpdate {
+            parent_id: None,
+            handle: Some(Some(b"new_space_handle".to_vec())),
+            content: Some(valid_content_ipfs()),
+            hidden: Some(true),
+            permissions: None,
+        };
+    }: _(RawOrigin::Signed(caller), space_id, update)
+    verify {
+        let space = Module::<T>::space_by_id(space_id).unwrap();
+        assert!(space.hidden);
+        assert_eq!(space.content, valid_content_ipfs());
+    }
+
+    update_space_settings {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let space_id = Module::<T>::next_space_id();
+        Module::<T>::create_space(
+            RawOrigin::Signed(caller.clone()).into(), None, None, Content::None, None, None
+        )?;
+
+        let update = SpaceSettingsUpdate {
+            shares_disabled: Some(Some(true)),
+            reject_duplicate_content: Some(Some(true)),
+            min_reputation_to_post: Some(Some(10)),
+            min_reputation_to_comment: Some(Some(1)),
+        };
+    }: _(RawOrigin::Signed(caller), space_id, update)
+    verify {
+        assert!(Module::<T>::are_shares_disabled(space_id));
+        assert!(Module::<T>::is_duplicate_content_rejected(space_id));
+    }
+
+    transfer_handle {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+
+        let from_space_id = Module::<T>::next_space_id();
+        Module::<T>::create_space(
+            RawOrigin::Signed(caller.clone()).into(), None, Some(b"space_handle".to_vec()), Content::None, None, None
+        )?;
+        let to_space_id = Module::<T>::next_space_id();
+        Module::<T>::create_space(
+            RawOrigin::Signed(caller.clone()).into(), None, None, Content::None, None, None
+        )?;
+    }: _(RawOrigin::Signed(caller), from_space_id, to_space_id)
+    verify {
+        assert_eq!(Module::<T>::space_id_by_handle(b"space_handle".to_vec()), Some(to_space_id));
+        assert!(Module::<T>::space_by_id(from_space_id).unwrap().handle.is_none());
+    }
+
+    recompute_space_stats {
+        let l in 1 .. 1_000;
+
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+
+        let space_id = Module::<T>::next_space_id();
+        Module::<T>::create_space(
+            RawOrigin::Signed(caller.clone()).into(), None, None, Content::None, None, None
+        )?;
+        SpaceById::<T>::mutate(space_id, |space| if let Some(space) = space { space.posts_count = l; });
+    }: _(RawOrigin::Signed(caller), space_id, l)
+    verify {
+        // Posts cannot be created from this pallet, so the drifted counter is reset to zero.
+        assert_eq!(Module::<T>::space_by_id(space_id).unwrap().posts_count, 0);
+    }
+}
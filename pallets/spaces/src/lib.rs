@@ -2,27 +2,39 @@
 
 use codec::{Decode, Encode};
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure,
+    decl_error, decl_event, decl_module, decl_storage, ensure, transactional,
     dispatch::{DispatchError, DispatchResult},
-    traits::{Get, Currency, ExistenceRequirement, ReservableCurrency},
+    traits::{Get, Currency, EnsureOrigin, ExistenceRequirement, ReservableCurrency},
+    weights::Weight,
 };
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{RuntimeDebug, traits::{One, Saturating, Zero}};
 use sp_std::prelude::*;
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_root, ensure_signed};
 
 use df_traits::{
-    SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider,
+    ContentValidator, SpaceForRoles, SpaceForRolesProvider, PermissionChecker, SpaceFollowsProvider,
+    SpacePostsProvider, SpaceSettingsProvider, UserResolver,
     moderation::{IsAccountBlocked, IsContentBlocked},
 };
 use pallet_permissions::{Module as Permissions, SpacePermission, SpacePermissions, SpacePermissionsContext};
-use pallet_utils::{Module as Utils, Error as UtilsError, SpaceId, WhoAndWhen, Content};
+use pallet_utils::{Module as Utils, Error as UtilsError, PalletVersion, SpaceId, User, WhoAndWhen, Content};
+
+pub mod migrations;
+pub mod rpc;
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod benchmarking;
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
 pub struct Space<T: Trait> {
     pub id: SpaceId,
+    /// The creator of this space. It never changes, even after the ownership is transferred.
     pub created: WhoAndWhen<T>,
     pub updated: Option<WhoAndWhen<T>>,
 
+    /// The current owner of this space. Only the owner can manage the space,
+    /// and it can be changed by the space-ownership pallet.
     pub owner: T::AccountId,
 
     // Can be updated by the owner:
@@ -39,6 +51,10 @@ pub struct Space<T: Trait> {
 
     /// Allows to override the default permissions for this space.
     pub permissions: Option<SpacePermissions>,
+
+    /// The number of comments on the root posts of this space, including hidden comments.
+    /// Comments move together with their root post to another space.
+    pub comments_count: u32,
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
@@ -51,6 +67,68 @@ pub struct SpaceUpdate {
     pub permissions: Option<Option<SpacePermissions>>,
 }
 
+/// The version of `SpaceSettings` that is written to storage by this runtime.
+pub const SPACE_SETTINGS_VERSION: u16 = 3;
+
+/// Per-space feature toggles that the owner of a space can change. Other pallets read them
+/// through `SpaceSettingsProvider`. `None` in a toggle means the default behaviour.
+///
+/// New toggles are appended as optional fields. `version` is increased together with
+/// a migration of the stored settings, so it is known which toggles a stored value has.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct SpaceSettings {
+    pub version: u16,
+    /// If `true`, posts cannot be shared into this space.
+    pub shares_disabled: Option<bool>,
+    /// If `true`, a post cannot be added to this space if it has a post with the same IPFS CID.
+    /// Added in version 2.
+    pub reject_duplicate_content: Option<bool>,
+    /// The minimum reputation of an account to create root posts in this space,
+    /// including shared posts and posts moved to this space. It does not apply to the space owner.
+    /// Added in version 3.
+    pub min_reputation_to_post: Option<u32>,
+    /// The minimum reputation of an account to comment on posts in this space.
+    /// It does not apply to the space owner. Added in version 3.
+    pub min_reputation_to_comment: Option<u32>,
+}
+
+impl Default for SpaceSettings {
+    fn default() -> Self {
+        SpaceSettings {
+            version: SPACE_SETTINGS_VERSION,
+            shares_disabled: None,
+            reject_duplicate_content: None,
+            min_reputation_to_post: None,
+            min_reputation_to_comment: None,
+        }
+    }
+}
+
+/// Progress of `recompute_space_stats` over the posts and then the followers of a space.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, Default)]
+pub struct SpaceStatsRecount {
+    /// Whether all posts were checked, so the followers are being counted.
+    pub all_posts_checked: bool,
+    /// The id of the last checked post. Posts are checked in the order of their ids, so posts
+    /// added to or removed from the space between calls do not shift the ones left to check.
+    pub last_post_id: Option<u64>,
+    /// The number of hidden posts among the posts that were checked so far.
+    pub hidden_posts_count: u32,
+    /// The storage key of the last counted follower in the space follows pallet.
+    pub last_follower_key: Option<Vec<u8>>,
+    /// The number of followers that were counted so far.
+    pub followers_count: u32,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, Default)]
+#[allow(clippy::option_option)]
+pub struct SpaceSettingsUpdate {
+    pub shares_disabled: Option<Option<bool>>,
+    pub reject_duplicate_content: Option<Option<bool>>,
+    pub min_reputation_to_post: Option<Option<u32>>,
+    pub min_reputation_to_comment: Option<Option<u32>>,
+}
+
 type BalanceOf<T> =
   <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
@@ -62,14 +140,23 @@ pub trait Trait: system::Trait
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
+    /// Validates the content of spaces.
+    type ContentValidator: ContentValidator;
+
     type Currency: ReservableCurrency<Self::AccountId>;
 
     type Roles: PermissionChecker<AccountId=Self::AccountId>;
 
     type SpaceFollows: SpaceFollowsProvider<AccountId=Self::AccountId>;
 
+    /// Reads posts of a space to recount its counters in `recompute_space_stats`.
+    type SpacePosts: SpacePostsProvider;
+
     type BeforeSpaceCreated: BeforeSpaceCreated<Self>;
 
+    /// Called before a space is edited, e.g. to refuse edits when its history is full.
+    type BeforeSpaceUpdated: BeforeSpaceUpdated<Self>;
+
     type AfterSpaceUpdated: AfterSpaceUpdated<Self>;
 
     type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
@@ -77,6 +164,29 @@ pub trait Trait: system::Trait
     type IsContentBlocked: IsContentBlocked;
 
     type HandleDeposit: Get<BalanceOf<Self>>;
+
+    /// If `true`, only the owner of a reserved space (with id `<= RESERVED_SPACE_COUNT`)
+    /// can create posts and subspaces in it.
+    type RestrictReservedSpaces: Get<bool>;
+
+    /// For how many blocks an idempotency key of `create_space` or `create_post` prevents
+    /// creating another space or post with the same key by the same account.
+    type IdempotencyKeyTtl: Get<Self::BlockNumber>;
+
+    /// How many expired idempotency keys of spaces, and separately of posts, can be removed
+    /// at the start of a block. The rest of the keys are left to the next block.
+    type MaxIdempotencyKeysRemovedPerBlock: Get<u32>;
+
+    /// For how many blocks after a handle of a space was changed it cannot be changed again,
+    /// so that handles cannot be rotated quickly to impersonate other spaces. Zero disables it.
+    type HandleChangeCooldown: Get<Self::BlockNumber>;
+
+    /// The origin which may create root spaces, i.e. spaces without a parent.
+    /// Subspaces are gated by the `CreateSubspaces` permission of their parent instead.
+    type CreateRootSpaceOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_error! {
@@ -95,6 +205,22 @@ decl_error! {
     NoPermissionToCreateSubspaces,
     /// Space is at root level, no parent_id specified
     SpaceIsAtRoot,
+    /// Only the owner of a reserved space can create posts and subspaces in it.
+    NotAReservedSpaceOwner,
+    /// Nothing to update in space settings.
+    NoUpdatesForSpaceSettings,
+    /// User has no permission to update settings of this space.
+    NoPermissionToUpdateSpaceSettings,
+    /// A handle can be transferred only between spaces of the same owner.
+    SpacesHaveDifferentOwners,
+    /// The space that a handle is transferred from has no handle.
+    SpaceHasNoHandle,
+    /// The space that a handle is transferred to already has a handle.
+    DestinationSpaceHasHandle,
+    /// The handle of this space was changed less than `HandleChangeCooldown` blocks ago.
+    HandleChangedTooRecently,
+    /// At least one post should be recounted per call.
+    ZeroRecountLimit,
   }
 }
 
@@ -104,6 +230,12 @@ pub const RESERVED_SPACE_COUNT: u64 = 1000;
 decl_storage! {
     trait Store for Module<T: Trait> as SpacesModule {
 
+        /// The version of the storage layout of this pallet. It is set to the current one
+        /// at genesis and after the migrations of this pallet are run on a runtime upgrade,
+        /// and it is not set on a chain that predates the storage versioning.
+        pub StorageVersion get(fn storage_version) build(|_| Some(migrations::STORAGE_VERSION)):
+            Option<PalletVersion>;
+
         pub NextSpaceId get(fn next_space_id): SpaceId = 1001;
 
         pub SpaceById get(fn space_by_id) build(|config: &GenesisConfig<T>| {
@@ -121,9 +253,65 @@ decl_storage! {
 
         pub SpaceIdsByOwner get(fn space_ids_by_owner):
             map hasher(twox_64_concat) T::AccountId => Vec<SpaceId>;
+
+        /// An account that holds a handle deposit of a space (key) and the reserved amount.
+        pub HandleDepositBySpaceId get(fn handle_deposit_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<(T::AccountId, BalanceOf<T>)>;
+
+        /// A space created by an account with an idempotency key, and the block it was created at.
+        /// A key is removed at the start of the block it expires at, or at a later block
+        /// if more than `MaxIdempotencyKeysRemovedPerBlock` keys expire at once.
+        pub UsedIdempotencyKeys get(fn used_idempotency_key):
+            map hasher(blake2_128_concat) (T::AccountId, T::Hash) => Option<(SpaceId, T::BlockNumber)>;
+
+        /// Keys of `UsedIdempotencyKeys` that expire at a block (key).
+        pub IdempotencyKeysByExpireBlock get(fn idempotency_keys_by_expire_block):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
+
+        /// Feature toggles of a space (key). A space without settings uses the defaults.
+        pub SpaceSettingsById get(fn space_settings_by_id):
+            map hasher(twox_64_concat) SpaceId => Option<SpaceSettings>;
+
+        /// The block at which the handle of a space (key) was changed last time.
+        /// Recorded only while `HandleChangeCooldown` is not zero.
+        pub LastHandleChangeBySpaceId get(fn last_handle_change_by_space_id):
+            map hasher(twox_64_concat) SpaceId => Option<T::BlockNumber>;
+
+        /// Progress of `recompute_space_stats` for a space (key) whose posts were not all checked yet.
+        pub SpaceStatsRecountById get(fn space_stats_recount_by_id):
+            map hasher(twox_64_concat) SpaceId => Option<SpaceStatsRecount>;
     }
     add_extra_genesis {
       config(endowed_account): T::AccountId;
+      // Spaces to create at genesis and to give to `endowed_account`: (space id, handle, content).
+      // A space with a reserved id replaces the blank reserved space with this id.
+      config(spaces): Vec<(SpaceId, Option<Vec<u8>>, Content)>;
+      build(|config| {
+        let owner = &config.endowed_account;
+        let mut seeded_ids = sp_std::collections::btree_set::BTreeSet::new();
+
+        for (space_id, handle_opt, content) in config.spaces.iter() {
+          assert!(*space_id > 0, "Genesis space id should not be zero");
+          assert!(seeded_ids.insert(*space_id), "Genesis space ids should be unique");
+          assert!(T::ContentValidator::is_valid_content(content.clone()).is_ok(), "Genesis space content should be valid");
+
+          if let Some(handle) = handle_opt {
+            let handle_in_lowercase = Module::<T>::lowercase_and_ensure_unique_handle(handle.clone())
+              .expect("Genesis space handle should be valid and unique");
+            SpaceIdByHandle::insert(handle_in_lowercase, space_id);
+          }
+
+          let space = Space::<T>::new(*space_id, None, owner.clone(), content.clone(), handle_opt.clone(), None);
+          if !space.is_reserved() {
+            <SpaceIdsByOwner<T>>::mutate(owner, |ids| ids.push(*space_id));
+          }
+          <SpaceById<T>>::insert(space_id, space);
+
+          if *space_id >= NextSpaceId::get() {
+            NextSpaceId::put(space_id.saturating_add(1));
+          }
+        }
+      });
     }
 }
 
@@ -134,6 +322,16 @@ decl_event!(
         SpaceCreated(AccountId, SpaceId),
         SpaceUpdated(AccountId, SpaceId),
         SpaceDeleted(AccountId, SpaceId),
+        SpacePermissionsUpdated(AccountId, SpaceId),
+        /// A space was not created, because the account created this space
+        /// with the same idempotency key recently.
+        SpaceAlreadyCreated(AccountId, SpaceId),
+        SpaceSettingsUpdated(AccountId, SpaceId),
+        /// A handle was moved from one space (first) to another (second) of the same owner.
+        SpaceHandleTransferred(AccountId, SpaceId, SpaceId),
+        /// Counters of a space were recounted and corrected by the given differences.
+        /// [space_id, posts_count_diff, hidden_posts_count_diff, followers_count_diff]
+        SpaceStatsRecomputed(SpaceId, i64, i64, i64),
     }
 );
 
@@ -143,161 +341,219 @@ decl_module! {
 
     const HandleDeposit: BalanceOf<T> = T::HandleDeposit::get();
 
+    const IdempotencyKeyTtl: T::BlockNumber = T::IdempotencyKeyTtl::get();
+
+    const MaxIdempotencyKeysRemovedPerBlock: u32 = T::MaxIdempotencyKeysRemovedPerBlock::get();
+
+    const HandleChangeCooldown: T::BlockNumber = T::HandleChangeCooldown::get();
+
     // Initializing errors
     type Error = Error<T>;
 
     // Initializing events
     fn deposit_event() = default;
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(4, 4)]
+    /// Remove idempotency keys that expire at this block.
+    fn on_initialize(block_number: T::BlockNumber) -> Weight {
+      Self::remove_expired_idempotency_keys(block_number)
+    }
+
+    /// Create a space. If an `idempotency_key` is given and the same account has created
+    /// a space with it during the last `IdempotencyKeyTtl` blocks, no space is created
+    /// and `SpaceAlreadyCreated` is emitted instead, so a retried transaction is harmless.
+    #[weight = <T as Trait>::WeightInfo::create_space()
+      .saturating_add(T::DbWeight::get().reads_writes(3, 4))
+      .saturating_add(<T as Trait>::WeightInfo::content_bytes(content.byte_len()))]
+    #[transactional]
     pub fn create_space(
       origin,
       parent_id_opt: Option<SpaceId>,
       handle_opt: Option<Vec<u8>>,
       content: Content,
-      permissions_opt: Option<SpacePermissions>
+      permissions_opt: Option<SpacePermissions>,
+      idempotency_key: Option<T::Hash>
     ) -> DispatchResult {
-      let owner = ensure_signed(origin)?;
+      Self::do_create_space(origin, parent_id_opt, handle_opt, content, permissions_opt, idempotency_key)
+    }
 
-      Utils::<T>::is_valid_content(content.clone())?;
+    #[weight = <T as Trait>::WeightInfo::update_space()
+      .saturating_add(<T as Trait>::WeightInfo::content_bytes(update.content.as_ref().map_or(0, Content::byte_len)))]
+    #[transactional]
+    pub fn update_space(origin, space_id: SpaceId, update: SpaceUpdate) -> DispatchResult {
+      Self::do_update_space(origin, space_id, update)
+    }
 
-      // TODO: add tests for this case
-      if let Some(parent_id) = parent_id_opt {
-        let parent_space = Self::require_space(parent_id)?;
+    #[weight = <T as Trait>::WeightInfo::update_space_settings()]
+    pub fn update_space_settings(origin, space_id: SpaceId, update: SpaceSettingsUpdate) -> DispatchResult {
+      let who = ensure_signed(origin)?;
 
-        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
-        ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
+      let has_updates =
+        update.shares_disabled.is_some() ||
+        update.reject_duplicate_content.is_some() ||
+        update.min_reputation_to_post.is_some() ||
+        update.min_reputation_to_comment.is_some();
 
-        Self::ensure_account_has_space_permission(
-          owner.clone(),
-          &parent_space,
-          SpacePermission::CreateSubspaces,
-          Error::<T>::NoPermissionToCreateSubspaces.into()
-        )?;
-      }
+      ensure!(has_updates, Error::<T>::NoUpdatesForSpaceSettings);
 
-      let permissions = permissions_opt.map(|perms| {
-        Permissions::<T>::override_permissions(perms)
-      });
+      let space = Self::require_space(space_id)?;
 
-      let space_id = Self::next_space_id();
-      let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt.clone(), permissions);
+      Self::ensure_account_has_space_permission(
+        who.clone(),
+        &space,
+        SpacePermission::UpdateSpaceSettings,
+        Error::<T>::NoPermissionToUpdateSpaceSettings.into()
+      )?;
+
+      let mut settings = Self::space_settings_by_id(space_id).unwrap_or_default();
+      let mut is_update_applied = false;
+
+      if let Some(shares_disabled) = update.shares_disabled {
+        if shares_disabled != settings.shares_disabled {
+          settings.shares_disabled = shares_disabled;
+          is_update_applied = true;
+        }
+      }
 
-      if let Some(handle) = handle_opt {
-        Self::reserve_handle(&new_space, handle)?;
+      if let Some(reject_duplicate_content) = update.reject_duplicate_content {
+        if reject_duplicate_content != settings.reject_duplicate_content {
+          settings.reject_duplicate_content = reject_duplicate_content;
+          is_update_applied = true;
+        }
       }
 
-      T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
+      if let Some(min_reputation_to_post) = update.min_reputation_to_post {
+        if min_reputation_to_post != settings.min_reputation_to_post {
+          settings.min_reputation_to_post = min_reputation_to_post;
+          is_update_applied = true;
+        }
+      }
 
-      <SpaceById<T>>::insert(space_id, new_space);
-      <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
-      NextSpaceId::mutate(|n| { *n += 1; });
+      if let Some(min_reputation_to_comment) = update.min_reputation_to_comment {
+        if min_reputation_to_comment != settings.min_reputation_to_comment {
+          settings.min_reputation_to_comment = min_reputation_to_comment;
+          is_update_applied = true;
+        }
+      }
 
-      Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
+      if is_update_applied {
+        SpaceSettingsById::insert(space_id, settings);
+        Self::deposit_event(RawEvent::SpaceSettingsUpdated(who, space_id));
+      }
       Ok(())
     }
 
-    #[weight = 500_000 + T::DbWeight::get().reads_writes(2, 3)]
-    pub fn update_space(origin, space_id: SpaceId, update: SpaceUpdate) -> DispatchResult {
+    /// Move the handle of one space to another space of the same owner in one step.
+    /// The handle deposit stays reserved, so the handle is never free to be claimed by others.
+    #[weight = <T as Trait>::WeightInfo::transfer_handle()]
+    pub fn transfer_handle(origin, from_space_id: SpaceId, to_space_id: SpaceId) -> DispatchResult {
       let owner = ensure_signed(origin)?;
 
-      let has_updates =
-        update.parent_id.is_some() ||
-        update.handle.is_some() ||
-        update.content.is_some() ||
-        update.hidden.is_some() ||
-        update.permissions.is_some();
+      let mut from_space = Self::require_space(from_space_id)?;
+      let mut to_space = Self::require_space(to_space_id)?;
 
-      ensure!(has_updates, Error::<T>::NoUpdatesForSpace);
+      from_space.ensure_space_owner(owner.clone())?;
+      ensure!(to_space.is_owner(&owner), Error::<T>::SpacesHaveDifferentOwners);
 
-      let mut space = Self::require_space(space_id)?;
+      let handle = from_space.handle.clone().ok_or(Error::<T>::SpaceHasNoHandle)?;
+      ensure!(to_space.handle.is_none(), Error::<T>::DestinationSpaceHasHandle);
 
-      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), from_space_id), UtilsError::<T>::AccountIsBlocked);
+      ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), to_space_id), UtilsError::<T>::AccountIsBlocked);
 
-      Self::ensure_account_has_space_permission(
-        owner.clone(),
-        &space,
-        SpacePermission::UpdateSpace,
-        Error::<T>::NoPermissionToUpdateSpace.into()
-      )?;
+      Self::ensure_handle_change_allowed(from_space_id)?;
+      Self::ensure_handle_change_allowed(to_space_id)?;
 
-      let mut is_update_applied = false;
-      let mut old_data = SpaceUpdate::default();
+      T::BeforeSpaceUpdated::before_space_updated(owner.clone(), &from_space)?;
+      T::BeforeSpaceUpdated::before_space_updated(owner.clone(), &to_space)?;
 
-      // TODO: add tests for this case
-      if let Some(parent_id_opt) = update.parent_id {
-        if parent_id_opt != space.parent_id {
+      // The deposit is recorded for the new space as is, without unreserving it.
+      let deposit = Self::handle_depositor(&from_space);
+      HandleDepositBySpaceId::<T>::remove(from_space_id);
+      HandleDepositBySpaceId::<T>::insert(to_space_id, deposit);
+      SpaceIdByHandle::insert(Utils::<T>::lowercase_handle(handle.clone()), to_space_id);
+      Self::record_handle_change(from_space_id);
+      Self::record_handle_change(to_space_id);
 
-          if let Some(parent_id) = parent_id_opt {
-            let parent_space = Self::require_space(parent_id)?;
+      from_space.handle = None;
+      from_space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+      to_space.handle = Some(handle.clone());
+      to_space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
 
-            Self::ensure_account_has_space_permission(
-              owner.clone(),
-              &parent_space,
-              SpacePermission::CreateSubspaces,
-              Error::<T>::NoPermissionToCreateSubspaces.into()
-            )?;
-          }
+      <SpaceById<T>>::insert(from_space_id, from_space.clone());
+      <SpaceById<T>>::insert(to_space_id, to_space.clone());
 
-          old_data.parent_id = Some(space.parent_id);
-          space.parent_id = parent_id_opt;
-          is_update_applied = true;
-        }
-      }
+      let from_old_data = SpaceUpdate { handle: Some(Some(handle)), ..Default::default() };
+      let to_old_data = SpaceUpdate { handle: Some(None), ..Default::default() };
+      T::AfterSpaceUpdated::after_space_updated(owner.clone(), &from_space, from_old_data);
+      T::AfterSpaceUpdated::after_space_updated(owner.clone(), &to_space, to_old_data);
 
-      if let Some(content) = update.content {
-        if content != space.content {
-          Utils::<T>::is_valid_content(content.clone())?;
+      Self::deposit_event(RawEvent::SpaceUpdated(owner.clone(), from_space_id));
+      Self::deposit_event(RawEvent::SpaceUpdated(owner.clone(), to_space_id));
+      Self::deposit_event(RawEvent::SpaceHandleTransferred(owner, from_space_id, to_space_id));
+      Ok(())
+    }
 
-          ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), space.id), UtilsError::<T>::ContentIsBlocked);
-          if let Some(parent_id) = space.parent_id {
-            ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
-          }
+    /// Recount `posts_count`, `hidden_posts_count` and `followers_count` of a space from the posts
+    /// and follows in storage, in case the counters drifted. Up to `limit` posts and then up to `limit`
+    /// followers are checked per call, and the counters are corrected by the call that counts
+    /// the last follower of the space. Can be called by root or by the space owner.
+    #[weight = <T as Trait>::WeightInfo::recompute_space_stats(*limit)]
+    pub fn recompute_space_stats(origin, space_id: SpaceId, limit: u32) -> DispatchResult {
+      let mut space = Self::require_space(space_id)?;
 
-          old_data.content = Some(space.content);
-          space.content = content;
-          is_update_applied = true;
-        }
+      if let Ok(who) = ensure_signed(origin.clone()) {
+        space.ensure_space_owner(who)?;
+      } else {
+        ensure_root(origin)?;
       }
 
-      if let Some(hidden) = update.hidden {
-        if hidden != space.hidden {
-          old_data.hidden = Some(space.hidden);
-          space.hidden = hidden;
-          is_update_applied = true;
+      ensure!(limit > 0, Error::<T>::ZeroRecountLimit);
+
+      let mut recount = Self::space_stats_recount_by_id(space_id).unwrap_or_default();
+
+      if !recount.all_posts_checked {
+        let (hidden_posts_in_page, last_post_id) =
+          T::SpacePosts::hidden_posts_count(space_id, recount.last_post_id, limit);
+
+        recount.hidden_posts_count = recount.hidden_posts_count.saturating_add(hidden_posts_in_page);
+        recount.last_post_id = last_post_id;
+        recount.all_posts_checked = last_post_id.is_none();
+
+        if !recount.all_posts_checked {
+          SpaceStatsRecountById::insert(space_id, recount);
+          return Ok(());
         }
       }
 
-      if let Some(overrides_opt) = update.permissions {
-        if space.permissions != overrides_opt {
-          old_data.permissions = Some(space.permissions);
+      let (followers_in_page, last_follower_key) =
+        T::SpaceFollows::count_followers(space_id, recount.last_follower_key.take(), limit);
 
-          if let Some(overrides) = overrides_opt.clone() {
-            space.permissions = Some(Permissions::<T>::override_permissions(overrides));
-          } else {
-            space.permissions = overrides_opt;
-          }
+      recount.followers_count = recount.followers_count.saturating_add(followers_in_page);
+      recount.last_follower_key = last_follower_key;
 
-          is_update_applied = true;
-        }
+      if recount.last_follower_key.is_some() {
+        SpaceStatsRecountById::insert(space_id, recount);
+        return Ok(());
       }
 
-      let is_handle_updated = Self::update_handle(&space, update.handle.clone())?;
-      if is_handle_updated {
-          old_data.handle = Some(space.handle);
-          space.handle = update.handle.unwrap();
-          is_update_applied = true
-        }
+      SpaceStatsRecountById::remove(space_id);
 
-      // Update this space only if at least one field should be updated:
-      if is_update_applied {
-        space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+      let posts_count = T::SpacePosts::posts_count(space_id);
+      let followers_count = recount.followers_count;
+      let diff = |new: u32, old: u32| new as i64 - old as i64;
+      let event = RawEvent::SpaceStatsRecomputed(
+        space_id,
+        diff(posts_count, space.posts_count),
+        diff(recount.hidden_posts_count, space.hidden_posts_count),
+        diff(followers_count, space.followers_count),
+      );
 
-        <SpaceById<T>>::insert(space_id, space.clone());
-        T::AfterSpaceUpdated::after_space_updated(owner.clone(), &space, old_data);
+      space.posts_count = posts_count;
+      space.hidden_posts_count = recount.hidden_posts_count;
+      space.followers_count = followers_count;
+      <SpaceById<T>>::insert(space_id, space);
 
-        Self::deposit_event(RawEvent::SpaceUpdated(owner, space_id));
-      }
+      Self::deposit_event(event);
       Ok(())
     }
   }
@@ -326,6 +582,7 @@ impl<T: Trait> Space<T> {
             followers_count: 0,
             score: 0,
             permissions,
+            comments_count: 0,
         }
     }
 
@@ -342,6 +599,11 @@ impl<T: Trait> Space<T> {
         Ok(())
     }
 
+    /// Whether this space is one of the placeholder spaces created at genesis.
+    pub fn is_reserved(&self) -> bool {
+        self.id <= RESERVED_SPACE_COUNT
+    }
+
     pub fn inc_posts(&mut self) {
         self.posts_count = self.posts_count.saturating_add(1);
     }
@@ -358,6 +620,14 @@ impl<T: Trait> Space<T> {
         self.hidden_posts_count = self.hidden_posts_count.saturating_sub(1);
     }
 
+    pub fn inc_comments(&mut self) {
+        self.comments_count = self.comments_count.saturating_add(1);
+    }
+
+    pub fn dec_comments(&mut self) {
+        self.comments_count = self.comments_count.saturating_sub(1);
+    }
+
     pub fn inc_followers(&mut self) {
         self.followers_count = self.followers_count.saturating_add(1);
     }
@@ -394,6 +664,219 @@ impl Default for SpaceUpdate {
 
 impl<T: Trait> Module<T> {
 
+    /// The body of `create_space`. It is kept out of the dispatchable, because `#[transactional]`
+    /// of this FRAME version does not allow `?` in the body of a function it wraps.
+    fn do_create_space(
+        origin: T::Origin,
+        parent_id_opt: Option<SpaceId>,
+        handle_opt: Option<Vec<u8>>,
+        content: Content,
+        permissions_opt: Option<SpacePermissions>,
+        idempotency_key: Option<T::Hash>
+    ) -> DispatchResult {
+        let owner = match parent_id_opt {
+            Some(_) => ensure_signed(origin)?,
+            None => T::CreateRootSpaceOrigin::ensure_origin(origin)?,
+        };
+
+        if let Some(key) = idempotency_key {
+            if let Some(space_id) = Self::live_idempotency_key_entity(Self::used_idempotency_key((owner.clone(), key))) {
+                Self::deposit_event(RawEvent::SpaceAlreadyCreated(owner, space_id));
+                return Ok(());
+            }
+        }
+
+        T::ContentValidator::is_valid_content(content.clone())?;
+
+        // TODO: add tests for this case
+        if let Some(parent_id) = parent_id_opt {
+            let parent_space = Self::require_space(parent_id)?;
+            Self::ensure_reserved_space_owner(&owner, &parent_space)?;
+
+            ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), parent_id), UtilsError::<T>::AccountIsBlocked);
+            ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
+
+            Self::ensure_account_has_space_permission(
+                owner.clone(),
+                &parent_space,
+                SpacePermission::CreateSubspaces,
+                Error::<T>::NoPermissionToCreateSubspaces.into()
+            )?;
+        }
+
+        let handle_in_lowercase = match handle_opt.clone() {
+            Some(handle) => Some(Self::lowercase_and_ensure_unique_handle(handle)?),
+            None => None,
+        };
+
+        let permissions = permissions_opt.map(|perms| {
+            Permissions::<T>::override_permissions(perms)
+        });
+
+        let space_id = Self::next_space_id();
+        let next_space_id = Utils::<T>::next_id(space_id)?;
+        let new_space = &mut Space::new(space_id, parent_id_opt, owner.clone(), content, handle_opt.clone(), permissions);
+
+        T::BeforeSpaceCreated::before_space_created(owner.clone(), new_space)?;
+
+        // A handle deposit is reserved after all the checks, as it cannot be undone by an error.
+        if let Some(handle_in_lowercase) = handle_in_lowercase {
+            Self::reserve_handle(&new_space, handle_in_lowercase)?;
+        }
+
+        <SpaceById<T>>::insert(space_id, new_space);
+        <SpaceIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(space_id));
+        NextSpaceId::put(next_space_id);
+
+        if let Some(key) = idempotency_key {
+            let now = <system::Module<T>>::block_number();
+            <UsedIdempotencyKeys<T>>::insert((owner.clone(), key), (space_id, now));
+            <IdempotencyKeysByExpireBlock<T>>::append(Self::idempotency_key_expire_block(now), (owner.clone(), key));
+        }
+
+        Self::deposit_event(RawEvent::SpaceCreated(owner, space_id));
+        Ok(())
+    }
+
+    /// The body of `update_space`, see `do_create_space`.
+    fn do_update_space(origin: T::Origin, space_id: SpaceId, update: SpaceUpdate) -> DispatchResult {
+        let owner = ensure_signed(origin.clone())?;
+
+        let has_updates =
+            update.parent_id.is_some() ||
+            update.handle.is_some() ||
+            update.content.is_some() ||
+            update.hidden.is_some() ||
+            update.permissions.is_some();
+
+        ensure!(has_updates, Error::<T>::NoUpdatesForSpace);
+
+        let mut space = Self::require_space(space_id)?;
+
+        ensure!(T::IsAccountBlocked::is_allowed_account(owner.clone(), space.id), UtilsError::<T>::AccountIsBlocked);
+
+        // An update of `hidden` alone is allowed with a narrower `HideSpace` permission.
+        let is_hidden_update_only =
+            update.hidden.is_some() &&
+            update.parent_id.is_none() &&
+            update.handle.is_none() &&
+            update.content.is_none() &&
+            update.permissions.is_none();
+
+        let can_hide_space = is_hidden_update_only && Self::ensure_account_has_space_permission(
+            owner.clone(),
+            &space,
+            SpacePermission::HideSpace,
+            Error::<T>::NoPermissionToUpdateSpace.into()
+        ).is_ok();
+
+        if !can_hide_space {
+            Self::ensure_account_has_space_permission(
+                owner.clone(),
+                &space,
+                SpacePermission::UpdateSpace,
+                Error::<T>::NoPermissionToUpdateSpace.into()
+            )?;
+        }
+
+        T::BeforeSpaceUpdated::before_space_updated(owner.clone(), &space)?;
+
+        let mut is_update_applied = false;
+        let mut old_data = SpaceUpdate::default();
+
+        // TODO: add tests for this case
+        if let Some(parent_id_opt) = update.parent_id {
+            if parent_id_opt != space.parent_id {
+
+                if let Some(parent_id) = parent_id_opt {
+                    let parent_space = Self::require_space(parent_id)?;
+                    Self::ensure_reserved_space_owner(&owner, &parent_space)?;
+
+                    Self::ensure_account_has_space_permission(
+                        owner.clone(),
+                        &parent_space,
+                        SpacePermission::CreateSubspaces,
+                        Error::<T>::NoPermissionToCreateSubspaces.into()
+                    )?;
+                } else {
+                    // A subspace that becomes a root space needs the same origin as a new root space.
+                    T::CreateRootSpaceOrigin::ensure_origin(origin)?;
+                }
+
+                old_data.parent_id = Some(space.parent_id);
+                space.parent_id = parent_id_opt;
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(content) = update.content {
+            if content != space.content {
+                T::ContentValidator::is_valid_content(content.clone())?;
+
+                ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), space.id), UtilsError::<T>::ContentIsBlocked);
+                if let Some(parent_id) = space.parent_id {
+                    ensure!(T::IsContentBlocked::is_allowed_content(content.clone(), parent_id), UtilsError::<T>::ContentIsBlocked);
+                }
+
+                old_data.content = Some(space.content);
+                space.content = content;
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(hidden) = update.hidden {
+            if hidden != space.hidden {
+                old_data.hidden = Some(space.hidden);
+                space.hidden = hidden;
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(overrides_opt) = update.permissions {
+            if space.permissions != overrides_opt {
+                old_data.permissions = Some(space.permissions);
+
+                if let Some(overrides) = overrides_opt.clone() {
+                    space.permissions = Some(Permissions::<T>::override_permissions(overrides));
+                } else {
+                    space.permissions = overrides_opt;
+                }
+
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(new_handle) = &update.handle {
+            if *new_handle != space.handle {
+                Self::ensure_handle_change_allowed(space_id)?;
+            }
+        }
+
+        let is_handle_updated = Self::update_handle(&space, update.handle.clone())?;
+        if is_handle_updated {
+            Self::record_handle_change(space_id);
+            old_data.handle = Some(space.handle);
+            space.handle = update.handle.unwrap();
+            is_update_applied = true
+        }
+
+        // Update this space only if at least one field should be updated:
+        if is_update_applied {
+            space.updated = Some(WhoAndWhen::<T>::new(owner.clone()));
+
+            let is_permissions_updated = old_data.permissions.is_some();
+
+            <SpaceById<T>>::insert(space_id, space.clone());
+            T::AfterSpaceUpdated::after_space_updated(owner.clone(), &space, old_data);
+
+            Self::deposit_event(RawEvent::SpaceUpdated(owner.clone(), space_id));
+            if is_permissions_updated {
+                Self::deposit_event(RawEvent::SpacePermissionsUpdated(owner, space_id));
+            }
+        }
+        Ok(())
+    }
+
     /// Check that there is a `Space` with such `space_id` in the storage
     /// or return`SpaceNotFound` error.
     pub fn ensure_space_exists(space_id: SpaceId) -> DispatchResult {
@@ -401,11 +884,68 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Whether an account owns at least one space.
+    pub fn owns_any_space(account: &T::AccountId) -> bool {
+        !Self::space_ids_by_owner(account).is_empty()
+    }
+
     /// Get `Space` by id from the storage or return `SpaceNotFound` error.
     pub fn require_space(space_id: SpaceId) -> Result<Space<T>, DispatchError> {
         Ok(Self::space_by_id(space_id).ok_or(Error::<T>::SpaceNotFound)?)
     }
 
+    /// Check that `account` can use a space as a target for new posts and subspaces
+    /// if reserved spaces are restricted in this runtime.
+    pub fn ensure_reserved_space_owner(account: &T::AccountId, space: &Space<T>) -> DispatchResult {
+        if T::RestrictReservedSpaces::get() && space.is_reserved() {
+            ensure!(space.is_owner(account), Error::<T>::NotAReservedSpaceOwner);
+        }
+        Ok(())
+    }
+
+    /// Get the id of a space or a post created with an idempotency key
+    /// if the key was used at most `IdempotencyKeyTtl` blocks ago.
+    pub fn live_idempotency_key_entity<Id>(used_key: Option<(Id, T::BlockNumber)>) -> Option<Id> {
+        let now = <system::Module<T>>::block_number();
+        used_key
+            .filter(|(_, used_at)| now < Self::idempotency_key_expire_block(*used_at))
+            .map(|(id, _)| id)
+    }
+
+    /// Remove up to `MaxIdempotencyKeysRemovedPerBlock` keys that expire at `block_number`
+    /// and move the rest of them to the next block. Returns the weight of the removal.
+    fn remove_expired_idempotency_keys(block_number: T::BlockNumber) -> Weight {
+        let mut keys = <IdempotencyKeysByExpireBlock<T>>::take(block_number);
+        let (mut reads, mut writes) = (1, 1);
+
+        let max_keys = T::MaxIdempotencyKeysRemovedPerBlock::get() as usize;
+        if keys.len() > max_keys {
+            let keys_left = keys.split_off(max_keys);
+            <IdempotencyKeysByExpireBlock<T>>::mutate(
+                block_number.saturating_add(One::one()),
+                |next_keys| next_keys.extend(keys_left)
+            );
+            reads += 1;
+            writes += 1;
+        }
+
+        for key in &keys {
+            // The key could be used again after it expired, then it is live until a later block.
+            if Self::live_idempotency_key_entity(Self::used_idempotency_key(key)).is_none() {
+                <UsedIdempotencyKeys<T>>::remove(key);
+                writes += 1;
+            }
+            reads += 1;
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// The block at which an idempotency key used at `used_at` expires.
+    pub fn idempotency_key_expire_block(used_at: T::BlockNumber) -> T::BlockNumber {
+        used_at.saturating_add(T::IdempotencyKeyTtl::get())
+    }
+
     pub fn ensure_account_has_space_permission(
         account: T::AccountId,
         space: &Space<T>,
@@ -472,45 +1012,91 @@ impl<T: Trait> Module<T> {
         <T as Trait>::Currency::unreserve(space_owner, T::HandleDeposit::get())
     }
 
+    /// Get an account that holds a handle deposit of this space and the reserved amount.
+    /// Spaces that got their handles before depositors were recorded
+    /// are considered to be paid by their current owner.
+    fn handle_depositor(space: &Space<T>) -> (T::AccountId, BalanceOf<T>) {
+        Self::handle_deposit_by_space_id(space.id)
+            .unwrap_or_else(|| (space.owner.clone(), T::HandleDeposit::get()))
+    }
+
     /// This function will be performed only if a space has a handle.
-    /// Unreserve a handle deposit from the current space owner,
+    /// Unreserve a handle deposit from an account that holds it,
     /// then transfer deposit amount to a new owner
     /// and reserve this amount from a new owner.
     pub fn maybe_transfer_handle_deposit_to_new_space_owner(space: &Space<T>, new_owner: &T::AccountId) -> DispatchResult {
         if space.handle.is_some() {
-            let old_owner = &space.owner;
-            Self::unreserve_handle_deposit(old_owner);
+            let (depositor, deposit) = Self::handle_depositor(space);
+            <T as Trait>::Currency::unreserve(&depositor, deposit);
             <T as Trait>::Currency::transfer(
-                old_owner,
+                &depositor,
                 new_owner,
-                T::HandleDeposit::get(),
+                deposit,
                 ExistenceRequirement::KeepAlive
             )?;
-            Self::reserve_handle_deposit(new_owner)?;
+            <T as Trait>::Currency::reserve(new_owner, deposit)?;
+            HandleDepositBySpaceId::<T>::insert(space.id, (new_owner.clone(), deposit));
         }
         Ok(())
     }
 
+    /// Move a handle deposit of a space from an account that holds it to the treasury.
+    /// Used when there is no owner that a deposit could be returned to later.
+    pub fn forfeit_handle_deposit_to_treasury(space: &Space<T>) {
+        if space.handle.is_some() {
+            let (depositor, deposit) = Self::handle_depositor(space);
+            let (forfeited, _) = <T as Trait>::Currency::slash_reserved(&depositor, deposit);
+            <T as Trait>::Currency::resolve_creating(&Utils::<T>::treasury_account(), forfeited);
+            HandleDepositBySpaceId::<T>::remove(space.id);
+        }
+    }
+
+    /// Reserve a handle deposit from the space owner and map a handle to the space.
+    /// A handle should be lowercased and checked to be unique with `lowercase_and_ensure_unique_handle`.
     fn reserve_handle(
         space: &Space<T>,
-        handle: Vec<u8>
+        handle_in_lowercase: Vec<u8>
     ) -> DispatchResult {
-        let handle_in_lowercase = Self::lowercase_and_ensure_unique_handle(handle)?;
         Self::reserve_handle_deposit(&space.owner)?;
         SpaceIdByHandle::insert(handle_in_lowercase, space.id);
+        HandleDepositBySpaceId::<T>::insert(space.id, (space.owner.clone(), T::HandleDeposit::get()));
         Ok(())
     }
 
+    /// Unreserve a handle deposit from an account that actually holds it,
+    /// which is not necessarily the current space owner.
     fn unreserve_handle(
         space: &Space<T>,
         handle: Vec<u8>
     ) -> DispatchResult {
         let handle_in_lowercase = Utils::<T>::lowercase_handle(handle);
-        Self::unreserve_handle_deposit(&space.owner);
+        let (depositor, deposit) = Self::handle_depositor(space);
+        <T as Trait>::Currency::unreserve(&depositor, deposit);
         SpaceIdByHandle::remove(handle_in_lowercase);
+        HandleDepositBySpaceId::<T>::remove(space.id);
+        Ok(())
+    }
+
+    /// Ensure that the handle of a space was not changed during the last `HandleChangeCooldown` blocks.
+    fn ensure_handle_change_allowed(space_id: SpaceId) -> DispatchResult {
+        let cooldown = T::HandleChangeCooldown::get();
+        if cooldown.is_zero() {
+            return Ok(());
+        }
+
+        if let Some(changed_at) = Self::last_handle_change_by_space_id(space_id) {
+            let now = <system::Module<T>>::block_number();
+            ensure!(now >= changed_at.saturating_add(cooldown), Error::<T>::HandleChangedTooRecently);
+        }
         Ok(())
     }
 
+    fn record_handle_change(space_id: SpaceId) {
+        if !T::HandleChangeCooldown::get().is_zero() {
+            <LastHandleChangeBySpaceId<T>>::insert(space_id, <system::Module<T>>::block_number());
+        }
+    }
+
     fn update_handle(
         space: &Space<T>,
         maybe_new_handle: Option<Option<Vec<u8>>>,
@@ -540,7 +1126,8 @@ impl<T: Trait> Module<T> {
                 }
             } else if let Some(new_handle) = new_handle_opt {
                 // Reserve a handle for the space that has no handle yet
-                Self::reserve_handle(space, new_handle.clone())?;
+                let new_handle_lc = Self::lowercase_and_ensure_unique_handle(new_handle)?;
+                Self::reserve_handle(space, new_handle_lc)?;
                 is_handle_updated = true;
             }
         }
@@ -561,6 +1148,39 @@ impl<T: Trait> SpaceForRolesProvider for Module<T> {
     }
 }
 
+impl<T: Trait> SpaceSettingsProvider for Module<T> {
+    fn are_shares_disabled(space_id: SpaceId) -> bool {
+        Self::space_settings_by_id(space_id)
+            .and_then(|settings| settings.shares_disabled)
+            .unwrap_or(false)
+    }
+
+    fn is_duplicate_content_rejected(space_id: SpaceId) -> bool {
+        Self::space_settings_by_id(space_id)
+            .and_then(|settings| settings.reject_duplicate_content)
+            .unwrap_or(false)
+    }
+
+    fn min_reputation_to_post(space_id: SpaceId) -> Option<u32> {
+        Self::space_settings_by_id(space_id).and_then(|settings| settings.min_reputation_to_post)
+    }
+
+    fn min_reputation_to_comment(space_id: SpaceId) -> Option<u32> {
+        Self::space_settings_by_id(space_id).and_then(|settings| settings.min_reputation_to_comment)
+    }
+}
+
+impl<T: Trait> UserResolver for Module<T> {
+    type AccountId = T::AccountId;
+
+    fn owner_of(user: &User<Self::AccountId>) -> Option<Self::AccountId> {
+        match user {
+            User::Account(account) => Some(account.clone()),
+            User::Space(space_id) => Module::<T>::space_by_id(space_id).map(|space| space.owner),
+        }
+    }
+}
+
 pub trait BeforeSpaceCreated<T: Trait> {
     fn before_space_created(follower: T::AccountId, space: &mut Space<T>) -> DispatchResult;
 }
@@ -571,7 +1191,22 @@ impl<T: Trait> BeforeSpaceCreated<T> for () {
     }
 }
 
+pub trait BeforeSpaceUpdated<T: Trait> {
+    fn before_space_updated(editor: T::AccountId, space: &Space<T>) -> DispatchResult;
+}
+
+impl<T: Trait> BeforeSpaceUpdated<T> for () {
+    fn before_space_updated(_editor: T::AccountId, _space: &Space<T>) -> DispatchResult {
+        Ok(())
+    }
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(10)]
 pub trait AfterSpaceUpdated<T: Trait> {
     fn after_space_updated(sender: T::AccountId, space: &Space<T>, old_data: SpaceUpdate);
 }
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait AfterSpaceOwnershipChanged<T: Trait> {
+    fn after_space_ownership_changed(old_owner: T::AccountId, space: &Space<T>);
+}
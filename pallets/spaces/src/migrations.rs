@@ -0,0 +1,185 @@
+//! Storage migrations of the Spaces pallet.
+//!
+//! The version of the storage layout is kept in `StorageVersion`, as FRAME of this version
+//! does not store pallet versions. A migration is added here together with a crate version bump,
+//! and it is run only if the version in storage is lower than the one that introduced the migration.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::Get,
+    weights::Weight,
+    IterableStorageMap, StorageMap, StorageValue,
+};
+use sp_std::{cell::Cell, vec::Vec};
+
+use pallet_permissions::SpacePermissions;
+use pallet_utils::{Content, PalletVersion, SpaceId, WhoAndWhen};
+
+use crate::{
+    IdempotencyKeysByExpireBlock, Module, Space, SpaceById, SpaceSettings, SpaceSettingsById, StorageVersion,
+    Trait, UsedIdempotencyKeys, SPACE_SETTINGS_VERSION,
+};
+
+/// The crate version that added `reject_duplicate_content` to space settings.
+pub const V0_7_0: PalletVersion = PalletVersion { major: 0, minor: 7, patch: 0 };
+
+/// The crate version that added the minimum reputation to post and comment to space settings.
+pub const V0_8_0: PalletVersion = PalletVersion { major: 0, minor: 8, patch: 0 };
+
+/// The crate version that added `comments_count` to a space.
+pub const V0_9_0: PalletVersion = PalletVersion { major: 0, minor: 9, patch: 0 };
+
+/// The crate version that added `IdempotencyKeysByExpireBlock`.
+pub const V0_10_0: PalletVersion = PalletVersion { major: 0, minor: 10, patch: 0 };
+
+/// The crate version that introduced the current storage layout of this pallet.
+pub const STORAGE_VERSION: PalletVersion = V0_10_0;
+
+/// Space settings as they were stored with `version` 1, before `V0_7_0`.
+#[derive(Encode, Decode)]
+pub struct SpaceSettingsV1 {
+    pub version: u16,
+    pub shares_disabled: Option<bool>,
+}
+
+/// Space settings as they were stored with `version` 2, before `V0_8_0`.
+#[derive(Encode, Decode)]
+pub struct SpaceSettingsV2 {
+    pub version: u16,
+    pub shares_disabled: Option<bool>,
+    pub reject_duplicate_content: Option<bool>,
+}
+
+/// A space as it was stored before `V0_9_0`.
+#[derive(Encode, Decode)]
+pub struct SpaceV0<T: Trait> {
+    pub id: SpaceId,
+    pub created: WhoAndWhen<T>,
+    pub updated: Option<WhoAndWhen<T>>,
+    pub owner: T::AccountId,
+    pub parent_id: Option<SpaceId>,
+    pub handle: Option<Vec<u8>>,
+    pub content: Content,
+    pub hidden: bool,
+    pub posts_count: u32,
+    pub hidden_posts_count: u32,
+    pub followers_count: u32,
+    pub score: i32,
+    pub permissions: Option<SpacePermissions>,
+}
+
+/// Whether the storage of this pallet should be migrated to `version`.
+/// A pallet without a version in storage predates the storage versioning.
+fn should_migrate_to<T: Trait>(version: PalletVersion) -> bool {
+    Module::<T>::storage_version().map_or(true, |stored| stored < version)
+}
+
+/// Run the migrations of this pallet that were not applied to storage yet.
+///
+/// Called by the runtime rather than by `decl_module!`, so the runtime controls
+/// the order in which pallets are migrated.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+
+    // Both migrations write the current layout of settings, so only the oldest one is needed.
+    if should_migrate_to::<T>(V0_7_0) {
+        weight = weight.saturating_add(migrate_to_v0_7_0::<T>());
+    } else if should_migrate_to::<T>(V0_8_0) {
+        weight = weight.saturating_add(migrate_to_v0_8_0::<T>());
+    }
+
+    if should_migrate_to::<T>(V0_9_0) {
+        weight = weight.saturating_add(migrate_to_v0_9_0::<T>());
+    }
+
+    if should_migrate_to::<T>(V0_10_0) {
+        weight = weight.saturating_add(migrate_to_v0_10_0::<T>());
+    }
+
+    StorageVersion::put(STORAGE_VERSION);
+    weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Add an unset `reject_duplicate_content` toggle to the settings of every space.
+pub fn migrate_to_v0_7_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    SpaceSettingsById::translate::<SpaceSettingsV1, _>(|_space_id, old| {
+        migrated.set(migrated.get() + 1);
+
+        Some(SpaceSettings {
+            version: SPACE_SETTINGS_VERSION,
+            shares_disabled: old.shares_disabled,
+            reject_duplicate_content: None,
+            min_reputation_to_post: None,
+            min_reputation_to_comment: None,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get())
+}
+
+/// Add unset minimum reputations to post and comment to the settings of every space.
+pub fn migrate_to_v0_8_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    SpaceSettingsById::translate::<SpaceSettingsV2, _>(|_space_id, old| {
+        migrated.set(migrated.get() + 1);
+
+        Some(SpaceSettings {
+            version: SPACE_SETTINGS_VERSION,
+            shares_disabled: old.shares_disabled,
+            reject_duplicate_content: old.reject_duplicate_content,
+            min_reputation_to_post: None,
+            min_reputation_to_comment: None,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get())
+}
+
+/// Add `comments_count` to every space, starting from zero.
+pub fn migrate_to_v0_9_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    SpaceById::<T>::translate::<SpaceV0<T>, _>(|_space_id, old| {
+        migrated.set(migrated.get() + 1);
+
+        Some(Space {
+            id: old.id,
+            created: old.created,
+            updated: old.updated,
+            owner: old.owner,
+            parent_id: old.parent_id,
+            handle: old.handle,
+            content: old.content,
+            hidden: old.hidden,
+            posts_count: old.posts_count,
+            hidden_posts_count: old.hidden_posts_count,
+            followers_count: old.followers_count,
+            score: old.score,
+            permissions: old.permissions,
+            comments_count: 0,
+        })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get())
+}
+
+/// Remove expired idempotency keys and schedule the removal of the live ones.
+pub fn migrate_to_v0_10_0<T: Trait>() -> Weight {
+    let migrated = Cell::new(0u64);
+
+    UsedIdempotencyKeys::<T>::translate::<(SpaceId, T::BlockNumber), _>(|key, used_key| {
+        migrated.set(migrated.get() + 1);
+
+        let used_at = used_key.1;
+        Module::<T>::live_idempotency_key_entity(Some(used_key))
+            .map(|space_id| {
+                IdempotencyKeysByExpireBlock::<T>::append(Module::<T>::idempotency_key_expire_block(used_at), key);
+                (space_id, used_at)
+            })
+    });
+
+    T::DbWeight::get().reads_writes(migrated.get(), migrated.get().saturating_mul(2))
+}
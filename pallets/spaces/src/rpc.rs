@@ -0,0 +1,111 @@
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+use pallet_permissions::SpacePermissions;
+use pallet_utils::{Content, SpaceId, SpaceOwnership, Module as Utils};
+
+use crate::{Module, Space, Trait};
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FlatSpace<AccountId, BlockNumber, Moment> {
+    pub id: SpaceId,
+
+    pub created_by: AccountId,
+    pub created_at_block: BlockNumber,
+    pub created_at_time: Moment,
+
+    pub updated_by: Option<AccountId>,
+    pub updated_at_block: Option<BlockNumber>,
+    pub updated_at_time: Option<Moment>,
+
+    pub owner: AccountId,
+    /// Whether the space is still owned by the account that created it.
+    pub is_original_owner: bool,
+
+    pub parent_id: Option<SpaceId>,
+    pub handle: Option<Vec<u8>>,
+    pub content: Content,
+    pub hidden: bool,
+
+    pub posts_count: u32,
+    pub hidden_posts_count: u32,
+    pub followers_count: u32,
+
+    pub score: i32,
+
+    pub permissions: Option<SpacePermissions>,
+
+    pub comments_count: u32,
+}
+
+impl<T: Trait> From<Space<T>> for FlatSpace<T::AccountId, T::BlockNumber, T::Moment> {
+    fn from(space: Space<T>) -> Self {
+        let Space {
+            id, created, updated, owner,
+            parent_id, handle, content, hidden,
+            posts_count, hidden_posts_count, followers_count,
+            score, permissions, comments_count
+        } = space;
+
+        let is_original_owner = owner == created.account;
+
+        Self {
+            id,
+
+            created_by: created.account,
+            created_at_block: created.block,
+            created_at_time: created.time,
+
+            updated_by: updated.clone().map(|x| x.account),
+            updated_at_block: updated.clone().map(|x| x.block),
+            updated_at_time: updated.map(|x| x.time),
+
+            owner,
+            is_original_owner,
+
+            parent_id,
+            handle,
+            content,
+            hidden,
+
+            posts_count,
+            hidden_posts_count,
+            followers_count,
+
+            score,
+
+            permissions,
+
+            comments_count,
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Find a space by its handle in any case, e.g. `Space_Handle` and `space_handle`
+    /// resolve to the same space.
+    pub fn get_space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<T::AccountId, T::BlockNumber, T::Moment>> {
+        let handle_in_lowercase = Utils::<T>::lowercase_handle(handle);
+
+        Self::space_id_by_handle(handle_in_lowercase)
+            .and_then(Self::space_by_id)
+            .map(|space| space.into())
+    }
+
+    /// Whether a handle in any case is already taken by a space,
+    /// so `create_space` with this handle would fail with `SpaceHandleIsNotUnique`.
+    /// A handle is not validated here, so an invalid one is reported as not taken.
+    pub fn would_handle_collide(handle: Vec<u8>) -> bool {
+        let handle_in_lowercase = Utils::<T>::lowercase_handle(handle);
+        Self::space_id_by_handle(handle_in_lowercase).is_some()
+    }
+
+    /// Get the single owner of a space. `None` if there is no space with this id.
+    pub fn get_space_owners(space_id: SpaceId) -> Option<SpaceOwnership<T::AccountId>> {
+        Self::space_by_id(space_id).map(|space| SpaceOwnership::Single(space.owner))
+    }
+}
@@ -0,0 +1,100 @@
+//! Weights for the Spaces pallet.
+//!
+//! `SubstrateWeight` holds the weights measured by the benchmarks of this pallet
+//! with `--steps 50 --repeat 20`. To update them, build the node with `--features runtime-benchmarks`
+//! and run `bunbi-node benchmark --chain dev --pallet pallet_spaces --extrinsic '*' --steps 50 --repeat 20`.
+//! The implementation for `()` keeps the weights that were set by hand and is used in tests.
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_spaces.
+pub trait WeightInfo {
+    fn create_space() -> Weight;
+    fn update_space() -> Weight;
+    fn update_space_settings() -> Weight;
+    fn transfer_handle() -> Weight;
+    fn recompute_space_stats(l: u32, ) -> Weight;
+    fn content_bytes(b: u32, ) -> Weight;
+}
+
+/// Weights for pallet_spaces measured by its benchmarks.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_space() -> Weight {
+        (42_328_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+
+    fn update_space() -> Weight {
+        (34_657_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+
+    fn update_space_settings() -> Weight {
+        (17_475_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn transfer_handle() -> Weight {
+        (55_425_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+
+    // The posts of a space are provided by another pallet, so the benchmark recomputes
+    // the stats of a space without posts, and the weight of a post is set by hand.
+    fn recompute_space_stats(l: u32, ) -> Weight {
+        (25_318_000 as Weight)
+            .saturating_add((40_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(l as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    // Not benchmarked, the weight is set by hand.
+    fn content_bytes(b: u32, ) -> Weight {
+        (1_000 as Weight).saturating_mul(b as Weight)
+    }
+}
+
+impl WeightInfo for () {
+    fn create_space() -> Weight {
+        (500_000 as Weight)
+            .saturating_add(DbWeight::get().reads(4 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+
+    fn update_space() -> Weight {
+        (500_000 as Weight)
+            .saturating_add(DbWeight::get().reads(3 as Weight))
+            .saturating_add(DbWeight::get().writes(4 as Weight))
+    }
+
+    fn update_space_settings() -> Weight {
+        (500_000 as Weight)
+            .saturating_add(DbWeight::get().reads(2 as Weight))
+            .saturating_add(DbWeight::get().writes(1 as Weight))
+    }
+
+    fn transfer_handle() -> Weight {
+        (500_000 as Weight)
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().writes(7 as Weight))
+    }
+
+    fn recompute_space_stats(l: u32, ) -> Weight {
+        (500_000 as Weight)
+            .saturating_add((40_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(DbWeight::get().reads(6 as Weight))
+            .saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(l as Weight)))
+            .saturating_add(DbWeight::get().writes(2 as Weight))
+    }
+
+    fn content_bytes(b: u32, ) -> Weight {
+        (1_000 as Weight).saturating_mul(b as Weight)
+    }
+}
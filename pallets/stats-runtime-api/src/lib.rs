@@ -0,0 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::Serialize;
+
+use pallet_posts::PostId;
+use pallet_reactions::ReactionId;
+use pallet_roles::RoleId;
+use pallet_utils::SpaceId;
+
+/// Ids that will be given to the next entities of every kind,
+/// i.e. how much of an id space of every kind is used.
+/// Report ids are not included while the moderation pallet is not a part of the runtime.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct NextIds {
+    pub next_space_id: SpaceId,
+    pub next_post_id: PostId,
+    pub next_reaction_id: ReactionId,
+    pub next_role_id: RoleId,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over chain-wide stats of the social pallets.
+    pub trait StatsApi {
+        /// Get the ids that will be given to the next spaces, posts, reactions and roles.
+        fn next_ids() -> NextIds;
+    }
+}
@@ -1,7 +1,6 @@
 use crate::*;
 
 use sp_runtime::traits::Saturating;
-use pallet_sudo::Module as Sudo;
 use frame_support::{
     dispatch::DispatchError,
     traits::schedule::DispatchTime,
@@ -50,9 +49,9 @@ impl<T: Trait> Module<T> {
         T::Scheduler::schedule_named(
             task_name,
             DispatchTime::At(when),
-            Some((period_in_blocks, 12)),
+            Some((period_in_blocks, u32::max_value())),
             1,
-            frame_system::RawOrigin::Signed(Sudo::<T>::key()).into(),
+            frame_system::RawOrigin::Root.into(),
             Call::process_subscription_payment(subscription_id).into()
         ).map_err(|_| Error::<T>::CannotScheduleReccurentPayment)?;
         Ok(())
@@ -64,18 +63,30 @@ impl<T: Trait> Module<T> {
         // todo: emmit event with status
     }
 
-    pub(crate) fn do_unsubscribe(who: T::AccountId, subscription: &mut Subscription<T>) -> DispatchResult {
-        let space_id = Self::require_plan(subscription.plan_id)?.space_id;
+    /// Stop recurring payments of a subscription and remove it from the lists of active ones.
+    pub(crate) fn do_cancel_subscription(
+        who: T::AccountId,
+        subscription: &mut Subscription<T>,
+        space_id: SpaceId
+    ) {
+        Self::cancel_recurring_subscription_payment(subscription.id);
+        Self::deactivate_subscription(who, subscription, space_id);
+    }
+
+    /// Mark a subscription as inactive and remove it from the lists of active ones.
+    /// It does not cancel recurring payments of the subscription.
+    pub(crate) fn deactivate_subscription(
+        who: T::AccountId,
+        subscription: &mut Subscription<T>,
+        space_id: SpaceId
+    ) {
         let subscription_id = subscription.id;
 
-        Self::cancel_recurring_subscription_payment(subscription_id);
         subscription.is_active = false;
 
         SubscriptionById::<T>::insert(subscription_id, subscription);
         SubscriptionIdsByPatron::<T>::mutate(who, |ids| remove_from_vec(ids, subscription_id));
         SubscriptionIdsBySpace::mutate(space_id, |ids| remove_from_vec(ids, subscription_id));
-
-        Ok(())
     }
 
     pub(crate) fn filter_subscriptions_by_plan(
@@ -135,6 +146,7 @@ impl<T: Trait> Subscription<T> {
             is_active: true,
             wallet,
             plan_id,
+            missed_payments: 0,
         }
     }
 
@@ -11,6 +11,10 @@
 //!
 //! This pallet uses Substrate's Schedule pallet to schedule recurring transfers from supporters'
 //! (patrons') wallets to creators' wallets.
+//!
+//! If a subscriber cannot pay for the next period, the payment is counted as missed.
+//! Once the number of missed payments in a row exceeds `MaxMissedPayments`,
+//! the subscription is cancelled automatically.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,8 +32,9 @@ use frame_support::{
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
 
+use df_traits::ContentValidator;
 use pallet_spaces::Module as Spaces;
-use pallet_utils::{Module as Utils, SpaceId, Content, WhoAndWhen, remove_from_vec};
+use pallet_utils::{SpaceId, Content, WhoAndWhen, remove_from_vec};
 
 /*#[cfg(test)]
 mod mock;
@@ -80,6 +85,9 @@ pub struct Subscription<T: Trait> {
 
 	pub wallet: Option<T::AccountId>,
 	pub plan_id: SubscriptionPlanId,
+
+	/// A number of recurring payments in a row that the subscriber failed to pay.
+	pub missed_payments: u32,
 }
 
 type BalanceOf<T> = <<T as pallet_utils::Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
@@ -89,14 +97,16 @@ pub trait Trait:
 	system::Trait
 	+ pallet_utils::Trait
 	+ pallet_spaces::Trait
-	+ pallet_sudo::Trait
 {
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
 	type Subscription: Dispatchable<Origin=<Self as system::Trait>::Origin> + From<Call<Self>>;
 
-	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Subscription, Self::Origin>;
+	/// The caller origin, overarching type of all pallets origins.
+	type PalletsOrigin: From<system::RawOrigin<Self::AccountId>>;
+
+	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Subscription, Self::PalletsOrigin>;
 
 	type DailyPeriodInBlocks: Get<Self::BlockNumber>;
 
@@ -107,6 +117,10 @@ pub trait Trait:
 	type QuarterlyPeriodInBlocks: Get<Self::BlockNumber>;
 
 	type YearlyPeriodInBlocks: Get<Self::BlockNumber>;
+
+	/// How many recurring payments in a row a subscriber can miss
+	/// before their subscription is cancelled automatically.
+	type MaxMissedPayments: Get<u32>;
 }
 
 decl_storage! {
@@ -128,12 +142,19 @@ decl_storage! {
 		pub SubscriptionById get(fn subscription_by_id):
 			map hasher(twox_64_concat) SubscriptionId => Option<Subscription<T>>;
 
+		/// Ids of active subscriptions of a patron account (key).
 		pub SubscriptionIdsByPatron get(fn subscription_ids_by_patron):
 			map hasher(blake2_128_concat) T::AccountId => Vec<SubscriptionId>;
 
+		/// Ids of active subscriptions to all plans of a space (key).
 		pub SubscriptionIdsBySpace get(fn subscription_ids_by_space):
 			map hasher(twox_64_concat) SpaceId => Vec<SubscriptionId>;
 
+		/// Ids of subscriptions that have been cancelled by their own recurring payment
+		/// in this block. Their scheduled tasks are cancelled at the end of the block,
+		/// because the scheduler reschedules a periodic task after dispatching it.
+		pub SubscriptionsToUnschedule get(fn subscriptions_to_unschedule): Vec<SubscriptionId>;
+
 		// Wallets
 
 		/// A recipient's wallet that receives transfers sent from their subscribers.
@@ -149,10 +170,21 @@ decl_storage! {
 // The pallet's events
 decl_event!(
 	pub enum Event<T> where
-		AccountId = <T as system::Trait>::AccountId
+		AccountId = <T as system::Trait>::AccountId,
+		BalanceOf = BalanceOf<T>
 	{
 		SubscriptionPlanCreated(AccountId, SubscriptionPlanId),
-		// todo: complete event list for this pallet once dispatches are implemented
+		SubscriptionPlanUpdated(AccountId, SubscriptionPlanId),
+		SubscriptionPlanDeleted(AccountId, SubscriptionPlanId),
+		Subscribed(AccountId, SubscriptionPlanId, SubscriptionId),
+		SubscriptionUpdated(AccountId, SubscriptionId),
+		SubscriptionCancelled(AccountId, SubscriptionId),
+		/// A recurring payment has been transferred from a subscriber to a plan recipient.
+		SubscriptionPaymentProcessed(AccountId, SubscriptionId, BalanceOf),
+		/// A subscriber could not pay for the next period of their subscription.
+		SubscriptionPaymentMissed(AccountId, SubscriptionId),
+		/// A subscription has been cancelled, because a subscriber missed too many payments.
+		SubscriptionCancelledForNonPayment(AccountId, SubscriptionId),
 	}
 );
 
@@ -189,12 +221,19 @@ decl_module! {
 		// Initializing events
 		fn deposit_event() = default;
 
+		/// Cancel the recurring payments of subscriptions that were cancelled automatically.
+		fn on_finalize(_block_number: T::BlockNumber) {
+			for subscription_id in SubscriptionsToUnschedule::take() {
+				Self::cancel_recurring_subscription_payment(subscription_id);
+			}
+		}
+
 		/// Create a new subscription plan for a specified space.
 		/// It's possible to specify a price and time period (in blocks) for the plan.
 		/// Content could be an IPFS CID that points to an off-chain data such as
 		/// plan's title, description and cover image.
 		#[weight = T::DbWeight::get().reads_writes(3, 3) + 25_000]
-		pub fn create_plan(
+		pub fn create_subscription_plan(
 			origin,
 			space_id: SpaceId,
 			custom_wallet: Option<T::AccountId>,
@@ -204,7 +243,7 @@ decl_module! {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			Utils::<T>::is_valid_content(content.clone())?;
+			T::ContentValidator::is_valid_content(content.clone())?;
 
 			ensure!(
 				price >= <T as pallet_utils::Trait>::Currency::minimum_balance(),
@@ -217,7 +256,7 @@ decl_module! {
 			let plan_id = Self::next_plan_id();
 			let subscription_plan = SubscriptionPlan::<T>::new(
 				plan_id,
-				sender,
+				sender.clone(),
 				space_id,
 				custom_wallet,
 				price,
@@ -229,6 +268,7 @@ decl_module! {
 			PlanIdsBySpace::mutate(space_id, |ids| ids.push(plan_id));
 			NextPlanId::mutate(|x| { *x += 1 });
 
+			Self::deposit_event(RawEvent::SubscriptionPlanCreated(sender, plan_id));
 			Ok(())
 		}
 
@@ -244,14 +284,15 @@ decl_module! {
 
 			ensure!(new_wallet != plan.wallet, Error::<T>::NothingToUpdate);
 			plan.wallet = new_wallet;
-			plan.updated = Some(WhoAndWhen::<T>::new(sender));
+			plan.updated = Some(WhoAndWhen::<T>::new(sender.clone()));
 			PlanById::<T>::insert(plan_id, plan);
 
+			Self::deposit_event(RawEvent::SubscriptionPlanUpdated(sender, plan_id));
 			Ok(())
 		}
 
-		/// Delete a subscription plan by its id.
-		#[weight = 10_000]
+		/// Delete a subscription plan by its id and cancel all active subscriptions to it.
+		#[weight = T::DbWeight::get().reads_writes(5, 5) + 50_000]
 		pub fn delete_plan(origin, plan_id: SubscriptionPlanId) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
@@ -259,17 +300,17 @@ decl_module! {
 			ensure!(plan.is_active, Error::<T>::PlanIsNotActive);
 
 			let space = Spaces::<T>::require_space(plan.space_id)?;
-			Self::ensure_subscriptions_manager(sender, &space)?;
+			Self::ensure_subscriptions_manager(sender.clone(), &space)?;
 
-			let space_subscriptions = SubscriptionIdsBySpace::take(plan.space_id);
-			let plan_subscriptions = space_subscriptions.iter()
-				.filter(|id| Self::filter_subscriptions_by_plan(**id, plan_id));
+			let plan_subscriptions: Vec<SubscriptionId> = Self::subscription_ids_by_space(plan.space_id)
+				.into_iter()
+				.filter(|id| Self::filter_subscriptions_by_plan(*id, plan_id))
+				.collect();
 
 			for id in plan_subscriptions {
-				if let Ok(mut subscription) = Self::require_subscription(*id) {
-					Self::cancel_recurring_subscription_payment(*id);
-					subscription.is_active = false;
-					SubscriptionById::<T>::insert(id, subscription);
+				if let Ok(mut subscription) = Self::require_subscription(id) {
+					let subscriber = subscription.created.account.clone();
+					Self::do_cancel_subscription(subscriber, &mut subscription, plan.space_id);
 				}
 			}
 
@@ -277,6 +318,7 @@ decl_module! {
 			PlanById::<T>::insert(plan_id, plan.clone());
 			PlanIdsBySpace::mutate(plan.space_id, |ids| remove_from_vec(ids, plan_id));
 
+			Self::deposit_event(RawEvent::SubscriptionPlanDeleted(sender, plan_id));
 			Ok(())
 		}
 
@@ -306,7 +348,8 @@ decl_module! {
 
 		/// Subscribe to a selected subscription plan and optionally specify a wallet
 		/// that will be used for recurring payments fro this subscription.
-		#[weight = T::DbWeight::get().reads_writes(5, 1) + 50_000]
+		/// The first period is paid immediately, the next ones are charged by the scheduler.
+		#[weight = T::DbWeight::get().reads_writes(6, 5) + 50_000]
 		pub fn subscribe(
 			origin,
 			plan_id: SubscriptionPlanId,
@@ -320,11 +363,13 @@ decl_module! {
 			let subscriptions = Self::subscription_ids_by_patron(&sender);
 			let is_already_subscribed = subscriptions.iter().any(|subscription_id| {
 				if let Ok(subscription) = Self::require_subscription(*subscription_id) {
-					return subscription.plan_id == plan_id;
+					return subscription.is_active && subscription.plan_id == plan_id;
 				}
 				false
 			});
-			ensure!(is_already_subscribed, Error::<T>::AlreadySubscribed);
+			ensure!(!is_already_subscribed, Error::<T>::AlreadySubscribed);
+
+			let recipient = plan.try_get_recipient().ok_or(Error::<T>::RecipientNotFound)?;
 
 			let subscription_id = Self::next_subscription_id();
 			let subscription = Subscription::<T>::new(
@@ -334,26 +379,22 @@ decl_module! {
 				plan_id
 			);
 
-			Self::schedule_recurring_subscription_payment(subscription_id, plan.period.clone())?;
-
-			let recipient = plan.try_get_recipient();
-			ensure!(recipient.is_some(), Error::<T>::RecipientNotFound);
-
 			// todo: maybe implement function `transfer_or_reserve`?
 			<T as pallet_utils::Trait>::Currency::transfer(
 				&sender,
-				&recipient.unwrap(),
+				&recipient,
 				plan.price,
 				ExistenceRequirement::KeepAlive
-			).map_err(|err| {
-				Self::cancel_recurring_subscription_payment(subscription_id);
-				err
-			})?;
+			)?;
+
+			Self::schedule_recurring_subscription_payment(subscription_id, plan.period.clone())?;
 
 			SubscriptionById::<T>::insert(subscription_id, subscription);
-			SubscriptionIdsByPatron::<T>::mutate(sender, |ids| ids.push(subscription_id));
+			SubscriptionIdsByPatron::<T>::mutate(sender.clone(), |ids| ids.push(subscription_id));
 			SubscriptionIdsBySpace::mutate(plan.space_id, |ids| ids.push(subscription_id));
+			NextSubscriptionId::mutate(|x| { *x += 1 });
 
+			Self::deposit_event(RawEvent::Subscribed(sender, plan_id, subscription_id));
 			Ok(())
 		}
 
@@ -371,15 +412,16 @@ decl_module! {
 			ensure!(new_wallet != subscription.wallet, Error::<T>::NothingToUpdate);
 
 			subscription.wallet = new_wallet;
-			subscription.updated = Some(WhoAndWhen::<T>::new(sender));
+			subscription.updated = Some(WhoAndWhen::<T>::new(sender.clone()));
 			SubscriptionById::<T>::insert(subscription_id, subscription);
 
+			Self::deposit_event(RawEvent::SubscriptionUpdated(sender, subscription_id));
 			Ok(())
 		}
 
-		/// Unsubscribe from one of your current subscriptions by its id.
+		/// Cancel one of your current subscriptions by its id.
 		#[weight = T::DbWeight::get().reads_writes(4, 3) + 25_000]
-		pub fn unsubscribe(origin, subscription_id: SubscriptionId) -> DispatchResult {
+		pub fn cancel_subscription(origin, subscription_id: SubscriptionId) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
 			let mut subscription = Self::require_subscription(subscription_id)?;
@@ -388,8 +430,10 @@ decl_module! {
 			ensure!(subscription.is_active, Error::<T>::SubscriptionIsNotActive);
 
 			// todo: add scheduled task to make subscription inactive at the end
-			Self::do_unsubscribe(sender, &mut subscription)?;
+			let space_id = Self::require_plan(subscription.plan_id)?.space_id;
+			Self::do_cancel_subscription(sender.clone(), &mut subscription, space_id);
 
+			Self::deposit_event(RawEvent::SubscriptionCancelled(sender, subscription_id));
 			Ok(())
 		}
 
@@ -418,24 +462,47 @@ decl_module! {
 			Ok(())
 		}
 
-		#[weight = T::DbWeight::get().reads_writes(4, 1) + 25_000]
+		/// Charge a subscriber for the next period of their subscription.
+		/// This dispatch is called by the scheduler only.
+		// The extra reads and writes are for the task cancellation in `on_finalize`.
+		#[weight = T::DbWeight::get().reads_writes(8, 7) + 25_000]
 		pub fn process_subscription_payment(origin, subscription_id: SubscriptionId) -> DispatchResult {
 			ensure_root(origin)?;
 
-			// todo: remove recurring payment if something in this block goes wrong
 			let mut subscription = Self::require_subscription(subscription_id)?;
+			ensure!(subscription.is_active, Error::<T>::SubscriptionIsNotActive);
+
 			let plan = Self::require_plan(subscription.plan_id)?;
-			let recipient = plan.try_get_recipient();
-			ensure!(recipient.is_some(), Error::<T>::RecipientNotFound);
+			let subscriber = subscription.created.account.clone();
+
+			let is_paid = plan.try_get_recipient().map_or(false, |recipient| {
+				<T as pallet_utils::Trait>::Currency::transfer(
+					&subscriber,
+					&recipient,
+					plan.price,
+					ExistenceRequirement::KeepAlive
+				).is_ok()
+			});
 
-			subscription.is_active = <T as pallet_utils::Trait>::Currency::transfer(
-				&subscription.created.account,
-				&recipient.unwrap(),
-				plan.price,
-				ExistenceRequirement::KeepAlive
-			).is_err();
+			if is_paid {
+				subscription.missed_payments = 0;
+				SubscriptionById::<T>::insert(subscription_id, subscription);
+				Self::deposit_event(RawEvent::SubscriptionPaymentProcessed(subscriber, subscription_id, plan.price));
+				return Ok(());
+			}
 
-			SubscriptionById::<T>::insert(subscription_id, subscription);
+			subscription.missed_payments = subscription.missed_payments.saturating_add(1);
+			Self::deposit_event(RawEvent::SubscriptionPaymentMissed(subscriber.clone(), subscription_id));
+
+			if subscription.missed_payments > T::MaxMissedPayments::get() {
+				// The task that is being dispatched cannot be cancelled from this dispatch,
+				// so it is cancelled in `on_finalize`.
+				Self::deactivate_subscription(subscriber.clone(), &mut subscription, plan.space_id);
+				SubscriptionsToUnschedule::append(subscription_id);
+				Self::deposit_event(RawEvent::SubscriptionCancelledForNonPayment(subscriber, subscription_id));
+			} else {
+				SubscriptionById::<T>::insert(subscription_id, subscription);
+			}
 
 			Ok(())
 		}
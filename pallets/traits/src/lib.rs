@@ -1,13 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_std::vec::Vec;
 
 use pallet_permissions::{
   SpacePermission,
   SpacePermissions,
   SpacePermissionsContext
 };
-use pallet_utils::{SpaceId, User};
+use pallet_utils::{Content, SpaceId, User};
 
 pub mod moderation;
 
@@ -23,15 +24,122 @@ pub trait SpaceForRolesProvider {
   fn get_space(id: SpaceId) -> Result<SpaceForRoles<Self::AccountId>, DispatchError>;
 }
 
+/// Resolves a `User` to the account that controls it:
+/// an account controls itself, while a space is controlled by its owner.
+pub trait UserResolver {
+  type AccountId;
+
+  fn owner_of(user: &User<Self::AccountId>) -> Option<Self::AccountId>;
+}
+
+/// Checks that content is valid before a pallet stores it.
+/// The utils pallet implements it, but a runtime can plug in other validation rules.
+pub trait ContentValidator {
+  fn is_valid_content(content: Content) -> DispatchResult;
+}
+
+impl<T: pallet_utils::Trait> ContentValidator for pallet_utils::Module<T> {
+  fn is_valid_content(content: Content) -> DispatchResult {
+    pallet_utils::Module::<T>::is_valid_content(content)
+  }
+}
+
+/// Tells whether an account has an identity confirmed by a trusted registrar.
+pub trait IdentityProvider<AccountId> {
+  fn has_positive_judgement(account: &AccountId) -> bool;
+}
+
+impl<AccountId> IdentityProvider<AccountId> for () {
+  fn has_positive_judgement(_account: &AccountId) -> bool {
+    false
+  }
+}
+
+/// Reads feature toggles of a space, so a pallet does not depend on the spaces pallet for it.
+pub trait SpaceSettingsProvider {
+  /// Whether posts cannot be shared into a space.
+  fn are_shares_disabled(space_id: SpaceId) -> bool;
+
+  /// Whether a post cannot be added to a space that already has a post with the same IPFS CID.
+  fn is_duplicate_content_rejected(space_id: SpaceId) -> bool;
+
+  /// The minimum reputation of an account to create root posts in a space, if any.
+  fn min_reputation_to_post(space_id: SpaceId) -> Option<u32>;
+
+  /// The minimum reputation of an account to comment in a space, if any.
+  fn min_reputation_to_comment(space_id: SpaceId) -> Option<u32>;
+}
+
+impl SpaceSettingsProvider for () {
+  fn are_shares_disabled(_space_id: SpaceId) -> bool {
+    false
+  }
+
+  fn is_duplicate_content_rejected(_space_id: SpaceId) -> bool {
+    false
+  }
+
+  fn min_reputation_to_post(_space_id: SpaceId) -> Option<u32> {
+    None
+  }
+
+  fn min_reputation_to_comment(_space_id: SpaceId) -> Option<u32> {
+    None
+  }
+}
+
+/// Reads the reputation of an account, so a pallet does not depend on the profiles pallet for it.
+pub trait ReputationProvider<AccountId> {
+  fn reputation_of(account: &AccountId) -> u32;
+}
+
+/// Every account has zero reputation. With this provider, only space owners can post
+/// or comment in a space that sets a minimum reputation to post or to comment.
+impl<AccountId> ReputationProvider<AccountId> for () {
+  fn reputation_of(_account: &AccountId) -> u32 {
+    0
+  }
+}
+
 pub trait SpaceFollowsProvider {
   type AccountId;
 
   fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool;
+
+  /// Count up to `limit` followers of a space in storage, starting after the storage key `start_after`,
+  /// or from the first follower if it is `None`. Return the number of counted followers
+  /// and the key to continue from, or `None` if all followers were counted.
+  fn count_followers(space_id: SpaceId, start_after: Option<Vec<u8>>, limit: u32) -> (u32, Option<Vec<u8>>);
+}
+
+/// Reads posts of a space, so the spaces pallet can recount its counters
+/// without depending on the posts pallet.
+pub trait SpacePostsProvider {
+  /// The number of posts in a space.
+  fn posts_count(space_id: SpaceId) -> u32;
+
+  /// The number of hidden posts among up to `limit` posts of a space with the lowest ids
+  /// above `after_post_id`, or from the first post if it is `None`. Return it with the id
+  /// of the last checked post to continue from, or `None` if all posts were checked.
+  fn hidden_posts_count(space_id: SpaceId, after_post_id: Option<u64>, limit: u32) -> (u32, Option<u64>);
+}
+
+impl SpacePostsProvider for () {
+  fn posts_count(_space_id: SpaceId) -> u32 {
+    0
+  }
+
+  fn hidden_posts_count(_space_id: SpaceId, _after_post_id: Option<u64>, _limit: u32) -> (u32, Option<u64>) {
+    (0, None)
+  }
 }
 
 pub trait PermissionChecker {
   type AccountId;
 
+  /// Whether an account holds at least one active role in a space.
+  fn has_active_role_in_space(account: Self::AccountId, space_id: SpaceId) -> bool;
+
   fn ensure_user_has_space_permission(
     user: User<Self::AccountId>,
     ctx: SpacePermissionsContext,
@@ -1,16 +1,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, FullCodec, FullEncode};
 use frame_support::{
     decl_error, decl_module, decl_storage, decl_event,
     dispatch::{DispatchError, DispatchResult}, ensure,
+    storage::StorageMap,
     traits::{
-        Currency, Get,
+        Currency, EnsureOrigin, Get,
         Imbalance, OnUnbalanced,
     },
 };
-use sp_runtime::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+use sp_runtime::{RuntimeDebug, traits::{CheckedAdd, One}};
 use sp_std::{
+    cmp::Ordering,
     collections::btree_set::BTreeSet,
     prelude::*,
 };
@@ -43,12 +47,23 @@ impl<T: Trait> WhoAndWhen<T> {
 }
 
 #[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum User<AccountId> {
     Account(AccountId),
     Space(SpaceId),
 }
 
+/// Who owns a space: either a single account or a set of accounts that confirm changes
+/// when at least `threshold` of them agree.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SpaceOwnership<AccountId> {
+    Single(AccountId),
+    Multi { owners: Vec<AccountId>, threshold: u16 },
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Content {
     /// No content.
     None,
@@ -68,6 +83,33 @@ impl Content {
     pub fn is_some(&self) -> bool {
         !self.is_none()
     }
+
+    /// The length of a content id in bytes, zero for `Content::None`.
+    pub fn byte_len(&self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Raw(bytes) | Self::IPFS(bytes) | Self::Hyper(bytes) => bytes.len() as u32,
+        }
+    }
+}
+
+/// A version of the storage layout of a pallet: the crate version that introduced the layout.
+/// Pallets compare it with the versions of their migrations to tell which ones to run.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+pub struct PalletVersion {
+    pub major: u16,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+/// What to do with a new edit history record when the history of an entity is full.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum HistoryOverflowPolicy {
+    /// Drop the oldest record to make room for the new one.
+    EvictOldest,
+    /// Keep the existing records. Edits of posts, spaces and profiles are refused with
+    /// `HistoryIsFull`, while other new records (e.g. of ownership changes) are dropped.
+    KeepOldest,
 }
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
@@ -87,11 +129,28 @@ pub trait Trait: system::Trait + pallet_timestamp::Trait
 
     /// Max length of a space handle.
     type MaxHandleLen: Get<u32>;
+
+    /// The origin which may change the treasury account, freeze or unfreeze accounts
+    /// and pause or unpause pallets.
+    type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+    /// Names of pallets (as in `construct_runtime!`) that can never be paused,
+    /// e.g. the pallets needed to produce blocks or to unpause other pallets.
+    type UnpausablePallets: Get<Vec<Vec<u8>>>;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as UtilsModule {
         pub TreasuryAccount get(fn treasury_account) build(|config| config.treasury_account.clone()): T::AccountId;
+
+        /// Accounts that are not allowed to perform social actions until they are unfrozen.
+        pub FrozenAccounts get(fn is_account_frozen):
+            map hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Pallets (by their name in `construct_runtime!`) whose extrinsics are rejected
+        /// by the runtime call filter until they are unpaused.
+        pub PausedPallets get(fn is_pallet_paused):
+            map hasher(blake2_128_concat) Vec<u8> => bool;
     }
     add_extra_genesis {
         config(treasury_account): T::AccountId;
@@ -117,6 +176,73 @@ decl_module! {
 
         // Initializing events
         fn deposit_event() = default;
+
+        /// Set a new treasury account that will receive transaction fees.
+        /// Can only be called by `ForceOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().writes(1)]
+        pub fn set_treasury_account(origin, new: T::AccountId) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            TreasuryAccount::<T>::put(new.clone());
+
+            Self::deposit_event(RawEvent::TreasuryAccountUpdated(new));
+            Ok(())
+        }
+
+        /// Freeze an account, so it cannot create or update spaces, posts, reactions
+        /// or space follows. Can only be called by `ForceOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+        pub fn freeze_account(origin, account: T::AccountId) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(!Self::is_account_frozen(&account), Error::<T>::AccountIsAlreadyFrozen);
+
+            FrozenAccounts::<T>::insert(account.clone(), true);
+
+            Self::deposit_event(RawEvent::AccountFrozen(account));
+            Ok(())
+        }
+
+        /// Unfreeze a previously frozen account. Can only be called by `ForceOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+        pub fn unfreeze_account(origin, account: T::AccountId) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(Self::is_account_frozen(&account), Error::<T>::AccountIsNotFrozen);
+
+            FrozenAccounts::<T>::remove(&account);
+
+            Self::deposit_event(RawEvent::AccountUnfrozen(account));
+            Ok(())
+        }
+
+        /// Pause all extrinsics of a pallet by its name in `construct_runtime!`, e.g. `Posts`.
+        /// Can only be called by `ForceOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+        pub fn pause_pallet(origin, pallet_name: Vec<u8>) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(!T::UnpausablePallets::get().contains(&pallet_name), Error::<T>::PalletCannotBePaused);
+            ensure!(!Self::is_pallet_paused(&pallet_name), Error::<T>::PalletIsAlreadyPaused);
+
+            PausedPallets::insert(pallet_name.clone(), true);
+
+            Self::deposit_event(RawEvent::PalletPaused(pallet_name));
+            Ok(())
+        }
+
+        /// Unpause a previously paused pallet. Can only be called by `ForceOrigin`.
+        #[weight = 10_000 + T::DbWeight::get().reads_writes(1, 1)]
+        pub fn unpause_pallet(origin, pallet_name: Vec<u8>) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            ensure!(Self::is_pallet_paused(&pallet_name), Error::<T>::PalletIsNotPaused);
+
+            PausedPallets::remove(&pallet_name);
+
+            Self::deposit_event(RawEvent::PalletUnpaused(pallet_name));
+            Ok(())
+        }
     }
 }
 
@@ -142,13 +268,44 @@ decl_error! {
         HandleContainsInvalidChars,
         /// Content type is `None`.
         ContentIsEmpty,
+        /// A user refers to a space that does not exist.
+        SpaceUserNotFound,
+        /// Edit history of an entity is full and its pallet keeps the oldest records,
+        /// so the entity cannot be edited anymore.
+        HistoryIsFull,
+        /// Account is already frozen.
+        AccountIsAlreadyFrozen,
+        /// Account is not frozen.
+        AccountIsNotFrozen,
+        /// Content id starts or ends with whitespace, e.g. a new line after an IPFS CID.
+        ContentHasWhitespace,
+        /// This pallet is needed to run or govern the chain, so it cannot be paused.
+        PalletCannotBePaused,
+        /// Pallet is already paused.
+        PalletIsAlreadyPaused,
+        /// Pallet is not paused.
+        PalletIsNotPaused,
+        /// An id counter reached the max value of its type, so no new id can be issued.
+        IdOverflow,
     }
 }
 
 decl_event!(
-    pub enum Event<T> where Balance = BalanceOf<T>
+    pub enum Event<T> where
+        <T as system::Trait>::AccountId,
+        Balance = BalanceOf<T>
     {
-		Deposit(Balance),
+		/// Fees were deposited to the treasury account. [treasury, amount]
+		Deposit(AccountId, Balance),
+		TreasuryAccountUpdated(AccountId),
+		/// An account was frozen by `ForceOrigin`. [account]
+		AccountFrozen(AccountId),
+		/// An account was unfrozen by `ForceOrigin`. [account]
+		AccountUnfrozen(AccountId),
+		/// Extrinsics of a pallet were paused by `ForceOrigin`. [pallet_name]
+		PalletPaused(Vec<u8>),
+		/// Extrinsics of a pallet were unpaused by `ForceOrigin`. [pallet_name]
+		PalletUnpaused(Vec<u8>),
     }
 );
 
@@ -167,19 +324,132 @@ pub fn log_2(x: u32) -> Option<u32> {
     } else { None }
 }
 
+/// Remove the first occurrence of an element from a vector. The last element takes its place,
+/// so use `remove_from_vec_keeping_order` for indexes whose order is shown to users.
 pub fn remove_from_vec<F: PartialEq>(vector: &mut Vec<F>, element: F) {
     if let Some(index) = vector.iter().position(|x| *x == element) {
         vector.swap_remove(index);
     }
 }
 
+/// Remove the first occurrence of an element from a vector and shift the following elements,
+/// so the rest of the vector stays in insertion order.
+pub fn remove_from_vec_keeping_order<F: PartialEq>(vector: &mut Vec<F>, element: F) {
+    if let Some(index) = vector.iter().position(|x| *x == element) {
+        vector.remove(index);
+    }
+}
+
+/// Append a record to an edit history that can hold at most `max_len` records.
+/// A history that is already longer than `max_len` (e.g. it was written before the limit
+/// was introduced) is truncated according to `policy` on the first append.
+/// `KeepOldest` drops the new record, so edits should be refused before, see `ensure_history_not_full`.
+pub fn push_to_bounded_history<R>(
+    history: &mut Vec<R>,
+    record: R,
+    max_len: u32,
+    policy: HistoryOverflowPolicy,
+) {
+    let max_len = max_len as usize;
+
+    match policy {
+        HistoryOverflowPolicy::EvictOldest => {
+            history.push(record);
+            if history.len() > max_len {
+                let excess = history.len() - max_len;
+                history.drain(..excess);
+            }
+        },
+        HistoryOverflowPolicy::KeepOldest => {
+            history.truncate(max_len);
+            if history.len() < max_len {
+                history.push(record);
+            }
+        },
+    }
+}
+
+/// Append a record to an edit history kept in `Storage`, see `push_to_bounded_history`.
+///
+/// A history below `max_len` is appended to without being decoded. Only a full history is
+/// decoded to apply `policy`, so a history written before the limit was introduced is
+/// truncated on its first append after that: this lazy migration decodes the whole
+/// history once, and every later append decodes at most `max_len` records.
+pub fn append_to_bounded_history<Storage, K, R>(
+    key: K,
+    record: R,
+    max_len: u32,
+    policy: HistoryOverflowPolicy,
+)
+where
+    K: FullEncode,
+    R: FullCodec,
+    Storage: StorageMap<K, Vec<R>, Query = Vec<R>>,
+{
+    if Storage::decode_len(&key).unwrap_or_default() < max_len as usize {
+        Storage::append(&key, record);
+    } else {
+        Storage::mutate(&key, |history| push_to_bounded_history(history, record, max_len, policy));
+    }
+}
+
+/// Get records edited within `from_block..=to_block` (inclusive), but no more than `limit`.
+/// Edit history is append-only, so records are sorted by block: the start of a range
+/// is found with a binary search and iteration stops at the first record after `to_block`.
+pub fn history_in_block_range<R, B, F>(
+    history: &[R],
+    block_of: F,
+    from_block: B,
+    to_block: B,
+    limit: u32,
+) -> &[R]
+    where B: Ord, F: Fn(&R) -> B
+{
+    let start = history
+        .binary_search_by(|record| {
+            if block_of(record) < from_block { Ordering::Less } else { Ordering::Greater }
+        })
+        .unwrap_or_else(|index| index);
+
+    let len = history[start..].iter()
+        .take(limit as usize)
+        .take_while(|record| block_of(record) <= to_block)
+        .count();
+
+    &history[start..start + len]
+}
+
 impl<T: Trait> Module<T> {
 
+    /// Fail with `HistoryIsFull` if an edit history of `history_len` records cannot take
+    /// one more record under `policy`. Call this before an entity is edited.
+    pub fn ensure_history_not_full(history_len: usize, max_len: u32, policy: HistoryOverflowPolicy) -> DispatchResult {
+        let is_full = history_len >= max_len as usize;
+        ensure!(!is_full || policy == HistoryOverflowPolicy::EvictOldest, Error::<T>::HistoryIsFull);
+        Ok(())
+    }
+
+    /// Get an id that follows `id` in a counter like `NextPostId`,
+    /// or fail with `IdOverflow` if `id` is the max value of its type.
+    ///
+    /// Call this before any storage is changed, so a failed call leaves no partial writes.
+    pub fn next_id<Id: CheckedAdd + One>(id: Id) -> Result<Id, DispatchError> {
+        id.checked_add(&Id::one()).ok_or_else(|| Error::<T>::IdOverflow.into())
+    }
+
+    /// Check that content can be stored on chain.
+    ///
+    /// An IPFS CID with surrounding ASCII whitespace is rejected with `ContentHasWhitespace`
+    /// rather than trimmed, because the content is stored exactly as it was submitted.
     pub fn is_valid_content(content: Content) -> DispatchResult {
         match content {
             Content::None => Ok(()),
             Content::Raw(_) => Err(Error::<T>::RawContentTypeNotSupported.into()),
             Content::IPFS(ipfs_cid) => {
+                let has_whitespace = ipfs_cid.first().map_or(false, u8::is_ascii_whitespace)
+                    || ipfs_cid.last().map_or(false, u8::is_ascii_whitespace);
+                ensure!(!has_whitespace, Error::<T>::ContentHasWhitespace);
+
                 let len = ipfs_cid.len();
                 // IPFS CID v0 is 46 bytes.
                 // IPFS CID v1 is 59 bytes.df-integration-tests/src/lib.rs:272:5
@@ -202,6 +472,23 @@ impl<T: Trait> Module<T> {
         Ok(users_set)
     }
 
+    /// Deduplicate a list of users and ensure that every `User::Space` in it
+    /// refers to a space that exists, according to `space_exists`.
+    pub fn convert_and_validate_users_vec<F>(
+        users_vec: Vec<User<T::AccountId>>,
+        space_exists: F,
+    ) -> Result<BTreeSet<User<T::AccountId>>, DispatchError>
+        where F: Fn(SpaceId) -> bool
+    {
+        for user in users_vec.iter() {
+            if let User::Space(space_id) = user {
+                ensure!(space_exists(*space_id), Error::<T>::SpaceUserNotFound);
+            }
+        }
+
+        Self::convert_users_vec_to_btree_set(users_vec)
+    }
+
     /// Check if a handle contains only valid chars: 0-9, a-z, _.
     /// An example of a valid handle: `good_handle_123`.
     fn is_valid_handle_char(c: u8) -> bool {
@@ -248,6 +535,6 @@ impl<T: Trait> OnUnbalanced<NegativeImbalanceOf<T>> for Module<T> {
         // Must resolve into existing but better to be safe.
         let _ = T::Currency::resolve_creating(&treasury_account, amount);
 
-        Self::deposit_event(RawEvent::Deposit(numeric_amount));
+        Self::deposit_event(RawEvent::Deposit(treasury_account, numeric_amount));
     }
 }
@@ -7,7 +7,11 @@ use sp_io::TestExternalities;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight, dispatch::DispatchError};
+use frame_support::{
+    impl_outer_origin, impl_outer_event, parameter_types,
+    weights::Weight,
+    dispatch::{DispatchError, DispatchResult},
+};
 use frame_system as system;
 
 impl_outer_origin! {
@@ -17,6 +21,18 @@ impl_outer_origin! {
 #[derive(Clone, Eq, PartialEq)]
 pub struct Test;
 
+mod utils {
+    pub use crate::Event;
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        system<T>,
+        pallet_balances<T>,
+        utils<T>,
+    }
+}
+
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const MaximumBlockWeight: Weight = 1024;
@@ -35,7 +51,7 @@ impl system::Trait for Test {
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = TestEvent;
     type BlockHashCount = BlockHashCount;
     type MaximumBlockWeight = MaximumBlockWeight;
     type DbWeight = ();
@@ -70,7 +86,7 @@ parameter_types! {
 impl pallet_balances::Trait for Test {
     type Balance = u64;
     type DustRemoval = ();
-    type Event = ();
+    type Event = TestEvent;
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
@@ -80,18 +96,21 @@ impl pallet_balances::Trait for Test {
 parameter_types! {
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  pub UnpausablePallets: Vec<Vec<u8>> = vec![b"Utils".to_vec()];
 }
 
 impl Trait for Test {
-    type Event = ();
+    type Event = TestEvent;
     type Currency = Balances;
     type MinHandleLen = MinHandleLen;
     type MaxHandleLen = MaxHandleLen;
+    type ForceOrigin = system::EnsureRoot<AccountId>;
+    type UnpausablePallets = UnpausablePallets;
 }
 
-type System = system::Module<Test>;
-type Balances = pallet_balances::Module<Test>;
-type Utils = Module<Test>;
+pub(crate) type System = system::Module<Test>;
+pub(crate) type Balances = pallet_balances::Module<Test>;
+pub(crate) type Utils = Module<Test>;
 
 pub type AccountId = u64;
 pub(crate) type UsersSet = BTreeSet<User<AccountId>>;
@@ -117,8 +136,54 @@ pub(crate) const USER1: User<AccountId> = User::Account(1);
 pub(crate) const USER2: User<AccountId> = User::Account(2);
 pub(crate) const USER3: User<AccountId> = User::Account(3);
 
+pub(crate) const ACCOUNT1: AccountId = 1;
+pub(crate) const TREASURY1: AccountId = 100;
+
 pub(crate) fn _convert_users_vec_to_btree_set(
     users_vec: Vec<User<AccountId>>
 ) -> Result<UsersSet, DispatchError> {
     Utils::convert_users_vec_to_btree_set(users_vec)
-}
\ No newline at end of file
+}
+pub(crate) fn _set_treasury_account(
+    origin: Option<Origin>,
+    new: Option<AccountId>,
+) -> DispatchResult {
+    Utils::set_treasury_account(
+        origin.unwrap_or_else(Origin::root),
+        new.unwrap_or(TREASURY1),
+    )
+}
+
+pub(crate) fn _freeze_account(
+    origin: Option<Origin>,
+    account: Option<AccountId>,
+) -> DispatchResult {
+    Utils::freeze_account(
+        origin.unwrap_or_else(Origin::root),
+        account.unwrap_or(ACCOUNT1),
+    )
+}
+
+pub(crate) fn _unfreeze_account(
+    origin: Option<Origin>,
+    account: Option<AccountId>,
+) -> DispatchResult {
+    Utils::unfreeze_account(
+        origin.unwrap_or_else(Origin::root),
+        account.unwrap_or(ACCOUNT1),
+    )
+}
+
+pub(crate) fn _pause_pallet(origin: Option<Origin>, pallet_name: Option<Vec<u8>>) -> DispatchResult {
+    Utils::pause_pallet(
+        origin.unwrap_or_else(Origin::root),
+        pallet_name.unwrap_or_else(|| b"Posts".to_vec()),
+    )
+}
+
+pub(crate) fn _unpause_pallet(origin: Option<Origin>, pallet_name: Option<Vec<u8>>) -> DispatchResult {
+    Utils::unpause_pallet(
+        origin.unwrap_or_else(Origin::root),
+        pallet_name.unwrap_or_else(|| b"Posts".to_vec()),
+    )
+}
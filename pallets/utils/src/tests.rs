@@ -1,5 +1,10 @@
-use crate::{mock::*, remove_from_vec, log_2};
+use crate::{
+    mock::*, remove_from_vec, remove_from_vec_keeping_order, log_2, history_in_block_range,
+    Content, Error, HistoryOverflowPolicy, RawEvent,
+};
 
+use frame_support::{assert_ok, assert_noop, traits::{Currency, OnUnbalanced}};
+use sp_runtime::DispatchError::BadOrigin;
 use sp_std::iter::FromIterator;
 
 #[test]
@@ -64,6 +69,61 @@ fn remove_from_vec_should_work_with_two_elements() {
     });
 }
 
+#[test]
+fn remove_from_vec_keeping_order_should_preserve_order_after_middle_element_removed() {
+    ExtBuilder::build().execute_with(|| {
+        let vector: &mut Vec<u16> = &mut vec![1, 2, 3, 4];
+
+        remove_from_vec_keeping_order(vector, 2);
+        assert_eq!(vector, &mut vec![1, 3, 4]);
+
+        // Nothing changes if there is no such element
+        remove_from_vec_keeping_order(vector, 5);
+        assert_eq!(vector, &mut vec![1, 3, 4]);
+
+        remove_from_vec_keeping_order(vector, 1);
+        assert_eq!(vector, &mut vec![3, 4]);
+    });
+}
+
+#[test]
+fn history_in_block_range_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        let blocks: Vec<u64> = vec![1, 3, 3, 5, 8];
+        let in_range = |from, to, limit| history_in_block_range(&blocks, |b| *b, from, to, limit).to_vec();
+
+        assert_eq!(in_range(3, 5, 10), vec![3, 3, 5]);
+        assert_eq!(in_range(0, 100, 10), blocks);
+
+        // Limit should truncate a range from its start
+        assert_eq!(in_range(2, 8, 2), vec![3, 3]);
+        assert!(in_range(1, 8, 0).is_empty());
+
+        // Empty ranges
+        assert!(in_range(6, 7, 10).is_empty());
+        assert!(in_range(9, 100, 10).is_empty());
+        assert!(in_range(5, 3, 10).is_empty());
+    });
+}
+
+#[test]
+fn ensure_history_not_full_should_fail_only_for_full_history_that_keeps_oldest_records() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(Utils::ensure_history_not_full(2, 3, HistoryOverflowPolicy::KeepOldest));
+        assert_ok!(Utils::ensure_history_not_full(3, 3, HistoryOverflowPolicy::EvictOldest));
+
+        assert_noop!(
+            Utils::ensure_history_not_full(3, 3, HistoryOverflowPolicy::KeepOldest),
+            Error::<Test>::HistoryIsFull
+        );
+        // A history written before the limit was introduced can be longer than the limit
+        assert_noop!(
+            Utils::ensure_history_not_full(5, 3, HistoryOverflowPolicy::KeepOldest),
+            Error::<Test>::HistoryIsFull
+        );
+    });
+}
+
 #[test]
 fn convert_users_vec_to_btree_set_should_work() {
     ExtBuilder::build().execute_with(|| {
@@ -91,3 +151,165 @@ fn convert_users_vec_to_btree_set_should_work() {
         );
     });
 }
+
+#[test]
+fn set_treasury_account_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_set_treasury_account(None, None));
+        assert_eq!(Utils::treasury_account(), TREASURY1);
+
+        // Fees should be deposited to the new treasury account
+        Utils::on_unbalanced(Balances::issue(50));
+        assert_eq!(Balances::free_balance(TREASURY1), 50);
+    });
+}
+
+#[test]
+fn set_treasury_account_should_fail_when_origin_is_not_root() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(
+            _set_treasury_account(Some(Origin::signed(ACCOUNT1)), None),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn on_unbalanced_should_deposit_to_treasury_and_emit_event() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_set_treasury_account(None, None));
+
+        Utils::on_unbalanced(Balances::issue(50));
+        assert_eq!(Balances::free_balance(TREASURY1), 50);
+
+        let last_event = System::events().pop().map(|record| record.event);
+        assert_eq!(last_event, Some(TestEvent::utils(RawEvent::Deposit(TREASURY1, 50))));
+    });
+}
+
+#[test]
+fn freeze_account_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_freeze_account(None, None));
+        assert!(Utils::is_account_frozen(ACCOUNT1));
+
+        let last_event = System::events().pop().map(|record| record.event);
+        assert_eq!(last_event, Some(TestEvent::utils(RawEvent::AccountFrozen(ACCOUNT1))));
+    });
+}
+
+#[test]
+fn freeze_account_should_fail_when_origin_is_not_root() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(
+            _freeze_account(Some(Origin::signed(ACCOUNT1)), None),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn freeze_account_should_fail_when_account_is_already_frozen() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_freeze_account(None, None));
+        assert_noop!(_freeze_account(None, None), Error::<Test>::AccountIsAlreadyFrozen);
+    });
+}
+
+#[test]
+fn unfreeze_account_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_freeze_account(None, None));
+        assert_ok!(_unfreeze_account(None, None));
+        assert!(!Utils::is_account_frozen(ACCOUNT1));
+    });
+}
+
+#[test]
+fn unfreeze_account_should_fail_when_account_is_not_frozen() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_unfreeze_account(None, None), Error::<Test>::AccountIsNotFrozen);
+    });
+}
+
+#[test]
+fn pause_pallet_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_pause_pallet(None, None));
+        assert!(Utils::is_pallet_paused(b"Posts".to_vec()));
+
+        let last_event = System::events().pop().map(|record| record.event);
+        assert_eq!(last_event, Some(TestEvent::utils(RawEvent::PalletPaused(b"Posts".to_vec()))));
+    });
+}
+
+#[test]
+fn pause_pallet_should_fail_when_origin_is_not_root() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_pause_pallet(Some(Origin::signed(ACCOUNT1)), None), BadOrigin);
+    });
+}
+
+#[test]
+fn pause_pallet_should_fail_when_pallet_is_unpausable() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_pause_pallet(None, Some(b"Utils".to_vec())), Error::<Test>::PalletCannotBePaused);
+    });
+}
+
+#[test]
+fn pause_pallet_should_fail_when_pallet_is_already_paused() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_pause_pallet(None, None));
+        assert_noop!(_pause_pallet(None, None), Error::<Test>::PalletIsAlreadyPaused);
+    });
+}
+
+#[test]
+fn unpause_pallet_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_pause_pallet(None, None));
+        assert_ok!(_unpause_pallet(None, None));
+        assert!(!Utils::is_pallet_paused(b"Posts".to_vec()));
+
+        let last_event = System::events().pop().map(|record| record.event);
+        assert_eq!(last_event, Some(TestEvent::utils(RawEvent::PalletUnpaused(b"Posts".to_vec()))));
+    });
+}
+
+#[test]
+fn unpause_pallet_should_fail_when_pallet_is_not_paused() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_unpause_pallet(None, None), Error::<Test>::PalletIsNotPaused);
+    });
+}
+
+fn ipfs_cid_v0() -> Vec<u8> {
+    b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec()
+}
+
+#[test]
+fn is_valid_content_should_accept_ipfs_cid() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(Utils::is_valid_content(Content::IPFS(ipfs_cid_v0())));
+    });
+}
+
+#[test]
+fn is_valid_content_should_reject_ipfs_cid_with_trailing_new_line() {
+    ExtBuilder::build().execute_with(|| {
+        let mut cid = ipfs_cid_v0();
+        cid.push(b'\n');
+
+        assert_noop!(Utils::is_valid_content(Content::IPFS(cid)), Error::<Test>::ContentHasWhitespace);
+    });
+}
+
+#[test]
+fn is_valid_content_should_reject_ipfs_cid_with_leading_space() {
+    ExtBuilder::build().execute_with(|| {
+        let cid = [b" ".to_vec(), ipfs_cid_v0()].concat();
+
+        assert_noop!(Utils::is_valid_content(Content::IPFS(cid)), Error::<Test>::ContentHasWhitespace);
+    });
+}
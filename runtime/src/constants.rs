@@ -6,6 +6,9 @@ pub mod currency {
 	pub const CENTS: Balance = DOLLARS / 100;      // 10_000_000_000
 	pub const MILLICENTS: Balance = CENTS / 1_000; // 10_000_000
 
+	pub const TOKEN_SYMBOL: &str = "BBU";
+	pub const TOKEN_DECIMALS: u8 = 12;
+
 	pub const fn deposit(items: u32, bytes: u32) -> Balance {
 		items as Balance * 15 * CENTS + (bytes as Balance) * 6 * CENTS
 	}
@@ -21,4 +24,73 @@ pub mod time {
 	pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 	pub const HOURS: BlockNumber = MINUTES * 60;
 	pub const DAYS: BlockNumber = HOURS * 24;
-}
\ No newline at end of file
+}
+
+pub mod fee {
+	use frame_support::weights::{
+		constants::{ExtrinsicBaseWeight, WEIGHT_PER_MICROS}, Weight, WeightToFeeCoefficient,
+		WeightToFeeCoefficients, WeightToFeePolynomial,
+	};
+	use sp_runtime::Perbill;
+	use super::currency::CENTS;
+	use crate::Balance;
+
+	/// The fee of an extrinsic with the base weight, i.e. of the lightest possible extrinsic.
+	pub const BASE_EXTRINSIC_FEE: Balance = CENTS / 10;
+
+	/// The weight up to which a call is charged at the base rate. Single social actions,
+	/// such as follows, reactions, posts and spaces, fit into this tier.
+	pub const LIGHT_TIER_MAX_WEIGHT: Weight = 2_000 * WEIGHT_PER_MICROS;
+
+	/// The weight up to which the weight above the light tier is charged at `MEDIUM_TIER_RATE`
+	/// times the base rate, e.g. of `delete_role` with many users.
+	pub const MEDIUM_TIER_MAX_WEIGHT: Weight = 10_000 * WEIGHT_PER_MICROS;
+	pub const MEDIUM_TIER_RATE: Balance = 2;
+
+	/// The weight above the medium tier, e.g. of a big batch of reactions,
+	/// is charged at `HEAVY_TIER_RATE` times the base rate.
+	pub const HEAVY_TIER_RATE: Balance = 4;
+
+	/// Converts a weight to a fee linearly, so that `ExtrinsicBaseWeight` costs `BASE_EXTRINSIC_FEE`.
+	/// This is the base rate of `WeightToFee`.
+	pub struct BaseWeightToFee;
+	impl WeightToFeePolynomial for BaseWeightToFee {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			let p = BASE_EXTRINSIC_FEE;
+			let q = Balance::from(ExtrinsicBaseWeight::get());
+			let mut coefficients = WeightToFeeCoefficients::new();
+			coefficients.push(WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_rational_approximation(p % q, q),
+				coeff_integer: p / q,
+			});
+			coefficients
+		}
+	}
+
+	/// Converts a weight to a fee by tiers: the weight within every tier is charged at the rate
+	/// of that tier. Single social actions pay in proportion to their benchmarked weights,
+	/// while heavy calls pay more than in proportion to theirs.
+	///
+	/// A polynomial cannot express the tiers, so `calc` is overridden, and `polynomial`
+	/// returns the base rate, i.e. the rate of the light tier.
+	pub struct WeightToFee;
+	impl WeightToFeePolynomial for WeightToFee {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			BaseWeightToFee::polynomial()
+		}
+
+		fn calc(weight: &Weight) -> Self::Balance {
+			let light_weight = (*weight).min(LIGHT_TIER_MAX_WEIGHT);
+			let medium_weight = (*weight).min(MEDIUM_TIER_MAX_WEIGHT).saturating_sub(LIGHT_TIER_MAX_WEIGHT);
+			let heavy_weight = weight.saturating_sub(MEDIUM_TIER_MAX_WEIGHT);
+
+			BaseWeightToFee::calc(&light_weight)
+				.saturating_add(BaseWeightToFee::calc(&medium_weight).saturating_mul(MEDIUM_TIER_RATE))
+				.saturating_add(BaseWeightToFee::calc(&heavy_weight).saturating_mul(HEAVY_TIER_RATE))
+		}
+	}
+}
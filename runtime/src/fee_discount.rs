@@ -0,0 +1,230 @@
+//! Transaction payment with a fee discount for high-reputation accounts.
+//!
+//! `ChargeTransactionPaymentWithDiscount` works the same way as
+//! `pallet_transaction_payment::ChargeTransactionPayment` and keeps its identifier and encoding,
+//! so clients can sign transactions as before. The only difference is that if a call belongs
+//! to one of the social pallets (see `is_social_call`), the fee without a tip is reduced by
+//! a discount that depends on the sender's reputation (see `reputation_fee_discount`).
+//! The paid fee is then shared with the owner of a space that the call targets
+//! (see `space_owner_fees`).
+//!
+//! `payment_queryInfo` RPC returns a fee with the discount too (see `query_info`).
+
+use codec::{Encode, Decode};
+use frame_support::traits::{
+    Currency, ExistenceRequirement, Imbalance, WithdrawReason,
+};
+use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use sp_runtime::{
+    DispatchResult, Perbill, RuntimeDebug,
+    traits::{
+        DispatchInfoOf, PostDispatchInfoOf, SaturatedConversion, SignedExtension, StaticLookup, Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
+    },
+};
+
+use crate::{
+    AccountId, Balance, Balances, Call, FeeDiscountReputationPerPercent,
+    FeeDiscountReputationThreshold, MaxReputationFeeDiscount, NegativeImbalance, Profiles,
+    Runtime, TransactionPayment, UncheckedExtrinsic, is_social_call, space_owner_fees,
+};
+
+/// Get a fee discount for the given reputation.
+///
+/// There is no discount below `FeeDiscountReputationThreshold`. Above it, the discount grows
+/// by one percent for every `FeeDiscountReputationPerPercent` points of reputation,
+/// but never exceeds `MaxReputationFeeDiscount`.
+pub fn reputation_fee_discount(reputation: u32) -> Perbill {
+    let threshold = FeeDiscountReputationThreshold::get();
+    if reputation < threshold {
+        return Perbill::zero();
+    }
+
+    let percent = (reputation - threshold) / FeeDiscountReputationPerPercent::get().max(1);
+    Perbill::from_percent(percent.min(100)).min(MaxReputationFeeDiscount::get())
+}
+
+/// Get a fee discount of `who` for `call`. Only calls of the social pallets are discounted.
+pub fn fee_discount(who: &AccountId, call: &Call) -> Perbill {
+    if !is_social_call(call) {
+        return Perbill::zero();
+    }
+
+    let reputation = Profiles::social_account_by_id(who)
+        .map(|social_account| social_account.reputation)
+        .unwrap_or_default();
+
+    reputation_fee_discount(reputation)
+}
+
+/// Apply a discount to a fee leaving a tip untouched.
+fn apply_discount(fee: Balance, tip: Balance, discount: Perbill) -> Balance {
+    let fee_without_tip = fee.saturating_sub(tip);
+    fee_without_tip
+        .saturating_sub(discount * fee_without_tip)
+        .saturating_add(tip)
+}
+
+/// Query the dispatch info and the fee of an extrinsic the same way as
+/// `pallet_transaction_payment::Module::query_info` does it, but with a discount of its signer.
+/// The fee does not include a tip, so it is the fee that will be charged if no tip is given.
+pub fn query_info(uxt: UncheckedExtrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
+    let signer = uxt.signature.as_ref()
+        .and_then(|(address, _, _)| <Runtime as frame_system::Trait>::Lookup::lookup(address.clone()).ok());
+    let discount = signer.map_or_else(Perbill::zero, |who| fee_discount(&who, &uxt.function));
+
+    let mut info = TransactionPayment::query_info(uxt, len);
+    info.partial_fee = apply_discount(info.partial_fee, Zero::zero(), discount);
+    info
+}
+
+/// Require the transactor to pay for themselves, with a discount for social actions
+/// of high-reputation accounts. It can also optionally include a tip.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct ChargeTransactionPaymentWithDiscount(#[codec(compact)] Balance);
+
+impl From<Balance> for ChargeTransactionPaymentWithDiscount {
+    fn from(tip: Balance) -> Self {
+        Self(tip)
+    }
+}
+
+impl ChargeTransactionPaymentWithDiscount {
+    /// Compute a fee that `who` will be charged for `call`, including a discount and a tip.
+    pub fn compute_fee(
+        &self,
+        who: &AccountId,
+        call: &Call,
+        info: &DispatchInfoOf<Call>,
+        len: usize,
+    ) -> (Balance, Perbill) {
+        let tip = self.0;
+        let discount = fee_discount(who, call);
+        let fee = TransactionPayment::compute_fee(len as u32, info, tip);
+
+        (apply_discount(fee, tip, discount), discount)
+    }
+
+    fn withdraw_fee(
+        &self,
+        who: &AccountId,
+        call: &Call,
+        info: &DispatchInfoOf<Call>,
+        len: usize,
+    ) -> Result<(Balance, Perbill, Option<NegativeImbalance>), TransactionValidityError> {
+        let tip = self.0;
+        let (fee, discount) = self.compute_fee(who, call, info, len);
+
+        if fee.is_zero() {
+            return Ok((fee, discount, None));
+        }
+
+        match Balances::withdraw(
+            who,
+            fee,
+            if tip.is_zero() {
+                WithdrawReason::TransactionPayment.into()
+            } else {
+                WithdrawReason::TransactionPayment | WithdrawReason::Tip
+            },
+            ExistenceRequirement::KeepAlive,
+        ) {
+            Ok(imbalance) => Ok((fee, discount, Some(imbalance))),
+            Err(_) => Err(InvalidTransaction::Payment.into()),
+        }
+    }
+
+    /// Get a priority of a transaction the same way as `ChargeTransactionPayment` does it:
+    /// a fee multiplied by the number of such transactions that could fit into a block.
+    fn get_priority(len: usize, info: &DispatchInfoOf<Call>, final_fee: Balance) -> TransactionPriority {
+        let max_block_weight = <Runtime as frame_system::Trait>::MaximumBlockWeight::get();
+        let max_block_length = <Runtime as frame_system::Trait>::MaximumBlockLength::get() as u64;
+
+        let weight_saturation = max_block_weight / info.weight.max(1);
+        let len_saturation = max_block_length / (len as u64).max(1);
+        let coefficient: Balance = weight_saturation.min(len_saturation).saturated_into();
+
+        final_fee.saturating_mul(coefficient).saturated_into::<TransactionPriority>()
+    }
+}
+
+impl SignedExtension for ChargeTransactionPaymentWithDiscount {
+    // The same identifier as the one of `ChargeTransactionPayment`, as the encoding is the same.
+    const IDENTIFIER: &'static str = "ChargeTransactionPayment";
+    type AccountId = AccountId;
+    type Call = Call;
+    type AdditionalSigned = ();
+    type Pre = (Balance, AccountId, Option<NegativeImbalance>, Balance, Perbill, Option<AccountId>);
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> { Ok(()) }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        let (fee, _, _) = self.withdraw_fee(who, call, info, len)?;
+
+        Ok(ValidTransaction {
+            priority: Self::get_priority(len, info, fee),
+            ..Default::default()
+        })
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        let (fee, discount, imbalance) = self.withdraw_fee(who, call, info, len)?;
+        let space_owner = space_owner_fees::space_owner_of_call(who, call);
+        Ok((self.0, who.clone(), imbalance, fee, discount, space_owner))
+    }
+
+    fn post_dispatch(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        let (tip, who, imbalance, fee, discount, space_owner) = pre;
+        // A call that failed did not act in the space, so its fee is not shared.
+        let space_owner = space_owner.filter(|_| result.is_ok());
+
+        if let Some(payed) = imbalance {
+            // The discount is taken from `pre`, because the reputation of `who`
+            // could have been changed while dispatching the call.
+            let actual_fee = apply_discount(
+                TransactionPayment::compute_actual_fee(len as u32, info, post_info, tip),
+                tip,
+                discount,
+            );
+            let refund = fee.saturating_sub(actual_fee);
+
+            let actual_payment = match Balances::deposit_into_existing(&who, refund) {
+                Ok(refund_imbalance) => {
+                    // The refund cannot be larger than the up-front payment.
+                    match payed.offset(refund_imbalance) {
+                        Ok(actual_payment) => actual_payment,
+                        Err(_) => return Err(InvalidTransaction::Payment.into()),
+                    }
+                }
+                // The account was reaped while dispatching, so there is no refund.
+                Err(_) => payed,
+            };
+
+            let (paid_tip, paid_fee) = actual_payment.split(tip);
+            space_owner_fees::deal_with_fee(paid_fee, paid_tip, space_owner);
+        }
+
+        Ok(())
+    }
+}
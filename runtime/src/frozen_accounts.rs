@@ -0,0 +1,114 @@
+//! An emergency brake that prevents frozen accounts from performing social actions.
+//!
+//! Accounts are frozen and unfrozen by `pallet_utils::Trait::ForceOrigin` with `Utils::freeze_account`
+//! and `Utils::unfreeze_account`. `BaseCallFilter` does not know who dispatches a call,
+//! that is why a frozen account is checked by a signed extension instead.
+//!
+//! An account with orphaned social state (see `orphaned_accounts`) is treated as frozen
+//! until it reclaims its social state.
+
+use codec::{Encode, Decode};
+use sp_runtime::{
+    RuntimeDebug,
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
+};
+
+use crate::{AccountId, Call, Profiles, SessionKeys, Utils};
+
+/// A custom `InvalidTransaction` code returned when a frozen account tries to perform
+/// a social action.
+pub const ACCOUNT_IS_FROZEN: u8 = 1;
+
+/// A custom `InvalidTransaction` code returned when an account with orphaned social state
+/// tries to perform a social action before reclaiming its social state.
+pub const ACCOUNT_IS_ORPHANED: u8 = 2;
+
+/// Whether a call is a social action that cannot be performed by a frozen account.
+fn is_social_action(call: &Call) -> bool {
+    matches!(call,
+        Call::Spaces(..) |
+        Call::Posts(..) |
+        Call::Reactions(..) |
+        Call::SpaceFollows(..)
+    )
+}
+
+/// Check that `who` is allowed to perform a social action.
+fn ensure_can_perform_social_action(who: &AccountId) -> Result<(), TransactionValidityError> {
+    if Utils::is_account_frozen(who) {
+        return Err(InvalidTransaction::Custom(ACCOUNT_IS_FROZEN).into());
+    }
+
+    if Profiles::is_social_account_orphaned(who) {
+        return Err(InvalidTransaction::Custom(ACCOUNT_IS_ORPHANED).into());
+    }
+
+    Ok(())
+}
+
+/// Check that a call of `who` does not perform a social action on behalf of a frozen account.
+///
+/// Calls that dispatch other calls are checked recursively: batches, derivative accounts,
+/// multisig calls, and proxy calls. A proxied call is checked for both the proxy and
+/// the account it acts for, so a frozen account can neither be a proxy nor be proxied.
+fn ensure_call_allowed(who: &AccountId, call: &Call) -> Result<(), TransactionValidityError> {
+    match call {
+        _ if is_social_action(call) => ensure_can_perform_social_action(who),
+        Call::Utility(pallet_utility::Call::batch(calls)) =>
+            calls.iter().try_for_each(|call| ensure_call_allowed(who, call)),
+        Call::Utility(pallet_utility::Call::as_derivative(_, call)) |
+        Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(_, call)) =>
+            ensure_call_allowed(who, call),
+        Call::Multisig(pallet_multisig::Call::as_multi(_, _, _, encoded_call, ..)) =>
+            // A call that cannot be decoded fails in the dispatch.
+            match Call::decode(&mut &encoded_call[..]) {
+                Ok(call) => ensure_call_allowed(who, &call),
+                Err(_) => Ok(()),
+            },
+        Call::Proxy(pallet_proxy::Call::proxy(real, .., call)) |
+        Call::Proxy(pallet_proxy::Call::proxy_announced(_, real, .., call)) => {
+            ensure_call_allowed(who, call)?;
+            ensure_call_allowed(real, call)
+        }
+        Call::SessionKeys(pallet_session_keys::Call::proxy(call)) => {
+            ensure_call_allowed(who, call)?;
+            match SessionKeys::key_details(who) {
+                Some(key) => ensure_call_allowed(&key.owner(), call),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject social actions signed by an account that was frozen by `pallet_utils::Trait::ForceOrigin`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct CheckFrozenAccount;
+
+impl CheckFrozenAccount {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SignedExtension for CheckFrozenAccount {
+    const IDENTIFIER: &'static str = "CheckFrozenAccount";
+    type AccountId = AccountId;
+    type Call = Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> { Ok(()) }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        ensure_call_allowed(who, call)?;
+        Ok(ValidTransaction::default())
+    }
+}
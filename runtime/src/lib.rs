@@ -9,10 +9,14 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use codec::{Encode, Decode};
+use sp_core::{
+    crypto::KeyTypeId, OpaqueMetadata,
+    u32_trait::{_1, _2},
+};
 use sp_runtime::{
-    ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-    transaction_validity::{TransactionValidity, TransactionSource},
+    ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, RuntimeDebug,
+    transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority, TransactionLongevity},
 };
 use sp_runtime::traits::{
     BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor, Saturating,
@@ -33,16 +37,48 @@ pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
     construct_runtime, parameter_types, StorageValue,
-    traits::{KeyOwnerProofSystem, Randomness, Currency, Imbalance, OnUnbalanced, Filter},
+    traits::{
+        KeyOwnerProofSystem, Randomness, Currency, Imbalance, OnUnbalanced, Filter, InstanceFilter,
+        GetCallMetadata,
+    },
     weights::{
-        Weight, IdentityFee,
+        Weight,
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
     },
 };
-use frame_system::EnsureRoot;
+use frame_system::{EnsureOneOf, EnsureRoot, EnsureSigned};
+use pallet_post_history::rpc::FlatPostHistoryRecord;
+use pallet_posts::{PostId, rpc::{FlatPost, Visibility}};
+use pallet_profile_history::rpc::FlatProfileHistoryRecord;
+use pallet_profiles::rpc::FlatSocialAccount;
+use pallet_reactions::{ReactionKind, rpc::{FlatAccountReaction, FlatReaction}};
+use pallet_space_follows::rpc::FlatSpaceFollower;
+use pallet_space_history::rpc::FlatSpaceHistoryRecord;
+use pallet_spaces::rpc::FlatSpace;
+use pallet_utils::{HistoryOverflowPolicy, SpaceId};
 
 pub mod constants;
-use constants::{currency::*, time::*};
+use constants::{currency::*, time::*, fee::WeightToFee};
+
+mod fee_discount;
+pub use fee_discount::ChargeTransactionPaymentWithDiscount;
+
+mod frozen_accounts;
+pub use frozen_accounts::CheckFrozenAccount;
+
+mod migrations;
+use migrations::CustomOnRuntimeUpgrade;
+
+mod orphaned_accounts;
+use orphaned_accounts::OrphanSocialAccount;
+
+mod post_space_check;
+pub use post_space_check::CheckPostSpaceExists;
+
+
+mod space_owner_fees;
+
+mod space_snapshot;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -102,10 +138,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("bunbinetwork"),
 	impl_name: create_runtime_str!("bunbinetwork"),
 	authoring_version: 0,
-	spec_version: 10,
+	spec_version: 98,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 31,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -196,7 +232,7 @@ impl frame_system::Trait for Runtime {
     /// What to do if a new account is created.
     type OnNewAccount = ();
     /// What to do if an account is fully reaped from the system.
-    type OnKilledAccount = ();
+    type OnKilledAccount = OrphanSocialAccount;
     /// Weight information for the extrinsics of this pallet.
     type SystemWeightInfo = ();
 }
@@ -255,13 +291,22 @@ impl pallet_balances::Trait for Runtime {
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1 * MILLICENTS;
+
+	pub const FeeDiscountReputationThreshold: u32 = 100;
+	pub const FeeDiscountReputationPerPercent: u32 = 20;
+	pub const MaxReputationFeeDiscount: Perbill = Perbill::from_percent(50);
+
+	/// A share of a fee of a social call that goes to the owner of the space targeted by the call.
+	pub const SpaceOwnerFeeShare: Permill = Permill::from_percent(20);
+	/// The minimum reputation of a sender whose fees are shared with space owners.
+	pub const SpaceOwnerFeeShareMinReputation: u32 = 100;
 }
 
 impl pallet_transaction_payment::Trait for Runtime {
     type Currency = Balances;
     type OnTransactionPayment = DealWithFees;
     type TransactionByteFee = TransactionByteFee;
-    type WeightToFee = IdentityFee<Balance>;
+    type WeightToFee = WeightToFee;
     type FeeMultiplierUpdate = ();
 }
 
@@ -292,12 +337,180 @@ impl pallet_utility::Trait for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+	pub const MultisigDepositBase: Balance = deposit(1, 88);
+	// Additional storage item size of 32 bytes.
+	pub const MultisigDepositFactor: Balance = deposit(0, 32);
+	pub const MaxSignatories: u16 = 100;
+}
+
+parameter_types! {
+	pub const BasicDeposit: Balance = deposit(1, 258);
+	pub const FieldDeposit: Balance = deposit(0, 66);
+	pub const SubAccountDeposit: Balance = deposit(1, 53);
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Trait for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = Utils;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = ();
+}
+
+impl pallet_multisig::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
+	pub const MaxPending: u32 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// A proxy that can do anything on behalf of the real account.
+	Any,
+	/// A proxy that can do anything except for moving funds of the real account.
+	NonTransfer,
+	/// A proxy that can only manage spaces, posts, reactions, follows and roles.
+	SocialActions,
+}
+
+impl Default for ProxyType { fn default() -> Self { Self::Any } }
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(c,
+				Call::Balances(..) |
+				Call::Donations(..) |
+				Call::Subscriptions(..) |
+				Call::SessionKeys(..)
+			),
+			ProxyType::SocialActions => is_social_call(c),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::NonTransfer, ProxyType::SocialActions) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Whether a call belongs to one of the social pallets:
+/// spaces, posts, reactions, follows or roles.
+fn is_social_call(c: &Call) -> bool {
+	matches!(c,
+		Call::Spaces(..) |
+		Call::Posts(..) |
+		Call::Reactions(..) |
+		Call::SpaceFollows(..) |
+		Call::ProfileFollows(..) |
+		Call::Roles(..)
+	)
+}
+
+impl pallet_proxy::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+}
+
+pub type CouncilCollective = pallet_collective::Instance1;
+
+/// The root origin or a council motion approved by more than a half of the council.
+/// Used instead of `EnsureRoot` to govern social parameters, treasury and moderation.
+pub type EnsureRootOrHalfCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
+
+impl pallet_collective::Trait<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
+impl pallet_membership::Trait<pallet_membership::Instance1> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+}
+
 // Subsocial custom pallets go below:
 // ------------------------------------------------------------------------------------------------
 
 parameter_types! {
   pub const MinHandleLen: u32 = 5;
   pub const MaxHandleLen: u32 = 50;
+  /// Block production, the pallet that unpauses the others and the governance
+  /// that can call it must keep working.
+  pub UnpausablePallets: Vec<Vec<u8>> = vec![
+    b"System".to_vec(),
+    b"Timestamp".to_vec(),
+    b"Sudo".to_vec(),
+    b"Utils".to_vec(),
+    b"Scheduler".to_vec(),
+    b"Council".to_vec(),
+    b"CouncilMembership".to_vec(),
+  ];
 }
 
 impl pallet_utils::Trait for Runtime {
@@ -305,6 +518,8 @@ impl pallet_utils::Trait for Runtime {
 	type Currency = Balances;
 	type MinHandleLen = MinHandleLen;
 	type MaxHandleLen = MaxHandleLen;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type UnpausablePallets = UnpausablePallets;
 }
 
 use pallet_permissions::default_permissions::DefaultSpacePermissions;
@@ -315,57 +530,141 @@ impl pallet_permissions::Trait for Runtime {
 
 parameter_types! {
   pub const MaxCommentDepth: u32 = 10;
+  pub const MaxShareDepth: u32 = 1;
+  pub const MaxPostsPerContent: u32 = 100;
 }
 
 impl pallet_posts::Trait for Runtime {
 	type Event = Event;
 	type MaxCommentDepth = MaxCommentDepth;
+	type MaxShareDepth = MaxShareDepth;
 	type PostScores = Scores;
+	type BeforePostUpdated = PostHistory;
 	type AfterPostUpdated = PostHistory;
+	type AfterPostCreated = Profiles;
 	type IsPostBlocked = ()/*Moderation*/;
+	type SpaceSettingsProvider = Spaces;
+	type MaxPostsPerContent = MaxPostsPerContent;
+	type ReputationProvider = Profiles;
+	type WeightInfo = pallet_posts::weights::SubstrateWeight<Runtime>;
 }
 
-parameter_types! {}
+parameter_types! {
+	pub const MaxPostHistoryRecords: u32 = 100;
+	pub const PostHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+}
 
-impl pallet_post_history::Trait for Runtime {}
+impl pallet_post_history::Trait for Runtime {
+	type MaxHistoryRecordsPerEntity = MaxPostHistoryRecords;
+	type HistoryOverflowPolicy = PostHistoryOverflowPolicy;
+}
 
-parameter_types! {}
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+parameter_types! {
+	pub const UnsignedFollowsAllowance: u32 = 10;
+	pub const MaxUnsignedFollowsPerBlock: u32 = 100;
+	pub const UnsignedFollowerMinBalance: Balance = 10 * CENTS;
+	// Free follows are included only into the space that fee-paying transactions leave in a block.
+	pub const UnsignedFollowPriority: TransactionPriority = TransactionPriority::min_value();
+	pub const UnsignedFollowLongevity: TransactionLongevity = 10 * MINUTES as TransactionLongevity;
+	pub const AutoFollowOwnSpace: bool = true;
+}
 
 impl pallet_profile_follows::Trait for Runtime {
 	type Event = Event;
 	type BeforeAccountFollowed = Scores;
 	type BeforeAccountUnfollowed = Scores;
+	type UnsignedFollowSignature = Signature;
+	type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+	type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+	type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+	type UnsignedFollowPriority = UnsignedFollowPriority;
+	type UnsignedFollowLongevity = UnsignedFollowLongevity;
+	type WeightInfo = pallet_profile_follows::weights::SubstrateWeight<Runtime>;
 }
 
-parameter_types! {}
+parameter_types! {
+	pub const BaseReputation: u32 = 1;
+}
 
 impl pallet_profiles::Trait for Runtime {
 	type Event = Event;
+	type ContentValidator = Utils;
+	type BeforeProfileUpdated = ProfileHistory;
 	type AfterProfileUpdated = ProfileHistory;
+	type IdentityProvider = IdentityVerification;
+	type BaseReputation = BaseReputation;
 }
 
-parameter_types! {}
+/// Treats an identity as verified if any registrar has judged it as reasonable or known good.
+pub struct IdentityVerification;
+impl df_traits::IdentityProvider<AccountId> for IdentityVerification {
+	fn has_positive_judgement(account: &AccountId) -> bool {
+		Identity::identity(account).map_or(false, |registration|
+			registration.judgements.iter().any(|(_, judgement)| matches!(judgement,
+				pallet_identity::Judgement::Reasonable |
+				pallet_identity::Judgement::KnownGood
+			))
+		)
+	}
+}
 
-impl pallet_profile_history::Trait for Runtime {}
+parameter_types! {
+	pub const MaxProfileHistoryRecords: u32 = 100;
+	pub const ProfileHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+}
 
-parameter_types! {}
+impl pallet_profile_history::Trait for Runtime {
+	type MaxHistoryRecordsPerEntity = MaxProfileHistoryRecords;
+	type HistoryOverflowPolicy = ProfileHistoryOverflowPolicy;
+}
+
+parameter_types! {
+	pub const MaxReactionsPerBatch: u16 = 50;
+}
 
 impl pallet_reactions::Trait for Runtime {
 	type Event = Event;
 	type PostReactionScores = Scores;
+	type AfterReactionUpdated = ReactionHistory;
+	type MaxReactionsPerBatch = MaxReactionsPerBatch;
+	type WeightInfo = pallet_reactions::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxReactionHistoryRecords: u32 = 100;
+	pub const ReactionHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+}
+
+impl pallet_reaction_history::Trait for Runtime {
+	type MaxHistoryRecordsPerEntity = MaxReactionHistoryRecords;
+	type HistoryOverflowPolicy = ReactionHistoryOverflowPolicy;
 }
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+  pub const MaxRolesPerSpace: u16 = 100;
+  pub const MaxRoleHistoryRecordsPerSpace: u32 = 1000;
+  pub const RoleHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
 }
 
 impl pallet_roles::Trait for Runtime {
 	type Event = Event;
+	type ContentValidator = Utils;
 	type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+	type MaxRolesPerSpace = MaxRolesPerSpace;
 	type Spaces = Spaces;
 	type SpaceFollows = SpaceFollows;
+	type UserResolver = Spaces;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
+	type MaxRoleHistoryRecordsPerSpace = MaxRoleHistoryRecordsPerSpace;
+	type RoleHistoryOverflowPolicy = RoleHistoryOverflowPolicy;
+	type WeightInfo = pallet_roles::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -380,6 +679,12 @@ parameter_types! {
   pub const ShareCommentActionWeight: i16 = 5;
   pub const UpvoteCommentActionWeight: i16 = 4;
   pub const DownvoteCommentActionWeight: i16 = -2;
+
+  pub const ScoreDecayPeriodInBlocks: BlockNumber = HOURS;
+  pub const MaxAccountsPerPostScoresCleanup: u16 = 100;
+
+  pub const ScoringPeriodInBlocks: BlockNumber = 1 * DAYS;
+  pub const MaxScoredActionsPerPeriod: u32 = 100;
 }
 
 impl pallet_scores::Trait for Runtime {
@@ -396,6 +701,13 @@ impl pallet_scores::Trait for Runtime {
 	type ShareCommentActionWeight = ShareCommentActionWeight;
 	type UpvoteCommentActionWeight = UpvoteCommentActionWeight;
 	type DownvoteCommentActionWeight = DownvoteCommentActionWeight;
+
+	type ScoreDecayPeriodInBlocks = ScoreDecayPeriodInBlocks;
+
+	type SetScoringWeightsOrigin = EnsureRootOrHalfCouncil;
+	type MaxAccountsPerPostScoresCleanup = MaxAccountsPerPostScoresCleanup;
+	type ScoringPeriodInBlocks = ScoringPeriodInBlocks;
+	type MaxScoredActionsPerPeriod = MaxScoredActionsPerPeriod;
 }
 
 parameter_types! {}
@@ -404,47 +716,129 @@ impl pallet_space_follows::Trait for Runtime {
 	type Event = Event;
 	type BeforeSpaceFollowed = Scores;
 	type BeforeSpaceUnfollowed = Scores;
+	type UnsignedFollowSignature = Signature;
+	type UnsignedFollowsAllowance = UnsignedFollowsAllowance;
+	type MaxUnsignedFollowsPerBlock = MaxUnsignedFollowsPerBlock;
+	type UnsignedFollowerMinBalance = UnsignedFollowerMinBalance;
+	type UnsignedFollowPriority = UnsignedFollowPriority;
+	type UnsignedFollowLongevity = UnsignedFollowLongevity;
+	type AutoFollowOwnSpace = AutoFollowOwnSpace;
+	type WeightInfo = pallet_space_follows::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {}
 
 impl pallet_space_ownership::Trait for Runtime {
 	type Event = Event;
+	type AfterSpaceOwnershipChanged = SpaceHistory;
 }
 
 parameter_types! {
 	pub HandleDeposit: Balance = 50 * CENTS;
+	pub const RestrictReservedSpaces: bool = true;
+	pub const IdempotencyKeyTtl: BlockNumber = 1 * HOURS;
+	pub const MaxIdempotencyKeysRemovedPerBlock: u32 = 500;
+	pub const HandleChangeCooldown: BlockNumber = 1 * DAYS;
 }
 
 impl pallet_spaces::Trait for Runtime {
 	type Event = Event;
+	type ContentValidator = Utils;
 	type Currency = Balances;
 	type Roles = Roles;
 	type SpaceFollows = SpaceFollows;
+	type SpacePosts = Posts;
 	type BeforeSpaceCreated = SpaceFollows;
+	type BeforeSpaceUpdated = SpaceHistory;
 	type AfterSpaceUpdated = SpaceHistory;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
 	type HandleDeposit = HandleDeposit;
+	type RestrictReservedSpaces = RestrictReservedSpaces;
+	type IdempotencyKeyTtl = IdempotencyKeyTtl;
+	type MaxIdempotencyKeysRemovedPerBlock = MaxIdempotencyKeysRemovedPerBlock;
+	type HandleChangeCooldown = HandleChangeCooldown;
+	type CreateRootSpaceOrigin = EnsureSigned<AccountId>;
+	type WeightInfo = pallet_spaces::weights::SubstrateWeight<Runtime>;
 }
 
-parameter_types! {}
+parameter_types! {
+	pub const MaxSpaceHistoryRecords: u32 = 100;
+	pub const SpaceHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+}
 
-impl pallet_space_history::Trait for Runtime {}
+impl pallet_space_history::Trait for Runtime {
+	type MaxHistoryRecordsPerEntity = MaxSpaceHistoryRecords;
+	type HistoryOverflowPolicy = SpaceHistoryOverflowPolicy;
+}
 
 pub struct BaseFilter;
 impl Filter<Call> for BaseFilter {
     fn filter(c: &Call) -> bool {
+        // Extrinsics of the pallets paused via `Utils::pause_pallet` are rejected.
+        if Utils::is_pallet_paused(c.get_call_metadata().pallet_name.as_bytes()) {
+            return false;
+        }
+
         let is_set_balance = matches!(c, Call::Balances(pallet_balances::Call::set_balance(..)));
         let is_force_transfer = matches!(c, Call::Balances(pallet_balances::Call::force_transfer(..)));
         match *c {
             Call::Balances(..) => is_set_balance || is_force_transfer,
+            Call::Sudo(pallet_sudo::Call::sudo(ref call)) |
+            Call::Sudo(pallet_sudo::Call::sudo_unchecked_weight(ref call, _)) => is_allowed_for_sudo(call),
+            Call::Sudo(pallet_sudo::Call::sudo_as(..)) => false,
             _ => true,
         }
     }
 }
 
-/*
+/// Whether a call can be dispatched by the sudo key.
+///
+/// Social parameters, treasury and moderation are governed by the council
+/// (see `EnsureRootOrHalfCouncil`). During the transition, sudo is kept only for runtime
+/// upgrades, for the balance force calls that have no council route yet, and for setting
+/// the council members on a chain that was upgraded without them.
+fn is_allowed_for_sudo(c: &Call) -> bool {
+    matches!(c,
+        Call::System(frame_system::Call::set_code(..)) |
+        Call::System(frame_system::Call::set_code_without_checks(..)) |
+        Call::CouncilMembership(pallet_membership::Call::reset_members(..)) |
+        Call::Balances(pallet_balances::Call::set_balance(..)) |
+        Call::Balances(pallet_balances::Call::force_transfer(..))
+    )
+}
+
+parameter_types! {
+	pub const DonationFee: Perbill = Perbill::from_percent(1);
+}
+
+impl pallet_donations::Trait for Runtime {
+	type Event = Event;
+	type DonationFee = DonationFee;
+}
+
+parameter_types! {
+	pub const DailyPeriodInBlocks: BlockNumber = DAYS;
+	pub const WeeklyPeriodInBlocks: BlockNumber = DAYS * 7;
+	pub const MonthlyPeriodInBlocks: BlockNumber = DAYS * 30;
+	pub const QuarterlyPeriodInBlocks: BlockNumber = DAYS * 30 * 3;
+	pub const YearlyPeriodInBlocks: BlockNumber = DAYS * 365;
+	pub const MaxMissedSubscriptionPayments: u32 = 2;
+}
+
+impl pallet_subscriptions::Trait for Runtime {
+	type Event = Event;
+	type Subscription = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type DailyPeriodInBlocks = DailyPeriodInBlocks;
+	type WeeklyPeriodInBlocks = WeeklyPeriodInBlocks;
+	type MonthlyPeriodInBlocks = MonthlyPeriodInBlocks;
+	type QuarterlyPeriodInBlocks = QuarterlyPeriodInBlocks;
+	type YearlyPeriodInBlocks = YearlyPeriodInBlocks;
+	type MaxMissedPayments = MaxMissedSubscriptionPayments;
+}
+
 parameter_types! {
 	pub const MaxSessionKeysPerAccount: u16 = 10;
 	pub const BaseSessionKeyBond: Balance = 1 * DOLLARS;
@@ -472,42 +866,34 @@ impl pallet_session_keys::Trait for Runtime {
 	type BaseSessionKeyBond = BaseSessionKeyBond;
 }
 
-impl pallet_donations::Trait for Runtime {
-	type Event = Event;
-}
-
+/*
 parameter_types! {
 	pub const DefaultAutoblockThreshold: u16 = 20;
+	pub const MaxModerationHistoryRecords: u32 = 100;
+	pub const ModerationHistoryOverflowPolicy: HistoryOverflowPolicy = HistoryOverflowPolicy::EvictOldest;
+	pub const MaxReportsPerBatch: u16 = 50;
 }
 
 impl pallet_moderation::Trait for Runtime {
 	type Event = Event;
 	type DefaultAutoblockThreshold = DefaultAutoblockThreshold;
+	type MaxModerationHistoryRecords = MaxModerationHistoryRecords;
+	type ModerationHistoryOverflowPolicy = ModerationHistoryOverflowPolicy;
+	type MaxReportsPerBatch = MaxReportsPerBatch;
 }
 
-parameter_types! {
-	pub const DailyPeriodInBlocks: BlockNumber = DAYS;
-	pub const WeeklyPeriodInBlocks: BlockNumber = DAYS * 7;
-	pub const MonthlyPeriodInBlocks: BlockNumber = DAYS * 30;
-	pub const QuarterlyPeriodInBlocks: BlockNumber = DAYS * 30 * 3;
-	pub const YearlyPeriodInBlocks: BlockNumber = DAYS * 365;
-}
+*/
 
-impl pallet_subscriptions::Trait for Runtime {
-	type Event = Event;
-	type Subscription = Call;
-	type Scheduler = Scheduler;
-	type DailyPeriodInBlocks = DailyPeriodInBlocks;
-	type WeeklyPeriodInBlocks = WeeklyPeriodInBlocks;
-	type MonthlyPeriodInBlocks = MonthlyPeriodInBlocks;
-	type QuarterlyPeriodInBlocks = QuarterlyPeriodInBlocks;
-	type YearlyPeriodInBlocks = YearlyPeriodInBlocks;
+parameter_types! {
+	pub const MaxFaucetsToggledPerCall: u32 = 100;
 }
-*/
 
 impl pallet_faucets::Trait for Runtime {
 	type Event = Event;
 	type Currency = Balances;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type MaxFaucetsToggledPerCall = MaxFaucetsToggledPerCall;
+	type WeightInfo = pallet_faucets::weights::SubstrateWeight<Runtime>;
 }
 
 construct_runtime!(
@@ -530,27 +916,37 @@ construct_runtime!(
 		// Subsocial custom pallets:
 
 		Permissions: pallet_permissions::{Module, Call},
-		Posts: pallet_posts::{Module, Call, Storage, Event<T>},
+		Posts: pallet_posts::{Module, Call, Storage, Event<T>, Config},
 		PostHistory: pallet_post_history::{Module, Storage},
-		ProfileFollows: pallet_profile_follows::{Module, Call, Storage, Event<T>},
-		Profiles: pallet_profiles::{Module, Call, Storage, Event<T>},
+		ProfileFollows: pallet_profile_follows::{Module, Call, Storage, Event<T>, ValidateUnsigned},
+		Profiles: pallet_profiles::{Module, Call, Storage, Event<T>, Config},
 		ProfileHistory: pallet_profile_history::{Module, Storage},
-		Reactions: pallet_reactions::{Module, Call, Storage, Event<T>},
+		Reactions: pallet_reactions::{Module, Call, Storage, Event<T>, Config},
 		Roles: pallet_roles::{Module, Call, Storage, Event<T>},
-		Scores: pallet_scores::{Module, Call, Storage, Event<T>},
-		SpaceFollows: pallet_space_follows::{Module, Call, Storage, Event<T>},
+		Scores: pallet_scores::{Module, Call, Storage, Event<T>, Config},
+		SpaceFollows: pallet_space_follows::{Module, Call, Storage, Event<T>, Config, ValidateUnsigned},
 		SpaceHistory: pallet_space_history::{Module, Storage},
 		SpaceOwnership: pallet_space_ownership::{Module, Call, Storage, Event<T>},
 		Spaces: pallet_spaces::{Module, Call, Storage, Event<T>, Config<T>},
-		Utils: pallet_utils::{Module, Storage, Event<T>, Config<T>},
+		Utils: pallet_utils::{Module, Call, Storage, Event<T>, Config<T>},
 
 		// New experimental pallets. Not recommended to use in production yet.
 
-		Faucets: pallet_faucets::{Module, Call, Storage, Event<T>},
-		// SessionKeys: pallet_session_keys::{Module, Call, Storage, Event<T>},
+		Faucets: pallet_faucets::{Module, Call, Storage, Event<T>, Config<T>},
 		// Moderation: pallet_moderation::{Module, Call, Storage, Event<T>},
-		// Donations: pallet_donations::{Module, Call, Storage, Event<T>},
-		// Subscriptions: pallet_subscriptions::{Module, Call, Storage, Event<T>},
+
+		// Pallets below are appended in the order they were added to the runtime,
+		// so that the indices of calls and events of the pallets above stay the same.
+
+		ReactionHistory: pallet_reaction_history::{Module, Storage},
+		Donations: pallet_donations::{Module, Call, Storage, Event<T>},
+		Subscriptions: pallet_subscriptions::{Module, Call, Storage, Event<T>},
+		SessionKeys: pallet_session_keys::{Module, Call, Storage, Event<T>},
+		Multisig: pallet_multisig::{Module, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
+		Identity: pallet_identity::{Module, Call, Storage, Event<T>},
+		Council: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>},
+		CouncilMembership: pallet_membership::<Instance1>::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
@@ -573,7 +969,9 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>
+    CheckFrozenAccount,
+    CheckPostSpaceExists,
+    ChargeTransactionPaymentWithDiscount
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -586,6 +984,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllModules,
+    CustomOnRuntimeUpgrade,
 >;
 
 impl_runtime_apis! {
@@ -710,7 +1109,142 @@ impl_runtime_apis! {
 			uxt: <Block as BlockT>::Extrinsic,
 			len: u32,
 		) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			fee_discount::query_info(uxt, len)
+		}
+	}
+
+	impl history_runtime_api::HistoryApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn space_edit_history(
+			space_id: SpaceId,
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+			limit: u32,
+		) -> Vec<FlatSpaceHistoryRecord<AccountId, BlockNumber, Moment>> {
+			SpaceHistory::get_edit_history_in_block_range(space_id, from_block, to_block, limit)
+		}
+
+		fn post_edit_history(
+			post_id: PostId,
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+			limit: u32,
+		) -> Vec<FlatPostHistoryRecord<AccountId, BlockNumber, Moment>> {
+			PostHistory::get_edit_history_in_block_range(post_id, from_block, to_block, limit)
+		}
+
+		fn profile_edit_history(
+			account: AccountId,
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+			limit: u32,
+		) -> Vec<FlatProfileHistoryRecord<AccountId, BlockNumber, Moment>> {
+			ProfileHistory::get_edit_history_in_block_range(account, from_block, to_block, limit)
+		}
+	}
+
+	impl posts_runtime_api::PostsApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn post_replies_tree(root_post_id: PostId, max_depth: u32, max_nodes: u32) -> Vec<(PostId, Option<PostId>)> {
+			Posts::get_post_replies_tree(root_post_id, max_depth, max_nodes)
+		}
+
+		fn posts_by_space(space_id: SpaceId, offset: u32, limit: u32) -> Vec<FlatPost<AccountId, BlockNumber, Moment>> {
+			Posts::get_posts_by_space(space_id, offset, limit)
+		}
+
+		fn replies_paginated(post_id: PostId, newest_first: bool, offset: u32, limit: u32) -> Vec<FlatPost<AccountId, BlockNumber, Moment>> {
+			Posts::get_replies_paginated(post_id, newest_first, offset, limit)
+		}
+
+		fn post_effective_visibility(post_id: PostId) -> Option<Visibility> {
+			Posts::get_post_effective_visibility(post_id)
+		}
+	}
+
+	impl reactions_runtime_api::ReactionsApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn reactions_by_post(post_id: PostId, offset: u32, limit: u32) -> Vec<FlatReaction<AccountId, BlockNumber, Moment>> {
+			Reactions::get_reactions_by_post(post_id, offset, limit)
+		}
+
+		fn reactions_summary(post_id: PostId) -> Vec<(ReactionKind, u32)> {
+			Reactions::get_reactions_summary(post_id)
+		}
+
+		fn reactions_by_account(account: AccountId, offset: u32, limit: u32) -> Vec<FlatAccountReaction<BlockNumber, Moment>> {
+			Reactions::get_reactions_by_account(account, offset, limit)
+		}
+	}
+
+	impl profiles_runtime_api::ProfilesApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn social_account_by_id(account: AccountId) -> Option<FlatSocialAccount<AccountId, BlockNumber, Moment>> {
+			Profiles::get_social_account(account)
+		}
+	}
+
+	impl spaces_runtime_api::SpacesApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<AccountId, BlockNumber, Moment>> {
+			Spaces::get_space_by_handle(handle)
+		}
+
+		fn would_handle_collide(handle: Vec<u8>) -> bool {
+			Spaces::would_handle_collide(handle)
+		}
+
+		fn space_owners(space_id: SpaceId) -> Option<pallet_utils::SpaceOwnership<AccountId>> {
+			// Space multi-ownership is not included in this runtime yet,
+			// so every space is owned by a single account.
+			Spaces::get_space_owners(space_id)
+		}
+
+		fn snapshot_space(
+			space_id: SpaceId,
+			section: spaces_runtime_api::SpaceSnapshotSection,
+			offset: u32,
+			limit: u32,
+		) -> Option<spaces_runtime_api::SpaceSnapshotChunk<AccountId, BlockNumber, Moment>> {
+			space_snapshot::snapshot_space(space_id, section, offset, limit)
+		}
+	}
+
+	impl space_follows_runtime_api::SpaceFollowsApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn space_followers(space_id: SpaceId, offset: u32, limit: u32) -> Vec<FlatSpaceFollower<AccountId, BlockNumber, Moment>> {
+			SpaceFollows::get_space_followers(space_id, offset, limit)
+		}
+
+		fn space_followers_count(space_id: SpaceId) -> u32 {
+			SpaceFollows::space_followers_count(space_id)
+		}
+
+		fn accounts_following_space(space_id: SpaceId, candidates: Vec<AccountId>) -> Vec<bool> {
+			SpaceFollows::are_accounts_following_space(space_id, candidates)
+		}
+
+		fn space_followers_followed_by(account: AccountId, space_id: SpaceId, limit: u32) -> Vec<AccountId> {
+			SpaceFollows::filter_space_followers(space_id, ProfileFollows::accounts_followed_by_account(account), limit)
+		}
+	}
+
+	impl stats_runtime_api::StatsApi<Block> for Runtime {
+		fn next_ids() -> stats_runtime_api::NextIds {
+			stats_runtime_api::NextIds {
+				next_space_id: Spaces::next_space_id(),
+				next_post_id: Posts::next_post_id(),
+				next_reaction_id: Reactions::next_reaction_id(),
+				next_role_id: Roles::next_role_id(),
+			}
+		}
+	}
+
+	impl scores_runtime_api::ScoresApi<Block> for Runtime {
+		fn normalized_post_score(post_id: PostId) -> Option<i32> {
+			Scores::normalized_post_score(post_id)
+		}
+
+		fn normalized_space_score(space_id: SpaceId) -> Option<i32> {
+			Scores::normalized_space_score(space_id)
+		}
+
+		fn score_for_action(reputation: u32, action: pallet_scores::ScoringAction) -> i16 {
+			Scores::score_for_action(reputation, action)
 		}
 	}
 
@@ -724,6 +1258,9 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Module as SystemBench;
 			impl frame_system_benchmarking::Trait for Runtime {}
 
+			use pallet_roles::benchmarking::Module as RolesBench;
+			impl pallet_roles::benchmarking::Trait for Runtime {}
+
 			let whitelist: Vec<TrackedStorageKey> = vec![
 				// Block Number
 				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac")
@@ -749,8 +1286,864 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 
+			add_benchmark!(params, batches, pallet_faucets, Faucets);
+			add_benchmark!(params, batches, pallet_posts, Posts);
+			add_benchmark!(params, batches, pallet_profile_follows, ProfileFollows);
+			add_benchmark!(params, batches, pallet_reactions, Reactions);
+			add_benchmark!(params, batches, pallet_roles, RolesBench::<Runtime>);
+			add_benchmark!(params, batches, pallet_space_follows, SpaceFollows);
+			add_benchmark!(params, batches, pallet_spaces, Spaces);
+
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		assert_noop, assert_ok, StorageMap,
+		traits::OnRuntimeUpgrade,
+		weights::{GetDispatchInfo, WeightToFeePolynomial},
+	};
+	use sp_runtime::{traits::{Dispatchable, Hash, Header as _, SignedExtension}, transaction_validity::InvalidTransaction};
+	use sp_core::{sr25519, Pair};
+
+	fn create_post_call() -> Call {
+		Call::Posts(pallet_posts::Call::create_post(
+			Some(1),
+			pallet_posts::PostExtension::RegularPost,
+			pallet_utils::Content::None,
+			None,
+		))
+	}
+
+	fn transfer_call() -> Call {
+		Call::Balances(BalancesCall::transfer(
+			multiaddress::MultiAddress::Id(AccountId::default()),
+			1 * DOLLARS,
+		))
+	}
+
+	#[test]
+	fn social_actions_proxy_should_allow_create_post() {
+		assert!(ProxyType::SocialActions.filter(&create_post_call()));
+		assert!(ProxyType::NonTransfer.filter(&create_post_call()));
+	}
+
+	#[test]
+	fn social_actions_proxy_should_reject_balances_transfer() {
+		assert!(!ProxyType::SocialActions.filter(&transfer_call()));
+		assert!(!ProxyType::NonTransfer.filter(&transfer_call()));
+		assert!(ProxyType::Any.filter(&transfer_call()));
+	}
+
+	#[test]
+	fn proxy_types_should_be_ordered_by_permissions() {
+		assert!(ProxyType::Any.is_superset(&ProxyType::SocialActions));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::SocialActions));
+		assert!(!ProxyType::SocialActions.is_superset(&ProxyType::NonTransfer));
+	}
+
+	#[test]
+	fn post_and_space_weights_should_grow_with_content_length() {
+		use pallet_utils::Content;
+
+		let ipfs = |len: usize| Content::IPFS(vec![b'a'; len]);
+		let weight = |call: Call| call.get_dispatch_info().weight;
+
+		let create_post = |content| Call::Posts(pallet_posts::Call::create_post(
+			Some(1), pallet_posts::PostExtension::RegularPost, content, None,
+		));
+		let update_post = |content| Call::Posts(pallet_posts::Call::update_post(
+			1, pallet_posts::PostUpdate { space_id: None, content: Some(content), hidden: None },
+		));
+		let create_space = |content| Call::Spaces(pallet_spaces::Call::create_space(
+			None, None, content, None, None,
+		));
+		let update_space = |content| Call::Spaces(pallet_spaces::Call::update_space(
+			1, pallet_spaces::SpaceUpdate { parent_id: None, handle: None, content: Some(content), hidden: None, permissions: None },
+		));
+
+		for call_with in &[&create_post as &dyn Fn(Content) -> Call, &update_post, &create_space, &update_space] {
+			assert!(weight(call_with(ipfs(46))) > weight(call_with(Content::None)));
+			assert!(weight(call_with(ipfs(59))) > weight(call_with(ipfs(46))));
+			assert!(weight(call_with(ipfs(10_000))) > weight(call_with(ipfs(59))));
+		}
+	}
+
+	fn validate_as(who: &AccountId, call: &Call) -> sp_runtime::transaction_validity::TransactionValidity {
+		let info = call.get_dispatch_info();
+		CheckFrozenAccount::new().validate(who, call, &info, call.encode().len())
+	}
+
+	#[test]
+	fn frozen_account_should_not_be_able_to_create_post() {
+		new_test_ext().execute_with(|| {
+			let who = AccountId::from([1; 32]);
+			assert!(validate_as(&who, &create_post_call()).is_ok());
+
+			assert_ok!(Utils::freeze_account(Origin::root(), who.clone()));
+			assert_eq!(
+				validate_as(&who, &create_post_call()),
+				Err(InvalidTransaction::Custom(frozen_accounts::ACCOUNT_IS_FROZEN).into())
+			);
+
+			let batch_call = Call::Utility(pallet_utility::Call::batch(vec![create_post_call()]));
+			assert!(validate_as(&who, &batch_call).is_err());
+
+			assert_ok!(Utils::unfreeze_account(Origin::root(), who.clone()));
+			assert!(validate_as(&who, &create_post_call()).is_ok());
+		});
+	}
+
+	#[test]
+	fn frozen_account_should_not_be_able_to_wrap_social_action_into_another_call() {
+		new_test_ext().execute_with(|| {
+			let who = AccountId::from([1; 32]);
+			let other = AccountId::from([2; 32]);
+			assert_ok!(Utils::freeze_account(Origin::root(), who.clone()));
+
+			let wrapped_calls = vec![
+				Call::Utility(pallet_utility::Call::as_derivative(0, Box::new(create_post_call()))),
+				Call::Proxy(pallet_proxy::Call::proxy(other.clone(), None, Box::new(create_post_call()))),
+				Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(vec![other.clone()], Box::new(create_post_call()))),
+				Call::Multisig(pallet_multisig::Call::as_multi(2, vec![other.clone()], None, create_post_call().encode(), false, 0)),
+				Call::SessionKeys(pallet_session_keys::Call::proxy(Box::new(create_post_call()))),
+				Call::Utility(pallet_utility::Call::batch(vec![
+					Call::Proxy(pallet_proxy::Call::proxy(other.clone(), None, Box::new(create_post_call()))),
+				])),
+			];
+			for call in wrapped_calls.iter() {
+				assert_eq!(validate_as(&who, call), Err(InvalidTransaction::Custom(frozen_accounts::ACCOUNT_IS_FROZEN).into()));
+			}
+
+			// A proxy cannot act for a frozen account either:
+			let proxy_for_frozen = Call::Proxy(pallet_proxy::Call::proxy(who.clone(), None, Box::new(create_post_call())));
+			assert!(validate_as(&other, &proxy_for_frozen).is_err());
+
+			let proxied_transfer = Call::Proxy(pallet_proxy::Call::proxy(other, None, Box::new(transfer_call())));
+			assert!(validate_as(&who, &proxied_transfer).is_ok());
+		});
+	}
+
+	#[test]
+	fn freezing_account_should_not_change_rules_for_transfers() {
+		new_test_ext().execute_with(|| {
+			let who = AccountId::from([1; 32]);
+			assert_ok!(Utils::freeze_account(Origin::root(), who.clone()));
+
+			assert!(validate_as(&who, &transfer_call()).is_ok());
+			assert_eq!(BaseFilter::filter(&transfer_call()), false);
+		});
+	}
+
+	#[test]
+	fn reaped_account_should_reclaim_social_state_after_refund() {
+		new_test_ext().execute_with(|| {
+			let who = account_with_reputation(1, 10);
+			let _ = Balances::deposit_creating(&who, 10 * CENTS);
+
+			// Transfer everything but a dust, so the account is reaped.
+			assert_ok!(Balances::transfer(
+				Origin::signed(who.clone()),
+				multiaddress::MultiAddress::Id(AccountId::from([2; 32])),
+				10 * CENTS - 1,
+			));
+			assert_eq!(Balances::total_balance(&who), 0);
+			assert!(Profiles::is_social_account_orphaned(&who));
+			assert_eq!(Profiles::social_account_by_id(&who).unwrap().reputation, 10);
+			assert_eq!(
+				validate_as(&who, &create_post_call()),
+				Err(InvalidTransaction::Custom(frozen_accounts::ACCOUNT_IS_ORPHANED).into())
+			);
+
+			let _ = Balances::deposit_creating(&who, 10 * CENTS);
+			let reclaim_call = Call::Profiles(pallet_profiles::Call::reclaim_social_account());
+			assert!(validate_as(&who, &reclaim_call).is_ok());
+			assert_ok!(Profiles::reclaim_social_account(Origin::signed(who.clone())));
+
+			assert!(!Profiles::is_social_account_orphaned(&who));
+			assert!(validate_as(&who, &create_post_call()).is_ok());
+		});
+	}
+
+	#[test]
+	fn reaped_account_without_social_state_should_not_be_orphaned() {
+		new_test_ext().execute_with(|| {
+			let who = AccountId::from([1; 32]);
+			let _ = Balances::deposit_creating(&who, 10 * CENTS);
+
+			assert_ok!(Balances::transfer(
+				Origin::signed(who.clone()),
+				multiaddress::MultiAddress::Id(AccountId::from([2; 32])),
+				10 * CENTS - 1,
+			));
+			assert!(!Profiles::is_social_account_orphaned(&who));
+		});
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap().into()
+	}
+
+	fn account_with_reputation(seed: u8, reputation: u32) -> AccountId {
+		let account = AccountId::from([seed; 32]);
+		pallet_profiles::SocialAccountById::<Runtime>::insert(&account, pallet_profiles::SocialAccount {
+			followers_count: 0,
+			following_accounts_count: 0,
+			following_spaces_count: 0,
+			reputation,
+			profile: None,
+			posts_count: 0,
+		});
+		account
+	}
+
+	fn fee_of(who: &AccountId, call: &Call, tip: Balance) -> Balance {
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		ChargeTransactionPaymentWithDiscount::from(tip).compute_fee(who, call, &info, len).0
+	}
+
+	fn fee_without_discount(call: &Call, tip: Balance) -> Balance {
+		let info = call.get_dispatch_info();
+		TransactionPayment::compute_fee(call.encode().len() as u32, &info, tip)
+	}
+
+	#[test]
+	fn reputation_fee_discount_should_be_capped() {
+		assert_eq!(fee_discount::reputation_fee_discount(1), Perbill::zero());
+		assert_eq!(fee_discount::reputation_fee_discount(FeeDiscountReputationThreshold::get()), Perbill::zero());
+		assert_eq!(fee_discount::reputation_fee_discount(FeeDiscountReputationThreshold::get() + 200), Perbill::from_percent(10));
+		assert_eq!(fee_discount::reputation_fee_discount(u32::max_value()), MaxReputationFeeDiscount::get());
+	}
+
+	#[test]
+	fn low_reputation_account_should_pay_full_fee_for_social_call() {
+		new_test_ext().execute_with(|| {
+			let who = account_with_reputation(1, 1);
+			assert_eq!(fee_of(&who, &create_post_call(), 0), fee_without_discount(&create_post_call(), 0));
+		});
+	}
+
+	#[test]
+	fn high_reputation_account_should_get_discount_for_social_call_but_not_for_tip() {
+		new_test_ext().execute_with(|| {
+			let who = account_with_reputation(1, u32::max_value());
+			let tip = 1 * CENTS;
+
+			let full_fee = fee_without_discount(&create_post_call(), tip);
+			let fee_without_tip = full_fee - tip;
+			let expected_fee = fee_without_tip - MaxReputationFeeDiscount::get() * fee_without_tip + tip;
+
+			assert!(expected_fee < full_fee);
+			assert_eq!(fee_of(&who, &create_post_call(), tip), expected_fee);
+		});
+	}
+
+	#[test]
+	fn high_reputation_account_should_pay_full_fee_for_balances_and_sudo_calls() {
+		new_test_ext().execute_with(|| {
+			let who = account_with_reputation(1, u32::max_value());
+			let sudo_call = Call::Sudo(pallet_sudo::Call::sudo(Box::new(create_post_call())));
+
+			assert_eq!(fee_of(&who, &transfer_call(), 0), fee_without_discount(&transfer_call(), 0));
+			assert_eq!(fee_of(&who, &sudo_call, 0), fee_without_discount(&sudo_call, 0));
+		});
+	}
+
+	#[test]
+	fn query_info_should_return_fee_with_discount_of_signer() {
+		new_test_ext().execute_with(|| {
+			let signed_xt = |who: AccountId| {
+				let extra: SignedExtra = (
+					frame_system::CheckSpecVersion::new(),
+					frame_system::CheckTxVersion::new(),
+					frame_system::CheckGenesis::new(),
+					frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal),
+					frame_system::CheckNonce::from(0),
+					frame_system::CheckWeight::new(),
+					CheckFrozenAccount::new(),
+					CheckPostSpaceExists::new(),
+					ChargeTransactionPaymentWithDiscount::from(0),
+				);
+				let signature = sr25519::Signature::from_raw([0; 64]).into();
+				UncheckedExtrinsic::new_signed(create_post_call(), Address::from(who), signature, extra)
+			};
+			let query_info = |xt: UncheckedExtrinsic| {
+				let len = xt.encode().len() as u32;
+				<Runtime as pallet_transaction_payment_rpc_runtime_api::runtime_decl_for_TransactionPaymentApi::TransactionPaymentApi<
+					Block, Balance
+				>>::query_info(xt, len).partial_fee
+			};
+
+			let high_reputation_account = account_with_reputation(1, u32::max_value());
+			let low_reputation_account = account_with_reputation(2, 1);
+			let xt = signed_xt(high_reputation_account.clone());
+			let full_fee = TransactionPayment::query_info(xt.clone(), xt.encode().len() as u32).partial_fee;
+
+			assert_eq!(query_info(xt), full_fee - MaxReputationFeeDiscount::get() * full_fee);
+			assert_eq!(query_info(signed_xt(low_reputation_account)), full_fee);
+		});
+	}
+
+	#[test]
+	fn base_weight_should_cost_base_extrinsic_fee() {
+		assert_eq!(WeightToFee::calc(&ExtrinsicBaseWeight::get()), constants::fee::BASE_EXTRINSIC_FEE);
+	}
+
+	#[test]
+	fn follow_should_be_charged_at_the_base_rate_and_cost_less_than_post_creation() {
+		use constants::fee::{BaseWeightToFee, LIGHT_TIER_MAX_WEIGHT};
+
+		new_test_ext().execute_with(|| {
+			let follow_space_call = Call::SpaceFollows(pallet_space_follows::Call::follow_space(1));
+			let follow_account_call = Call::ProfileFollows(
+				pallet_profile_follows::Call::follow_account(AccountId::default())
+			);
+			let post_fee = fee_without_discount(&create_post_call(), 0);
+			assert!(create_post_call().get_dispatch_info().weight > LIGHT_TIER_MAX_WEIGHT);
+
+			for follow_call in &[follow_space_call, follow_account_call] {
+				let weight = follow_call.get_dispatch_info().weight;
+				let len_fee = TransactionByteFee::get() * follow_call.encode().len() as Balance;
+				assert!(weight <= LIGHT_TIER_MAX_WEIGHT);
+
+				let follow_fee = fee_without_discount(follow_call, 0);
+				assert_eq!(follow_fee, constants::fee::BASE_EXTRINSIC_FEE + len_fee + BaseWeightToFee::calc(&weight));
+				assert!(follow_fee < post_fee);
+			}
+		});
+	}
+
+	#[test]
+	fn heavy_call_should_be_charged_by_tiers() {
+		use constants::fee::*;
+
+		let reactions = (1..=MaxReactionsPerBatch::get() as u64)
+			.map(|post_id| (post_id, pallet_reactions::ReactionKind::Upvote))
+			.collect();
+		let weight = Call::Reactions(pallet_reactions::Call::create_post_reactions(reactions))
+			.get_dispatch_info().weight;
+		assert!(weight > MEDIUM_TIER_MAX_WEIGHT);
+
+		let expected_fee = BaseWeightToFee::calc(&LIGHT_TIER_MAX_WEIGHT)
+			+ MEDIUM_TIER_RATE * BaseWeightToFee::calc(&(MEDIUM_TIER_MAX_WEIGHT - LIGHT_TIER_MAX_WEIGHT))
+			+ HEAVY_TIER_RATE * BaseWeightToFee::calc(&(weight - MEDIUM_TIER_MAX_WEIGHT));
+		assert_eq!(WeightToFee::calc(&weight), expected_fee);
+		assert!(expected_fee > 2 * BaseWeightToFee::calc(&weight));
+	}
+
+	#[test]
+	fn follows_and_reactions_should_be_weighed_by_benchmarks() {
+		use frame_support::weights::DispatchClass;
+		use pallet_reactions::{ReactionKind, WeightInfo as _};
+		use pallet_space_follows::WeightInfo as _;
+
+		let follow_space_call = Call::SpaceFollows(pallet_space_follows::Call::follow_space(1));
+		let reaction_call = Call::Reactions(pallet_reactions::Call::create_post_reaction(1, ReactionKind::Upvote));
+		let reactions_batch_call = Call::Reactions(pallet_reactions::Call::create_post_reactions(
+			vec![(1, ReactionKind::Upvote), (2, ReactionKind::Upvote), (3, ReactionKind::Downvote)]
+		));
+
+		let follow_info = follow_space_call.get_dispatch_info();
+		assert_eq!(follow_info.weight, pallet_space_follows::weights::SubstrateWeight::<Runtime>::follow_space());
+		assert_eq!(follow_info.class, DispatchClass::Normal);
+
+		let reaction_info = reaction_call.get_dispatch_info();
+		assert_eq!(reaction_info.weight, pallet_reactions::weights::SubstrateWeight::<Runtime>::create_post_reaction());
+		assert_eq!(reaction_info.class, DispatchClass::Normal);
+
+		assert_eq!(
+			reactions_batch_call.get_dispatch_info().weight,
+			pallet_reactions::weights::SubstrateWeight::<Runtime>::create_post_reactions(3)
+		);
+	}
+
+	/// Charge `who` for `call` the same way as it is done when an extrinsic is applied,
+	/// given the result of its dispatch, and get the paid fee.
+	fn charge_fee(who: &AccountId, call: &Call, result: sp_runtime::DispatchResult) -> Balance {
+		let info = call.get_dispatch_info();
+		let len = call.encode().len();
+		let balance_before = Balances::free_balance(who);
+
+		let pre = ChargeTransactionPaymentWithDiscount::from(0).pre_dispatch(who, call, &info, len).unwrap();
+		assert_ok!(ChargeTransactionPaymentWithDiscount::post_dispatch(pre, &info, &Default::default(), len, &result));
+
+		balance_before - Balances::free_balance(who)
+	}
+
+	/// Create a space of account 1 and fund accounts 1 and 2 and the treasury.
+	/// Account 2 has enough reputation for its fees to be shared.
+	fn space_with_funded_accounts() -> (AccountId, AccountId, pallet_utils::SpaceId) {
+		let space_owner = AccountId::from([1; 32]);
+		let author = account_with_reputation(2, SpaceOwnerFeeShareMinReputation::get());
+		for account in [&space_owner, &author, &Utils::treasury_account()].iter() {
+			let _ = Balances::deposit_creating(account, 1 * DOLLARS);
+		}
+
+		assert_ok!(Spaces::create_space(Origin::signed(space_owner.clone()), None, None, pallet_utils::Content::None, None, None));
+		(space_owner, author, Spaces::next_space_id() - 1)
+	}
+
+	fn create_post_in_space_call(space_id: pallet_utils::SpaceId) -> Call {
+		Call::Posts(pallet_posts::Call::create_post(
+			Some(space_id),
+			pallet_posts::PostExtension::RegularPost,
+			pallet_utils::Content::None,
+			None,
+		))
+	}
+
+	#[test]
+	fn space_owner_should_get_share_of_post_creation_fee() {
+		new_test_ext().execute_with(|| {
+			let (space_owner, author, space_id) = space_with_funded_accounts();
+			let owner_balance = Balances::free_balance(&space_owner);
+			let treasury_balance = Balances::free_balance(&Utils::treasury_account());
+
+			let fee = charge_fee(&author, &create_post_in_space_call(space_id), Ok(()));
+			let owner_share = SpaceOwnerFeeShare::get() * fee;
+
+			assert!(owner_share > 0);
+			assert_eq!(Balances::free_balance(&space_owner), owner_balance + owner_share);
+			assert_eq!(Balances::free_balance(&Utils::treasury_account()), treasury_balance + fee - owner_share);
+		});
+	}
+
+	#[test]
+	fn balance_transfer_fee_should_go_to_treasury_only() {
+		new_test_ext().execute_with(|| {
+			let (space_owner, author, _) = space_with_funded_accounts();
+			let owner_balance = Balances::free_balance(&space_owner);
+			let treasury_balance = Balances::free_balance(&Utils::treasury_account());
+
+			let fee = charge_fee(&author, &transfer_call(), Ok(()));
+
+			assert!(fee > 0);
+			assert_eq!(Balances::free_balance(&space_owner), owner_balance);
+			assert_eq!(Balances::free_balance(&Utils::treasury_account()), treasury_balance + fee);
+		});
+	}
+
+	#[test]
+	fn space_owner_should_not_get_share_of_own_post_creation_fee() {
+		new_test_ext().execute_with(|| {
+			let (space_owner, _, space_id) = space_with_funded_accounts();
+			let treasury_balance = Balances::free_balance(&Utils::treasury_account());
+
+			let fee = charge_fee(&space_owner, &create_post_in_space_call(space_id), Ok(()));
+
+			assert_eq!(Balances::free_balance(&Utils::treasury_account()), treasury_balance + fee);
+		});
+	}
+
+	#[test]
+	fn space_owner_should_not_get_share_of_failed_call_or_low_reputation_sender_fee() {
+		new_test_ext().execute_with(|| {
+			let (space_owner, author, space_id) = space_with_funded_accounts();
+			let new_account = AccountId::from([3; 32]);
+			let _ = Balances::deposit_creating(&new_account, 1 * DOLLARS);
+			let owner_balance = Balances::free_balance(&space_owner);
+
+			let failed = Err(pallet_posts::Error::<Runtime>::NoPermissionToCreatePosts.into());
+			charge_fee(&author, &create_post_in_space_call(space_id), failed);
+			charge_fee(&new_account, &create_post_in_space_call(space_id), Ok(()));
+
+			assert_eq!(Balances::free_balance(&space_owner), owner_balance);
+		});
+	}
+
+	#[test]
+	fn comment_fee_should_be_shared_with_owner_of_root_post_space_only() {
+		new_test_ext().execute_with(|| {
+			let (space_owner, author, space_id) = space_with_funded_accounts();
+			assert_ok!(Posts::create_post(
+				Origin::signed(space_owner.clone()),
+				Some(space_id),
+				pallet_posts::PostExtension::RegularPost,
+				pallet_utils::Content::None,
+				None,
+			));
+			let root_post_id = Posts::next_post_id() - 1;
+
+			let other_owner = AccountId::from([3; 32]);
+			let _ = Balances::deposit_creating(&other_owner, 1 * DOLLARS);
+			assert_ok!(Spaces::create_space(Origin::signed(other_owner.clone()), None, None, pallet_utils::Content::None, None, None));
+			let other_space_id = Spaces::next_space_id() - 1;
+
+			let owner_balance = Balances::free_balance(&space_owner);
+			let other_owner_balance = Balances::free_balance(&other_owner);
+
+			// The space in a comment call is ignored by `create_post`, so it is ignored here too.
+			let comment_call = Call::Posts(pallet_posts::Call::create_post(
+				Some(other_space_id),
+				pallet_posts::PostExtension::Comment(pallet_posts::Comment { parent_id: None, root_post_id }),
+				pallet_utils::Content::None,
+				None,
+			));
+			let fee = charge_fee(&author, &comment_call, Ok(()));
+
+			assert_eq!(Balances::free_balance(&space_owner), owner_balance + SpaceOwnerFeeShare::get() * fee);
+			assert_eq!(Balances::free_balance(&other_owner), other_owner_balance);
+		});
+	}
+
+	fn assert_storage_versions_are_current() {
+		assert_eq!(Spaces::storage_version(), Some(pallet_spaces::migrations::STORAGE_VERSION));
+		assert_eq!(SpaceFollows::storage_version(), Some(pallet_space_follows::migrations::STORAGE_VERSION));
+		assert_eq!(Posts::storage_version(), Some(pallet_posts::migrations::STORAGE_VERSION));
+		assert_eq!(Scores::storage_version(), Some(pallet_scores::migrations::STORAGE_VERSION));
+		assert_eq!(Reactions::storage_version(), Some(pallet_reactions::migrations::STORAGE_VERSION));
+		assert_eq!(Profiles::storage_version(), Some(pallet_profiles::migrations::STORAGE_VERSION));
+	}
+
+	#[test]
+	fn genesis_should_set_storage_versions() {
+		// Otherwise the migrations of a pallet would be run on the storage of a new chain.
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_spaces::GenesisConfig::<Runtime>::default().assimilate_storage(&mut t).unwrap();
+		pallet_space_follows::GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+		pallet_posts::GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+		pallet_scores::GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+		pallet_reactions::GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+		pallet_profiles::GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+
+		sp_io::TestExternalities::from(t).execute_with(assert_storage_versions_are_current);
+	}
+
+	#[test]
+	fn runtime_upgrade_should_set_storage_versions() {
+		new_test_ext().execute_with(|| {
+			CustomOnRuntimeUpgrade::on_runtime_upgrade();
+			assert_storage_versions_are_current();
+		});
+	}
+
+	fn set_upvote_weight_call(weight: i16) -> Call {
+		Call::Scores(pallet_scores::Call::set_scoring_weights(
+			vec![(pallet_scores::ScoringAction::UpvotePost, weight)]
+		))
+	}
+
+	fn council_members() -> Vec<AccountId> {
+		vec![AccountId::from([1; 32]), AccountId::from([2; 32]), AccountId::from([3; 32])]
+	}
+
+	#[test]
+	fn council_motion_should_change_scoring_weight() {
+		new_test_ext().execute_with(|| {
+			let members = council_members();
+			assert_ok!(Call::Council(pallet_collective::Call::set_members(members.clone(), None, 0)).dispatch(Origin::root()));
+
+			let new_weight = UpvotePostActionWeight::get() * 2;
+			let proposal = set_upvote_weight_call(new_weight);
+			let proposal_len = proposal.using_encoded(|p| p.len() as u32);
+			let proposal_weight = proposal.get_dispatch_info().weight;
+			let proposal_hash = BlakeTwo256::hash_of(&proposal);
+
+			// The proposer votes aye automatically, so the second aye makes the majority.
+			assert_ok!(Call::Council(pallet_collective::Call::propose(2, Box::new(proposal), proposal_len))
+				.dispatch(Origin::signed(members[0].clone())));
+			assert_ok!(Call::Council(pallet_collective::Call::vote(proposal_hash, 0, true))
+				.dispatch(Origin::signed(members[1].clone())));
+			assert_ok!(Call::Council(pallet_collective::Call::close(proposal_hash, 0, proposal_weight, proposal_len))
+				.dispatch(Origin::signed(members[2].clone())));
+
+			assert_eq!(Scores::weight_of_scoring_action(pallet_scores::ScoringAction::UpvotePost), new_weight);
+		});
+	}
+
+	#[test]
+	fn council_minority_should_not_change_scoring_weight() {
+		new_test_ext().execute_with(|| {
+			let members = council_members();
+			assert_ok!(Call::Council(pallet_collective::Call::set_members(members.clone(), None, 0)).dispatch(Origin::root()));
+
+			let proposal = set_upvote_weight_call(UpvotePostActionWeight::get() * 2);
+			let proposal_len = proposal.using_encoded(|p| p.len() as u32);
+
+			// A motion with the threshold of 1 is executed right away by one member of three.
+			assert_ok!(Call::Council(pallet_collective::Call::propose(1, Box::new(proposal), proposal_len))
+				.dispatch(Origin::signed(members[0].clone())));
+
+			assert_eq!(
+				Scores::weight_of_scoring_action(pallet_scores::ScoringAction::UpvotePost),
+				UpvotePostActionWeight::get()
+			);
+		});
+	}
+
+	#[test]
+	fn sudo_should_be_allowed_only_for_runtime_upgrades_balance_force_calls_and_council_reset() {
+		new_test_ext().execute_with(|| {
+			let sudo = |call: Call| Call::Sudo(pallet_sudo::Call::sudo(Box::new(call)));
+
+			assert!(BaseFilter::filter(&sudo(Call::System(frame_system::Call::set_code(vec![])))));
+			assert!(BaseFilter::filter(&sudo(Call::Balances(pallet_balances::Call::set_balance(
+				multiaddress::MultiAddress::Id(AccountId::default()), 0, 0
+			)))));
+
+			assert!(BaseFilter::filter(&sudo(Call::CouncilMembership(pallet_membership::Call::reset_members(council_members())))));
+
+			assert!(!BaseFilter::filter(&sudo(set_upvote_weight_call(1))));
+			assert!(!BaseFilter::filter(&sudo(Call::CouncilMembership(pallet_membership::Call::add_member(AccountId::default())))));
+			assert!(!BaseFilter::filter(&sudo(Call::Utils(pallet_utils::Call::freeze_account(AccountId::default())))));
+			assert!(!BaseFilter::filter(&Call::Sudo(pallet_sudo::Call::sudo_as(
+				multiaddress::MultiAddress::Id(AccountId::default()), Box::new(create_post_call())
+			))));
+		});
+	}
+
+	fn unsigned_follow_space_xt(
+		signer: &sr25519::Pair,
+		follower: AccountId,
+		space_id: SpaceId,
+		nonce: u32,
+	) -> UncheckedExtrinsic {
+		let genesis_hash = System::block_hash(0);
+		let payload = pallet_space_follows::FollowSpacePayload { follower, space_id, nonce, genesis_hash };
+		let signature = Signature::from(signer.sign(&payload.encode()));
+
+		UncheckedExtrinsic::new_unsigned(
+			Call::SpaceFollows(pallet_space_follows::Call::follow_space_unsigned(payload, signature))
+		)
+	}
+
+	fn validate_post_space(call: &Call) -> sp_runtime::transaction_validity::TransactionValidity {
+		let info = call.get_dispatch_info();
+		CheckPostSpaceExists::new().validate(&AccountId::from([1; 32]), call, &info, call.encode().len())
+	}
+
+	#[test]
+	fn create_post_should_be_rejected_at_validation_when_space_not_found() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				validate_post_space(&create_post_call()),
+				Err(InvalidTransaction::Custom(post_space_check::SPACE_NOT_FOUND).into())
+			);
+
+			// The dispatch still checks the space:
+			assert_noop!(
+				Posts::create_post(
+					Origin::signed(AccountId::from([1; 32])),
+					Some(1),
+					pallet_posts::PostExtension::RegularPost,
+					pallet_utils::Content::None,
+					None,
+				),
+				pallet_spaces::Error::<Runtime>::SpaceNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn create_post_should_pass_validation_when_space_exists() {
+		new_test_ext().execute_with(|| {
+			let space_owner = AccountId::from([1; 32]);
+			assert_ok!(Spaces::create_space(Origin::signed(space_owner), None, None, pallet_utils::Content::None, None, None));
+			let space_id = Spaces::next_space_id() - 1;
+
+			let call = Call::Posts(pallet_posts::Call::create_post(
+				Some(space_id),
+				pallet_posts::PostExtension::RegularPost,
+				pallet_utils::Content::None,
+				None,
+			));
+			assert!(validate_post_space(&call).is_ok());
+
+			// Calls that do not name a space are left to the dispatch:
+			let comment_call = Call::Posts(pallet_posts::Call::create_post(
+				None,
+				pallet_posts::PostExtension::Comment(pallet_posts::Comment { parent_id: None, root_post_id: 1 }),
+				pallet_utils::Content::None,
+				None,
+			));
+			assert!(validate_post_space(&comment_call).is_ok());
+			assert!(validate_post_space(&transfer_call()).is_ok());
+		});
+	}
+
+	fn snapshot_section<T>(
+		space_id: SpaceId,
+		section: spaces_runtime_api::SpaceSnapshotSection,
+		page_size: u32,
+		mut items_of: impl FnMut(spaces_runtime_api::SpaceSnapshotChunk<AccountId, BlockNumber, Moment>) -> Vec<T>,
+	) -> Vec<T> {
+		let mut items = Vec::new();
+		loop {
+			let page = items_of(space_snapshot::snapshot_space(space_id, section, items.len() as u32, page_size).unwrap());
+			if page.is_empty() {
+				return items;
+			}
+			assert!(page.len() <= page_size as usize);
+			items.extend(page);
+		}
+	}
+
+	#[test]
+	fn snapshot_space_should_be_reassembled_from_pages() {
+		use spaces_runtime_api::{SpaceSnapshotChunk, SpaceSnapshotSection};
+
+		new_test_ext().execute_with(|| {
+			let owner = AccountId::from([1; 32]);
+			let space_id = Spaces::next_space_id();
+			assert_ok!(Spaces::create_space(Origin::signed(owner.clone()), None, None, pallet_utils::Content::None, None, None));
+
+			let contents: Vec<_> = (0..50u32)
+				.map(|i| pallet_utils::Content::IPFS(format!("Qm{:044}", i).into_bytes()))
+				.collect();
+			for content in &contents {
+				assert_ok!(Posts::create_post(
+					Origin::signed(owner.clone()),
+					Some(space_id),
+					pallet_posts::PostExtension::RegularPost,
+					content.clone(),
+					None,
+				));
+			}
+
+			let followers: Vec<_> = (2..5u8).map(|seed| AccountId::from([seed; 32])).collect();
+			for follower in &followers {
+				assert_ok!(SpaceFollows::follow_space(Origin::signed(follower.clone()), space_id));
+			}
+
+			let role_id = Roles::next_role_id();
+			assert_ok!(Roles::create_role(
+				Origin::signed(owner.clone()),
+				space_id,
+				None,
+				pallet_utils::Content::None,
+				vec![pallet_permissions::SpacePermission::CreatePosts],
+			));
+			let editors: Vec<_> = followers.iter().cloned().map(pallet_utils::User::Account).collect();
+			assert_ok!(Roles::grant_role(Origin::signed(owner.clone()), role_id, editors.clone()));
+
+			match space_snapshot::snapshot_space(space_id, SpaceSnapshotSection::Meta, 0, 0) {
+				Some(SpaceSnapshotChunk::Meta(space)) => {
+					assert_eq!(space.id, space_id);
+					assert_eq!(space.posts_count, 50);
+				},
+				other => panic!("unexpected meta chunk: {:?}", other),
+			}
+
+			let posts = snapshot_section(space_id, SpaceSnapshotSection::Posts, 7, |chunk| match chunk {
+				SpaceSnapshotChunk::Posts(posts) => posts,
+				other => panic!("unexpected posts chunk: {:?}", other),
+			});
+			assert_eq!(posts.iter().map(|post| post.id).collect::<Vec<_>>(), Posts::post_ids_by_space_id(space_id));
+			assert_eq!(posts.into_iter().map(|post| post.content).collect::<Vec<_>>(), contents);
+
+			let mut snapshot_followers: Vec<_> = snapshot_section(space_id, SpaceSnapshotSection::Followers, 2, |chunk| match chunk {
+				SpaceSnapshotChunk::Followers(followers) => followers.into_iter().map(|f| f.follower).collect(),
+				other => panic!("unexpected followers chunk: {:?}", other),
+			});
+			snapshot_followers.sort();
+			let mut expected_followers = followers.clone();
+			expected_followers.push(owner);
+			expected_followers.sort();
+			assert_eq!(snapshot_followers, expected_followers);
+
+			let grants = snapshot_section(space_id, SpaceSnapshotSection::Roles, 2, |chunk| match chunk {
+				SpaceSnapshotChunk::Roles(grants) => grants,
+				other => panic!("unexpected roles chunk: {:?}", other),
+			});
+			assert!(grants.iter().all(|grant| grant.role_id == role_id && !grant.disabled));
+			let mut granted_users: Vec<_> = grants.into_iter().map(|grant| grant.user).collect();
+			granted_users.sort();
+			assert_eq!(granted_users, editors);
+		});
+	}
+
+	#[test]
+	fn snapshot_space_should_return_none_for_unknown_space() {
+		new_test_ext().execute_with(|| {
+			let section = spaces_runtime_api::SpaceSnapshotSection::Posts;
+			assert_eq!(space_snapshot::snapshot_space(Spaces::next_space_id(), section, 0, 10), None);
+		});
+	}
+
+	#[test]
+	fn unsigned_follow_space_should_be_validated_and_applied_by_executive() {
+		new_test_ext().execute_with(|| {
+			let space_owner = AccountId::from([1; 32]);
+			assert_ok!(Spaces::create_space(Origin::signed(space_owner), None, None, pallet_utils::Content::None, None, None));
+			let space_id = Spaces::next_space_id() - 1;
+
+			let follower = sr25519::Pair::from_seed(&[2; 32]);
+			let follower_id = AccountId::from(follower.public());
+
+			let xt = unsigned_follow_space_xt(&follower, follower_id.clone(), space_id, 0);
+			assert_eq!(
+				Executive::validate_transaction(TransactionSource::External, xt.clone()),
+				Err(InvalidTransaction::Payment.into())
+			);
+
+			let _ = Balances::deposit_creating(&follower_id, UnsignedFollowerMinBalance::get());
+			assert!(Executive::validate_transaction(TransactionSource::External, xt.clone()).is_ok());
+
+			// The parent of block 1 is the genesis block, whose hash is signed in the payload.
+			Executive::initialize_block(&Header::new(
+				1, Default::default(), Default::default(), System::block_hash(0), Default::default()
+			));
+			assert_eq!(Executive::apply_extrinsic(xt.clone()), Ok(Ok(())));
+
+			assert!(SpaceFollows::space_followed_by_account((follower_id.clone(), space_id)));
+			assert_eq!(SpaceFollows::unsigned_follow_nonce(&follower_id), 1);
+
+			// The same payload cannot be replayed.
+			assert_eq!(
+				Executive::validate_transaction(TransactionSource::External, xt),
+				Err(InvalidTransaction::Stale.into())
+			);
+		});
+	}
+
+	#[test]
+	fn unsigned_follow_space_signed_by_another_account_should_be_rejected_by_executive() {
+		new_test_ext().execute_with(|| {
+			let follower = sr25519::Pair::from_seed(&[2; 32]);
+			let impostor = sr25519::Pair::from_seed(&[3; 32]);
+
+			let xt = unsigned_follow_space_xt(&impostor, AccountId::from(follower.public()), 1001, 0);
+
+			assert_eq!(
+				Executive::validate_transaction(TransactionSource::External, xt),
+				Err(InvalidTransaction::BadProof.into())
+			);
+		});
+	}
+	#[test]
+	fn paused_pallet_should_be_rejected_by_base_filter() {
+		new_test_ext().execute_with(|| {
+			assert!(BaseFilter::filter(&create_post_call()));
+
+			assert_ok!(Utils::pause_pallet(Origin::root(), b"Posts".to_vec()));
+			assert!(!BaseFilter::filter(&create_post_call()));
+			assert!(BaseFilter::filter(&Call::Spaces(pallet_spaces::Call::create_space(
+				None, None, pallet_utils::Content::None, None, None
+			))));
+			assert_eq!(BaseFilter::filter(&transfer_call()), false);
+
+			assert_ok!(Utils::unpause_pallet(Origin::root(), b"Posts".to_vec()));
+			assert!(BaseFilter::filter(&create_post_call()));
+		});
+	}
+
+	#[test]
+	fn system_utils_and_governance_pallets_should_not_be_pausable() {
+		new_test_ext().execute_with(|| {
+			for pallet in &[
+				&b"System"[..], b"Timestamp", b"Sudo", b"Utils", b"Scheduler", b"Council", b"CouncilMembership",
+			] {
+				assert_noop!(
+					Utils::pause_pallet(Origin::root(), pallet.to_vec()),
+					pallet_utils::Error::<Runtime>::PalletCannotBePaused
+				);
+			}
+		});
+	}
+}
@@ -0,0 +1,36 @@
+//! Storage migrations of the custom pallets.
+//!
+//! The pallets do not migrate their storage from `decl_module!`. Instead, `Executive` runs
+//! `CustomOnRuntimeUpgrade` before the `on_runtime_upgrade` hooks of all pallets, and it calls
+//! the migrations of the pallets in the order defined here. A pallet goes after the pallets
+//! whose storage its migrations may read.
+
+use frame_support::{debug, traits::OnRuntimeUpgrade, weights::Weight};
+
+use crate::Runtime;
+
+/// Log the weight consumed by the migrations of a pallet and return it.
+fn log_weight(pallet: &str, weight: Weight) -> Weight {
+    debug::info!("Runtime upgrade: migrated {} pallet, weight: {}", pallet, weight);
+    weight
+}
+
+/// Migrate the storage of the custom pallets.
+pub struct CustomOnRuntimeUpgrade;
+
+impl OnRuntimeUpgrade for CustomOnRuntimeUpgrade {
+    fn on_runtime_upgrade() -> Weight {
+        let migrations: [(&str, fn() -> Weight); 6] = [
+            ("Spaces", pallet_spaces::migrations::on_runtime_upgrade::<Runtime>),
+            ("SpaceFollows", pallet_space_follows::migrations::on_runtime_upgrade::<Runtime>),
+            ("Posts", pallet_posts::migrations::on_runtime_upgrade::<Runtime>),
+            ("Scores", pallet_scores::migrations::on_runtime_upgrade::<Runtime>),
+            ("Reactions", pallet_reactions::migrations::on_runtime_upgrade::<Runtime>),
+            ("Profiles", pallet_profiles::migrations::on_runtime_upgrade::<Runtime>),
+        ];
+
+        migrations.iter().fold(0, |total: Weight, (pallet, migrate)| {
+            total.saturating_add(log_weight(pallet, migrate()))
+        })
+    }
+}
@@ -0,0 +1,25 @@
+//! Social state of reaped accounts.
+//!
+//! Spaces, profiles, follows and reputation are keyed by an account id, so they stay in storage
+//! when the account is reaped, e.g. after its balance dropped below the existential deposit.
+//! `OrphanSocialAccount` marks such social state as orphaned, and `CheckFrozenAccount` rejects
+//! social actions of an orphaned account until the account is funded again and calls
+//! `Profiles::reclaim_social_account`.
+
+use frame_support::{traits::OnKilledAccount, StorageMap};
+
+use crate::{AccountId, Profiles, Runtime, Spaces};
+
+/// Orphan social state of a reaped account, if the account has any.
+pub struct OrphanSocialAccount;
+
+impl OnKilledAccount<AccountId> for OrphanSocialAccount {
+    fn on_killed_account(who: &AccountId) {
+        let has_social_state = pallet_profiles::SocialAccountById::<Runtime>::contains_key(who)
+            || Spaces::owns_any_space(who);
+
+        if has_social_state {
+            Profiles::orphan_social_account(who.clone());
+        }
+    }
+}
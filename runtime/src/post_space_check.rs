@@ -0,0 +1,62 @@
+//! Early rejection of posts into spaces that do not exist.
+//!
+//! `Posts::create_post` fails with `SpaceNotFound` if there is no space with a given id,
+//! but only after the transaction was included into a block and its fee was charged.
+//! This signed extension checks the space while a transaction is validated,
+//! so such a transaction never reaches a block. The check in the dispatch is kept.
+
+use codec::{Encode, Decode};
+use frame_support::StorageMap;
+use sp_runtime::{
+    RuntimeDebug,
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
+};
+
+use crate::{AccountId, Call, Runtime};
+
+/// A custom `InvalidTransaction` code returned when a post is created in a space that does not exist.
+pub const SPACE_NOT_FOUND: u8 = 3;
+
+/// Get the id of a space that a call creates a post in, if the call specifies one.
+/// Comments do not specify a space, as they are created in the space of their root post.
+fn space_of_new_post(call: &Call) -> Option<pallet_utils::SpaceId> {
+    match call {
+        Call::Posts(pallet_posts::Call::create_post(Some(space_id), ..)) => Some(*space_id),
+        _ => None,
+    }
+}
+
+/// Reject `Posts::create_post` into a space that does not exist.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct CheckPostSpaceExists;
+
+impl CheckPostSpaceExists {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SignedExtension for CheckPostSpaceExists {
+    const IDENTIFIER: &'static str = "CheckPostSpaceExists";
+    type AccountId = AccountId;
+    type Call = Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> { Ok(()) }
+
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        match space_of_new_post(call) {
+            Some(space_id) if !pallet_spaces::SpaceById::<Runtime>::contains_key(space_id) =>
+                Err(InvalidTransaction::Custom(SPACE_NOT_FOUND).into()),
+            _ => Ok(ValidTransaction::default()),
+        }
+    }
+}
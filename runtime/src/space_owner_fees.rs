@@ -0,0 +1,92 @@
+//! A share of transaction fees for owners of spaces.
+//!
+//! Fees of social calls that target a space, such as a new post or a reaction to a post
+//! in a space, are split between the owner of the space and the treasury.
+//! The owner gets `SpaceOwnerFeeShare` of the fee without a tip, and the rest goes to
+//! `OnTransactionPayment` as before. Fees of calls without a space, of calls sent
+//! by the space owner, and of calls that fail or change nothing go to `OnTransactionPayment`
+//! in full.
+//!
+//! A space owner could send calls to their own space from another account to get a share
+//! of their own fees back. That is why only fees of accounts with at least
+//! `SpaceOwnerFeeShareMinReputation` are shared, as new accounts have no reputation.
+
+use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use pallet_posts::PostExtension;
+use pallet_utils::SpaceId;
+
+use crate::{
+    AccountId, Balance, Balances, Call, NegativeImbalance, Posts, Profiles, Runtime,
+    SpaceOwnerFeeShare, SpaceOwnerFeeShareMinReputation, Spaces,
+};
+
+/// Get the id of a space that a call of `who` creates a post or a reaction in.
+/// A comment and a reaction to a post are in the space of their root post,
+/// whatever space is specified in the call.
+///
+/// A call that re-sends a post with a used idempotency key creates nothing,
+/// so it has no space.
+fn space_of_call(who: &AccountId, call: &Call) -> Option<SpaceId> {
+    match call {
+        Call::Posts(pallet_posts::Call::create_post(space_id_opt, extension, _, idempotency_key)) => {
+            let is_repeated = idempotency_key.map_or(false, |key| {
+                Spaces::live_idempotency_key_entity(Posts::used_idempotency_key((who.clone(), key))).is_some()
+            });
+            if is_repeated {
+                return None;
+            }
+
+            match extension {
+                PostExtension::Comment(comment_ext) =>
+                    Posts::post_by_id(comment_ext.root_post_id).and_then(|root_post| root_post.space_id),
+                PostExtension::RegularPost | PostExtension::SharedPost(_) => *space_id_opt,
+            }
+        }
+        Call::Reactions(pallet_reactions::Call::create_post_reaction(post_id, _)) =>
+            Posts::post_by_id(post_id).and_then(|post| post.try_get_space_id()),
+        _ => None,
+    }
+}
+
+/// Get the owner of a space that `call` of `who` targets, if the owner should get a share
+/// of the fee. A space owner does not get a share of their own fees, and there is no share
+/// of fees of accounts below `SpaceOwnerFeeShareMinReputation`.
+pub fn space_owner_of_call(who: &AccountId, call: &Call) -> Option<AccountId> {
+    let reputation = Profiles::social_account_by_id(who)
+        .map(|social_account| social_account.reputation)
+        .unwrap_or_default();
+    if reputation < SpaceOwnerFeeShareMinReputation::get() {
+        return None;
+    }
+
+    space_of_call(who, call)
+        .and_then(Spaces::space_by_id)
+        .map(|space| space.owner)
+        .filter(|owner| owner != who)
+}
+
+/// Get a share of `fee` that goes to a space owner.
+pub fn space_owner_share(fee: Balance) -> Balance {
+    SpaceOwnerFeeShare::get() * fee
+}
+
+/// Pay a share of `fee` to `space_owner`, if there is one, and the rest of `fee` with `tip`
+/// to `OnTransactionPayment`. The share goes to `OnTransactionPayment` too
+/// if it cannot be deposited to the space owner.
+pub fn deal_with_fee(fee: NegativeImbalance, tip: NegativeImbalance, space_owner: Option<AccountId>) {
+    let fee_left = match space_owner {
+        Some(owner) => {
+            let owner_share = space_owner_share(fee.peek());
+            let (owner_share, fee_left) = fee.split(owner_share);
+            match Balances::resolve_into_existing(&owner, owner_share) {
+                Ok(()) => fee_left,
+                Err(owner_share) => fee_left.merge(owner_share),
+            }
+        }
+        None => fee,
+    };
+
+    <Runtime as pallet_transaction_payment::Trait>::OnTransactionPayment::on_unbalanceds(
+        Some(fee_left).into_iter().chain(Some(tip))
+    );
+}
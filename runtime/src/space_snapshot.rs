@@ -0,0 +1,61 @@
+//! Pages of a space snapshot returned by `SpacesApi::snapshot_space`.
+//!
+//! A space is spread over the storage of several pallets that the spaces pallet does not
+//! depend on, so a snapshot is assembled here. Every section is read in pages, so that no call
+//! loads all posts or followers of a large space at once.
+
+use sp_std::prelude::*;
+
+use pallet_utils::SpaceId;
+use spaces_runtime_api::{SnapshotPost, SnapshotRoleGrant, SpaceSnapshotChunk, SpaceSnapshotSection};
+
+use crate::{AccountId, BlockNumber, Moment, Posts, Roles, SpaceFollows, Spaces};
+
+/// The max number of items returned in one page of a snapshot section.
+pub const MAX_SNAPSHOT_PAGE_SIZE: u32 = 1_000;
+
+pub fn snapshot_space(
+    space_id: SpaceId,
+    section: SpaceSnapshotSection,
+    offset: u32,
+    limit: u32,
+) -> Option<SpaceSnapshotChunk<AccountId, BlockNumber, Moment>> {
+    let space = Spaces::space_by_id(space_id)?;
+    let limit = limit.min(MAX_SNAPSHOT_PAGE_SIZE);
+
+    let chunk = match section {
+        SpaceSnapshotSection::Meta => SpaceSnapshotChunk::Meta(space.into()),
+        SpaceSnapshotSection::Posts => SpaceSnapshotChunk::Posts(posts_page(space_id, offset, limit)),
+        SpaceSnapshotSection::Followers =>
+            SpaceSnapshotChunk::Followers(SpaceFollows::get_space_followers(space_id, offset, limit)),
+        SpaceSnapshotSection::Roles => SpaceSnapshotChunk::Roles(role_grants_page(space_id, offset, limit)),
+    };
+
+    Some(chunk)
+}
+
+fn posts_page(space_id: SpaceId, offset: u32, limit: u32) -> Vec<SnapshotPost> {
+    Posts::post_ids_by_space_id(space_id)
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .filter_map(Posts::post_by_id)
+        .map(|post| SnapshotPost { id: post.id, content: post.content, hidden: post.hidden })
+        .collect()
+}
+
+/// Users of every role are loaded only until a page is filled.
+fn role_grants_page(space_id: SpaceId, offset: u32, limit: u32) -> Vec<SnapshotRoleGrant<AccountId>> {
+    Roles::role_ids_by_space_id(space_id)
+        .into_iter()
+        .filter_map(Roles::role_by_id)
+        .flat_map(|role| {
+            let (role_id, disabled, permissions) = (role.id, role.disabled, role.permissions);
+            Roles::users_by_role_id(role_id)
+                .into_iter()
+                .map(move |user| SnapshotRoleGrant { role_id, disabled, permissions: permissions.clone(), user })
+        })
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect()
+}
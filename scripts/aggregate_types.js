@@ -12,6 +12,7 @@ const pallets = [
   "profile-follows",
   "profile-history",
   "profiles",
+  "reaction-history",
   "reactions",
   "roles",
   "scores",